@@ -1,7 +1,7 @@
 from typing import Union
 
-from .implica import Graph, Query, Edge, Node, Term, Type, Constant
+from .implica import Graph, Query, Edge, Node, Term, Type, Constant, Sum, Snapshot, GraphBuilder, ResultIterator, PathPattern
 
 Element = Union[Edge, Node, Term, Type]
 
-__all__ = ["Graph", "Query", "Edge", "Node", "Term", "Type", "Element", "Constant"]
+__all__ = ["Graph", "Query", "Edge", "Node", "Term", "Type", "Element", "Constant", "Sum", "Snapshot", "GraphBuilder", "ResultIterator", "PathPattern"]
@@ -1,14 +1,22 @@
-from typing import Tuple, List, Dict, Any, Optional
+from typing import Tuple, List, Dict, Any, Optional, Callable, NamedTuple, overload
 
 class Type:
     def __str__(self) -> str: ...
     def __repr__(self) -> str: ...
     def uid(self) -> str: ...
+    def to_sexpr(self) -> str: ...
+    @staticmethod
+    def from_sexpr(sexpr: str, graph: Graph) -> Type: ...
 
 class Term:
     def __str__(self) -> str: ...
     def __repr__(self) -> str: ...
     def uid(self) -> str: ...
+    def type(self) -> Type: ...
+    def partial_apply(self, argument: Term) -> Term: ...
+    def to_sexpr(self) -> str: ...
+    @staticmethod
+    def from_sexpr(sexpr: str, graph: Graph) -> Term: ...
 
 class Node:
     def __str__(self) -> str: ...
@@ -17,6 +25,11 @@ class Node:
     def properties(self) -> Dict[str, Any]: ...
     def type(self) -> Type: ...
     def term(self) -> Optional[Term]: ...
+    def clear_term(self, cascade: bool = False) -> bool: ...
+    def context(self) -> Optional[Dict[str, Any]]: ...
+    def created_at(self) -> float: ...
+    def modified_at(self) -> float: ...
+    def structurally_equals(self, other: Node) -> bool: ...
 
 class Edge:
     def __str__(self) -> str: ...
@@ -25,28 +38,209 @@ class Edge:
     def properties(self) -> Dict[str, Any]: ...
     def type(self) -> Type: ...
     def term(self) -> Term: ...
+    def endpoint_types(self) -> Tuple[Type, Type]: ...
+    def endpoint_terms(self) -> Tuple[Optional[Term], Optional[Term]]: ...
+    def created_at(self) -> float: ...
+    def modified_at(self) -> float: ...
+    def structurally_equals(self, other: Edge) -> bool: ...
 
 type Element = Type | Term | Node | Edge
 
 class Constant:
     name: str
     def __init__(self, name: str, type_schema: str) -> None: ...
+    def type(self, graph: "Graph") -> Type: ...
+    def inhabits(self, type: Type) -> bool: ...
+
+class Sum:
+    name: str
+    cases: List[Constant]
+    def __init__(self, name: str, cases: List[Constant]) -> None: ...
+
+class PathPattern:
+    @staticmethod
+    def parse(pattern: str) -> PathPattern: ...
+    def validate(self) -> None: ...
+    def __str__(self) -> str: ...
+    def __repr__(self) -> str: ...
+
+class ResultIterator:
+    def __iter__(self) -> "ResultIterator": ...
+    def __next__(self) -> Dict[str, Element]: ...
+    def __len__(self) -> int: ...
+    def __repr__(self) -> str: ...
+    def seek(self, index: int) -> None: ...
+    def position(self) -> int: ...
 
 class Query:
     def __str__(self) -> str: ...
+    def cached(self, enabled: bool = True) -> "Query": ...
+    def optimize(self, enabled: bool = True) -> "Query": ...
+    def max_intermediate_rows(self, count: int) -> "Query": ...
+    def run_on(self, graph: "Graph") -> "Query": ...
     def execute(self) -> None: ...
-    def return_(self, *variables: str) -> List[Dict[str, Element]]: ...
-    def match(self, pattern: str) -> "Query": ...
-    def create(self, pattern: str) -> "Query": ...
+    def delete_matching(self, pattern: str) -> int: ...
+    def return_(
+        self,
+        *items: str | Tuple[str, Any] | List[str | Tuple[str, Any]],
+        as_: str = "dict",
+    ) -> List[Dict[str, Any]] | List[NamedTuple]: ...
+    def return_case(
+        self,
+        expression: str,
+        cases: Dict[Any, Any],
+        *items: str | Tuple[str, Any] | List[str | Tuple[str, Any]],
+        default: Any = None,
+        as_: str = "case",
+    ) -> List[Dict[str, Any]]: ...
+    def return_triples(self, variable: str) -> List[Dict[str, Node | Edge]]: ...
+    def to_graph(self) -> "Graph": ...
+    def to_parquet(self, path: str, *columns: str | Tuple[str, Any]) -> int: ...
+    def iter_(self, *variables: str) -> ResultIterator: ...
+    def __len__(self) -> int: ...
+    @overload
+    def __getitem__(self, index: int) -> Dict[str, Element]: ...
+    @overload
+    def __getitem__(self, index: slice) -> List[Dict[str, Element]]: ...
+    def first(self, *variables: str) -> Optional[Dict[str, Element]]: ...
+    def one(self, *variables: str) -> Dict[str, Element]: ...
+    def canonical(self) -> str: ...
+    def pending_operations(self) -> int: ...
+    def describe_operations(self) -> List[str]: ...
+    def explain_analyze(
+        self,
+        *items: str | Tuple[str, Any] | List[str | Tuple[str, Any]],
+        as_: str = "dict",
+    ) -> Dict[str, Any]: ...
+    def exists(self) -> bool: ...
+    def assert_count(self, expected: int) -> None: ...
+    def assert_returns(self, expected_rows: List[Dict[str, Element]]) -> None: ...
+    def compare_to(self, expected_rows: List[Dict[str, Element]]) -> Dict[str, List[Dict[str, Element]]]: ...
+    def match(
+        self,
+        pattern: Optional[str] = None,
+        edge: Optional[str] = None,
+        incident: Optional[str] = None,
+        from_node: Optional[str] = None,
+        to_node: Optional[str] = None,
+        property_match: str = "superset",
+        node: Optional[str] = None,
+        any_property: Optional[Dict[str, Dict[str, Any]]] = None,
+        all_property: Optional[Dict[str, Dict[str, Any]]] = None,
+        missing_properties: Optional[List[str]] = None,
+    ) -> "Query": ...
+    def match_within(
+        self,
+        seed: List[Node | str],
+        k: int,
+        direction: str = "forward",
+        variable: str = "n",
+    ) -> "Query": ...
+    def ego(self, node: Node | str, radius: int = 1, direction: str = "any") -> "Graph": ...
+    def create(
+        self,
+        pattern: str,
+        merge_properties: bool = False,
+        property_fn: Optional[Callable[[str, Optional[str]], Dict[str, Any]]] = None,
+        property_fn_wins: bool = False,
+        overwrite: bool = False,
+    ) -> "Query": ...
+    def create_return(
+        self,
+        pattern: str,
+        *variables: str,
+        merge_properties: bool = False,
+        property_fn: Optional[Callable[[str, Optional[str]], Dict[str, Any]]] = None,
+        property_fn_wins: bool = False,
+        overwrite: bool = False,
+    ) -> List[Dict[str, Element]]: ...
     def remove(self, *variables: str) -> "Query": ...
+    def clear_term(self, *variables: str, cascade: bool = False) -> "Query": ...
     def set(self, variable: str, properties: Dict[str, Any], overwrite: bool = True) -> "Query": ...
+    def set_expr(self, property: str, expression: str) -> "Query": ...
+    def filter(self, callback: Callable[[Dict[str, Element]], bool]) -> "Query": ...
+    def with_(self, items: List[str | Tuple[str, str]]) -> "Query": ...
+    def collect_pattern(self, variable: str, pattern: str, collect: str, as_: Optional[str] = None) -> "Query": ...
+    def register_function(self, name: str, callback: Callable[..., Any]) -> "Query": ...
+    def where_(self, expression: str) -> "Query": ...
+    def where_distinct(self, variables: List[str]) -> "Query": ...
+    def let_(self, variable: str, expression: str) -> "Query": ...
+    def order_by(self, *expressions: str, descending: bool = False) -> "Query": ...
+    def limit(self, count: int) -> "Query": ...
+    def nearest(self, expression: str, target: float, k: int) -> List[Dict[str, Element]]: ...
+
+class Snapshot:
+    def __repr__(self) -> str: ...
+
+class GraphBuilder:
+    def node(
+        self,
+        name: str,
+        type: Optional[str] = None,
+        term: Optional[str] = None,
+        properties: Optional[Dict[str, Any]] = None,
+    ) -> "GraphBuilder": ...
+    def edge(
+        self,
+        start: str,
+        end: str,
+        term: Optional[str] = None,
+        name: Optional[str] = None,
+        properties: Optional[Dict[str, Any]] = None,
+    ) -> "GraphBuilder": ...
+    def commit(self) -> Dict[str, Element]: ...
 
 class Graph:
-    def __init__(self, constants: List[Constant] = []) -> None: ...
+    def __init__(
+        self,
+        constants: List[Constant] = [],
+        normalize_numerics: bool = False,
+        sums: List[Sum] = [],
+        directed: bool = True,
+    ) -> None: ...
+    def is_directed(self) -> bool: ...
     def query(self) -> Query: ...
+    def build(self) -> GraphBuilder: ...
     def nodes(self) -> List[Node]: ...
     def edges(self) -> List[Edge]: ...
+    def edges_by_type(self, by: str = "type") -> Dict[str, List[Edge]]: ...
+    def out_edges(self, node: Node | str) -> List[Edge]: ...
+    def in_edges(self, node: Node | str) -> List[Edge]: ...
+    def sources(self) -> List[Node]: ...
+    def sinks(self) -> List[Node]: ...
+    def isolated(self) -> List[Node]: ...
+    def on_change(self, callback: Callable[[Dict[str, Any]], None]) -> None: ...
+    def declare_subtype(self, sub: str, sup: str) -> None: ...
+    def set_identity_key(self, type: str, keys: List[str]) -> None: ...
+    def declare_schema(self, type: str, schema: Dict[str, str]) -> None: ...
+    def freeze(self) -> None: ...
+    def unfreeze(self) -> None: ...
+    def is_frozen(self) -> bool: ...
+    def version(self) -> int: ...
+    def snapshot(self) -> Snapshot: ...
+    def restore(self, checkpoint: Snapshot) -> None: ...
+    def export_schema(self) -> Dict[str, List[Dict[str, Any]]]: ...
+    @staticmethod
+    def load_schema(
+        schema: Dict[str, List[Dict[str, Any]]], normalize_numerics: bool = False
+    ) -> "Graph": ...
+    def stats(self) -> Dict[str, Any]: ...
+    def type_check(self) -> List[Dict[str, Any]]: ...
+    def scan_info(self) -> Dict[str, int]: ...
+    def cache_info(self) -> Dict[str, int]: ...
+    def set_value_serializer(self, serializer: Optional[Callable[[Any], Any]]) -> None: ...
+    def set_lock_retry(self, attempts: int, backoff_ms: int = 1) -> None: ...
+    def set_strict_edges(self, strict: bool) -> None: ...
+    def adjacency_matrix(
+        self, weight: Optional[str] = None
+    ) -> Tuple[List[List[int | float]], List[Node]]: ...
+    def minimum_spanning_tree(
+        self, weight: str, default: Optional[float] = None
+    ) -> Tuple[List[Edge], float]: ...
     def set_node_properties(self, map: Dict[str, Dict[str, Any]], overwrite: bool = True): ...
+    def set_properties_by_uid(self, mapping: Dict[str, Dict[str, Any]], parallel: bool = True) -> List[str]: ...
     def set_edge_properties(
         self, map: Dict[Tuple[str, str], Dict[str, Any]], overwrite: bool = True
     ): ...
+    def map_nodes(self, callback: Callable[[Node], Dict[str, Any]]) -> "Graph": ...
+    def map_edges(self, callback: Callable[[Edge], Dict[str, Any]]) -> "Graph": ...
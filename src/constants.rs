@@ -1,7 +1,10 @@
 use pyo3::prelude::*;
 
-use crate::errors::IntoPyResult;
+use crate::errors::{ImplicaError, IntoPyResult};
+use crate::graph::PyGraph;
 use crate::patterns::{TypePattern, TypeSchema};
+use crate::query::references::TypeRef;
+use crate::utils::validate_variable_name;
 
 #[pyclass]
 #[derive(Debug, Clone)]
@@ -26,6 +29,45 @@ impl Constant {
             free_variables,
         })
     }
+
+    /// The type the constant is declared with, in the given graph.
+    pub fn r#type(&self, graph: &PyGraph) -> PyResult<TypeRef> {
+        graph.constant_type(self)
+    }
+
+    /// Whether the constant is a value of the given type.
+    pub fn inhabits(&self, r#type: &TypeRef) -> PyResult<bool> {
+        r#type.is_inhabited_by(self)
+    }
+}
+
+/// A named enumeration of constants. A type schema `$name$` matches the type of any node or
+/// edge whose term is one of the cases.
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct Sum {
+    #[pyo3(get)]
+    pub name: String,
+    #[pyo3(get)]
+    pub cases: Vec<Constant>,
+}
+
+#[pymethods]
+impl Sum {
+    #[new]
+    pub fn new(name: String, cases: Vec<Constant>) -> PyResult<Sum> {
+        validate_variable_name(&name).into_py_result()?;
+
+        if cases.is_empty() {
+            return Err(ImplicaError::InvalidType {
+                reason: format!("sum type '{}' needs at least one case", name),
+            }
+            .into())
+            .into_py_result();
+        }
+
+        Ok(Sum { name, cases })
+    }
 }
 
 impl TypeSchema {
@@ -39,6 +81,7 @@ impl TypeSchema {
         match pattern {
             TypePattern::Wildcard => (),
             TypePattern::Variable(_) => (),
+            TypePattern::Sum(_) => (),
             TypePattern::Capture { name, pattern } => {
                 variables = Self::get_pattern_free_variables_recursive(pattern);
                 variables.push(name.clone());
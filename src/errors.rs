@@ -1,5 +1,5 @@
 use pyo3::pyclass::PyClassGuardError;
-use pyo3::{exceptions, PyErr, PyResult};
+use pyo3::{exceptions, PyErr, PyResult, Python};
 use std::convert::Infallible;
 
 use error_stack::Report;
@@ -31,8 +31,9 @@ pub enum ImplicaError {
         context: Option<String>,
     },
 
-    //#[error("Evaluation Error: '{message}'")]
-    //EvaluationError { message: String },
+    #[error("Evaluation Error: '{message}'")]
+    EvaluationError { message: String },
+
     #[error("Invalid Type: '{reason}'")]
     InvalidType { reason: String },
 
@@ -125,6 +126,15 @@ pub enum ImplicaError {
         reason: String,
         context: Option<String>,
     },
+
+    #[error("Snapshot Mismatch: '{reason}'{}",context.as_ref().map(|c| format!(" ({})", c)).unwrap_or_default())]
+    SnapshotMismatch {
+        reason: String,
+        context: Option<String>,
+    },
+
+    #[error("IO Error on '{path}': {message}")]
+    IoError { path: String, message: String },
 }
 
 pub type ImplicaResult<T> = Result<T, Report<ImplicaError>>;
@@ -136,6 +146,14 @@ pub trait IntoPyResult<T> {
 impl<T> IntoPyResult<T> for ImplicaResult<T> {
     fn into_py_result(self) -> PyResult<T> {
         self.map_err(|report| {
+            // Errors raised by user supplied Python callbacks are re-raised unchanged.
+            if let Some(err) = report
+                .frames()
+                .find_map(|frame| frame.downcast_ref::<PyErr>())
+            {
+                return Python::attach(|py| err.clone_ref(py));
+            }
+
             let current_error = report.current_context();
             let full_message = format_report(&report);
 
@@ -151,7 +169,8 @@ impl<T> IntoPyResult<T> for ImplicaResult<T> {
                 | ImplicaError::SchemaValidation { .. }
                 | ImplicaError::ContextConflict { .. }
                 | ImplicaError::InvalidNumberOfArguments { .. }
-                | ImplicaError::HexConversionError { .. } => {
+                | ImplicaError::HexConversionError { .. }
+                | ImplicaError::SnapshotMismatch { .. } => {
                     exceptions::PyValueError::new_err(full_message)
                 }
                 ImplicaError::VariableAlreadyExists { .. }
@@ -166,7 +185,7 @@ impl<T> IntoPyResult<T> for ImplicaResult<T> {
                 }
                 ImplicaError::PythonError { .. }
                 | ImplicaError::RuntimeError { .. }
-                //| ImplicaError::EvaluationError { .. }
+                | ImplicaError::EvaluationError { .. }
                 | ImplicaError::LockError { .. } => {
                     exceptions::PyRuntimeError::new_err(full_message)
                 }
@@ -176,6 +195,7 @@ impl<T> IntoPyResult<T> for ImplicaResult<T> {
                 ImplicaError::IndexOutOfRange { .. } => {
                     exceptions::PyKeyError::new_err(full_message)
                 }
+                ImplicaError::IoError { .. } => exceptions::PyOSError::new_err(full_message),
                 ImplicaError::Infallible {} => exceptions::PySystemError::new_err(full_message),
             }
         })
@@ -0,0 +1,125 @@
+use dashmap::DashMap;
+
+use crate::ctx;
+use crate::errors::{ImplicaError, ImplicaResult};
+use crate::graph::base::{EdgeSet, Graph, Uid};
+use crate::patterns::CompiledDirection;
+
+impl Graph {
+    /// Returns the edges leaving a node, read straight from the start index.
+    pub(crate) fn out_edges(&self, node: &Uid) -> ImplicaResult<Vec<(Uid, Uid)>> {
+        Self::indexed_edges(&self.start_to_edge_index, node).ok_or_else(|| {
+            ImplicaError::NodeNotFound {
+                uid: *node,
+                context: Some(ctx!("graph - out edges")),
+            }
+            .into()
+        })
+    }
+
+    /// Returns the edges entering a node, read straight from the end index.
+    pub(crate) fn in_edges(&self, node: &Uid) -> ImplicaResult<Vec<(Uid, Uid)>> {
+        Self::indexed_edges(&self.end_to_edge_index, node).ok_or_else(|| {
+            ImplicaError::NodeNotFound {
+                uid: *node,
+                context: Some(ctx!("graph - in edges")),
+            }
+            .into()
+        })
+    }
+
+    /// The direction edges are walked in when asked for `direction`: any direction at all in
+    /// an undirected graph.
+    pub(crate) fn walk_direction(&self, direction: &CompiledDirection) -> CompiledDirection {
+        if self.directed {
+            direction.clone()
+        } else {
+            CompiledDirection::Any
+        }
+    }
+
+    /// Returns the edges that can be walked from a node in a direction, the outgoing ones
+    /// first, each ordered by uid. In an undirected graph these are all the edges touching
+    /// the node.
+    pub(crate) fn walkable_edges(
+        &self,
+        node: &Uid,
+        direction: &CompiledDirection,
+    ) -> ImplicaResult<Vec<(Uid, Uid)>> {
+        let edges = match self.walk_direction(direction) {
+            CompiledDirection::Forward => self.out_edges(node)?,
+            CompiledDirection::Backward => self.in_edges(node)?,
+            CompiledDirection::Any => {
+                let mut edges = self.out_edges(node)?;
+                edges.extend(
+                    self.in_edges(node)?
+                        .into_iter()
+                        .filter(|edge| edge.0 != edge.1),
+                );
+                edges
+            }
+        };
+
+        Ok(edges)
+    }
+
+    /// Returns the nodes, ordered by uid, that have no incoming edges when `no_incoming` is set
+    /// and no outgoing edges when `no_outgoing` is set. In an undirected graph every edge
+    /// touching a node is both, so only nodes without any edge qualify.
+    pub(crate) fn nodes_without_edges(&self, no_incoming: bool, no_outgoing: bool) -> Vec<Uid> {
+        let is_empty = |index: &DashMap<Uid, EdgeSet>, node: &Uid| {
+            index.get(node).is_none_or(|edges| edges.is_empty())
+        };
+        let walks_no_edge = |direction: CompiledDirection, node: &Uid| match self
+            .walk_direction(&direction)
+        {
+            CompiledDirection::Forward => is_empty(&self.start_to_edge_index, node),
+            CompiledDirection::Backward => is_empty(&self.end_to_edge_index, node),
+            CompiledDirection::Any => {
+                is_empty(&self.start_to_edge_index, node) && is_empty(&self.end_to_edge_index, node)
+            }
+        };
+
+        let mut nodes: Vec<Uid> = self
+            .nodes
+            .iter()
+            .map(|entry| *entry.key())
+            .filter(|node| !no_incoming || walks_no_edge(CompiledDirection::Backward, node))
+            .filter(|node| !no_outgoing || walks_no_edge(CompiledDirection::Forward, node))
+            .collect();
+        nodes.sort();
+        nodes
+    }
+
+    /// Returns the start and end nodes of an edge, provided the edge and both of them are
+    /// still in the graph.
+    pub(crate) fn edge_endpoints(&self, edge: &(Uid, Uid)) -> ImplicaResult<(Uid, Uid)> {
+        if !self.edges.contains_key(edge) {
+            return Err(ImplicaError::EdgeNotFound {
+                uid: *edge,
+                context: Some(ctx!("graph - edge endpoints")),
+            }
+            .into());
+        }
+
+        for node in [edge.0, edge.1] {
+            if !self.nodes.contains_key(&node) {
+                return Err(ImplicaError::NodeNotFound {
+                    uid: node,
+                    context: Some(ctx!("graph - edge endpoints")),
+                }
+                .into());
+            }
+        }
+
+        Ok(*edge)
+    }
+
+    fn indexed_edges(index: &DashMap<Uid, EdgeSet>, node: &Uid) -> Option<Vec<(Uid, Uid)>> {
+        index.get(node).map(|entry| {
+            let mut edges: Vec<(Uid, Uid)> = entry.value().iter().map(|edge| *edge).collect();
+            edges.sort();
+            edges
+        })
+    }
+}
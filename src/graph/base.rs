@@ -1,28 +1,39 @@
 use error_stack::ResultExt;
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
+use pyo3::types::{PyDict, PyList};
 use rayon::iter::IntoParallelRefIterator;
 use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::iter::zip;
 use std::ops::ControlFlow;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64};
+use std::sync::{Arc, Mutex};
 
+use dashmap::mapref::one::Ref;
 use dashmap::{DashMap, DashSet};
 use rayon::prelude::*;
 
-use crate::constants::Constant;
+use crate::constants::{Constant, Sum};
 use crate::ctx;
 use crate::errors::{ImplicaError, ImplicaResult, IntoPyResult};
 use crate::matches::{Match, MatchElement};
-use crate::patterns::{TermPattern, TermSchema, TypePattern, TypeSchema};
+use crate::patterns::{CompiledDirection, TermPattern, TermSchema, TypePattern, TypeSchema};
 use crate::properties::PropertyMap;
-use crate::query::Query;
+use crate::query::{GraphBuilder, Query};
 use crate::typing::{Application, Arrow, BasicTerm, Term, Type, Variable};
 use crate::utils::hex_str_to_uid;
-use crate::{EdgeRef, NodeRef};
+use crate::{EdgeRef, NodeRef, TermRef, TypeRef};
+
+pub(crate) use __create::{CreateOptions, Creations};
+use __events::ChangeEvent;
+use __schema::Schema;
+pub use __snapshot::PySnapshot;
+use __typecheck::TypedElement;
 
 #[path = "matches/edge.rs"]
 mod __matches_edge_pattern;
+#[path = "matches/exists.rs"]
+mod __matches_exists;
 #[path = "matches/node.rs"]
 mod __matches_node_pattern;
 #[path = "matches/path.rs"]
@@ -33,9 +44,51 @@ mod __matches_properties;
 mod __matches_term_schema;
 #[path = "matches/type_schema.rs"]
 mod __matches_type_schema;
-
+#[path = "matches/variable_length.rs"]
+mod __matches_variable_length;
+#[path = "matches/within.rs"]
+mod __matches_within;
+
+#[path = "adjacency.rs"]
+mod __adjacency;
+#[path = "cache.rs"]
+mod __cache;
+#[path = "constants.rs"]
+mod __constants;
 #[path = "create.rs"]
 mod __create;
+#[path = "equality.rs"]
+mod __equality;
+#[path = "events.rs"]
+mod __events;
+#[path = "export.rs"]
+mod __export;
+#[path = "frozen.rs"]
+mod __frozen;
+#[path = "identity.rs"]
+mod __identity;
+#[path = "metadata.rs"]
+mod __metadata;
+#[path = "plan.rs"]
+mod __plan;
+#[path = "property_schema.rs"]
+mod __property_schema;
+#[path = "schema.rs"]
+mod __schema;
+#[path = "sexpr.rs"]
+mod __sexpr;
+#[path = "snapshot.rs"]
+mod __snapshot;
+#[path = "spanning.rs"]
+mod __spanning;
+#[path = "stats.rs"]
+mod __stats;
+#[path = "subgraph.rs"]
+mod __subgraph;
+#[path = "subtypes.rs"]
+mod __subtypes;
+#[path = "typecheck.rs"]
+mod __typecheck;
 
 pub type Uid = [u8; 32];
 
@@ -71,7 +124,16 @@ enum TermRep {
     Base(String),
     Application(Uid, Uid),
 }
-type EdgeSet = Arc<DashSet<(Uid, Uid)>>;
+pub(in crate::graph) type EdgeSet = Arc<DashSet<(Uid, Uid)>>;
+
+/// What a term is built from: the types each of its constants and free names occur at, and
+/// the types of all its subterms, the term's own included.
+#[derive(Debug, Default)]
+pub(crate) struct TermContext {
+    pub constants: BTreeMap<String, BTreeSet<Uid>>,
+    pub variables: BTreeMap<String, BTreeSet<Uid>>,
+    pub types: BTreeSet<Uid>,
+}
 
 #[derive(Clone, Debug)]
 pub struct Graph {
@@ -87,17 +149,78 @@ pub struct Graph {
     start_to_edge_index: Arc<DashMap<Uid, EdgeSet>>,
     end_to_edge_index: Arc<DashMap<Uid, EdgeSet>>,
 
+    /// Symbol table used to resolve `@name(...)` terms. It is copied from the constants given
+    /// at construction and never modified, so a query always resolves the same names.
     constants: Arc<DashMap<String, Constant>>,
+    /// Sum types by name, each listing the names of its case constants. Fixed at construction
+    /// like the constants.
+    sums: Arc<DashMap<String, Vec<String>>>,
+    /// Types named by declarations, such as subtypes, identity keys and property schemas.
+    /// They are kept apart from the type index, so declaring a type does not add it to the
+    /// types of the graph.
+    declared_types: Arc<DashMap<Uid, TypeRep>>,
+    /// Declared subtype relation, from each type to its direct supertypes.
+    supertypes: Arc<DashMap<Uid, HashSet<Uid>>>,
+    /// Properties identifying the node of a type, see `set_identity_key`.
+    identity_keys: Arc<DashMap<Uid, Vec<String>>>,
+    /// Properties declared for the node of a type, see `declare_property_schema`.
+    property_schemas: Arc<DashMap<Uid, __property_schema::PropertySchema>>,
+
+    node_timestamps: Arc<DashMap<Uid, __metadata::Timestamps>>,
+    edge_timestamps: Arc<DashMap<(Uid, Uid), __metadata::Timestamps>>,
+
+    version: Arc<AtomicU64>,
+    match_cache: Arc<Mutex<__cache::MatchCache>>,
+    /// Candidate nodes examined by node-pattern matching since the graph was created.
+    nodes_scanned: Arc<AtomicU64>,
+    /// Edges checked against the type or term schema of an edge pattern since the graph was
+    /// created.
+    edge_schemas_checked: Arc<AtomicU64>,
+
+    observed: Arc<AtomicBool>,
+    /// Whether the graph is read-only, see `freeze`.
+    frozen: Arc<AtomicBool>,
+    observers: Arc<Mutex<__events::Observers>>,
+
+    /// Python callable turning property values of unsupported types into exportable ones.
+    value_serializer: Arc<Mutex<Option<Arc<Py<PyAny>>>>>,
+
+    /// Times pattern matching retries reading a property map held by a writer, zero meaning
+    /// it blocks until the lock is free, and the initial backoff between tries.
+    lock_retries: Arc<AtomicU32>,
+    lock_backoff_micros: Arc<AtomicU64>,
+    /// Whether pattern matching raises on an edge whose endpoints are gone instead of
+    /// skipping it, see `set_strict_edges`.
+    strict_edges: Arc<AtomicBool>,
+
+    /// Whether integer properties are widened to floats as they are stored. Integers beyond
+    /// 2^53 are rounded in the process.
+    normalize_numerics: bool,
+
+    /// Whether edges are only walked from start to end. An undirected graph walks every
+    /// edge both ways.
+    directed: bool,
 }
 
 impl Default for Graph {
     fn default() -> Self {
-        Self::new(Vec::new())
+        Self::new(Vec::new(), Vec::new(), false, true)
     }
 }
 
 impl Graph {
-    pub(crate) fn new(constants: Vec<Constant>) -> Self {
+    pub(crate) fn new(
+        constants: Vec<Constant>,
+        sums: Vec<Sum>,
+        normalize_numerics: bool,
+        directed: bool,
+    ) -> Self {
+        // The cases of a sum type are constants in their own right
+        let constants: Vec<Constant> = constants
+            .into_iter()
+            .chain(sums.iter().flat_map(|sum| sum.cases.iter().cloned()))
+            .collect();
+
         Graph {
             nodes: Arc::new(DashMap::new()),
             edges: Arc::new(DashMap::new()),
@@ -113,6 +236,47 @@ impl Graph {
                     .map(|c| (c.name.clone(), c.clone()))
                     .collect(),
             ),
+            sums: Arc::new(
+                sums.iter()
+                    .map(|sum| {
+                        (
+                            sum.name.clone(),
+                            sum.cases.iter().map(|c| c.name.clone()).collect(),
+                        )
+                    })
+                    .collect(),
+            ),
+            declared_types: Arc::new(DashMap::new()),
+            supertypes: Arc::new(DashMap::new()),
+            identity_keys: Arc::new(DashMap::new()),
+            property_schemas: Arc::new(DashMap::new()),
+            node_timestamps: Arc::new(DashMap::new()),
+            edge_timestamps: Arc::new(DashMap::new()),
+            version: Arc::new(AtomicU64::new(0)),
+            match_cache: Arc::new(Mutex::new(__cache::MatchCache::default())),
+            nodes_scanned: Arc::new(AtomicU64::new(0)),
+            edge_schemas_checked: Arc::new(AtomicU64::new(0)),
+            observed: Arc::new(AtomicBool::new(false)),
+            frozen: Arc::new(AtomicBool::new(false)),
+            observers: Arc::new(Mutex::new(__events::Observers::default())),
+            value_serializer: Arc::new(Mutex::new(None)),
+            lock_retries: Arc::new(AtomicU32::new(0)),
+            lock_backoff_micros: Arc::new(AtomicU64::new(0)),
+            strict_edges: Arc::new(AtomicBool::new(false)),
+            normalize_numerics,
+            directed,
+        }
+    }
+
+    /// Prepares properties for storage, widening their integers when the graph normalizes
+    /// numerics.
+    fn stored_properties(&self, properties: PropertyMap) -> ImplicaResult<PropertyMap> {
+        if self.normalize_numerics {
+            properties
+                .with_widened_numerics()
+                .attach(ctx!("graph - stored properties"))
+        } else {
+            Ok(properties)
         }
     }
 
@@ -121,10 +285,21 @@ impl Graph {
         r#type: Type,
         term: Option<Term>,
         properties: PropertyMap,
+        creations: Option<&Creations>,
     ) -> ImplicaResult<Uid> {
+        self.check_not_frozen("add node")
+            .attach(ctx!("graph - add node"))?;
+
+        self.bump_version();
+
         let mut expand = false;
         let type_uid = self.insert_type(&r#type);
 
+        if !self.nodes.contains_key(&type_uid) {
+            self.check_property_schema(&type_uid, &properties)
+                .attach(ctx!("graph - add node"))?;
+        }
+
         if let Some(term) = term {
             if !self.term_index.contains_key(&type_uid) {
                 // TODO: term comparison?
@@ -144,15 +319,25 @@ impl Graph {
 
                     if !self.nodes.contains_key(&left_type) {
                         let term = self.infer_term(&left_type)?;
-                        self.add_node(arr.left.as_ref().clone(), term, PropertyMap::default())?;
+                        self.add_node(
+                            arr.left.as_ref().clone(),
+                            term,
+                            PropertyMap::default(),
+                            creations,
+                        )?;
                     }
 
                     if !self.nodes.contains_key(&right_type) {
                         let term = self.infer_term(&right_type)?;
-                        self.add_node(arr.right.as_ref().clone(), term, PropertyMap::default())?;
+                        self.add_node(
+                            arr.right.as_ref().clone(),
+                            term,
+                            PropertyMap::default(),
+                            creations,
+                        )?;
                     }
 
-                    self.add_edge(term, PropertyMap::default())?;
+                    self.add_edge(term, PropertyMap::default(), creations)?;
                 }
 
                 expand = true;
@@ -160,11 +345,19 @@ impl Graph {
         }
 
         if !self.nodes.contains_key(&type_uid) {
+            let properties = self
+                .stored_properties(properties)
+                .attach(ctx!("graph - add node"))?;
             self.nodes.insert(type_uid, properties);
+            self.record_node_created(&type_uid);
+            if let Some(creations) = creations {
+                creations.nodes.insert(type_uid);
+            }
             self.start_to_edge_index
                 .insert(type_uid, Arc::new(DashSet::new()));
             self.end_to_edge_index
                 .insert(type_uid, Arc::new(DashSet::new()));
+            self.emit(ChangeEvent::NodeAdded(type_uid));
         }
 
         if expand {
@@ -187,7 +380,7 @@ impl Graph {
 
                         let new_term = edge_term.apply(&original_term).attach(ctx!("graph - add node"))?;
 
-                        self.add_node(new_term.r#type().as_ref().clone(), Some(new_term), PropertyMap::default()).attach(ctx!("graph - add node"))?;
+                        self.add_node(new_term.r#type().as_ref().clone(), Some(new_term), PropertyMap::default(), creations).attach(ctx!("graph - add node"))?;
                     }
 
                     Ok(())
@@ -204,12 +397,48 @@ impl Graph {
         Ok(type_uid)
     }
 
+    /// Fails when the type of `term` already holds a different term.
+    ///
+    /// The term is stored under its type, which the edge's endpoints fix, so an edge
+    /// cannot take a second term alongside the one it already holds. The check leaves
+    /// the graph untouched.
+    pub(in crate::graph) fn check_edge_term(&self, term: &Term) -> ImplicaResult<()> {
+        let type_uid = Self::type_uid(term.r#type().as_ref());
+        if self.term_index.contains_key(&type_uid) {
+            let existing = self
+                .term_from_uid(&type_uid)
+                .attach(ctx!("graph - check edge term"))?;
+            if &existing != term {
+                return Err(ImplicaError::InvalidTerm {
+                    reason: format!(
+                        "type '{}' already holds the term '{}', not '{}'",
+                        term.r#type(),
+                        existing,
+                        term
+                    ),
+                }
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+
     pub(in crate::graph) fn add_edge(
         // TODO: revisar logica de esta funcion
         &self,
         term: Term,
         properties: PropertyMap,
+        creations: Option<&Creations>,
     ) -> ImplicaResult<(Uid, Uid)> {
+        self.check_not_frozen("add edge")
+            .attach(ctx!("graph - add edge"))?;
+
+        self.check_edge_term(&term)
+            .attach(ctx!("graph - add edge"))?;
+
+        self.bump_version();
+
         let term_uid = self.insert_term(&term);
 
         let edge_uid = if let Some(ref type_rep) = self.type_index.get(&term_uid) {
@@ -259,7 +488,18 @@ impl Graph {
             .into());
         }
 
-        self.edges.insert(edge_uid, properties);
+        let properties = self
+            .stored_properties(properties)
+            .attach(ctx!("graph - add edge"))?;
+        if self.edges.insert(edge_uid, properties).is_some() {
+            self.emit(ChangeEvent::EdgePropertiesSet(edge_uid));
+        } else {
+            self.emit(ChangeEvent::EdgeAdded(edge_uid));
+            if let Some(creations) = creations {
+                creations.edges.insert(edge_uid);
+            }
+        }
+        self.record_edge_created(&edge_uid);
 
         if self.term_index.contains_key(&edge_uid.0) && !self.term_index.contains_key(&edge_uid.1) {
             let start_term = self.term_from_uid(&edge_uid.0)?;
@@ -275,15 +515,25 @@ impl Graph {
 
                     if !self.nodes.contains_key(&left_type) {
                         let term = self.infer_term(&left_type)?;
-                        self.add_node(arr.left.as_ref().clone(), term, PropertyMap::default())?;
+                        self.add_node(
+                            arr.left.as_ref().clone(),
+                            term,
+                            PropertyMap::default(),
+                            creations,
+                        )?;
                     }
 
                     if !self.nodes.contains_key(&right_type) {
                         let term = self.infer_term(&right_type)?;
-                        self.add_node(arr.right.as_ref().clone(), term, PropertyMap::default())?;
+                        self.add_node(
+                            arr.right.as_ref().clone(),
+                            term,
+                            PropertyMap::default(),
+                            creations,
+                        )?;
                     }
 
-                    self.add_edge(term, PropertyMap::default())?;
+                    self.add_edge(term, PropertyMap::default(), creations)?;
                 }
             }
         }
@@ -292,7 +542,12 @@ impl Graph {
     }
 
     pub(crate) fn remove_node(&self, node_uid: &Uid) -> ImplicaResult<Option<Uid>> {
+        self.check_not_frozen("remove node")
+            .attach(ctx!("graph - remove node"))?;
+
         if let Some((uid, _)) = self.nodes.remove(node_uid) {
+            self.bump_version();
+
             let start_by_node: Vec<(Uid, Uid)> = match self.start_to_edge_index.get(&uid) {
                 Some(l) => l.value().clone(),
                 None => Arc::new(DashSet::new()),
@@ -317,6 +572,8 @@ impl Graph {
 
             self.start_to_edge_index.remove(&uid);
             self.end_to_edge_index.remove(&uid);
+            self.node_timestamps.remove(&uid);
+            self.emit(ChangeEvent::NodeRemoved(uid));
 
             Ok(Some(uid))
         } else {
@@ -325,10 +582,16 @@ impl Graph {
     }
 
     pub(crate) fn remove_edge(&self, edge_uid: &(Uid, Uid)) -> ImplicaResult<Option<(Uid, Uid)>> {
+        self.check_not_frozen("remove edge")
+            .attach(ctx!("graph - remove edge"))?;
+
         let (uid, _) = match self.edges.remove(edge_uid) {
             Some(uid) => uid,
             None => return Ok(None),
         };
+        self.bump_version();
+        self.edge_timestamps.remove(edge_uid);
+
         let (_, type_uid) = match self.edge_to_type_index.remove(edge_uid) {
             Some(pair) => pair,
             None => return Ok(None),
@@ -368,6 +631,8 @@ impl Graph {
             .into());
         }
 
+        self.emit(ChangeEvent::EdgeRemoved(uid));
+
         Ok(Some(uid))
     }
 
@@ -394,6 +659,38 @@ impl Graph {
         }
     }
 
+    /// Uid a type is interned under, computed without interning it.
+    pub(in crate::graph) fn type_uid(r#type: &Type) -> Uid {
+        match r#type {
+            Type::Variable(var) => TypeRep::Variable(var.name.clone()).uid(),
+            Type::Arrow(arr) => {
+                TypeRep::Arrow(Self::type_uid(&arr.left), Self::type_uid(&arr.right)).uid()
+            }
+        }
+    }
+
+    /// Records a type named by a declaration among the declared types, leaving the type
+    /// index untouched, and returns its uid.
+    pub(in crate::graph) fn declare_type(&self, r#type: &Type) -> Uid {
+        let type_rep = match r#type {
+            Type::Variable(var) => TypeRep::Variable(var.name.clone()),
+            Type::Arrow(arr) => {
+                TypeRep::Arrow(self.declare_type(&arr.left), self.declare_type(&arr.right))
+            }
+        };
+        let type_uid = type_rep.uid();
+
+        self.declared_types.insert(type_uid, type_rep);
+        type_uid
+    }
+
+    /// Looks a type up in the type index, then among the declared types.
+    fn lookup_type(&self, r#type: &Uid) -> Option<Ref<'_, Uid, TypeRep>> {
+        self.type_index
+            .get(r#type)
+            .or_else(|| self.declared_types.get(r#type))
+    }
+
     pub(in crate::graph) fn insert_term(&self, term: &Term) -> Uid {
         let term_type = term.r#type();
         let type_uid = self.insert_type(term_type.as_ref());
@@ -451,6 +748,11 @@ impl Graph {
                 reason: "Cannot convert wildcard to type".to_string(),
             }
             .into()),
+            TypePattern::Sum(name) => Err(ImplicaError::InvalidPattern {
+                pattern: format!("${}$", name),
+                reason: "Cannot convert a sum type to a type".to_string(),
+            }
+            .into()),
             TypePattern::Arrow { left, right } => {
                 let left_type = self
                     .pattern_to_type_recursive(left, r#match.clone())
@@ -695,7 +997,7 @@ impl Graph {
 
 impl Graph {
     pub(crate) fn type_to_string(&self, r#type: &Uid) -> ImplicaResult<String> {
-        if let Some(entry) = self.type_index.get(r#type) {
+        if let Some(entry) = self.lookup_type(r#type) {
             let type_rep = entry.value();
 
             match type_rep {
@@ -740,6 +1042,106 @@ impl Graph {
         }
     }
 
+    /// Collects the constants and free names a term references, with the types they occur
+    /// at, and the types of its subterms.
+    pub(crate) fn term_context(&self, term: &Uid) -> ImplicaResult<TermContext> {
+        let mut context = TermContext::default();
+        self.collect_term_context(term, &mut context)
+            .attach(ctx!("graph - term context"))?;
+        Ok(context)
+    }
+
+    fn collect_term_context(&self, term: &Uid, context: &mut TermContext) -> ImplicaResult<()> {
+        let term_rep = match self.term_index.get(term) {
+            Some(entry) => entry.value().clone(),
+            None => {
+                return Err(ImplicaError::TermNotFound {
+                    uid: *term,
+                    context: Some("term context".to_string()),
+                }
+                .into())
+            }
+        };
+
+        // A term is stored under the uid of its type
+        context.types.insert(*term);
+
+        match term_rep {
+            TermRep::Base(name) => {
+                let names = if self.constants.contains_key(&name) {
+                    &mut context.constants
+                } else {
+                    &mut context.variables
+                };
+                names.entry(name).or_default().insert(*term);
+                Ok(())
+            }
+            TermRep::Application(func, arg) => {
+                self.collect_term_context(&func, context)?;
+                self.collect_term_context(&arg, context)
+            }
+        }
+    }
+
+    /// Applies the term stored under `function` to the one stored under `argument`, returning
+    /// the uid of the result. The argument must have the type the function expects first, so
+    /// a curried `A -> B -> C` applied to an `A` yields a `B -> C`, e.g. to label a new edge.
+    ///
+    /// The result is stored like any other term, under its type. A type holds a single term,
+    /// so the application fails when its type already holds a different one.
+    pub(crate) fn apply_term(&self, function: &Uid, argument: &Uid) -> ImplicaResult<Uid> {
+        self.check_not_frozen("apply term")
+            .attach(ctx!("graph - apply term"))?;
+
+        let function = self
+            .term_from_uid(function)
+            .attach(ctx!("graph - apply term"))?;
+        let argument = self
+            .term_from_uid(argument)
+            .attach(ctx!("graph - apply term"))?;
+        let applied = function
+            .apply(&argument)
+            .attach(ctx!("graph - apply term"))?;
+
+        self.intern_term(&applied)
+            .attach(ctx!("graph - apply term"))
+    }
+
+    /// Stores a term and its subterms under their types, returning its uid. A type holds a
+    /// single term, so this fails when one of them already holds a different term; terms
+    /// already stored are left as they are.
+    pub(in crate::graph) fn intern_term(&self, term: &Term) -> ImplicaResult<Uid> {
+        if let Term::Application(app) = term {
+            self.intern_term(&app.function)
+                .attach(ctx!("graph - intern term"))?;
+            self.intern_term(&app.argument)
+                .attach(ctx!("graph - intern term"))?;
+        }
+
+        let r#type = self.insert_type(term.r#type().as_ref());
+        if self.term_index.contains_key(&r#type) {
+            let existing = self
+                .term_from_uid(&r#type)
+                .attach(ctx!("graph - intern term"))?;
+            if existing != *term {
+                return Err(ImplicaError::InvalidTerm {
+                    reason: format!(
+                        "type '{}' already holds the term '{}', not '{}'",
+                        term.r#type(),
+                        existing,
+                        term
+                    ),
+                }
+                .into());
+            }
+
+            return Ok(r#type);
+        }
+
+        self.bump_version();
+        Ok(self.insert_term(term))
+    }
+
     pub(crate) fn node_to_string(&self, node: &Uid) -> ImplicaResult<String> {
         if let Some(entry) = self.nodes.get(node) {
             let props = entry.value();
@@ -821,8 +1223,25 @@ impl Graph {
         properties: PropertyMap,
         overwrite: bool,
     ) -> ImplicaResult<()> {
+        self.check_not_frozen("set node properties")
+            .attach(ctx!("graph - set node properties"))?;
+
+        if overwrite {
+            self.check_property_schema(node, &properties)
+        } else {
+            self.check_merged_property_schema(node, &properties)
+        }
+        .attach(ctx!("graph - set node properties"))?;
+
+        self.bump_version();
+        let properties = self
+            .stored_properties(properties)
+            .attach(ctx!("graph - set node properties"))?;
+
         if overwrite {
             self.nodes.insert(*node, properties);
+            self.record_node_modified(node);
+            self.emit(ChangeEvent::NodePropertiesSet(*node));
             Ok(())
         } else if let Some(mut entry) = self.nodes.get_mut(node) {
             let node_props = entry.value_mut();
@@ -835,6 +1254,9 @@ impl Graph {
                     .insert(k.to_string(), v)
                     .attach(ctx!("graph - set node properties"))?;
             }
+            drop(entry);
+            self.record_node_modified(node);
+            self.emit(ChangeEvent::NodePropertiesSet(*node));
 
             Ok(())
         } else {
@@ -852,8 +1274,18 @@ impl Graph {
         properties: PropertyMap,
         overwrite: bool,
     ) -> ImplicaResult<()> {
+        self.check_not_frozen("set edge properties")
+            .attach(ctx!("graph - set edge properties"))?;
+
+        self.bump_version();
+        let properties = self
+            .stored_properties(properties)
+            .attach(ctx!("graph - set edge properties"))?;
+
         if overwrite {
             self.edges.insert(*edge, properties);
+            self.record_edge_modified(edge);
+            self.emit(ChangeEvent::EdgePropertiesSet(*edge));
             Ok(())
         } else if let Some(mut entry) = self.edges.get_mut(edge) {
             let node_props = entry.value_mut();
@@ -866,6 +1298,9 @@ impl Graph {
                     .insert(k.to_string(), v)
                     .attach(ctx!("graph - set node properties"))?;
             }
+            drop(entry);
+            self.record_edge_modified(edge);
+            self.emit(ChangeEvent::EdgePropertiesSet(*edge));
 
             Ok(())
         } else {
@@ -876,10 +1311,106 @@ impl Graph {
             .into())
         }
     }
+
+    /// Detaches the term of a node, keeping the node and its properties.
+    ///
+    /// The term of an arrow-typed node also labels the edge between its endpoints, and other
+    /// stored terms may apply it or be applied to it. With `cascade`, such an edge is removed
+    /// and the terms built from the cleared one are cleared as well, transitively; without
+    /// it, the call fails and leaves the graph untouched. Returns whether the node had a
+    /// term to clear.
+    pub(crate) fn clear_term(&self, node: &Uid, cascade: bool) -> ImplicaResult<bool> {
+        self.check_not_frozen("clear term")
+            .attach(ctx!("graph - clear term"))?;
+
+        if !self.nodes.contains_key(node) {
+            return Err(ImplicaError::NodeNotFound {
+                uid: *node,
+                context: Some(ctx!("graph - clear term")),
+            }
+            .into());
+        }
+
+        if !self.term_index.contains_key(node) {
+            return Ok(false);
+        }
+
+        if !cascade {
+            if self.type_to_edge_index.contains_key(node) {
+                return Err(ImplicaError::InvalidTerm {
+                    reason: "the term labels an edge of the graph, clear it with cascade=True to remove the edge as well".to_string(),
+                }
+                .into());
+            }
+
+            if !self.dependent_terms(node).is_empty() {
+                return Err(ImplicaError::InvalidTerm {
+                    reason: "other terms are built from the term, clear it with cascade=True to clear them as well".to_string(),
+                }
+                .into());
+            }
+        }
+
+        self.bump_version();
+        self.detach_term(node).attach(ctx!("graph - clear term"))?;
+
+        Ok(true)
+    }
 }
 
 impl Graph {
+    /// Types whose stored term is an application of the term of `r#type`, or of something
+    /// to it.
+    fn dependent_terms(&self, r#type: &Uid) -> Vec<Uid> {
+        self.term_index
+            .iter()
+            .filter_map(|entry| match entry.value() {
+                TermRep::Application(function, argument)
+                    if function == r#type || argument == r#type =>
+                {
+                    Some(*entry.key())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Removes the term stored under a type together with the terms built from it and the
+    /// edges they label.
+    fn detach_term(&self, r#type: &Uid) -> ImplicaResult<()> {
+        if self.term_index.remove(r#type).is_none() {
+            return Ok(());
+        }
+
+        for dependent in self.dependent_terms(r#type) {
+            self.detach_term(&dependent)
+                .attach(ctx!("graph - detach term"))?;
+        }
+
+        let edge = self
+            .type_to_edge_index
+            .get(r#type)
+            .map(|entry| *entry.value());
+        if let Some(edge) = edge {
+            self.remove_edge(&edge)
+                .attach(ctx!("graph - detach term"))?;
+        }
+
+        if self.nodes.contains_key(r#type) {
+            self.record_node_modified(r#type);
+            self.emit(ChangeEvent::NodeTermCleared(*r#type));
+        }
+
+        Ok(())
+    }
+
+    /// Infers the term of a node from the registered constants.
+    ///
+    /// A term is only inferred when exactly one constant fits the type; with several
+    /// candidates the choice would be arbitrary, so the term is left unset.
     fn infer_term(&self, r#type: &Uid) -> ImplicaResult<Option<Term>> {
+        let mut candidate: Option<String> = None;
+
         for entry in self.constants.iter() {
             let constant = entry.value();
 
@@ -892,16 +1423,25 @@ impl Graph {
                 .attach(ctx!("graph - infer term"))?
                 .is_some()
             {
+                if candidate.is_some() {
+                    return Ok(None);
+                }
+
+                candidate = Some(constant.name.clone());
+            }
+        }
+
+        match candidate {
+            Some(name) => {
                 let term_type = self
                     .type_from_uid(r#type)
                     .attach(ctx!("graph - infer term"))?;
-                return Ok(Some(Term::Basic(
-                    BasicTerm::new(constant.name.clone(), Arc::new(term_type))
-                        .attach(ctx!("graph - infer term"))?,
-                )));
+                Ok(Some(Term::Basic(
+                    BasicTerm::new(name, Arc::new(term_type)).attach(ctx!("graph - infer term"))?,
+                )))
             }
+            None => Ok(None),
         }
-        Ok(None)
     }
 }
 
@@ -930,18 +1470,24 @@ pub struct PyGraph {
 
 impl Default for PyGraph {
     fn default() -> Self {
-        Self::new(None)
+        Self::new(None, false, None, true)
     }
 }
 
 #[pymethods]
 impl PyGraph {
     #[new]
-    #[pyo3(signature=(constants=None))]
-    pub fn new(constants: Option<Vec<Constant>>) -> Self {
+    #[pyo3(signature=(constants=None, normalize_numerics=false, sums=None, directed=true))]
+    pub fn new(
+        constants: Option<Vec<Constant>>,
+        normalize_numerics: bool,
+        sums: Option<Vec<Sum>>,
+        directed: bool,
+    ) -> Self {
         let constants = constants.unwrap_or_default();
+        let sums = sums.unwrap_or_default();
 
-        let graph = Graph::new(constants);
+        let graph = Graph::new(constants, sums, normalize_numerics, directed);
 
         PyGraph {
             graph: Arc::new(graph),
@@ -952,6 +1498,10 @@ impl PyGraph {
         Query::new(self.graph.clone())
     }
 
+    pub fn build(&self) -> GraphBuilder {
+        GraphBuilder::new(self.graph.clone())
+    }
+
     pub fn nodes(&self) -> Vec<NodeRef> {
         self.graph
             .nodes
@@ -968,6 +1518,462 @@ impl PyGraph {
             .collect()
     }
 
+    /// Groups the edges by their type, or by their term with `by="term"`, keyed by its string
+    /// form.
+    #[pyo3(signature = (by="type"))]
+    pub fn edges_by_type(&self, by: &str) -> PyResult<BTreeMap<String, Vec<EdgeRef>>> {
+        let by_term = match by {
+            "type" => false,
+            "term" => true,
+            other => {
+                return Err(ImplicaError::InvalidQuery {
+                    query: format!("edges_by_type(by='{}')", other),
+                    reason: "edges can be grouped by 'type' or 'term'".to_string(),
+                    context: Some(ctx!("graph - edges by type")),
+                }
+                .into())
+                .into_py_result()
+            }
+        };
+
+        let groups = self
+            .graph
+            .edges_grouped(by_term)
+            .attach(ctx!("graph - edges by type"))
+            .into_py_result()?;
+
+        Ok(groups
+            .into_iter()
+            .map(|(key, edges)| {
+                let refs = edges
+                    .into_iter()
+                    .map(|edge| EdgeRef::new(self.graph.clone(), edge))
+                    .collect();
+                (key, refs)
+            })
+            .collect())
+    }
+
+    /// Whether edges are only walked from start to end.
+    pub fn is_directed(&self) -> bool {
+        self.graph.directed
+    }
+
+    /// Edges leaving a node; in an undirected graph, every edge touching it.
+    pub fn out_edges(&self, node: &Bound<PyAny>) -> PyResult<Vec<EdgeRef>> {
+        let uid = Self::node_uid(node)?;
+
+        let edges = self
+            .graph
+            .walkable_edges(&uid, &CompiledDirection::Forward)
+            .attach(ctx!("graph - out edges"))
+            .into_py_result()?;
+
+        Ok(edges
+            .into_iter()
+            .map(|edge| EdgeRef::new(self.graph.clone(), edge))
+            .collect())
+    }
+
+    /// Edges entering a node; in an undirected graph, every edge touching it.
+    pub fn in_edges(&self, node: &Bound<PyAny>) -> PyResult<Vec<EdgeRef>> {
+        let uid = Self::node_uid(node)?;
+
+        let edges = self
+            .graph
+            .walkable_edges(&uid, &CompiledDirection::Backward)
+            .attach(ctx!("graph - in edges"))
+            .into_py_result()?;
+
+        Ok(edges
+            .into_iter()
+            .map(|edge| EdgeRef::new(self.graph.clone(), edge))
+            .collect())
+    }
+
+    /// Nodes without incoming edges; in an undirected graph, nodes without any edge.
+    pub fn sources(&self) -> Vec<NodeRef> {
+        self.node_refs(self.graph.nodes_without_edges(true, false))
+    }
+
+    /// Nodes without outgoing edges; in an undirected graph, nodes without any edge.
+    pub fn sinks(&self) -> Vec<NodeRef> {
+        self.node_refs(self.graph.nodes_without_edges(false, true))
+    }
+
+    /// Nodes without any edge.
+    pub fn isolated(&self) -> Vec<NodeRef> {
+        self.node_refs(self.graph.nodes_without_edges(true, true))
+    }
+
+    pub fn on_change(&self, callback: Py<PyAny>) -> PyResult<()> {
+        self.graph
+            .on_change(callback)
+            .attach(ctx!("graph - on change"))
+            .into_py_result()
+    }
+
+    /// Declares `sub` a subtype of `sup`, for patterns using the `subtype_of` node modifier.
+    pub fn declare_subtype(&self, sub: String, sup: String) -> PyResult<()> {
+        let sub = TypeSchema::new(sub)
+            .attach(ctx!("graph - declare subtype"))
+            .into_py_result()?;
+        let sup = TypeSchema::new(sup)
+            .attach(ctx!("graph - declare subtype"))
+            .into_py_result()?;
+
+        self.graph
+            .declare_subtype(&sub, &sup)
+            .attach(ctx!("graph - declare subtype"))
+            .into_py_result()
+    }
+
+    /// Makes the graph read-only: every later mutation raises until `unfreeze` is called,
+    /// so the graph can be handed to parallel readers safely.
+    pub fn freeze(&self) {
+        self.graph.freeze();
+    }
+
+    pub fn unfreeze(&self) {
+        self.graph.unfreeze();
+    }
+
+    pub fn is_frozen(&self) -> bool {
+        self.graph.is_frozen()
+    }
+
+    /// Designates properties of the node of type `type` as its identity: creating that type
+    /// again with different values for them raises instead of reusing the node. An empty
+    /// list removes the key.
+    pub fn set_identity_key(&self, r#type: String, keys: Vec<String>) -> PyResult<()> {
+        let r#type = TypeSchema::new(r#type)
+            .attach(ctx!("graph - set identity key"))
+            .into_py_result()?;
+
+        self.graph
+            .set_identity_key(&r#type, keys)
+            .attach(ctx!("graph - set identity key"))
+            .into_py_result()
+    }
+
+    /// Declares the properties of the node of type `type`, mapping each key to `"str"`,
+    /// `"int"`, `"float"`, `"bool"`, `"list"` or `"dict"`, with a trailing `?` when the
+    /// property may be absent. Creating the node or setting its properties then fails unless
+    /// the required ones are present and every declared one has its type. An empty dict
+    /// removes the schema.
+    pub fn declare_schema(&self, r#type: String, schema: BTreeMap<String, String>) -> PyResult<()> {
+        let r#type = TypeSchema::new(r#type)
+            .attach(ctx!("graph - declare schema"))
+            .into_py_result()?;
+
+        self.graph
+            .declare_property_schema(&r#type, schema)
+            .attach(ctx!("graph - declare schema"))
+            .into_py_result()
+    }
+
+    pub fn version(&self) -> u64 {
+        self.graph.version()
+    }
+
+    pub fn snapshot(&self) -> PyResult<PySnapshot> {
+        let snapshot = self
+            .graph
+            .snapshot()
+            .attach(ctx!("graph - snapshot"))
+            .into_py_result()?;
+
+        Ok(PySnapshot {
+            graph: self.graph.clone(),
+            snapshot: Arc::new(snapshot),
+        })
+    }
+
+    pub fn restore(&self, checkpoint: &PySnapshot) -> PyResult<()> {
+        checkpoint
+            .check_source(&self.graph)
+            .attach(ctx!("graph - restore"))
+            .into_py_result()?;
+
+        self.graph
+            .restore(&checkpoint.snapshot)
+            .attach(ctx!("graph - restore"))
+            .into_py_result()
+    }
+
+    /// Describes the constants, sum types, declared subtypes, identity keys and property
+    /// schemas of the graph and whether it is directed, leaving out its nodes and edges, as a
+    /// dict of plain lists and strings.
+    pub fn export_schema<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let schema = self
+            .graph
+            .schema()
+            .attach(ctx!("graph - export schema"))
+            .into_py_result()?;
+
+        let constants = PyList::empty(py);
+        for (name, type_schema) in schema.constants {
+            let constant = PyDict::new(py);
+            constant.set_item("name", name)?;
+            constant.set_item("type", type_schema)?;
+            constants.append(constant)?;
+        }
+
+        let sums = PyList::empty(py);
+        for (name, cases) in schema.sums {
+            let sum = PyDict::new(py);
+            sum.set_item("name", name)?;
+            sum.set_item("cases", cases)?;
+            sums.append(sum)?;
+        }
+
+        let subtypes = PyList::empty(py);
+        for (sub, sup) in schema.subtypes {
+            let subtype = PyDict::new(py);
+            subtype.set_item("sub", sub)?;
+            subtype.set_item("sup", sup)?;
+            subtypes.append(subtype)?;
+        }
+
+        let identity_keys = PyList::empty(py);
+        for (r#type, keys) in schema.identity_keys {
+            let identity_key = PyDict::new(py);
+            identity_key.set_item("type", r#type)?;
+            identity_key.set_item("keys", keys)?;
+            identity_keys.append(identity_key)?;
+        }
+
+        let property_schemas = PyList::empty(py);
+        for (r#type, declarations) in schema.property_schemas {
+            let property_schema = PyDict::new(py);
+            property_schema.set_item("type", r#type)?;
+            property_schema.set_item("properties", declarations)?;
+            property_schemas.append(property_schema)?;
+        }
+
+        let description = PyDict::new(py);
+        description.set_item("constants", constants)?;
+        description.set_item("sums", sums)?;
+        description.set_item("subtypes", subtypes)?;
+        description.set_item("identity_keys", identity_keys)?;
+        description.set_item("property_schemas", property_schemas)?;
+        description.set_item("directed", schema.directed)?;
+        Ok(description)
+    }
+
+    /// Creates an empty graph with the vocabulary of a schema returned by `export_schema`.
+    #[staticmethod]
+    #[pyo3(signature = (schema, normalize_numerics=false))]
+    pub fn load_schema(schema: &Bound<PyDict>, normalize_numerics: bool) -> PyResult<PyGraph> {
+        let mut description = Schema {
+            directed: true,
+            ..Schema::default()
+        };
+
+        if let Some(constants) = schema.get_item("constants")? {
+            for constant in constants.try_iter()? {
+                let constant = constant?;
+                description.constants.push((
+                    constant.get_item("name")?.extract()?,
+                    constant.get_item("type")?.extract()?,
+                ));
+            }
+        }
+        if let Some(sums) = schema.get_item("sums")? {
+            for sum in sums.try_iter()? {
+                let sum = sum?;
+                description.sums.push((
+                    sum.get_item("name")?.extract()?,
+                    sum.get_item("cases")?.extract()?,
+                ));
+            }
+        }
+        if let Some(subtypes) = schema.get_item("subtypes")? {
+            for subtype in subtypes.try_iter()? {
+                let subtype = subtype?;
+                description.subtypes.push((
+                    subtype.get_item("sub")?.extract()?,
+                    subtype.get_item("sup")?.extract()?,
+                ));
+            }
+        }
+        if let Some(directed) = schema.get_item("directed")? {
+            description.directed = directed.extract()?;
+        }
+        if let Some(identity_keys) = schema.get_item("identity_keys")? {
+            for identity_key in identity_keys.try_iter()? {
+                let identity_key = identity_key?;
+                description.identity_keys.push((
+                    identity_key.get_item("type")?.extract()?,
+                    identity_key.get_item("keys")?.extract()?,
+                ));
+            }
+        }
+        if let Some(property_schemas) = schema.get_item("property_schemas")? {
+            for property_schema in property_schemas.try_iter()? {
+                let property_schema = property_schema?;
+                description.property_schemas.push((
+                    property_schema.get_item("type")?.extract()?,
+                    property_schema.get_item("properties")?.extract()?,
+                ));
+            }
+        }
+
+        let graph = Graph::from_schema(&description, normalize_numerics)
+            .attach(ctx!("graph - load schema"))
+            .into_py_result()?;
+
+        Ok(PyGraph {
+            graph: Arc::new(graph),
+        })
+    }
+
+    pub fn stats<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let stats = self.graph.stats();
+
+        let summary = PyDict::new(py);
+        summary.set_item("nodes", stats.nodes)?;
+        summary.set_item("edges", stats.edges)?;
+        summary.set_item("types", stats.types)?;
+        summary.set_item("terms", stats.terms)?;
+        summary.set_item("average_degree", stats.average_degree)?;
+        summary.set_item("max_degree", stats.max_degree)?;
+        Ok(summary)
+    }
+
+    /// Checks that every edge's arrow type goes between the types of its endpoints and that
+    /// every term has the type of the node or edge holding it. Returns one dict per
+    /// violation, with the `kind` (`"node"` or `"edge"`) and `uid` of the element and the
+    /// `expected` and `actual` types; an empty list means the graph is consistent.
+    pub fn type_check<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        let violations = self
+            .graph
+            .type_check()
+            .attach(ctx!("graph - type check"))
+            .into_py_result()?;
+
+        violations
+            .into_iter()
+            .map(|violation| {
+                let report = PyDict::new(py);
+                match violation.element {
+                    TypedElement::Node(uid) => {
+                        report.set_item("kind", "node")?;
+                        report.set_item("uid", hex::encode(uid))?;
+                    }
+                    TypedElement::Edge((start, end)) => {
+                        report.set_item("kind", "edge")?;
+                        report.set_item("uid", (hex::encode(start), hex::encode(end)))?;
+                    }
+                }
+                report.set_item("expected", violation.expected)?;
+                report.set_item("actual", violation.actual)?;
+                Ok(report)
+            })
+            .collect()
+    }
+
+    pub fn scan_info<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let info = PyDict::new(py);
+        info.set_item("nodes_scanned", self.graph.nodes_scanned())?;
+        info.set_item("edge_schemas_checked", self.graph.edge_schemas_checked())?;
+        Ok(info)
+    }
+
+    pub fn cache_info<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let (hits, misses, size) = self
+            .graph
+            .cache_info()
+            .attach(ctx!("graph - cache info"))
+            .into_py_result()?;
+
+        let info = PyDict::new(py);
+        info.set_item("hits", hits)?;
+        info.set_item("misses", misses)?;
+        info.set_item("size", size)?;
+        Ok(info)
+    }
+
+    /// Registers the callable exporters use to serialize property values of types they do
+    /// not support, such as custom objects. Passing `None` removes it.
+    #[pyo3(signature = (serializer))]
+    pub fn set_value_serializer(&self, serializer: Option<Py<PyAny>>) -> PyResult<()> {
+        self.graph
+            .set_value_serializer(serializer)
+            .attach(ctx!("graph - set value serializer"))
+            .into_py_result()
+    }
+
+    /// Makes pattern matching retry reading properties locked by a concurrent write up to
+    /// `attempts` times, waiting `backoff_ms` milliseconds and doubling the wait after each
+    /// try, before raising. Zero attempts, the default, blocks until the lock is free.
+    #[pyo3(signature = (attempts, backoff_ms=1))]
+    pub fn set_lock_retry(&self, attempts: u32, backoff_ms: u64) {
+        self.graph
+            .set_lock_retry(attempts, std::time::Duration::from_millis(backoff_ms));
+    }
+
+    /// Makes pattern matching raise an `IndexError` on an edge whose start or end node is
+    /// no longer in the graph, instead of skipping it. An edge whose node is being removed
+    /// by another thread counts as well, so this is meant for graphs whose matches do not
+    /// race removals.
+    pub fn set_strict_edges(&self, strict: bool) {
+        self.graph.set_strict_edges(strict);
+    }
+
+    #[pyo3(signature = (weight=None))]
+    pub fn adjacency_matrix<'py>(
+        &self,
+        py: Python<'py>,
+        weight: Option<String>,
+    ) -> PyResult<(Bound<'py, PyAny>, Vec<NodeRef>)> {
+        let (order, matrix) = self
+            .graph
+            .adjacency_matrix(weight.as_deref())
+            .attach(ctx!("graph - adjacency matrix"))
+            .into_py_result()?;
+
+        let nodes = order
+            .into_iter()
+            .map(|uid| NodeRef::new(self.graph.clone(), uid))
+            .collect();
+
+        let matrix = if weight.is_some() {
+            matrix.into_pyobject(py)?.into_any()
+        } else {
+            matrix
+                .into_iter()
+                .map(|row| row.into_iter().map(|v| v as i64).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+                .into_pyobject(py)?
+                .into_any()
+        };
+
+        Ok((matrix, nodes))
+    }
+
+    /// Returns the edges of a minimum spanning forest, weighted by the `weight` property,
+    /// together with their total weight.
+    #[pyo3(signature = (weight, default=None))]
+    pub fn minimum_spanning_tree(
+        &self,
+        weight: String,
+        default: Option<f64>,
+    ) -> PyResult<(Vec<EdgeRef>, f64)> {
+        let (edges, total) = self
+            .graph
+            .minimum_spanning_tree(&weight, default)
+            .attach(ctx!("graph - minimum spanning tree"))
+            .into_py_result()?;
+
+        let edges = edges
+            .into_iter()
+            .map(|edge| EdgeRef::new(self.graph.clone(), edge))
+            .collect();
+
+        Ok((edges, total))
+    }
+
     #[pyo3(signature = (map, overwrite=true))]
     pub fn set_node_properties(&self, map: &Bound<PyAny>, overwrite: bool) -> PyResult<()> {
         let dict = map.cast::<PyDict>()?;
@@ -995,6 +2001,11 @@ impl PyGraph {
             }
         });
 
+        self.graph
+            .notify_observers()
+            .attach(ctx!("graph - set node properties"))
+            .into_py_result()?;
+
         match result {
             ControlFlow::Continue(()) => Ok(()),
             ControlFlow::Break(e) => Err(e).into_py_result(),
@@ -1031,9 +2042,175 @@ impl PyGraph {
             }
         });
 
+        self.graph
+            .notify_observers()
+            .attach(ctx!("graph - set edge properties"))
+            .into_py_result()?;
+
         match result {
             ControlFlow::Continue(()) => Ok(()),
             ControlFlow::Break(e) => Err(e).into_py_result(),
         }
     }
+
+    /// Merges the properties of each node in the mapping, keyed by uid, into the ones it
+    /// already has. Uids that match no node are skipped and returned; with `parallel` the
+    /// nodes are written concurrently.
+    #[pyo3(signature = (mapping, parallel=true))]
+    pub fn set_properties_by_uid(
+        &self,
+        mapping: &Bound<PyDict>,
+        parallel: bool,
+    ) -> PyResult<Vec<String>> {
+        let mut entries = Vec::with_capacity(mapping.len());
+
+        for (key, value) in mapping.iter() {
+            let key_str: String = key.extract()?;
+            let uid = hex_str_to_uid(&key_str)
+                .attach(ctx!("graph - set properties by uid"))
+                .into_py_result()?;
+            let property_map = PropertyMap::new(&value)
+                .attach(ctx!("graph - set properties by uid"))
+                .into_py_result()?;
+
+            entries.push((key_str, uid, property_map));
+        }
+
+        let apply = |(key, uid, properties): &(String, Uid, PropertyMap)| match self
+            .graph
+            .set_node_properties(uid, properties.clone(), false)
+        {
+            Ok(()) => Ok(None),
+            Err(e) if matches!(e.current_context(), ImplicaError::NodeNotFound { .. }) => {
+                Ok(Some(key.clone()))
+            }
+            Err(e) => Err(e.attach(ctx!("graph - set properties by uid"))),
+        };
+
+        let result = if parallel {
+            entries
+                .par_iter()
+                .map(apply)
+                .collect::<ImplicaResult<Vec<_>>>()
+        } else {
+            entries.iter().map(apply).collect::<ImplicaResult<Vec<_>>>()
+        };
+
+        self.graph
+            .notify_observers()
+            .attach(ctx!("graph - set properties by uid"))
+            .into_py_result()?;
+
+        Ok(result.into_py_result()?.into_iter().flatten().collect())
+    }
+
+    /// Returns a copy of the graph in which each node's properties are replaced by the dict
+    /// `callback` returns for it. The callback receives the node of this graph; types, terms
+    /// and edges are copied unchanged.
+    pub fn map_nodes(&self, callback: &Bound<PyAny>) -> PyResult<PyGraph> {
+        let graph = self
+            .graph
+            .copy()
+            .attach(ctx!("graph - map nodes"))
+            .into_py_result()?;
+        let nodes: Vec<Uid> = graph.nodes.iter().map(|entry| *entry.key()).collect();
+
+        for uid in nodes {
+            let node = NodeRef::new(self.graph.clone(), uid);
+            let result = callback.call1((node,))?;
+            let properties = PropertyMap::new(result.cast::<PyDict>()?.as_any())
+                .attach(ctx!("graph - map nodes"))
+                .into_py_result()?;
+
+            graph
+                .set_node_properties(&uid, properties, true)
+                .attach(ctx!("graph - map nodes"))
+                .into_py_result()?;
+        }
+
+        Ok(PyGraph::from_graph(graph))
+    }
+
+    /// Returns a copy of the graph in which each edge's properties are replaced by the dict
+    /// `callback` returns for it, like `map_nodes` for edges.
+    pub fn map_edges(&self, callback: &Bound<PyAny>) -> PyResult<PyGraph> {
+        let graph = self
+            .graph
+            .copy()
+            .attach(ctx!("graph - map edges"))
+            .into_py_result()?;
+        let edges: Vec<(Uid, Uid)> = graph.edges.iter().map(|entry| *entry.key()).collect();
+
+        for uid in edges {
+            let edge = EdgeRef::new(self.graph.clone(), uid);
+            let result = callback.call1((edge,))?;
+            let properties = PropertyMap::new(result.cast::<PyDict>()?.as_any())
+                .attach(ctx!("graph - map edges"))
+                .into_py_result()?;
+
+            graph
+                .set_edge_properties(&uid, properties, true)
+                .attach(ctx!("graph - map edges"))
+                .into_py_result()?;
+        }
+
+        Ok(PyGraph::from_graph(graph))
+    }
+}
+
+impl PyGraph {
+    pub(crate) fn from_graph(graph: Graph) -> Self {
+        PyGraph {
+            graph: Arc::new(graph),
+        }
+    }
+
+    pub(crate) fn type_from_sexpr(&self, sexpr: &str) -> PyResult<TypeRef> {
+        let uid = self
+            .graph
+            .type_from_sexpr(sexpr)
+            .attach(ctx!("graph - type from sexpr"))
+            .into_py_result()?;
+
+        Ok(TypeRef::new(self.graph.clone(), uid))
+    }
+
+    pub(crate) fn term_from_sexpr(&self, sexpr: &str) -> PyResult<TermRef> {
+        let uid = self
+            .graph
+            .term_from_sexpr(sexpr)
+            .attach(ctx!("graph - term from sexpr"))
+            .into_py_result()?;
+
+        Ok(TermRef::new(self.graph.clone(), uid))
+    }
+
+    pub(crate) fn constant_type(&self, constant: &Constant) -> PyResult<TypeRef> {
+        let uid = self
+            .graph
+            .constant_type(constant)
+            .attach(ctx!("graph - constant type"))
+            .into_py_result()?;
+
+        Ok(TypeRef::new(self.graph.clone(), uid))
+    }
+
+    fn node_refs(&self, nodes: Vec<Uid>) -> Vec<NodeRef> {
+        nodes
+            .into_iter()
+            .map(|uid| NodeRef::new(self.graph.clone(), uid))
+            .collect()
+    }
+
+    /// Accepts either a `Node` or its hex uid.
+    pub(crate) fn node_uid(node: &Bound<PyAny>) -> PyResult<Uid> {
+        let uid = match node.cast::<NodeRef>() {
+            Ok(node) => node.borrow().uid(),
+            Err(_) => node.extract::<String>()?,
+        };
+
+        hex_str_to_uid(&uid)
+            .attach(ctx!("graph - node uid"))
+            .into_py_result()
+    }
 }
@@ -0,0 +1,100 @@
+use std::collections::VecDeque;
+use std::sync::atomic::Ordering;
+
+use crate::ctx;
+use crate::errors::{ImplicaError, ImplicaResult};
+use crate::graph::base::Graph;
+use crate::matches::MatchSet;
+
+const MATCH_CACHE_CAPACITY: usize = 32;
+
+/// Least-recently-used cache of match results keyed on the query text and graph version.
+#[derive(Debug, Default)]
+pub(in crate::graph) struct MatchCache {
+    entries: VecDeque<(String, u64, MatchSet)>,
+    hits: u64,
+    misses: u64,
+}
+
+impl Graph {
+    /// Current version of the graph, bumped by every mutation.
+    pub(crate) fn version(&self) -> u64 {
+        self.version.load(Ordering::SeqCst)
+    }
+
+    pub(in crate::graph) fn bump_version(&self) {
+        self.version.fetch_add(1, Ordering::SeqCst);
+    }
+
+    /// Looks up the matches stored for `key` at the current graph version.
+    pub(crate) fn cached_matches(&self, key: &str) -> ImplicaResult<Option<MatchSet>> {
+        let version = self.version();
+        let mut cache = self
+            .match_cache
+            .lock()
+            .map_err(|e| ImplicaError::LockError {
+                rw: "write".to_string(),
+                message: e.to_string(),
+                context: Some(ctx!("graph - cached matches")),
+            })?;
+
+        match cache
+            .entries
+            .iter()
+            .position(|(k, v, _)| k == key && *v == version)
+        {
+            Some(idx) => {
+                cache.hits += 1;
+                let entry = cache.entries.remove(idx);
+
+                Ok(entry.map(|entry| {
+                    let matches = entry.2.clone();
+                    cache.entries.push_front(entry);
+                    matches
+                }))
+            }
+            None => {
+                cache.misses += 1;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Stores the matches computed for `key` at the given graph version, evicting the least
+    /// recently used entry when the cache is full.
+    pub(crate) fn store_matches(
+        &self,
+        key: String,
+        version: u64,
+        matches: MatchSet,
+    ) -> ImplicaResult<()> {
+        let mut cache = self
+            .match_cache
+            .lock()
+            .map_err(|e| ImplicaError::LockError {
+                rw: "write".to_string(),
+                message: e.to_string(),
+                context: Some(ctx!("graph - store matches")),
+            })?;
+
+        cache.entries.retain(|(k, _, _)| k != &key);
+        cache.entries.push_front((key, version, matches));
+        cache.entries.truncate(MATCH_CACHE_CAPACITY);
+
+        Ok(())
+    }
+
+    /// Returns `(hits, misses, size)` of the match cache.
+    pub(crate) fn cache_info(&self) -> ImplicaResult<(u64, u64, usize)> {
+        let cache = self
+            .match_cache
+            .lock()
+            .map_err(|e| ImplicaError::LockError {
+                rw: "read".to_string(),
+                message: e.to_string(),
+                context: Some(ctx!("graph - cache info")),
+            })?;
+
+        Ok((cache.hits, cache.misses, cache.entries.len()))
+    }
+}
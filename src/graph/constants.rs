@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+use error_stack::ResultExt;
+
+use crate::constants::Constant;
+use crate::ctx;
+use crate::errors::{ImplicaError, ImplicaResult};
+use crate::graph::base::{Graph, Uid};
+use crate::matches::Match;
+
+impl Graph {
+    /// Returns the uid of the type a constant is declared with, which the graph must already
+    /// hold. Reading it leaves the graph untouched.
+    ///
+    /// A parametric constant, or one declared with a wildcard, has a type for each instance
+    /// of its schema rather than a single one, and fails.
+    pub(crate) fn constant_type(&self, constant: &Constant) -> ImplicaResult<Uid> {
+        let uid = Self::interned_type_uid(&constant.type_schema).ok_or_else(|| {
+            ImplicaError::SchemaValidation {
+                schema: constant.type_schema.pattern.clone(),
+                reason: format!(
+                    "constant '{}' is parametric and has no single type",
+                    constant.name
+                ),
+            }
+        })?;
+
+        if !self.type_index.contains_key(&uid) {
+            return Err(ImplicaError::TypeNotFound {
+                uid,
+                context: Some(ctx!(format!("graph - constant type - {}", constant.name))),
+            }
+            .into());
+        }
+
+        Ok(uid)
+    }
+
+    /// Whether a constant is a value of the given type, i.e. its declared schema matches the
+    /// type. A parametric constant inhabits every instance of its schema.
+    pub(crate) fn constant_inhabits(
+        &self,
+        constant: &Constant,
+        r#type: &Uid,
+    ) -> ImplicaResult<bool> {
+        let found = self
+            .check_type_schema_matches(r#type, &constant.type_schema, Arc::new(Match::new(None)))
+            .attach(ctx!(format!(
+                "graph - constant inhabits - {}",
+                constant.name
+            )))?;
+
+        Ok(found.is_some())
+    }
+}
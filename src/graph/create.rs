@@ -1,8 +1,9 @@
-use error_stack::ResultExt;
+use error_stack::{Report, ResultExt};
+use pyo3::prelude::*;
 use std::ops::ControlFlow;
 use std::sync::Arc;
 
-use dashmap::DashMap;
+use dashmap::{DashMap, DashSet};
 use rayon::prelude::*;
 
 use crate::ctx;
@@ -15,6 +16,31 @@ use crate::properties::PropertyMap;
 use crate::typing::{Arrow, Term, Type};
 use crate::utils::{DataQueue, QueueItem};
 
+/// How a CREATE operation treats the properties of the elements it creates or reuses.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CreateOptions {
+    /// Merge the pattern properties into nodes and edges that already exist.
+    pub merge_properties: bool,
+    /// With `merge_properties`, replace the properties of existing nodes and edges with the
+    /// pattern ones instead of merging into them.
+    pub overwrite: bool,
+    /// Python callable computing extra properties for every new node from its type and term.
+    pub property_fn: Option<Arc<Py<PyAny>>>,
+    /// Let the computed properties override the ones written in the pattern.
+    pub property_fn_wins: bool,
+    /// Where to record the nodes and edges the operation inserts, if anywhere.
+    pub creations: Option<Arc<Creations>>,
+}
+
+/// Nodes and edges inserted by the CREATE operations recording them, including those added
+/// along the way for the endpoints and applications of their terms. Elements that already
+/// existed are not listed.
+#[derive(Debug, Default)]
+pub(crate) struct Creations {
+    pub(in crate::graph) nodes: DashSet<Uid>,
+    pub(in crate::graph) edges: DashSet<(Uid, Uid)>,
+}
+
 #[derive(Debug)]
 struct NodeData {
     variable: Option<String>,
@@ -72,15 +98,39 @@ impl EdgeData {
 }
 
 impl Graph {
+    /// Creates the elements of a path pattern for every row of `matches`.
+    ///
+    /// Nodes and edges that already exist are reused. By default the properties given in the
+    /// pattern are then dropped for nodes and replace those of edges; with `merge_properties`
+    /// they are merged into the existing element instead, the pattern winning on conflicts,
+    /// or replace its properties altogether when `overwrite` is set too.
+    /// New nodes additionally receive the properties computed by `property_fn`, if any.
     pub(crate) fn create_path(
         &self,
         pattern: &PathPattern,
+        options: &CreateOptions,
         matches: MatchSet,
     ) -> ImplicaResult<MatchSet> {
         let out_map = Arc::new(DashMap::new());
 
         pattern.validate().attach(ctx!("graph - create path"))?;
 
+        if pattern
+            .edges
+            .iter()
+            .any(|edge| edge.min_arity.is_some() || edge.hops.is_some())
+            || pattern
+                .nodes
+                .iter()
+                .any(|node| !node.missing_properties.is_empty() || node.subtype_of.is_some())
+        {
+            return Err(ImplicaError::InvalidPattern {
+                pattern: pattern.to_string(),
+                reason: "modifiers can only be used to match nodes and edges".to_string(),
+            }
+            .into());
+        }
+
         let result = matches.par_iter().try_for_each(|row| {
             let (_prev_uid, r#match) = row.value().clone();
 
@@ -795,7 +845,7 @@ impl Graph {
                 if let Some(node_var) = &nd.variable {
                     if !new_match.contains_key(node_var) {
 
-                        prev_uid = match self.add_node(nd.r#type.unwrap(), nd.term, nd.properties) {
+                        prev_uid = match self.create_node(nd.r#type.unwrap(), nd.term, nd.properties, options) {
                             Ok(uid) => uid,
                             Err(e) => return ControlFlow::Break(e.attach(ctx!("graph - create path")))
 
@@ -807,7 +857,7 @@ impl Graph {
                         }
                     }
                 } else {
-                    match self.add_node(nd.r#type.unwrap(), nd.term, nd.properties) {
+                    match self.create_node(nd.r#type.unwrap(), nd.term, nd.properties, options) {
                         Ok(_) => (),
                         Err(e) => {
                             return ControlFlow::Break(e.attach(ctx!("graph - create path")))
@@ -819,7 +869,7 @@ impl Graph {
             for ed in edges_data.into_iter() {
                 if let Some(edge_var) = &ed.variable {
                     if !new_match.contains_key(edge_var) {
-                    let edge = match self.add_edge(ed.term.unwrap(), ed.properties) {
+                    let edge = match self.create_edge(ed.term.unwrap(), ed.properties, options) {
                         Ok(e) => e,
                         Err(e) => return ControlFlow::Break(e.attach(ctx!("graph - create path")))
                     };
@@ -830,7 +880,7 @@ impl Graph {
                     }
                 }
                 } else {
-                    match self.add_edge(ed.term.unwrap(), ed.properties) {
+                    match self.create_edge(ed.term.unwrap(), ed.properties, options) {
                         Ok(..) => (),
                         Err(e) => return ControlFlow::Break(e.attach(ctx!("graph - create path")))
                     }
@@ -850,4 +900,134 @@ impl Graph {
             ControlFlow::Break(e) => Err(e),
         }
     }
+
+    fn create_node(
+        &self,
+        r#type: Type,
+        term: Option<Term>,
+        properties: PropertyMap,
+        options: &CreateOptions,
+    ) -> ImplicaResult<Uid> {
+        let uid = self.insert_type(&r#type);
+        let existed = self.nodes.contains_key(&uid);
+
+        if existed {
+            self.check_identity_key(&uid, &properties)
+                .attach(ctx!("graph - create node"))?;
+        }
+
+        if existed && options.merge_properties {
+            let uid = self
+                .add_node(
+                    r#type,
+                    term,
+                    PropertyMap::empty(),
+                    options.creations.as_deref(),
+                )
+                .attach(ctx!("graph - create node"))?;
+
+            if !properties.is_empty().attach(ctx!("graph - create node"))? {
+                self.set_node_properties(&uid, properties, options.overwrite)
+                    .attach(ctx!("graph - create node"))?;
+            }
+
+            Ok(uid)
+        } else {
+            let properties = match options.property_fn {
+                Some(ref property_fn) if !existed => Self::computed_properties(
+                    property_fn,
+                    &r#type,
+                    term.as_ref(),
+                    properties,
+                    options.property_fn_wins,
+                )
+                .attach(ctx!("graph - create node"))?,
+                _ => properties,
+            };
+
+            self.add_node(r#type, term, properties, options.creations.as_deref())
+                .attach(ctx!("graph - create node"))
+        }
+    }
+
+    /// Calls `property_fn` with the type and term of a node about to be created and merges the
+    /// dict it returns with the pattern properties.
+    fn computed_properties(
+        property_fn: &Py<PyAny>,
+        r#type: &Type,
+        term: Option<&Term>,
+        properties: PropertyMap,
+        property_fn_wins: bool,
+    ) -> ImplicaResult<PropertyMap> {
+        let computed = Python::attach(|py| {
+            let result = property_fn
+                .call1(py, (r#type.to_string(), term.map(|t| t.to_string())))
+                .map_err(|e| Report::new(ImplicaError::from(e.clone_ref(py))).attach_opaque(e))?;
+
+            PropertyMap::new(result.bind(py))
+        })
+        .attach(ctx!("graph - computed properties"))?;
+
+        let (base, overrides) = if property_fn_wins {
+            (properties, computed)
+        } else {
+            (computed, properties)
+        };
+
+        for (k, v) in overrides
+            .iter()
+            .attach(ctx!("graph - computed properties"))?
+        {
+            base.insert(k.to_string(), v)
+                .attach(ctx!("graph - computed properties"))?;
+        }
+
+        Ok(base)
+    }
+
+    fn create_edge(
+        &self,
+        term: Term,
+        properties: PropertyMap,
+        options: &CreateOptions,
+    ) -> ImplicaResult<(Uid, Uid)> {
+        if options.merge_properties {
+            if let Some(arrow) = term.r#type().as_arrow() {
+                let edge_uid = (Self::type_uid(&arrow.left), Self::type_uid(&arrow.right));
+
+                // An edge is identified by its endpoints, so an existing one only needs its
+                // properties updated, once the term is known to be the one it holds.
+                if self.edges.contains_key(&edge_uid) {
+                    self.check_edge_term(&term)
+                        .attach(ctx!("graph - create edge"))?;
+                    if !properties.is_empty().attach(ctx!("graph - create edge"))? {
+                        self.set_edge_properties(&edge_uid, properties, options.overwrite)
+                            .attach(ctx!("graph - create edge"))?;
+                    }
+
+                    return Ok(edge_uid);
+                }
+            }
+        }
+
+        self.add_edge(term, properties, options.creations.as_deref())
+            .attach(ctx!("graph - create edge"))
+    }
+}
+
+impl Graph {
+    /// Removes the nodes and edges recorded in `creations`, edges first, through the usual
+    /// removal paths so that observers hear of each removal.
+    pub(crate) fn undo_creations(&self, creations: &Creations) -> ImplicaResult<()> {
+        for edge in creations.edges.iter() {
+            self.remove_edge(edge.key())
+                .attach(ctx!("graph - undo creations"))?;
+        }
+        for node in creations.nodes.iter() {
+            self.remove_node(node.key())
+                .attach(ctx!("graph - undo creations"))?;
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,119 @@
+use error_stack::ResultExt;
+use rhai::Dynamic;
+
+use crate::ctx;
+use crate::errors::ImplicaResult;
+use crate::graph::base::{Graph, TermRep, Uid};
+use crate::properties::PropertyMap;
+use crate::utils::compare_values;
+
+impl Graph {
+    /// Whether a node of this graph and a node of `other` (possibly this same graph) have the
+    /// same type, term and properties, whatever graph they belong to.
+    pub(crate) fn node_structurally_equals(
+        &self,
+        node: &Uid,
+        other: &Graph,
+        other_node: &Uid,
+    ) -> ImplicaResult<bool> {
+        let properties = self
+            .node_properties(node)
+            .attach(ctx!("graph - node structurally equals"))?;
+        let other_properties = other
+            .node_properties(other_node)
+            .attach(ctx!("graph - node structurally equals"))?;
+
+        // Type uids hash the type's structure, so equal types have equal uids in any graph
+        Ok(node == other_node
+            && self
+                .terms_structurally_equal(node, other, other_node)
+                .attach(ctx!("graph - node structurally equals"))?
+            && properties_equal(&properties, &other_properties)
+                .attach(ctx!("graph - node structurally equals"))?)
+    }
+
+    /// Whether an edge of this graph and an edge of `other` have the same type, term and
+    /// properties and structurally equal endpoints.
+    pub(crate) fn edge_structurally_equals(
+        &self,
+        edge: &(Uid, Uid),
+        other: &Graph,
+        other_edge: &(Uid, Uid),
+    ) -> ImplicaResult<bool> {
+        let r#type = self
+            .get_edge_type(edge)
+            .attach(ctx!("graph - edge structurally equals"))?;
+        let other_type = other
+            .get_edge_type(other_edge)
+            .attach(ctx!("graph - edge structurally equals"))?;
+
+        if r#type != other_type
+            || !self
+                .terms_structurally_equal(&r#type, other, &other_type)
+                .attach(ctx!("graph - edge structurally equals"))?
+        {
+            return Ok(false);
+        }
+
+        let properties = self
+            .edge_properties(edge)
+            .attach(ctx!("graph - edge structurally equals"))?;
+        let other_properties = other
+            .edge_properties(other_edge)
+            .attach(ctx!("graph - edge structurally equals"))?;
+
+        Ok(properties_equal(&properties, &other_properties)
+            .attach(ctx!("graph - edge structurally equals"))?
+            && self
+                .node_structurally_equals(&edge.0, other, &other_edge.0)
+                .attach(ctx!("graph - edge structurally equals"))?
+            && self
+                .node_structurally_equals(&edge.1, other, &other_edge.1)
+                .attach(ctx!("graph - edge structurally equals"))?)
+    }
+
+    /// Compares the terms stored under two types. Terms have no binders, so equality modulo
+    /// renaming of bound variables is plain structural equality.
+    fn terms_structurally_equal(
+        &self,
+        r#type: &Uid,
+        other: &Graph,
+        other_type: &Uid,
+    ) -> ImplicaResult<bool> {
+        let term = self
+            .term_index
+            .get(r#type)
+            .map(|entry| entry.value().clone());
+        let other_term = other
+            .term_index
+            .get(other_type)
+            .map(|entry| entry.value().clone());
+
+        match (term, other_term) {
+            (None, None) => Ok(true),
+            (Some(TermRep::Base(name)), Some(TermRep::Base(other_name))) => Ok(name == other_name),
+            (
+                Some(TermRep::Application(func, arg)),
+                Some(TermRep::Application(other_func, other_arg)),
+            ) => Ok(self
+                .terms_structurally_equal(&func, other, &other_func)
+                .attach(ctx!("graph - terms structurally equal"))?
+                && self
+                    .terms_structurally_equal(&arg, other, &other_arg)
+                    .attach(ctx!("graph - terms structurally equal"))?),
+            _ => Ok(false),
+        }
+    }
+}
+
+fn properties_equal(properties: &PropertyMap, other: &PropertyMap) -> ImplicaResult<bool> {
+    let map = properties
+        .to_map()
+        .attach(ctx!("graph - properties equal"))?;
+    let other_map = other.to_map().attach(ctx!("graph - properties equal"))?;
+
+    Ok(compare_values(
+        &Dynamic::from(map),
+        &Dynamic::from(other_map),
+    ))
+}
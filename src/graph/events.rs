@@ -0,0 +1,132 @@
+use error_stack::{Report, ResultExt};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use crate::ctx;
+use crate::errors::{ImplicaError, ImplicaResult};
+use crate::graph::base::{Graph, Uid};
+
+/// A single mutation of the graph, as reported to `on_change` observers.
+#[derive(Debug, Clone, Copy)]
+pub(in crate::graph) enum ChangeEvent {
+    NodeAdded(Uid),
+    NodeRemoved(Uid),
+    NodePropertiesSet(Uid),
+    NodeTermCleared(Uid),
+    EdgeAdded((Uid, Uid)),
+    EdgeRemoved((Uid, Uid)),
+    EdgePropertiesSet((Uid, Uid)),
+}
+
+impl ChangeEvent {
+    fn kind(&self) -> &'static str {
+        match self {
+            ChangeEvent::NodeAdded(_) => "node_added",
+            ChangeEvent::NodeRemoved(_) => "node_removed",
+            ChangeEvent::NodePropertiesSet(_) => "node_set",
+            ChangeEvent::NodeTermCleared(_) => "node_term_cleared",
+            ChangeEvent::EdgeAdded(_) => "edge_added",
+            ChangeEvent::EdgeRemoved(_) => "edge_removed",
+            ChangeEvent::EdgePropertiesSet(_) => "edge_set",
+        }
+    }
+
+    fn to_dict<'py>(self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let event = PyDict::new(py);
+        event.set_item("kind", self.kind())?;
+
+        match self {
+            ChangeEvent::NodeAdded(uid)
+            | ChangeEvent::NodeRemoved(uid)
+            | ChangeEvent::NodePropertiesSet(uid)
+            | ChangeEvent::NodeTermCleared(uid) => event.set_item("uid", hex::encode(uid))?,
+            ChangeEvent::EdgeAdded(uid)
+            | ChangeEvent::EdgeRemoved(uid)
+            | ChangeEvent::EdgePropertiesSet(uid) => {
+                event.set_item("uid", (hex::encode(uid.0), hex::encode(uid.1)))?
+            }
+        }
+
+        Ok(event)
+    }
+}
+
+/// Registered change callbacks and the events waiting to be delivered to them.
+#[derive(Debug, Default)]
+pub(in crate::graph) struct Observers {
+    callbacks: Vec<Arc<Py<PyAny>>>,
+    pending: Vec<ChangeEvent>,
+}
+
+impl Graph {
+    /// Registers a callback to be invoked with every subsequent change event.
+    pub(crate) fn on_change(&self, callback: Py<PyAny>) -> ImplicaResult<()> {
+        let mut observers = self.observers.lock().map_err(|e| ImplicaError::LockError {
+            rw: "write".to_string(),
+            message: e.to_string(),
+            context: Some(ctx!("graph - on change")),
+        })?;
+
+        observers.callbacks.push(Arc::new(callback));
+        self.observed.store(true, Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    /// Queues an event for the observers. Graphs nobody observes skip this entirely.
+    pub(in crate::graph) fn emit(&self, event: ChangeEvent) {
+        if !self.observed.load(Ordering::SeqCst) {
+            return;
+        }
+
+        if let Ok(mut observers) = self.observers.lock() {
+            observers.pending.push(event);
+        }
+    }
+
+    /// Delivers the queued events to every observer.
+    ///
+    /// Mutations may run on worker threads that cannot take the GIL, so events are only
+    /// queued there and handed to Python here, on the thread that ran the operation.
+    pub(crate) fn notify_observers(&self) -> ImplicaResult<()> {
+        if !self.observed.load(Ordering::SeqCst) {
+            return Ok(());
+        }
+
+        let (callbacks, events) = {
+            let mut observers = self.observers.lock().map_err(|e| ImplicaError::LockError {
+                rw: "write".to_string(),
+                message: e.to_string(),
+                context: Some(ctx!("graph - notify observers")),
+            })?;
+
+            (
+                observers.callbacks.clone(),
+                std::mem::take(&mut observers.pending),
+            )
+        };
+
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        Python::attach(|py| {
+            for event in events {
+                let event = event.to_dict(py).map_err(|e| {
+                    Report::new(ImplicaError::from(e.clone_ref(py))).attach_opaque(e)
+                })?;
+
+                for callback in callbacks.iter() {
+                    callback.call1(py, (event.clone(),)).map_err(|e| {
+                        Report::new(ImplicaError::from(e.clone_ref(py))).attach_opaque(e)
+                    })?;
+                }
+            }
+
+            Ok::<(), Report<ImplicaError>>(())
+        })
+        .attach(ctx!("graph - notify observers"))
+    }
+}
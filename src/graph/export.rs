@@ -0,0 +1,137 @@
+use error_stack::{Report, ResultExt};
+use pyo3::prelude::*;
+use rhai::Dynamic;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::ctx;
+use crate::errors::{ImplicaError, ImplicaResult};
+use crate::graph::base::{Graph, Uid};
+use crate::properties::{py_to_rhai, PyOpaque};
+use crate::utils::numeric_value;
+
+impl Graph {
+    /// Registers the callable used to serialize property values exporters do not support,
+    /// replacing any previous one.
+    pub(crate) fn set_value_serializer(&self, serializer: Option<Py<PyAny>>) -> ImplicaResult<()> {
+        let mut slot = self
+            .value_serializer
+            .lock()
+            .map_err(|e| ImplicaError::LockError {
+                rw: "write".to_string(),
+                message: e.to_string(),
+                context: Some(ctx!("graph - set value serializer")),
+            })?;
+
+        *slot = serializer.map(Arc::new);
+
+        Ok(())
+    }
+
+    /// Prepares a property value for export.
+    ///
+    /// Values stored as opaque Python objects are handed to the registered serializer and
+    /// replaced by what it returns; every other value is returned unchanged. Without a
+    /// serializer, opaque values are returned as they are and left to the exporter to reject.
+    pub(crate) fn export_value(&self, value: Dynamic) -> ImplicaResult<Dynamic> {
+        if !value.is::<PyOpaque>() {
+            return Ok(value);
+        }
+
+        let serializer = self
+            .value_serializer
+            .lock()
+            .map_err(|e| ImplicaError::LockError {
+                rw: "read".to_string(),
+                message: e.to_string(),
+                context: Some(ctx!("graph - export value")),
+            })?
+            .clone();
+
+        let Some(serializer) = serializer else {
+            return Ok(value);
+        };
+
+        let opaque = value.cast::<PyOpaque>();
+
+        Python::attach(|py| {
+            let serialized = serializer
+                .call1(py, (opaque.0.clone_ref(py),))
+                .map_err(|e| Report::new(ImplicaError::from(e.clone_ref(py))).attach_opaque(e))?;
+
+            py_to_rhai(serialized.bind(py))
+        })
+        .attach(ctx!("graph - export value"))
+    }
+
+    /// Builds a dense adjacency matrix of the graph.
+    ///
+    /// Nodes are ordered by uid so the same graph always yields the same matrix. Entry
+    /// `[i][j]` holds `1.0` when there is an edge from node `i` to node `j`, or the value of
+    /// the `weight` property of that edge when one is requested (edges lacking it count as
+    /// `1.0`). Weights of unsupported types go through the value serializer, if one is set.
+    /// An undirected graph walks every edge both ways, so its matrix is symmetric.
+    pub(crate) fn adjacency_matrix(
+        &self,
+        weight: Option<&str>,
+    ) -> ImplicaResult<(Vec<Uid>, Vec<Vec<f64>>)> {
+        let mut order: Vec<Uid> = self.nodes.iter().map(|entry| *entry.key()).collect();
+        order.sort();
+
+        let positions: HashMap<Uid, usize> = order
+            .iter()
+            .enumerate()
+            .map(|(idx, uid)| (*uid, idx))
+            .collect();
+
+        let mut matrix = vec![vec![0.0; order.len()]; order.len()];
+
+        for entry in self.edges.iter() {
+            let (start, end) = *entry.key();
+
+            let (i, j) = match (positions.get(&start), positions.get(&end)) {
+                (Some(i), Some(j)) => (*i, *j),
+                _ => {
+                    return Err(ImplicaError::IndexCorruption {
+                        message: "edge points to a node that is not in the NodeIndex".to_string(),
+                        context: Some(ctx!("graph - adjacency matrix")),
+                    }
+                    .into())
+                }
+            };
+
+            let value = match weight {
+                Some(key) => match entry
+                    .value()
+                    .get(key)
+                    .attach(ctx!("graph - adjacency matrix"))?
+                {
+                    Some(value) => match numeric_value(
+                        &self
+                            .export_value(value.clone())
+                            .attach(ctx!("graph - adjacency matrix"))?,
+                    ) {
+                        Some(v) => v,
+                        None => {
+                            return Err(ImplicaError::TypeMismatch {
+                                expected: "int or float".to_string(),
+                                got: value.type_name().to_string(),
+                                context: Some(ctx!("graph - adjacency matrix - weight property")),
+                            }
+                            .into())
+                        }
+                    },
+                    None => 1.0,
+                },
+                None => 1.0,
+            };
+
+            matrix[i][j] = value;
+            if !self.directed {
+                matrix[j][i] = value;
+            }
+        }
+
+        Ok((order, matrix))
+    }
+}
@@ -0,0 +1,36 @@
+use std::sync::atomic::Ordering;
+
+use crate::ctx;
+use crate::errors::{ImplicaError, ImplicaResult};
+use crate::graph::base::Graph;
+
+impl Graph {
+    /// Marks the graph read-only until `unfreeze` is called. Matching keeps working, while
+    /// every mutation fails.
+    pub(crate) fn freeze(&self) {
+        self.frozen.store(true, Ordering::SeqCst);
+    }
+
+    pub(crate) fn unfreeze(&self) {
+        self.frozen.store(false, Ordering::SeqCst);
+    }
+
+    pub(crate) fn is_frozen(&self) -> bool {
+        self.frozen.load(Ordering::SeqCst)
+    }
+
+    /// Fails with an invalid query naming `operation` when the graph is frozen. Every entry
+    /// point modifying the graph calls it before touching anything.
+    pub(in crate::graph) fn check_not_frozen(&self, operation: &str) -> ImplicaResult<()> {
+        if self.is_frozen() {
+            return Err(ImplicaError::InvalidQuery {
+                query: operation.to_string(),
+                reason: "graph is frozen".to_string(),
+                context: Some(ctx!("graph - check not frozen")),
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use error_stack::ResultExt;
+use std::sync::Arc;
+
+use crate::ctx;
+use crate::errors::{ImplicaError, ImplicaResult};
+use crate::graph::base::{Graph, Uid};
+use crate::matches::Match;
+use crate::patterns::TypeSchema;
+use crate::properties::PropertyMap;
+use crate::utils::compare_values;
+
+impl Graph {
+    /// Designates the properties identifying the node of a type. An empty list removes the
+    /// identity key of the type.
+    pub(crate) fn set_identity_key(
+        &self,
+        r#type: &TypeSchema,
+        keys: Vec<String>,
+    ) -> ImplicaResult<()> {
+        self.check_not_frozen("set identity key")
+            .attach(ctx!("graph - set identity key"))?;
+
+        let r#type = self
+            .type_schema_to_type(r#type, Arc::new(Match::new(None)))
+            .attach(ctx!("graph - set identity key"))?;
+        let uid = self.declare_type(&r#type);
+
+        if keys.is_empty() {
+            self.identity_keys.remove(&uid);
+        } else {
+            self.identity_keys.insert(uid, keys);
+        }
+
+        Ok(())
+    }
+
+    /// Checks that creating the node of a type with the given properties refers to the node
+    /// already holding that type.
+    ///
+    /// A node is identified by its type, so a second node of a type is always deduplicated
+    /// into the first. With an identity key declared for the type, the properties of the key
+    /// given in the pattern must equal those of the existing node; a different value
+    /// describes another node, which the graph cannot hold, and fails with
+    /// `NodeAlreadyExists`. Keys left out of the pattern are not compared.
+    pub(in crate::graph) fn check_identity_key(
+        &self,
+        node: &Uid,
+        properties: &PropertyMap,
+    ) -> ImplicaResult<()> {
+        let keys = match self.identity_keys.get(node) {
+            Some(entry) => entry.value().clone(),
+            None => return Ok(()),
+        };
+        let existing = self
+            .node_properties(node)
+            .attach(ctx!("graph - check identity key"))?;
+
+        for key in keys.iter() {
+            let Some(value) = properties
+                .get_path(key)
+                .attach(ctx!("graph - check identity key"))?
+            else {
+                continue;
+            };
+            let matches = existing
+                .get_path(key)
+                .attach(ctx!("graph - check identity key"))?
+                .is_some_and(|other| compare_values(&value, &other));
+
+            if !matches {
+                return Err(ImplicaError::NodeAlreadyExists {
+                    uid: *node,
+                    context: Some(format!(
+                        "graph - check identity key - the node of this type has a different '{}'",
+                        key
+                    )),
+                }
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+}
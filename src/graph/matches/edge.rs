@@ -1,4 +1,5 @@
 use std::ops::ControlFlow;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 use dashmap::DashMap;
@@ -10,14 +11,27 @@ use crate::errors::{ImplicaError, ImplicaResult};
 use crate::graph::Uid;
 use crate::matches::{next_match_id, Match, MatchElement, MatchSet};
 use crate::patterns::CompiledDirection;
-use crate::{graph::base::Graph, patterns::EdgePattern};
+use crate::{
+    graph::base::{EdgeSet, Graph, TypeRep},
+    patterns::EdgePattern,
+};
 
 impl Graph {
+    /// Makes edge matching fail with an index corruption error on an edge whose endpoints
+    /// are gone, instead of skipping it.
+    pub(crate) fn set_strict_edges(&self, strict: bool) {
+        self.strict_edges.store(strict, Ordering::Relaxed);
+    }
+
     pub(super) fn match_edge_pattern(
         &self,
         pattern: &EdgePattern,
         matches: MatchSet,
     ) -> ImplicaResult<MatchSet> {
+        if pattern.hops.is_some() {
+            return self.match_variable_length_edge(pattern, matches);
+        }
+
         let out_map: MatchSet = Arc::new(DashMap::new());
 
         let result =
@@ -25,6 +39,7 @@ impl Graph {
                 .par_iter()
                 .try_for_each(|entry| -> ControlFlow<Report<ImplicaError>> {
                     let (prev_uid, r#match) = entry.value().clone();
+                    let direction = self.walk_direction(&pattern.compiled_direction);
 
                     // Check if match already holds the desired edge
                     if let Some(ref var) = pattern.variable {
@@ -40,13 +55,7 @@ impl Graph {
 
                             match self.check_edge_matches(&prev_uid, &old_edge, pattern, r#match.clone()) {
                                 Ok(Some(new_match)) => {
-                                    let next_uid = match pattern.compiled_direction {
-                                        CompiledDirection::Forward => old_edge.1,
-                                        CompiledDirection::Backward => old_edge.0,
-                                        CompiledDirection::Any => {
-                                            todo!("any direction is not supported yet")
-                                        }
-                                    };
+                                    let next_uid = Self::far_endpoint(&prev_uid, &old_edge, &direction);
 
                                     out_map.insert(next_match_id(), (next_uid, new_match));
 
@@ -62,24 +71,13 @@ impl Graph {
 
                     // Get possible edges based on prev_uid
 
-                    let possible_edges = match pattern.compiled_direction {
-                        CompiledDirection::Forward => {
-                            match self.start_to_edge_index.get(&prev_uid) {
-                                Some(edges) => edges.value().clone(),
-                                None => return ControlFlow::Break(ImplicaError::IndexCorruption { message: "prev_uid should be pointing at a valid node, and it dos not have an entry in the StartToEdgeIndex".to_string(), context: Some("graph - match edge pattern".to_string()) }.into())
-                            }
-                        }
-                        CompiledDirection::Backward => {
-                            match self.end_to_edge_index.get(&prev_uid) {
-                                Some(edges) => edges.value().clone(),
-                                None => return ControlFlow::Break(ImplicaError::IndexCorruption { message: "prev_uid should be pointing at a valid node, and it dos not have an entry in the StartToEdgeIndex".to_string(), context: Some("graph - match edge pattern".to_string()) }.into())
-                            }
-                        }
-                        CompiledDirection::Any => todo!("any direction not supported yet")
-                    } ;
+                    let possible_edges = match self.incident_edges(&prev_uid, &direction) {
+                        Some(edges) => edges,
+                        None => return ControlFlow::Break(ImplicaError::IndexCorruption { message: "prev_uid should be pointing at a valid node, and it dos not have an entry in the StartToEdgeIndex".to_string(), context: Some("graph - match edge pattern".to_string()) }.into())
+                    };
 
-                    possible_edges.par_iter().try_for_each(|entry| -> ControlFlow<Report<ImplicaError>> {
-                        let edge = *entry.key();
+                    possible_edges.par_iter().try_for_each(|edge| -> ControlFlow<Report<ImplicaError>> {
+                        let edge = *edge;
 
                         match self.check_edge_matches(&prev_uid, &edge, pattern, r#match.clone()) {
                             Ok(Some(new_match)) => {
@@ -91,13 +89,7 @@ impl Graph {
                                     }
                                 }
 
-                                let next_uid = match pattern.compiled_direction {
-                                    CompiledDirection::Forward => edge.1,
-                                    CompiledDirection::Backward => edge.0,
-                                    CompiledDirection::Any => {
-                                        todo!("any direction is not supported yet")
-                                    }
-                                };
+                                let next_uid = Self::far_endpoint(&prev_uid, &edge, &direction);
 
                                 out_map.insert(next_match_id(), (next_uid, new_match));
 
@@ -118,7 +110,7 @@ impl Graph {
         }
     }
 
-    fn check_edge_matches(
+    pub(super) fn check_edge_matches(
         &self,
         prev_uid: &Uid,
         edge: &(Uid, Uid),
@@ -126,13 +118,37 @@ impl Graph {
         r#match: Arc<Match>,
     ) -> ImplicaResult<Option<Arc<Match>>> {
         // Check endpoint matches
-        if !Self::match_endpoint(prev_uid, edge, &pattern.compiled_direction) {
+        if !Self::match_endpoint(
+            prev_uid,
+            edge,
+            &self.walk_direction(&pattern.compiled_direction),
+        ) {
+            return Ok(None);
+        }
+
+        // An edge still listed in the indexes after it, or one of its endpoints, was removed
+        // is being torn down by another thread; it is skipped rather than matched with a
+        // dangling endpoint, unless strict edges are on.
+        if !self.is_live_edge(edge) {
+            if self.strict_edges.load(Ordering::Relaxed) {
+                return Err(ImplicaError::IndexCorruption {
+                    message: format!(
+                        "edge ({}, {}) is indexed but it or one of its endpoints is not in the graph",
+                        hex::encode(edge.0),
+                        hex::encode(edge.1)
+                    ),
+                    context: Some("check edge matches".to_string()),
+                }
+                .into());
+            }
+
             return Ok(None);
         }
 
         // Get the type uid of the edge
         let edge_type = match self.edge_to_type_index.get(edge) {
             Some(uid) => *uid.value(),
+            None if !self.edges.contains_key(edge) => return Ok(None),
             None => {
                 return Err(ImplicaError::IndexCorruption {
                     message: "missing type for edge in edge_to_type_index".to_string(),
@@ -142,13 +158,36 @@ impl Graph {
             }
         };
 
+        // Checks run cheapest first so an edge failing any of them is pruned before the
+        // type and term schemas, which walk the type index and bind variables, are tried:
+        // arity, then property equality, then the type schema, then the term schema.
+
+        // Check if its type has enough arguments
+        if let Some(min_arity) = pattern.min_arity {
+            if self.type_arity(&edge_type) < min_arity {
+                return Ok(None);
+            }
+        }
+
+        // Check if properties match
+        if let Some(ref properties) = pattern.properties {
+            match self.check_edge_matches_properties(edge, properties) {
+                Ok(true) => (),
+                Ok(false) => return Ok(None),
+                Err(e) => return Err(e.attach(ctx!("check edge matches"))),
+            }
+        }
+
         // Create new match element
         let mut new_match = Arc::new(Match::new(Some(r#match)));
 
+        if pattern.type_schema.is_some() || pattern.term_schema.is_some() {
+            self.record_edge_schema_checked();
+        }
+
         // Check if its type satisfies the type schema
         if let Some(ref type_schema) = pattern.type_schema {
-            new_match = match self.check_type_matches(&edge_type, &type_schema.compiled, new_match)
-            {
+            new_match = match self.check_type_schema_matches(&edge_type, type_schema, new_match) {
                 Ok(m) => match m {
                     Some(m) => m,
                     None => return Ok(None),
@@ -169,23 +208,155 @@ impl Graph {
             }
         }
 
-        // Check if properties match
-        if let Some(ref properties) = pattern.properties {
-            match self.check_edge_matches_properties(edge, properties) {
-                Ok(true) => (),
-                Ok(false) => return Ok(None),
-                Err(e) => return Err(e.attach(ctx!("check edge matches"))),
-            }
+        Ok(Some(new_match))
+    }
+
+    /// Whether an edge and both its endpoints are still in the graph.
+    fn is_live_edge(&self, edge: &(Uid, Uid)) -> bool {
+        self.edges.contains_key(edge)
+            && self.nodes.contains_key(&edge.0)
+            && self.nodes.contains_key(&edge.1)
+    }
+
+    /// Number of arguments a type takes, following the right-hand side of its arrows.
+    fn type_arity(&self, r#type: &Uid) -> usize {
+        let mut arity = 0;
+        let mut current = *r#type;
+
+        while let Some(TypeRep::Arrow(_, right)) = self.type_index.get(&current).map(|t| t.clone())
+        {
+            arity += 1;
+            current = right;
         }
 
-        Ok(Some(new_match))
+        arity
     }
 
     fn match_endpoint(endpoint: &Uid, edge: &(Uid, Uid), direction: &CompiledDirection) -> bool {
         match direction {
             CompiledDirection::Forward => edge.0 == *endpoint,
             CompiledDirection::Backward => edge.1 == *endpoint,
-            CompiledDirection::Any => todo!("any direction not supported yet"),
+            CompiledDirection::Any => edge.0 == *endpoint || edge.1 == *endpoint,
+        }
+    }
+
+    /// The endpoint of an edge reached when walking it from `endpoint`.
+    pub(super) fn far_endpoint(
+        endpoint: &Uid,
+        edge: &(Uid, Uid),
+        direction: &CompiledDirection,
+    ) -> Uid {
+        match direction {
+            CompiledDirection::Forward => edge.1,
+            CompiledDirection::Backward => edge.0,
+            CompiledDirection::Any => {
+                if edge.0 == *endpoint {
+                    edge.1
+                } else {
+                    edge.0
+                }
+            }
+        }
+    }
+
+    /// Edges that can be walked from a node in the given direction, copied out of the edge
+    /// indexes. An edge from the node to itself is listed once.
+    pub(super) fn incident_edges(
+        &self,
+        node: &Uid,
+        direction: &CompiledDirection,
+    ) -> Option<Vec<(Uid, Uid)>> {
+        let read = |index: &DashMap<Uid, EdgeSet>| {
+            index
+                .get(node)
+                .map(|edges| edges.value().iter().map(|edge| *edge).collect::<Vec<_>>())
+        };
+
+        match direction {
+            CompiledDirection::Forward => read(&self.start_to_edge_index),
+            CompiledDirection::Backward => read(&self.end_to_edge_index),
+            CompiledDirection::Any => {
+                let mut edges = read(&self.start_to_edge_index)?;
+                edges.extend(
+                    read(&self.end_to_edge_index)?
+                        .into_iter()
+                        .filter(|edge| edge.0 != edge.1),
+                );
+                Some(edges)
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use crate::constants::Constant;
+    use crate::errors::ImplicaError;
+    use crate::graph::base::Graph;
+    use crate::matches::default_match_set;
+    use crate::patterns::PathPattern;
+    use crate::properties::PropertyMap;
+    use crate::typing::{Arrow, BasicTerm, Term, Type, Variable};
+
+    fn variable(name: &str) -> Type {
+        Type::Variable(Variable::new(name.to_string()).unwrap())
+    }
+
+    /// A graph holding `f : A -> B` as the edge between the nodes `A` and `B`, with the node
+    /// `B` then dropped from the node map alone so that the edge is left orphaned.
+    fn orphaned_edge_graph() -> Graph {
+        let graph = Graph::new(
+            vec![Constant::new("f".to_string(), "A -> B".to_string()).unwrap()],
+            Vec::new(),
+            false,
+            true,
+        );
+        let arrow = Arc::new(Type::Arrow(Arrow::new(
+            Arc::new(variable("A")),
+            Arc::new(variable("B")),
+        )));
+        let f = Term::Basic(BasicTerm::new("f".to_string(), arrow.clone()).unwrap());
+
+        graph
+            .add_node(
+                arrow.as_ref().clone(),
+                Some(f),
+                PropertyMap::default(),
+                None,
+            )
+            .unwrap();
+
+        let b = graph.insert_type(&variable("B"));
+        graph.nodes.remove(&b);
+        graph
+    }
+
+    fn match_edges(graph: &Graph) -> crate::errors::ImplicaResult<usize> {
+        let pattern = PathPattern::new("(a)-[e]->(b)".to_string()).unwrap();
+        graph
+            .match_path_pattern(&pattern, default_match_set())
+            .map(|matches| matches.len())
+    }
+
+    #[test]
+    fn orphaned_edge_is_skipped() {
+        let graph = orphaned_edge_graph();
+
+        assert_eq!(match_edges(&graph).unwrap(), 0);
+    }
+
+    #[test]
+    fn orphaned_edge_is_an_error_with_strict_edges() {
+        let graph = orphaned_edge_graph();
+        graph.set_strict_edges(true);
+
+        let error = match_edges(&graph).unwrap_err();
+
+        assert!(matches!(
+            error.current_context(),
+            ImplicaError::IndexCorruption { .. }
+        ));
+    }
+}
@@ -0,0 +1,38 @@
+use error_stack::ResultExt;
+use rayon::prelude::*;
+use std::sync::Arc;
+
+use crate::ctx;
+use crate::errors::{ImplicaError, ImplicaResult};
+use crate::graph::base::{Graph, Uid};
+use crate::matches::Match;
+use crate::patterns::NodePattern;
+
+impl Graph {
+    /// Checks whether any node matches a standalone node pattern, stopping at the first one
+    /// found instead of building the whole match set.
+    pub(crate) fn node_pattern_exists(&self, pattern: &NodePattern) -> ImplicaResult<bool> {
+        let found = self
+            .nodes
+            .par_iter()
+            .map(|entry| self.node_matches_pattern(entry.key(), pattern))
+            .find_any(|result| !matches!(result, Ok(false)));
+
+        match found {
+            Some(result) => result.attach(ctx!("graph - node pattern exists")),
+            None => Ok(false),
+        }
+    }
+
+    fn node_matches_pattern(&self, node: &Uid, pattern: &NodePattern) -> ImplicaResult<bool> {
+        self.record_node_scanned();
+
+        match self.check_node_matches(node, pattern, Arc::new(Match::new(None))) {
+            Ok(found) => Ok(found.is_some()),
+            Err(e) => match e.current_context() {
+                ImplicaError::TermNotFound { .. } | ImplicaError::NodeNotFound { .. } => Ok(false),
+                _ => Err(e),
+            },
+        }
+    }
+}
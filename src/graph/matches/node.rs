@@ -1,3 +1,4 @@
+use error_stack::ResultExt;
 use std::ops::ControlFlow;
 use std::sync::Arc;
 
@@ -31,9 +32,11 @@ impl Graph {
                         }
                     };
 
+                    self.record_node_scanned();
+
                     let mut new_match = r#match.clone();
                     if let Some(ref type_schema) = pattern.type_schema {
-                        let res = self.check_type_matches(&old, &type_schema.compiled, new_match);
+                        let res = self.check_type_schema_matches(&old, type_schema, new_match);
 
                         match res {
                             Ok(m) => match m {
@@ -63,17 +66,13 @@ impl Graph {
                         }
                     }
 
-                    if let Some(ref properties) = pattern.properties {
-                        let res = self.check_node_matches_properties(&old, properties);
+                    let res = self.check_node_pattern_properties(&old, pattern);
 
-                        match res {
-                            Ok(true) => (),
-                            Ok(false) => return ControlFlow::Continue(()),
-                            Err(e) => {
-                                return ControlFlow::Break(
-                                    e.attach(ctx!("graph - match node pattern")),
-                                )
-                            }
+                    match res {
+                        Ok(true) => (),
+                        Ok(false) => return ControlFlow::Continue(()),
+                        Err(e) => {
+                            return ControlFlow::Break(e.attach(ctx!("graph - match node pattern")))
                         }
                     }
 
@@ -99,6 +98,7 @@ impl Graph {
                     if !self.nodes.contains_key(&prev_uid) {
                         return ControlFlow::Continue(());
                     }
+                    self.record_node_scanned();
 
                     let m = Arc::new(Match::new(Some(original_match)));
 
@@ -106,17 +106,13 @@ impl Graph {
                         match self.check_term_matches(&prev_uid, &term_schema.compiled, m.clone()) {
                             Ok(m) => match m {
                                 Some(m) => {
-                                    if let Some(ref properties) = pattern.properties {
-                                        match self
-                                            .check_node_matches_properties(&prev_uid, properties)
-                                        {
-                                            Ok(true) => (),
-                                            Ok(false) => return ControlFlow::Continue(()),
-                                            Err(e) => {
-                                                return ControlFlow::Break(
-                                                    e.attach(ctx!("graph - match node pattern")),
-                                                )
-                                            }
+                                    match self.check_node_pattern_properties(&prev_uid, pattern) {
+                                        Ok(true) => (),
+                                        Ok(false) => return ControlFlow::Continue(()),
+                                        Err(e) => {
+                                            return ControlFlow::Break(
+                                                e.attach(ctx!("graph - match node pattern")),
+                                            )
                                         }
                                     }
 
@@ -145,15 +141,13 @@ impl Graph {
                             },
                         }
                     } else {
-                        if let Some(ref properties) = pattern.properties {
-                            match self.check_node_matches_properties(&prev_uid, properties) {
-                                Ok(true) => (),
-                                Ok(false) => return ControlFlow::Continue(()),
-                                Err(e) => {
-                                    return ControlFlow::Break(
-                                        e.attach(ctx!("graph - match node pattern")),
-                                    )
-                                }
+                        match self.check_node_pattern_properties(&prev_uid, pattern) {
+                            Ok(true) => (),
+                            Ok(false) => return ControlFlow::Continue(()),
+                            Err(e) => {
+                                return ControlFlow::Break(
+                                    e.attach(ctx!("graph - match node pattern")),
+                                )
                             }
                         }
 
@@ -187,16 +181,13 @@ impl Graph {
                     if !self.nodes.contains_key(&prev_uid) {
                         return ControlFlow::Continue(());
                     }
+                    self.record_node_scanned();
 
-                    if let Some(ref properties) = pattern.properties {
-                        match self.check_node_matches_properties(&prev_uid, properties) {
-                            Ok(true) => (),
-                            Ok(false) => return ControlFlow::Continue(()),
-                            Err(e) => {
-                                return ControlFlow::Break(
-                                    e.attach(ctx!("graph - match node pattern")),
-                                )
-                            }
+                    match self.check_node_pattern_properties(&prev_uid, pattern) {
+                        Ok(true) => (),
+                        Ok(false) => return ControlFlow::Continue(()),
+                        Err(e) => {
+                            return ControlFlow::Break(e.attach(ctx!("graph - match node pattern")))
                         }
                     }
 
@@ -216,18 +207,25 @@ impl Graph {
                     ControlFlow::Continue(())
                 })
             } else {
-                self.nodes.par_iter().try_for_each(|entry| {
-                    let new_uid = *entry.key();
-
-                    if let Some(ref properties) = pattern.properties {
-                        match self.check_node_matches_properties(&new_uid, properties) {
-                            Ok(true) => (),
-                            Ok(false) => return ControlFlow::Continue(()),
-                            Err(e) => {
-                                return ControlFlow::Break(
-                                    e.attach(ctx!("graph - match node pattern")),
-                                )
-                            }
+                // The uids are copied out so no shard lock of the node index is held while
+                // the nodes are checked, which reads the index again and would deadlock
+                // behind a waiting writer.
+                let candidates: Vec<Uid> = self.nodes.iter().map(|entry| *entry.key()).collect();
+
+                candidates.par_iter().try_for_each(|&new_uid| {
+                    self.record_node_scanned();
+
+                    match self.check_node_pattern_properties(&new_uid, pattern) {
+                        Ok(true) => (),
+                        Ok(false) => return ControlFlow::Continue(()),
+                        // Removed by a concurrent query after the candidates were taken
+                        Err(e)
+                            if matches!(e.current_context(), ImplicaError::NodeNotFound { .. }) =>
+                        {
+                            return ControlFlow::Continue(())
+                        }
+                        Err(e) => {
+                            return ControlFlow::Break(e.attach(ctx!("graph - match node pattern")))
                         }
                     }
 
@@ -257,6 +255,51 @@ impl Graph {
         }
     }
 
+    /// Matches a standalone node pattern, scanning nodes one at a time and stopping as soon
+    /// as `limit` of them have matched.
+    ///
+    /// Which nodes are returned depends on the iteration order of the node index, the same
+    /// as for a LIMIT applied to the full match set.
+    pub(crate) fn match_node_pattern_limited(
+        &self,
+        pattern: &NodePattern,
+        limit: usize,
+    ) -> ImplicaResult<MatchSet> {
+        let out_map: MatchSet = Arc::new(DashMap::new());
+
+        let candidates: Vec<Uid> = self.nodes.iter().map(|entry| *entry.key()).collect();
+
+        for node in candidates {
+            if out_map.len() >= limit {
+                break;
+            }
+
+            self.record_node_scanned();
+
+            let r#match = match self.check_node_matches(&node, pattern, Arc::new(Match::new(None)))
+            {
+                Ok(Some(m)) => m,
+                Ok(None) => continue,
+                Err(e) => match e.current_context() {
+                    ImplicaError::TermNotFound { .. } | ImplicaError::NodeNotFound { .. } => {
+                        continue
+                    }
+                    _ => return Err(e.attach(ctx!("graph - match node pattern limited"))),
+                },
+            };
+
+            if let Some(ref var) = pattern.variable {
+                r#match
+                    .insert(var, MatchElement::Node(node))
+                    .attach(ctx!("graph - match node pattern limited"))?;
+            }
+
+            out_map.insert(next_match_id(), (node, r#match));
+        }
+
+        Ok(out_map)
+    }
+
     pub(super) fn check_node_matches(
         &self,
         node: &Uid,
@@ -267,7 +310,7 @@ impl Graph {
 
         // Check node matches type schema
         if let Some(ref type_schema) = pattern.type_schema {
-            new_match = match self.check_type_matches(node, &type_schema.compiled, new_match) {
+            new_match = match self.check_type_schema_matches(node, type_schema, new_match) {
                 Ok(m) => match m {
                     Some(m) => m,
                     None => return Ok(None),
@@ -288,12 +331,10 @@ impl Graph {
         }
 
         // Check properties match
-        if let Some(ref properties) = pattern.properties {
-            match self.check_node_matches_properties(node, properties) {
-                Ok(true) => (),
-                Ok(false) => return Ok(None),
-                Err(e) => return Err(e.attach(ctx!("check node matches"))),
-            }
+        match self.check_node_pattern_properties(node, pattern) {
+            Ok(true) => (),
+            Ok(false) => return Ok(None),
+            Err(e) => return Err(e.attach(ctx!("check node matches"))),
         }
 
         Ok(Some(new_match))
@@ -1,4 +1,6 @@
 use error_stack::{Report, ResultExt};
+use std::borrow::Cow;
+use std::collections::HashSet;
 use std::iter::zip;
 use std::ops::ControlFlow;
 use std::sync::Arc;
@@ -8,11 +10,20 @@ use rayon::prelude::*;
 
 use crate::ctx;
 use crate::errors::{ImplicaError, ImplicaResult};
-use crate::graph::base::Graph;
-use crate::matches::{next_match_id, MatchElement, MatchSet};
+use crate::graph::base::{Graph, Uid};
+use crate::matches::{next_match_id, Match, MatchElement, MatchSet};
 use crate::patterns::PathPattern;
 
 impl Graph {
+    /// Extends every row with the paths matching the pattern, walking it one node and edge
+    /// at a time from its first node.
+    ///
+    /// Each row is matched on its own, so variables it already binds carry into every step:
+    /// a first node bound by an earlier operation is checked against the pattern instead of
+    /// being searched for, and only paths leaving it are matched.
+    ///
+    /// Distinct single-hop edge patterns, named or anonymous, never bind the same edge in a
+    /// row, so a path cannot walk an edge twice.
     pub(crate) fn match_path_pattern(
         &self,
         pattern: &PathPattern,
@@ -24,6 +35,9 @@ impl Graph {
             .validate()
             .attach(ctx!("graph - match path pattern"))?;
 
+        let (pattern, hidden) = Self::name_anonymous_edges(pattern);
+        let edge_variables = Self::unique_edge_variables(&pattern);
+
         let result = matches.par_iter().try_for_each(|row| {
             let (_prev_uid, r#match) = row.value().clone();
 
@@ -32,7 +46,18 @@ impl Graph {
                 (_prev_uid, r#match.clone()),
             )]));
 
-            let node_pattern = pattern.nodes.first().unwrap();
+            let node_pattern = match pattern.nodes.first() {
+                Some(n) => n,
+                None => {
+                    return ControlFlow::Break(
+                        Report::new(ImplicaError::InvalidPattern {
+                            pattern: pattern.to_string(),
+                            reason: "a path pattern cannot be empty".to_string(),
+                        })
+                        .attach(ctx!("graph - match path pattern")),
+                    )
+                }
+            };
 
             matches = match self.match_node_pattern(node_pattern, matches) {
                 Ok(m) => m,
@@ -100,6 +125,12 @@ impl Graph {
             matches
                 .par_iter()
                 .try_for_each(|m| {
+                    match self.reuses_edge(&m.value().1, &edge_variables) {
+                        Ok(false) => (),
+                        Ok(true) => return ControlFlow::Continue(()),
+                        Err(e) => return ControlFlow::Break(e.attach(ctx!("graph - match path pattern"))),
+                    }
+
                     match out_map.insert(next_match_id(), m.value().clone()) {
                         None => ControlFlow::Continue(()),
                         Some(_) => ControlFlow::Break(ImplicaError::RuntimeError { message: "Unique identifier generator next_match_id created a previously existing id (should not happen)".to_string(), context: Some("match path pattern".to_string()) }.into())
@@ -107,9 +138,175 @@ impl Graph {
                 })
         });
 
-        match result {
-            ControlFlow::Continue(()) => Ok(out_map),
-            ControlFlow::Break(e) => Err(e),
+        if let ControlFlow::Break(e) = result {
+            return Err(e);
+        }
+
+        // The names given to anonymous edges are dropped once every row has been checked:
+        // rows can share the match an edge was bound in.
+        for row in out_map.iter() {
+            for var in hidden.iter() {
+                row.value().1.remove(var);
+            }
+        }
+
+        Ok(out_map)
+    }
+
+    /// Matches a standalone single-hop path pattern, walking out of candidate first nodes one
+    /// at a time and stopping as soon as `limit` paths have matched.
+    ///
+    /// As for `match_node_pattern_limited`, which paths are returned depends on the iteration
+    /// order of the node index.
+    pub(crate) fn match_edge_path_limited(
+        &self,
+        pattern: &PathPattern,
+        limit: usize,
+    ) -> ImplicaResult<MatchSet> {
+        let out_map: MatchSet = Arc::new(DashMap::new());
+
+        let (start, edge, end) = match (pattern.nodes.as_slice(), pattern.edges.as_slice()) {
+            ([start, end], [edge]) if edge.hops.is_none() => (start, edge, end),
+            _ => {
+                return Err(ImplicaError::InvalidPattern {
+                    pattern: pattern.to_string(),
+                    reason: "a limited path match takes a single single-hop edge".to_string(),
+                }
+                .into())
+            }
+        };
+
+        let candidates: Vec<Uid> = self.nodes.iter().map(|entry| *entry.key()).collect();
+
+        for node in candidates {
+            if out_map.len() >= limit {
+                break;
+            }
+
+            self.record_node_scanned();
+
+            let r#match = match self.check_node_matches(&node, start, Arc::new(Match::new(None))) {
+                Ok(Some(m)) => m,
+                Ok(None) => continue,
+                Err(e) => match e.current_context() {
+                    ImplicaError::TermNotFound { .. } | ImplicaError::NodeNotFound { .. } => {
+                        continue
+                    }
+                    _ => return Err(e.attach(ctx!("graph - match edge path limited"))),
+                },
+            };
+
+            if let Some(ref var) = start.variable {
+                r#match
+                    .insert(var, MatchElement::Node(node))
+                    .attach(ctx!("graph - match edge path limited"))?;
+            }
+
+            let walked = self
+                .match_edge_pattern(
+                    edge,
+                    Arc::new(DashMap::from_iter([(next_match_id(), (node, r#match))])),
+                )
+                .attach(ctx!("graph - match edge path limited"))?;
+
+            for entry in walked.iter() {
+                if out_map.len() >= limit {
+                    break;
+                }
+
+                let (far, r#match) = entry.value().clone();
+
+                let r#match = match self.check_node_matches(&far, end, r#match) {
+                    Ok(Some(m)) => m,
+                    Ok(None) => continue,
+                    Err(e) => match e.current_context() {
+                        ImplicaError::TermNotFound { .. } | ImplicaError::NodeNotFound { .. } => {
+                            continue
+                        }
+                        _ => return Err(e.attach(ctx!("graph - match edge path limited"))),
+                    },
+                };
+
+                if let Some(ref var) = end.variable {
+                    match r#match.get(var) {
+                        Some(element) => {
+                            let bound = element
+                                .as_node(var, Some("get previously matched element".to_string()))
+                                .attach(ctx!("graph - match edge path limited"))?;
+
+                            if bound != far {
+                                continue;
+                            }
+                        }
+                        None => r#match
+                            .insert(var, MatchElement::Node(far))
+                            .attach(ctx!("graph - match edge path limited"))?,
+                    }
+                }
+
+                out_map.insert(next_match_id(), (far, r#match));
+            }
+        }
+
+        Ok(out_map)
+    }
+
+    /// Names the anonymous single-hop edges of a pattern so the edges they bind can be told
+    /// apart, returning the names given. Patterns with fewer than two single-hop edges are
+    /// left untouched, as they cannot reuse an edge.
+    fn name_anonymous_edges(pattern: &PathPattern) -> (Cow<'_, PathPattern>, Vec<String>) {
+        if pattern.edges.iter().filter(|e| e.hops.is_none()).count() < 2 {
+            return (Cow::Borrowed(pattern), Vec::new());
         }
+
+        let mut named = pattern.clone();
+        let mut hidden = Vec::new();
+
+        for (index, edge) in named.edges.iter_mut().enumerate() {
+            if edge.variable.is_none() && edge.hops.is_none() {
+                let var = format!("#edge{index}");
+                edge.variable = Some(var.clone());
+                hidden.push(var);
+            }
+        }
+
+        (Cow::Owned(named), hidden)
+    }
+
+    /// Distinct variables of the single-hop edges of a pattern. A variable repeated across
+    /// segments names the same edge and is listed once.
+    fn unique_edge_variables(pattern: &PathPattern) -> Vec<String> {
+        let mut seen = HashSet::new();
+
+        pattern
+            .edges
+            .iter()
+            .filter(|e| e.hops.is_none())
+            .filter_map(|e| e.variable.clone())
+            .filter(|var| seen.insert(var.clone()))
+            .collect()
+    }
+
+    /// Whether two of the edge variables are bound to the same edge in a row.
+    fn reuses_edge(&self, r#match: &Match, edge_variables: &[String]) -> ImplicaResult<bool> {
+        if edge_variables.len() < 2 {
+            return Ok(false);
+        }
+
+        let mut edges = HashSet::new();
+
+        for var in edge_variables.iter() {
+            if let Some(element) = r#match.get(var) {
+                let edge = element
+                    .as_edge(var, Some("check edge uniqueness".to_string()))
+                    .attach(ctx!("graph - reuses edge"))?;
+
+                if !edges.insert(edge) {
+                    return Ok(true);
+                }
+            }
+        }
+
+        Ok(false)
     }
 }
@@ -1,12 +1,36 @@
 use error_stack::ResultExt;
+use rhai::Dynamic;
+use std::collections::HashSet;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
 
 use crate::ctx;
 use crate::errors::{ImplicaError, ImplicaResult};
 use crate::graph::base::{Graph, Uid};
-use crate::properties::PropertyMap;
-use crate::utils::compare_values;
+use crate::patterns::{NodePattern, PropertyMatch};
+use crate::properties::{LockRetry, PropertyMap};
+use crate::utils::matches_value;
 
 impl Graph {
+    /// Makes pattern matching retry reading a property map held by a writer up to `attempts`
+    /// times, doubling `backoff` after each try, before failing with a lock error. Zero
+    /// attempts restores the default of blocking until the lock is free.
+    pub(crate) fn set_lock_retry(&self, attempts: u32, backoff: Duration) {
+        self.lock_backoff_micros
+            .store(backoff.as_micros() as u64, Ordering::Relaxed);
+        self.lock_retries.store(attempts, Ordering::Relaxed);
+    }
+
+    fn lock_retry(&self) -> Option<LockRetry> {
+        match self.lock_retries.load(Ordering::Relaxed) {
+            0 => None,
+            attempts => Some(LockRetry {
+                attempts,
+                backoff: Duration::from_micros(self.lock_backoff_micros.load(Ordering::Relaxed)),
+            }),
+        }
+    }
+
     pub(super) fn check_node_matches_properties(
         &self,
         node_uid: &Uid,
@@ -14,13 +38,14 @@ impl Graph {
     ) -> ImplicaResult<bool> {
         if let Some(entry) = self.nodes.get(node_uid) {
             let node_properties = entry.value();
+            let retry = self.lock_retry();
 
             properties.try_par_compare(|key, value| {
                 if let Some(other) = node_properties
-                    .get(key)
+                    .get_path_retrying(key, retry)
                     .attach(ctx!("graph - check node matches properties"))?
                 {
-                    Ok(compare_values(value, &other))
+                    Ok(matches_value(value, &other))
                 } else {
                     Ok(false)
                 }
@@ -34,6 +59,188 @@ impl Graph {
         }
     }
 
+    /// Checks both the properties a node pattern requires and the ones it must lack, along
+    /// with its `subtype_of` modifier.
+    pub(super) fn check_node_pattern_properties(
+        &self,
+        node_uid: &Uid,
+        pattern: &NodePattern,
+    ) -> ImplicaResult<bool> {
+        if !self
+            .check_node_subtype(node_uid, pattern)
+            .attach(ctx!("graph - check node pattern properties"))?
+        {
+            return Ok(false);
+        }
+
+        match pattern.property_match {
+            PropertyMatch::Superset => {
+                if let Some(ref properties) = pattern.properties {
+                    if !self.check_node_matches_properties(node_uid, properties)? {
+                        return Ok(false);
+                    }
+                }
+            }
+            PropertyMatch::Exact => {
+                let properties = pattern.properties.clone().unwrap_or_default();
+                if !self.check_node_matches_properties(node_uid, &properties)?
+                    || !self
+                        .check_node_properties_within(node_uid, &properties, false)
+                        .attach(ctx!("graph - check node pattern properties"))?
+                {
+                    return Ok(false);
+                }
+            }
+            PropertyMatch::Subset => {
+                let properties = pattern.properties.clone().unwrap_or_default();
+                if !self
+                    .check_node_properties_within(node_uid, &properties, true)
+                    .attach(ctx!("graph - check node pattern properties"))?
+                {
+                    return Ok(false);
+                }
+            }
+        }
+
+        if !self
+            .check_node_quantified_properties(node_uid, pattern)
+            .attach(ctx!("graph - check node pattern properties"))?
+        {
+            return Ok(false);
+        }
+
+        if pattern.missing_properties.is_empty() {
+            return Ok(true);
+        }
+
+        if let Some(entry) = self.nodes.get(node_uid) {
+            let retry = self.lock_retry();
+            for key in pattern.missing_properties.iter() {
+                if entry
+                    .value()
+                    .get_path_retrying(key, retry)
+                    .attach(ctx!("graph - check node pattern properties"))?
+                    .is_some()
+                {
+                    return Ok(false);
+                }
+            }
+
+            Ok(true)
+        } else {
+            Err(ImplicaError::NodeNotFound {
+                uid: *node_uid,
+                context: Some("check node pattern properties".to_string()),
+            }
+            .into())
+        }
+    }
+
+    /// Whether a node's list properties satisfy the quantified predicates of a pattern. A node
+    /// lacking one of the properties does not match, and one holding a value that is not a
+    /// list is an error.
+    fn check_node_quantified_properties(
+        &self,
+        node_uid: &Uid,
+        pattern: &NodePattern,
+    ) -> ImplicaResult<bool> {
+        if pattern.quantified.is_empty() {
+            return Ok(true);
+        }
+
+        let node_properties = match self.nodes.get(node_uid) {
+            Some(entry) => entry.value().clone(),
+            None => {
+                return Err(ImplicaError::NodeNotFound {
+                    uid: *node_uid,
+                    context: Some("check node quantified properties".to_string()),
+                }
+                .into())
+            }
+        };
+        let retry = self.lock_retry();
+
+        for predicate in pattern.quantified.iter() {
+            let value = match node_properties
+                .get_path_retrying(&predicate.key, retry)
+                .attach(ctx!("graph - check node quantified properties"))?
+            {
+                Some(value) => value,
+                None => return Ok(false),
+            };
+
+            let list = match value.clone().try_cast::<Vec<Dynamic>>() {
+                Some(list) => list,
+                None => {
+                    return Err(ImplicaError::TypeMismatch {
+                        expected: "list".to_string(),
+                        got: value.type_name().to_string(),
+                        context: Some(ctx!(format!(
+                            "graph - check node quantified properties - {} over property '{}'",
+                            predicate.quantifier.to_string(),
+                            predicate.key
+                        ))),
+                    }
+                    .into())
+                }
+            };
+
+            if !predicate.holds(&list) {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Whether every property of a node is named by the pattern properties: the node has no
+    /// key beyond them, counting the first segment of a dotted key. With `compare_values`, the
+    /// node's values must also match the pattern's.
+    fn check_node_properties_within(
+        &self,
+        node_uid: &Uid,
+        properties: &PropertyMap,
+        compare_values: bool,
+    ) -> ImplicaResult<bool> {
+        let node_properties = match self.nodes.get(node_uid) {
+            Some(entry) => entry.value().clone(),
+            None => {
+                return Err(ImplicaError::NodeNotFound {
+                    uid: *node_uid,
+                    context: Some("check node properties within".to_string()),
+                }
+                .into())
+            }
+        };
+
+        let keys: HashSet<String> = properties
+            .iter()
+            .attach(ctx!("graph - check node properties within"))?
+            .map(|(key, _)| key.split('.').next().unwrap_or_default().to_string())
+            .collect();
+
+        for (key, value) in node_properties
+            .iter()
+            .attach(ctx!("graph - check node properties within"))?
+        {
+            if !keys.contains(key.as_str()) {
+                return Ok(false);
+            }
+
+            if compare_values {
+                match properties
+                    .get(&key)
+                    .attach(ctx!("graph - check node properties within"))?
+                {
+                    Some(expected) if matches_value(&expected, &value) => (),
+                    _ => return Ok(false),
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
     pub(super) fn check_edge_matches_properties(
         &self,
         edge_uid: &(Uid, Uid),
@@ -41,15 +248,16 @@ impl Graph {
     ) -> ImplicaResult<bool> {
         if let Some(entry) = self.edges.get(edge_uid) {
             let edge_properties = entry.value();
+            let retry = self.lock_retry();
 
             properties.try_par_compare(|key, value| {
                 if let Some(other) = edge_properties
-                    .get(key)
+                    .get_path_retrying(key, retry)
                     .attach(ctx!("graph - check edge matches properties"))?
                 {
-                    Ok(compare_values(value, &other))
+                    Ok(matches_value(value, &other))
                 } else {
-                    Ok(true)
+                    Ok(false)
                 }
             })
         } else {
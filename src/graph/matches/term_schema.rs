@@ -29,18 +29,26 @@ impl Graph {
     ) -> ImplicaResult<MatchSet> {
         let out_map: MatchSet = Arc::new(DashMap::new());
 
+        // The candidates are copied out so no shard lock of the index is held while they
+        // are checked, which reads the index again and would deadlock behind a writer.
+        let candidates: Vec<Uid> = self.term_index.iter().map(|entry| *entry.key()).collect();
+
         let result = matches.par_iter().try_for_each(|row| {
             let (_prev_uid, r#match) = row.value();
             let r#match = r#match.clone();
 
-            self.term_index.par_iter().try_for_each(|entry| {
-                match self.check_term_matches(entry.key(), pattern, r#match.clone()) {
+            candidates.par_iter().try_for_each(|uid| {
+                match self.check_term_matches(uid, pattern, r#match.clone()) {
                     Ok(new_match_op) => {
                         if let Some(new_match) = new_match_op {
-                            out_map.insert(next_match_id(), (*entry.key(), new_match));
+                            out_map.insert(next_match_id(), (*uid, new_match));
                         }
                         ControlFlow::Continue(())
                     }
+                    // Cleared by a concurrent query after the candidates were taken
+                    Err(e) if matches!(e.current_context(), ImplicaError::TermNotFound { .. }) => {
+                        ControlFlow::Continue(())
+                    }
                     Err(e) => ControlFlow::Break(e.attach(ctx!("graph - match term pattern"))),
                 }
             })
@@ -133,7 +141,7 @@ impl Graph {
                                         element.as_type(v, Some(ctx!("check term matches")))?;
 
                                     new_match = match self
-                                        .check_type_matches(&matched_type, &arg.compiled, new_match)
+                                        .check_type_schema_matches(&matched_type, arg, new_match)
                                         .attach(ctx!("check term matches"))?
                                     {
                                         Some(m) => m,
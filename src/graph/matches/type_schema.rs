@@ -7,7 +7,7 @@ use rayon::prelude::*;
 
 use crate::ctx;
 use crate::errors::{ImplicaError, ImplicaResult};
-use crate::graph::base::{Graph, TypeRep, Uid};
+use crate::graph::base::{Graph, TermRep, TypeRep, Uid};
 use crate::matches::{next_match_id, Match, MatchElement, MatchSet};
 use crate::patterns::{TypePattern, TypeSchema};
 
@@ -17,10 +17,98 @@ impl Graph {
         type_schema: &TypeSchema,
         matches: MatchSet,
     ) -> ImplicaResult<MatchSet> {
+        if let Some(uid) = Self::interned_type_uid(type_schema) {
+            let out_map: MatchSet = Arc::new(DashMap::new());
+            let mut rest: Vec<Arc<Match>> = Vec::new();
+
+            for row in matches.iter() {
+                let (_prev_uid, r#match) = row.value();
+
+                if Self::binds_type_names(&type_schema.compiled, r#match) {
+                    rest.push(r#match.clone());
+                } else if self.type_index.contains_key(&uid) {
+                    out_map.insert(next_match_id(), (uid, r#match.clone()));
+                }
+            }
+
+            if rest.is_empty() {
+                return Ok(out_map);
+            }
+
+            let rest: MatchSet = Arc::new(
+                rest.into_iter()
+                    .map(|m| (next_match_id(), (uid, m)))
+                    .collect(),
+            );
+            for entry in self
+                .match_type_pattern(&type_schema.compiled, rest)
+                .attach(ctx!("graph - match type schema"))?
+                .iter()
+            {
+                out_map.insert(*entry.key(), entry.value().clone());
+            }
+
+            return Ok(out_map);
+        }
+
         self.match_type_pattern(&type_schema.compiled, matches)
             .attach(ctx!("graph - match type schema"))
     }
 
+    /// Checks a type against a type schema, comparing uids when the schema names a concrete
+    /// type instead of walking both structures.
+    pub(super) fn check_type_schema_matches(
+        &self,
+        type_uid: &Uid,
+        type_schema: &TypeSchema,
+        r#match: Arc<Match>,
+    ) -> ImplicaResult<Option<Arc<Match>>> {
+        if let Some(uid) = Self::interned_type_uid(type_schema) {
+            if !Self::binds_type_names(&type_schema.compiled, &r#match) {
+                return Ok((uid == *type_uid).then_some(r#match));
+            }
+        }
+
+        self.check_type_matches(type_uid, &type_schema.compiled, r#match)
+            .attach(ctx!("graph - check type schema matches"))
+    }
+
+    /// Returns the interned uid of the concrete type a schema spells out, if it does.
+    ///
+    /// Types are interned by a hash of their structure, so the uid is known without
+    /// looking at the graph and is computed once per schema.
+    pub(in crate::graph) fn interned_type_uid(type_schema: &TypeSchema) -> Option<Uid> {
+        *type_schema
+            .interned
+            .get_or_init(|| Self::concrete_type_uid(&type_schema.compiled))
+    }
+
+    fn concrete_type_uid(pattern: &TypePattern) -> Option<Uid> {
+        match pattern {
+            TypePattern::Variable(name) => Some(TypeRep::Variable(name.clone()).uid()),
+            TypePattern::Arrow { left, right } => Some(
+                TypeRep::Arrow(
+                    Self::concrete_type_uid(left)?,
+                    Self::concrete_type_uid(right)?,
+                )
+                .uid(),
+            ),
+            TypePattern::Wildcard | TypePattern::Sum(_) | TypePattern::Capture { .. } => None,
+        }
+    }
+
+    /// Whether a type name in the pattern refers to a type bound earlier in the query, in
+    /// which case it stands for that type rather than for itself.
+    fn binds_type_names(pattern: &TypePattern, r#match: &Match) -> bool {
+        match pattern {
+            TypePattern::Variable(name) => r#match.get(name).is_some(),
+            TypePattern::Arrow { left, right } => {
+                Self::binds_type_names(left, r#match) || Self::binds_type_names(right, r#match)
+            }
+            TypePattern::Wildcard | TypePattern::Sum(_) | TypePattern::Capture { .. } => false,
+        }
+    }
+
     fn match_type_pattern(
         &self,
         pattern: &TypePattern,
@@ -28,15 +116,19 @@ impl Graph {
     ) -> ImplicaResult<MatchSet> {
         let out_map: MatchSet = Arc::new(DashMap::new());
 
+        // The candidates are copied out so no shard lock of the index is held while they
+        // are checked, which reads the index again and would deadlock behind a writer.
+        let candidates: Vec<Uid> = self.type_index.iter().map(|entry| *entry.key()).collect();
+
         let result = matches.par_iter().try_for_each(|row| {
             let (_prev_uid, r#match) = row.value();
             let r#match = r#match.clone();
 
-            self.type_index.par_iter().try_for_each(|entry| {
-                match self.check_type_matches(entry.key(), pattern, r#match.clone()) {
+            candidates.par_iter().try_for_each(|uid| {
+                match self.check_type_matches(uid, pattern, r#match.clone()) {
                     Ok(new_match_op) => {
                         if let Some(new_match) = new_match_op {
-                            out_map.insert(next_match_id(), (*entry.key(), new_match));
+                            out_map.insert(next_match_id(), (*uid, new_match));
                         }
                         ControlFlow::Continue(())
                     }
@@ -57,9 +149,29 @@ impl Graph {
         pattern: &TypePattern,
         r#match: Arc<Match>,
     ) -> ImplicaResult<Option<Arc<Match>>> {
-        if let Some(type_row) = self.type_index.get(type_uid) {
+        if let Some(type_row) = self.lookup_type(type_uid) {
             match pattern {
                 TypePattern::Wildcard => Ok(Some(r#match.clone())),
+                TypePattern::Sum(name) => {
+                    let cases = match self.sums.get(name) {
+                        Some(entry) => entry.value().clone(),
+                        None => {
+                            return Err(ImplicaError::InvalidPattern {
+                                pattern: format!("${}$", name),
+                                reason: format!("no sum type named '{}' in the graph", name),
+                            }
+                            .into())
+                        }
+                    };
+
+                    // A term is stored under the uid of its type
+                    match self.term_index.get(type_uid).as_deref() {
+                        Some(TermRep::Base(constant)) if cases.contains(constant) => {
+                            Ok(Some(r#match.clone()))
+                        }
+                        _ => Ok(None),
+                    }
+                }
                 TypePattern::Variable(var) => {
                     if let Some(ref old_element) = r#match.get(var) {
                         let old_uid = old_element
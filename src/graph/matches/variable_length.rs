@@ -0,0 +1,140 @@
+use std::ops::ControlFlow;
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use error_stack::Report;
+use rayon::prelude::*;
+
+use crate::ctx;
+use crate::errors::{ImplicaError, ImplicaResult};
+use crate::graph::base::{Graph, Uid};
+use crate::matches::{next_match_id, Match, MatchSet};
+use crate::patterns::{CompiledDirection, CycleMode, EdgePattern};
+
+/// A partial path of a variable-length edge, extended one hop at a time.
+struct Walk {
+    node: Uid,
+    hops: usize,
+    r#match: Arc<Match>,
+    nodes: Vec<Uid>,
+    edges: Vec<(Uid, Uid)>,
+}
+
+impl Graph {
+    /// Matches an edge pattern spanning a range of consecutive edges.
+    ///
+    /// Every hop must satisfy the edge pattern on its own, and every distinct path yields a
+    /// row, so two paths reaching the same node produce two rows. The pattern's cycle mode
+    /// decides which paths are walked: by default no node is visited twice, with
+    /// `relationships` no edge is used twice, and with `any` paths are only bounded by the
+    /// maximum number of hops.
+    pub(super) fn match_variable_length_edge(
+        &self,
+        pattern: &EdgePattern,
+        matches: MatchSet,
+    ) -> ImplicaResult<MatchSet> {
+        let (min_hops, max_hops) = match pattern.hops {
+            Some(hops) => hops,
+            None => {
+                return Err(ImplicaError::InvalidPattern {
+                    pattern: pattern.to_string(),
+                    reason: "expected a variable-length edge".to_string(),
+                }
+                .into())
+            }
+        };
+
+        // An undirected graph walks every hop both ways; a directed one needs the pattern
+        // to pick a direction.
+        let direction = self.walk_direction(&pattern.compiled_direction);
+        if self.directed && direction == CompiledDirection::Any {
+            return Err(ImplicaError::InvalidPattern {
+                pattern: pattern.to_string(),
+                reason: "a variable-length edge needs a direction".to_string(),
+            }
+            .into());
+        }
+
+        let out_map: MatchSet = Arc::new(DashMap::new());
+
+        let result = matches
+            .par_iter()
+            .try_for_each(|entry| -> ControlFlow<Report<ImplicaError>> {
+                let (prev_uid, r#match) = entry.value().clone();
+
+                let mut stack = vec![Walk {
+                    node: prev_uid,
+                    hops: 0,
+                    r#match,
+                    nodes: vec![prev_uid],
+                    edges: Vec::new(),
+                }];
+
+                while let Some(walk) = stack.pop() {
+                    if walk.hops >= min_hops {
+                        out_map.insert(next_match_id(), (walk.node, walk.r#match.clone()));
+                    }
+
+                    if max_hops.is_some_and(|max| walk.hops >= max) {
+                        continue;
+                    }
+
+                    let candidates: Vec<(Uid, Uid)> = match self.incident_edges(&walk.node, &direction) {
+                        Some(edges) => edges,
+                        None => {
+                            return ControlFlow::Break(
+                                ImplicaError::IndexCorruption {
+                                    message: "node reached by a variable-length edge has no entry in the edge indexes".to_string(),
+                                    context: Some(ctx!("graph - match variable length edge")),
+                                }
+                                .into(),
+                            )
+                        }
+                    };
+
+                    for edge in candidates {
+                        let next = Self::far_endpoint(&walk.node, &edge, &direction);
+
+                        let allowed = match pattern.cycles {
+                            CycleMode::NoRepeatedNodes => !walk.nodes.contains(&next),
+                            CycleMode::NoRepeatedRelationships => !walk.edges.contains(&edge),
+                            CycleMode::Any => true,
+                        };
+                        if !allowed {
+                            continue;
+                        }
+
+                        match self.check_edge_matches(&walk.node, &edge, pattern, walk.r#match.clone()) {
+                            Ok(Some(new_match)) => {
+                                let mut nodes = walk.nodes.clone();
+                                nodes.push(next);
+                                let mut edges = walk.edges.clone();
+                                edges.push(edge);
+
+                                stack.push(Walk {
+                                    node: next,
+                                    hops: walk.hops + 1,
+                                    r#match: new_match,
+                                    nodes,
+                                    edges,
+                                });
+                            }
+                            Ok(None) => (),
+                            Err(e) => {
+                                return ControlFlow::Break(
+                                    e.attach(ctx!("graph - match variable length edge")),
+                                )
+                            }
+                        }
+                    }
+                }
+
+                ControlFlow::Continue(())
+            });
+
+        match result {
+            ControlFlow::Continue(()) => Ok(out_map),
+            ControlFlow::Break(e) => Err(e),
+        }
+    }
+}
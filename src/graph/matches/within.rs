@@ -0,0 +1,111 @@
+use std::collections::{HashSet, VecDeque};
+use std::ops::ControlFlow;
+use std::sync::Arc;
+
+use dashmap::DashMap;
+use error_stack::ResultExt;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+use crate::ctx;
+use crate::errors::ImplicaResult;
+use crate::graph::base::{Graph, Uid};
+use crate::matches::{next_match_id, Match, MatchElement, MatchSet};
+use crate::patterns::CompiledDirection;
+
+impl Graph {
+    /// Returns the nodes at most `k` hops away from any of the seeds, seeds included, in
+    /// the order a breadth-first search from the seeds reaches them.
+    pub(crate) fn reachable_within(
+        &self,
+        seeds: &[Uid],
+        k: usize,
+        direction: &CompiledDirection,
+    ) -> ImplicaResult<Vec<Uid>> {
+        let mut visited: HashSet<Uid> = HashSet::new();
+        let mut reached: Vec<Uid> = Vec::new();
+        let mut queue: VecDeque<(Uid, usize)> = VecDeque::new();
+
+        for seed in seeds.iter() {
+            if visited.insert(*seed) {
+                reached.push(*seed);
+                queue.push_back((*seed, 0));
+            }
+        }
+
+        while let Some((node, depth)) = queue.pop_front() {
+            self.record_node_scanned();
+
+            // Seeds are looked up too, so an unknown seed is reported even when k is 0.
+            let next: Vec<Uid> = self
+                .walkable_edges(&node, direction)
+                .attach(ctx!("graph - reachable within"))?
+                .into_iter()
+                .map(|(start, end)| if start == node { end } else { start })
+                .collect();
+
+            if depth == k {
+                continue;
+            }
+
+            for neighbour in next {
+                if visited.insert(neighbour) {
+                    reached.push(neighbour);
+                    queue.push_back((neighbour, depth + 1));
+                }
+            }
+        }
+
+        Ok(reached)
+    }
+
+    /// Binds `variable` to every node within `k` hops of the seeds.
+    ///
+    /// A row that already binds `variable` is kept only if its node is in the neighbourhood;
+    /// any other row is extended once per node of the neighbourhood.
+    pub(crate) fn match_within(
+        &self,
+        variable: &str,
+        seeds: &[Uid],
+        k: usize,
+        direction: &CompiledDirection,
+        matches: MatchSet,
+    ) -> ImplicaResult<MatchSet> {
+        let reached = self
+            .reachable_within(seeds, k, direction)
+            .attach(ctx!("graph - match within"))?;
+        let neighbourhood: HashSet<Uid> = reached.iter().copied().collect();
+
+        let out_map: MatchSet = Arc::new(DashMap::new());
+
+        let result = matches.par_iter().try_for_each(|row| {
+            let (prev_uid, r#match) = row.value().clone();
+
+            if let Some(element) = r#match.get(variable) {
+                let node = match element.as_node(variable, Some("match within".to_string())) {
+                    Ok(uid) => uid,
+                    Err(e) => return ControlFlow::Break(e.attach(ctx!("graph - match within"))),
+                };
+
+                if neighbourhood.contains(&node) {
+                    out_map.insert(next_match_id(), (prev_uid, r#match.clone()));
+                }
+                return ControlFlow::Continue(());
+            }
+
+            for node in reached.iter() {
+                let m = Arc::new(Match::new(Some(r#match.clone())));
+                if let Err(e) = m.insert(variable, MatchElement::Node(*node)) {
+                    return ControlFlow::Break(e.attach(ctx!("graph - match within")));
+                }
+                out_map.insert(next_match_id(), (*node, m));
+            }
+
+            ControlFlow::Continue(())
+        });
+
+        match result {
+            ControlFlow::Continue(()) => Ok(out_map),
+            ControlFlow::Break(e) => Err(e),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::errors::{ImplicaError, ImplicaResult};
+use crate::graph::base::{Graph, Uid};
+
+/// Creation and last-modification times of a node or edge, in seconds since the Unix epoch.
+#[derive(Debug, Clone, Copy)]
+pub(in crate::graph) struct Timestamps {
+    created_at: f64,
+    modified_at: f64,
+}
+
+impl Timestamps {
+    fn now() -> Self {
+        let now = epoch_seconds();
+
+        Timestamps {
+            created_at: now,
+            modified_at: now,
+        }
+    }
+}
+
+fn epoch_seconds() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or_default()
+}
+
+impl Graph {
+    pub(in crate::graph) fn record_node_created(&self, node: &Uid) {
+        self.node_timestamps
+            .entry(*node)
+            .or_insert_with(Timestamps::now);
+    }
+
+    pub(in crate::graph) fn record_node_modified(&self, node: &Uid) {
+        if let Some(mut entry) = self.node_timestamps.get_mut(node) {
+            entry.modified_at = epoch_seconds();
+        }
+    }
+
+    pub(in crate::graph) fn record_edge_created(&self, edge: &(Uid, Uid)) {
+        self.edge_timestamps
+            .entry(*edge)
+            .or_insert_with(Timestamps::now);
+    }
+
+    pub(in crate::graph) fn record_edge_modified(&self, edge: &(Uid, Uid)) {
+        if let Some(mut entry) = self.edge_timestamps.get_mut(edge) {
+            entry.modified_at = epoch_seconds();
+        }
+    }
+
+    /// Returns the `(created_at, modified_at)` timestamps of a node.
+    pub(crate) fn node_timestamps(&self, node: &Uid) -> ImplicaResult<(f64, f64)> {
+        match self.node_timestamps.get(node) {
+            Some(entry) => Ok((entry.created_at, entry.modified_at)),
+            None => Err(ImplicaError::NodeNotFound {
+                uid: *node,
+                context: Some("node timestamps".to_string()),
+            }
+            .into()),
+        }
+    }
+
+    /// Returns the `(created_at, modified_at)` timestamps of an edge.
+    pub(crate) fn edge_timestamps(&self, edge: &(Uid, Uid)) -> ImplicaResult<(f64, f64)> {
+        match self.edge_timestamps.get(edge) {
+            Some(entry) => Ok((entry.created_at, entry.modified_at)),
+            None => Err(ImplicaError::EdgeNotFound {
+                uid: *edge,
+                context: Some("edge timestamps".to_string()),
+            }
+            .into()),
+        }
+    }
+}
@@ -1,4 +1,4 @@
 mod base;
 
-pub use base::PyGraph;
-pub(crate) use base::{Graph, Uid};
+pub(crate) use base::{CreateOptions, Creations, Graph, Uid};
+pub use base::{PyGraph, PySnapshot};
@@ -0,0 +1,28 @@
+use crate::graph::base::Graph;
+use crate::patterns::{NodePattern, PathPattern};
+
+impl Graph {
+    /// Estimates how many nodes could anchor a match of the given path pattern.
+    ///
+    /// Every node pattern is estimated on its own and the most selective one bounds the
+    /// whole path. A node pattern with a fully concrete type schema can match at most one
+    /// node, since nodes are identified by their type; anything else may match every node.
+    pub(crate) fn estimate_path_pattern(&self, pattern: &PathPattern) -> usize {
+        pattern
+            .nodes
+            .iter()
+            .map(|node| self.estimate_node_pattern(node))
+            .min()
+            .unwrap_or(0)
+    }
+
+    fn estimate_node_pattern(&self, pattern: &NodePattern) -> usize {
+        if let Some(ref type_schema) = pattern.type_schema {
+            if let Some(uid) = Self::interned_type_uid(type_schema) {
+                return if self.nodes.contains_key(&uid) { 1 } else { 0 };
+            }
+        }
+
+        self.nodes.len()
+    }
+}
@@ -0,0 +1,250 @@
+use error_stack::ResultExt;
+use rhai::{Dynamic, Map};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use crate::ctx;
+use crate::errors::{ImplicaError, ImplicaResult};
+use crate::graph::base::{Graph, Uid};
+use crate::matches::Match;
+use crate::patterns::TypeSchema;
+use crate::properties::PropertyMap;
+
+/// The kind of value a declared property holds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(in crate::graph) enum PropertyKind {
+    Str,
+    Int,
+    Float,
+    Bool,
+    List,
+    Dict,
+}
+
+impl PropertyKind {
+    fn from_string(s: &str) -> Option<Self> {
+        match s {
+            "str" => Some(PropertyKind::Str),
+            "int" => Some(PropertyKind::Int),
+            "float" => Some(PropertyKind::Float),
+            "bool" => Some(PropertyKind::Bool),
+            "list" => Some(PropertyKind::List),
+            "dict" => Some(PropertyKind::Dict),
+            _ => None,
+        }
+    }
+
+    fn to_string(self) -> &'static str {
+        match self {
+            PropertyKind::Str => "str",
+            PropertyKind::Int => "int",
+            PropertyKind::Float => "float",
+            PropertyKind::Bool => "bool",
+            PropertyKind::List => "list",
+            PropertyKind::Dict => "dict",
+        }
+    }
+
+    /// Whether a value is of this kind. Floats accept ints, as numbers compare by value. On a
+    /// graph that normalizes numerics, ints are stored as floats, so ints then accept floats
+    /// with no fractional part.
+    fn accepts(self, value: &Dynamic, normalize_numerics: bool) -> bool {
+        match self {
+            PropertyKind::Str => value.is_string(),
+            PropertyKind::Int if normalize_numerics && value.is_float() => value
+                .as_float()
+                .map(|value| value.fract() == 0.0)
+                .unwrap_or(false),
+            PropertyKind::Int => value.is_int(),
+            PropertyKind::Float => value.is_float() || value.is_int(),
+            PropertyKind::Bool => value.is_bool(),
+            PropertyKind::List => value.is_array(),
+            PropertyKind::Dict => value.is_map(),
+        }
+    }
+}
+
+/// A property declared for the node of a type: the kind of its value and whether the node
+/// must have it.
+#[derive(Clone, Copy, Debug)]
+pub(in crate::graph) struct DeclaredProperty {
+    kind: PropertyKind,
+    required: bool,
+}
+
+impl DeclaredProperty {
+    /// Reads a declaration such as `"int"`, or `"int?"` for an optional property.
+    fn parse(declaration: &str) -> Option<Self> {
+        let declaration = declaration.trim();
+        let (kind, required) = match declaration.strip_suffix('?') {
+            Some(kind) => (kind.trim(), false),
+            None => (declaration, true),
+        };
+
+        Some(DeclaredProperty {
+            kind: PropertyKind::from_string(kind)?,
+            required,
+        })
+    }
+
+    /// The declaration read by `parse`, e.g. `"int?"` for an optional int.
+    pub(in crate::graph) fn declaration(&self) -> String {
+        if self.required {
+            self.kind.to_string().to_string()
+        } else {
+            format!("{}?", self.kind.to_string())
+        }
+    }
+}
+
+/// The declared properties of a type, by key.
+pub(in crate::graph) type PropertySchema = BTreeMap<String, DeclaredProperty>;
+
+impl Graph {
+    /// Declares the properties of the node of a type, each mapped to the kind of its value
+    /// with a trailing `?` when it may be absent. An empty declaration removes the schema of
+    /// the type. The node of the type, if there is one, must already conform.
+    pub(crate) fn declare_property_schema(
+        &self,
+        r#type: &TypeSchema,
+        declarations: BTreeMap<String, String>,
+    ) -> ImplicaResult<()> {
+        self.check_not_frozen("declare property schema")
+            .attach(ctx!("graph - declare property schema"))?;
+
+        let mut schema = PropertySchema::new();
+        for (key, declaration) in declarations.iter() {
+            let declared = DeclaredProperty::parse(declaration).ok_or_else(|| {
+                ImplicaError::SchemaValidation {
+                    schema: r#type.to_string(),
+                    reason: format!(
+                        "property '{}' is declared as '{}', expected one of str, int, float, bool, list or dict, optionally followed by '?'",
+                        key, declaration
+                    ),
+                }
+            })?;
+
+            schema.insert(key.clone(), declared);
+        }
+
+        let type_uid = {
+            let r#type = self
+                .type_schema_to_type(r#type, Arc::new(Match::new(None)))
+                .attach(ctx!("graph - declare property schema"))?;
+            self.declare_type(&r#type)
+        };
+
+        if schema.is_empty() {
+            self.property_schemas.remove(&type_uid);
+            return Ok(());
+        }
+
+        if let Some(entry) = self.nodes.get(&type_uid) {
+            let properties = entry
+                .value()
+                .to_map()
+                .attach(ctx!("graph - declare property schema"))?;
+            drop(entry);
+
+            self.check_properties_against(&schema, &type_uid, &properties)
+                .attach(ctx!("graph - declare property schema"))?;
+        }
+
+        self.property_schemas.insert(type_uid, schema);
+        Ok(())
+    }
+
+    /// Checks the properties a node of the given type would hold against the schema declared
+    /// for the type, if any: every required property must be present, and every declared one
+    /// that is present must be of its kind. Undeclared properties are not checked.
+    pub(in crate::graph) fn check_property_schema(
+        &self,
+        node: &Uid,
+        properties: &PropertyMap,
+    ) -> ImplicaResult<()> {
+        let schema = match self.property_schemas.get(node) {
+            Some(entry) => entry.value().clone(),
+            None => return Ok(()),
+        };
+        let properties = properties
+            .to_map()
+            .attach(ctx!("graph - check property schema"))?;
+
+        self.check_properties_against(&schema, node, &properties)
+            .attach(ctx!("graph - check property schema"))
+    }
+
+    /// Like `check_property_schema`, for the properties a node holds once `properties` is
+    /// merged into its current ones.
+    pub(in crate::graph) fn check_merged_property_schema(
+        &self,
+        node: &Uid,
+        properties: &PropertyMap,
+    ) -> ImplicaResult<()> {
+        if !self.property_schemas.contains_key(node) {
+            return Ok(());
+        }
+
+        let mut merged = match self.nodes.get(node) {
+            Some(entry) => entry
+                .value()
+                .to_map()
+                .attach(ctx!("graph - check merged property schema"))?,
+            None => Map::new(),
+        };
+        merged.extend(
+            properties
+                .to_map()
+                .attach(ctx!("graph - check merged property schema"))?,
+        );
+
+        self.check_property_schema(node, &PropertyMap::from_map(merged))
+            .attach(ctx!("graph - check merged property schema"))
+    }
+
+    fn check_properties_against(
+        &self,
+        schema: &PropertySchema,
+        node: &Uid,
+        properties: &Map,
+    ) -> ImplicaResult<()> {
+        for (key, declared) in schema.iter() {
+            // A None value counts as absent
+            let value = properties
+                .get(key.as_str())
+                .filter(|value| !value.is_unit());
+
+            match value {
+                None if declared.required => {
+                    return Err(ImplicaError::SchemaValidation {
+                        schema: self
+                            .type_to_string(node)
+                            .attach(ctx!("graph - check properties against"))?,
+                        reason: format!(
+                            "missing required property '{}' of type {}",
+                            key,
+                            declared.kind.to_string()
+                        ),
+                    }
+                    .into())
+                }
+                Some(value) if !declared.kind.accepts(value, self.normalize_numerics) => {
+                    return Err(ImplicaError::InvalidType {
+                        reason: format!(
+                            "property '{}' of {} must be {}, got {}",
+                            key,
+                            self.type_to_string(node)
+                                .attach(ctx!("graph - check properties against"))?,
+                            declared.kind.to_string(),
+                            value.type_name()
+                        ),
+                    }
+                    .into())
+                }
+                _ => (),
+            }
+        }
+
+        Ok(())
+    }
+}
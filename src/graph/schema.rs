@@ -0,0 +1,176 @@
+use error_stack::ResultExt;
+use std::collections::BTreeMap;
+
+use crate::constants::{Constant, Sum};
+use crate::ctx;
+use crate::errors::{ImplicaError, ImplicaResult};
+use crate::graph::base::Graph;
+use crate::patterns::TypeSchema;
+use crate::utils::validate_variable_name;
+
+/// The vocabulary of a graph without any of its nodes or edges: its constants with their
+/// type schemas, its sum types with the names of their cases, its declared subtypes, the
+/// identity keys and property schemas declared for its types, and whether it is directed.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Schema {
+    pub(crate) constants: Vec<(String, String)>,
+    pub(crate) sums: Vec<(String, Vec<String>)>,
+    pub(crate) subtypes: Vec<(String, String)>,
+    pub(crate) identity_keys: Vec<(String, Vec<String>)>,
+    pub(crate) property_schemas: Vec<(String, BTreeMap<String, String>)>,
+    pub(crate) directed: bool,
+}
+
+impl Graph {
+    /// Describes the vocabulary of the graph, every part sorted so that equal vocabularies
+    /// give equal schemas.
+    pub(crate) fn schema(&self) -> ImplicaResult<Schema> {
+        let mut constants: Vec<(String, String)> = self
+            .constants
+            .iter()
+            .map(|entry| {
+                (
+                    entry.key().clone(),
+                    entry.value().type_schema.pattern.clone(),
+                )
+            })
+            .collect();
+        constants.sort();
+
+        let mut sums: Vec<(String, Vec<String>)> = self
+            .sums
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect();
+        sums.sort();
+
+        let mut subtypes: Vec<(String, String)> = Vec::new();
+        for entry in self.supertypes.iter() {
+            let sub = self
+                .type_to_string(entry.key())
+                .attach(ctx!("graph - schema"))?;
+
+            for sup in entry.value().iter() {
+                let sup = self.type_to_string(sup).attach(ctx!("graph - schema"))?;
+                subtypes.push((sub.clone(), sup));
+            }
+        }
+        subtypes.sort();
+
+        let mut identity_keys: Vec<(String, Vec<String>)> = Vec::new();
+        for entry in self.identity_keys.iter() {
+            let r#type = self
+                .type_to_string(entry.key())
+                .attach(ctx!("graph - schema"))?;
+            identity_keys.push((r#type, entry.value().clone()));
+        }
+        identity_keys.sort();
+
+        let mut property_schemas: Vec<(String, BTreeMap<String, String>)> = Vec::new();
+        for entry in self.property_schemas.iter() {
+            let r#type = self
+                .type_to_string(entry.key())
+                .attach(ctx!("graph - schema"))?;
+            let declarations = entry
+                .value()
+                .iter()
+                .map(|(key, declared)| (key.clone(), declared.declaration()))
+                .collect();
+            property_schemas.push((r#type, declarations));
+        }
+        property_schemas.sort();
+
+        Ok(Schema {
+            constants,
+            sums,
+            subtypes,
+            identity_keys,
+            property_schemas,
+            directed: self.directed,
+        })
+    }
+
+    /// Builds an empty graph with the vocabulary described by `schema`.
+    ///
+    /// Constant and sum names are validated like identifiers, and the cases of every sum
+    /// must be among the constants of the schema.
+    pub(crate) fn from_schema(schema: &Schema, normalize_numerics: bool) -> ImplicaResult<Self> {
+        let mut constants: Vec<Constant> = Vec::with_capacity(schema.constants.len());
+        for (name, type_schema) in schema.constants.iter() {
+            validate_variable_name(name).attach(ctx!("graph - from schema"))?;
+
+            let type_schema =
+                TypeSchema::new(type_schema.clone()).attach(ctx!("graph - from schema"))?;
+            let free_variables = type_schema.get_free_variables();
+
+            constants.push(Constant {
+                name: name.clone(),
+                type_schema,
+                free_variables,
+            });
+        }
+
+        let mut sums: Vec<Sum> = Vec::with_capacity(schema.sums.len());
+        for (name, cases) in schema.sums.iter() {
+            validate_variable_name(name).attach(ctx!("graph - from schema"))?;
+
+            if cases.is_empty() {
+                return Err(ImplicaError::InvalidType {
+                    reason: format!("sum type '{}' needs at least one case", name),
+                }
+                .into());
+            }
+
+            let cases = cases
+                .iter()
+                .map(|case| {
+                    constants
+                        .iter()
+                        .find(|c| &c.name == case)
+                        .cloned()
+                        .ok_or_else(|| {
+                            ImplicaError::ConstantNotFound {
+                                name: case.clone(),
+                                context: Some(ctx!(format!("graph - from schema - sum {}", name))),
+                            }
+                            .into()
+                        })
+                })
+                .collect::<ImplicaResult<Vec<Constant>>>()?;
+
+            sums.push(Sum {
+                name: name.clone(),
+                cases,
+            });
+        }
+
+        let graph = Graph::new(constants, sums, normalize_numerics, schema.directed);
+
+        for (sub, sup) in schema.subtypes.iter() {
+            let sub = TypeSchema::new(sub.clone()).attach(ctx!("graph - from schema"))?;
+            let sup = TypeSchema::new(sup.clone()).attach(ctx!("graph - from schema"))?;
+
+            graph
+                .declare_subtype(&sub, &sup)
+                .attach(ctx!("graph - from schema"))?;
+        }
+
+        for (r#type, keys) in schema.identity_keys.iter() {
+            let r#type = TypeSchema::new(r#type.clone()).attach(ctx!("graph - from schema"))?;
+
+            graph
+                .set_identity_key(&r#type, keys.clone())
+                .attach(ctx!("graph - from schema"))?;
+        }
+
+        for (r#type, declarations) in schema.property_schemas.iter() {
+            let r#type = TypeSchema::new(r#type.clone()).attach(ctx!("graph - from schema"))?;
+
+            graph
+                .declare_property_schema(&r#type, declarations.clone())
+                .attach(ctx!("graph - from schema"))?;
+        }
+
+        Ok(graph)
+    }
+}
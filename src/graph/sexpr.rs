@@ -0,0 +1,146 @@
+use std::sync::Arc;
+
+use error_stack::ResultExt;
+
+use crate::ctx;
+use crate::errors::{ImplicaError, ImplicaResult};
+use crate::graph::base::{Graph, TermRep, TypeRep, Uid};
+use crate::matches::Match;
+use crate::patterns::TermPattern;
+use crate::typing::{Arrow, SExpr, Type, Variable};
+
+impl Graph {
+    /// Writes a type as an S-expression: a variable by its name, an arrow as
+    /// `(arrow left right)`.
+    pub(crate) fn type_to_sexpr(&self, r#type: &Uid) -> ImplicaResult<SExpr> {
+        let type_rep = match self.type_index.get(r#type) {
+            Some(entry) => entry.value().clone(),
+            None => {
+                return Err(ImplicaError::TypeNotFound {
+                    uid: *r#type,
+                    context: Some("type to sexpr".to_string()),
+                }
+                .into())
+            }
+        };
+
+        match type_rep {
+            TypeRep::Variable(name) => Ok(SExpr::Atom(name)),
+            TypeRep::Arrow(left, right) => Ok(SExpr::List(vec![
+                SExpr::Atom("arrow".to_string()),
+                self.type_to_sexpr(&left)
+                    .attach(ctx!("graph - type to sexpr"))?,
+                self.type_to_sexpr(&right)
+                    .attach(ctx!("graph - type to sexpr"))?,
+            ])),
+        }
+    }
+
+    /// Writes a term as an S-expression: a constant by its name, an application as
+    /// `(app function argument)`.
+    pub(crate) fn term_to_sexpr(&self, term: &Uid) -> ImplicaResult<SExpr> {
+        let term_rep = match self.term_index.get(term) {
+            Some(entry) => entry.value().clone(),
+            None => {
+                return Err(ImplicaError::TermNotFound {
+                    uid: *term,
+                    context: Some("term to sexpr".to_string()),
+                }
+                .into())
+            }
+        };
+
+        match term_rep {
+            TermRep::Base(name) => Ok(SExpr::Atom(name)),
+            TermRep::Application(function, argument) => Ok(SExpr::List(vec![
+                SExpr::Atom("app".to_string()),
+                self.term_to_sexpr(&function)
+                    .attach(ctx!("graph - term to sexpr"))?,
+                self.term_to_sexpr(&argument)
+                    .attach(ctx!("graph - term to sexpr"))?,
+            ])),
+        }
+    }
+
+    /// Reads a type written as an S-expression and interns it, returning its uid.
+    pub(crate) fn type_from_sexpr(&self, input: &str) -> ImplicaResult<Uid> {
+        self.check_not_frozen("type from sexpr")
+            .attach(ctx!("graph - type from sexpr"))?;
+
+        let sexpr = SExpr::parse(input)
+            .map_err(|reason| ImplicaError::InvalidType {
+                reason: format!("'{}' is not an S-expression: {}", input, reason),
+            })
+            .attach(ctx!("graph - type from sexpr"))?;
+
+        let r#type = Self::sexpr_to_type(&sexpr).attach(ctx!("graph - type from sexpr"))?;
+
+        let known = self.type_index.contains_key(&Self::type_uid(&r#type));
+        let uid = self.insert_type(&r#type);
+        if !known {
+            self.bump_version();
+        }
+
+        Ok(uid)
+    }
+
+    /// Reads a term written as an S-expression over the graph's constants and stores it,
+    /// returning its uid. Like an application built with `apply_term`, it fails when a type
+    /// it needs already holds a different term.
+    pub(crate) fn term_from_sexpr(&self, input: &str) -> ImplicaResult<Uid> {
+        self.check_not_frozen("term from sexpr")
+            .attach(ctx!("graph - term from sexpr"))?;
+
+        let sexpr = SExpr::parse(input)
+            .map_err(|reason| ImplicaError::InvalidTerm {
+                reason: format!("'{}' is not an S-expression: {}", input, reason),
+            })
+            .attach(ctx!("graph - term from sexpr"))?;
+
+        let pattern =
+            Self::sexpr_to_term_pattern(&sexpr).attach(ctx!("graph - term from sexpr"))?;
+        let term = self
+            .pattern_to_term_recursive(&pattern, Arc::new(Match::new(None)))
+            .attach(ctx!("graph - term from sexpr"))?;
+
+        self.intern_term(&term)
+            .attach(ctx!("graph - term from sexpr"))
+    }
+
+    fn sexpr_to_type(sexpr: &SExpr) -> ImplicaResult<Type> {
+        match sexpr {
+            SExpr::Atom(name) => Ok(Type::Variable(
+                Variable::new(name.clone()).attach(ctx!("graph - sexpr to type"))?,
+            )),
+            _ => match sexpr.operands("arrow") {
+                Some([left, right]) => Ok(Type::Arrow(Arrow::new(
+                    Arc::new(Self::sexpr_to_type(left)?),
+                    Arc::new(Self::sexpr_to_type(right)?),
+                ))),
+                _ => Err(ImplicaError::InvalidType {
+                    reason: format!("expected a name or '(arrow A B)', found '{}'", sexpr),
+                }
+                .into()),
+            },
+        }
+    }
+
+    fn sexpr_to_term_pattern(sexpr: &SExpr) -> ImplicaResult<TermPattern> {
+        match sexpr {
+            SExpr::Atom(name) => Ok(TermPattern::Constant {
+                name: name.clone(),
+                args: Vec::new(),
+            }),
+            _ => match sexpr.operands("app") {
+                Some([function, argument]) => Ok(TermPattern::Application {
+                    function: Box::new(Self::sexpr_to_term_pattern(function)?),
+                    argument: Box::new(Self::sexpr_to_term_pattern(argument)?),
+                }),
+                _ => Err(ImplicaError::InvalidTerm {
+                    reason: format!("expected a constant or '(app f x)', found '{}'", sexpr),
+                }
+                .into()),
+            },
+        }
+    }
+}
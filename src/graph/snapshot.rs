@@ -0,0 +1,197 @@
+use dashmap::DashSet;
+use error_stack::ResultExt;
+use pyo3::prelude::*;
+use std::sync::Arc;
+
+use crate::ctx;
+use crate::errors::{ImplicaError, ImplicaResult};
+use crate::graph::base::{Graph, TermRep, TypeRep, Uid};
+use crate::properties::PropertyMap;
+
+use super::__metadata::Timestamps;
+
+/// A full copy of the contents of a graph, taken by `Graph::snapshot`.
+///
+/// Property maps are copied rather than shared, so later mutations of the graph never leak
+/// into the snapshot and the same snapshot can be restored any number of times.
+#[derive(Debug, Clone)]
+pub(crate) struct GraphSnapshot {
+    nodes: Vec<(Uid, PropertyMap)>,
+    edges: Vec<((Uid, Uid), PropertyMap)>,
+    type_index: Vec<(Uid, TypeRep)>,
+    term_index: Vec<(Uid, TermRep)>,
+    type_to_edge_index: Vec<(Uid, (Uid, Uid))>,
+    start_to_edge_index: Vec<(Uid, Vec<(Uid, Uid)>)>,
+    end_to_edge_index: Vec<(Uid, Vec<(Uid, Uid)>)>,
+    node_timestamps: Vec<(Uid, Timestamps)>,
+    edge_timestamps: Vec<((Uid, Uid), Timestamps)>,
+}
+
+pub(in crate::graph) fn copy_properties(properties: &PropertyMap) -> ImplicaResult<PropertyMap> {
+    Ok(PropertyMap::from_map(
+        properties
+            .to_map()
+            .attach(ctx!("graph - copy properties"))?,
+    ))
+}
+
+impl Graph {
+    /// Copies the nodes, edges, types, terms and indexes of the graph.
+    pub(crate) fn snapshot(&self) -> ImplicaResult<GraphSnapshot> {
+        Ok(GraphSnapshot {
+            nodes: self
+                .nodes
+                .iter()
+                .map(|entry| Ok((*entry.key(), copy_properties(entry.value())?)))
+                .collect::<ImplicaResult<_>>()
+                .attach(ctx!("graph - snapshot"))?,
+            edges: self
+                .edges
+                .iter()
+                .map(|entry| Ok((*entry.key(), copy_properties(entry.value())?)))
+                .collect::<ImplicaResult<_>>()
+                .attach(ctx!("graph - snapshot"))?,
+            type_index: self
+                .type_index
+                .iter()
+                .map(|entry| (*entry.key(), entry.value().clone()))
+                .collect(),
+            term_index: self
+                .term_index
+                .iter()
+                .map(|entry| (*entry.key(), entry.value().clone()))
+                .collect(),
+            type_to_edge_index: self
+                .type_to_edge_index
+                .iter()
+                .map(|entry| (*entry.key(), *entry.value()))
+                .collect(),
+            start_to_edge_index: self
+                .start_to_edge_index
+                .iter()
+                .map(|entry| (*entry.key(), entry.value().iter().map(|e| *e).collect()))
+                .collect(),
+            end_to_edge_index: self
+                .end_to_edge_index
+                .iter()
+                .map(|entry| (*entry.key(), entry.value().iter().map(|e| *e).collect()))
+                .collect(),
+            node_timestamps: self
+                .node_timestamps
+                .iter()
+                .map(|entry| (*entry.key(), *entry.value()))
+                .collect(),
+            edge_timestamps: self
+                .edge_timestamps
+                .iter()
+                .map(|entry| (*entry.key(), *entry.value()))
+                .collect(),
+        })
+    }
+
+    /// Replaces the contents of the graph with those of a snapshot.
+    ///
+    /// Elements created after the snapshot was taken disappear, so references to them stop
+    /// resolving. The version is bumped, which invalidates any cached match.
+    pub(crate) fn restore(&self, snapshot: &GraphSnapshot) -> ImplicaResult<()> {
+        self.check_not_frozen("restore")
+            .attach(ctx!("graph - restore"))?;
+
+        self.bump_version();
+
+        self.nodes.clear();
+        for (uid, properties) in snapshot.nodes.iter() {
+            self.nodes.insert(
+                *uid,
+                copy_properties(properties).attach(ctx!("graph - restore"))?,
+            );
+        }
+
+        self.edges.clear();
+        for (uid, properties) in snapshot.edges.iter() {
+            self.edges.insert(
+                *uid,
+                copy_properties(properties).attach(ctx!("graph - restore"))?,
+            );
+        }
+
+        self.type_index.clear();
+        for (uid, type_rep) in snapshot.type_index.iter() {
+            self.type_index.insert(*uid, type_rep.clone());
+        }
+
+        self.term_index.clear();
+        for (uid, term_rep) in snapshot.term_index.iter() {
+            self.term_index.insert(*uid, term_rep.clone());
+        }
+
+        self.type_to_edge_index.clear();
+        self.edge_to_type_index.clear();
+        for (type_uid, edge) in snapshot.type_to_edge_index.iter() {
+            self.type_to_edge_index.insert(*type_uid, *edge);
+            self.edge_to_type_index.insert(*edge, *type_uid);
+        }
+
+        self.start_to_edge_index.clear();
+        for (uid, edges) in snapshot.start_to_edge_index.iter() {
+            self.start_to_edge_index.insert(
+                *uid,
+                Arc::new(edges.iter().copied().collect::<DashSet<_>>()),
+            );
+        }
+
+        self.end_to_edge_index.clear();
+        for (uid, edges) in snapshot.end_to_edge_index.iter() {
+            self.end_to_edge_index.insert(
+                *uid,
+                Arc::new(edges.iter().copied().collect::<DashSet<_>>()),
+            );
+        }
+
+        self.node_timestamps.clear();
+        for (uid, timestamps) in snapshot.node_timestamps.iter() {
+            self.node_timestamps.insert(*uid, *timestamps);
+        }
+
+        self.edge_timestamps.clear();
+        for (uid, timestamps) in snapshot.edge_timestamps.iter() {
+            self.edge_timestamps.insert(*uid, *timestamps);
+        }
+
+        Ok(())
+    }
+}
+
+#[pyclass(name = "Snapshot")]
+#[derive(Debug, Clone)]
+pub struct PySnapshot {
+    pub(in crate::graph) graph: Arc<Graph>,
+    pub(in crate::graph) snapshot: Arc<GraphSnapshot>,
+}
+
+impl PySnapshot {
+    /// Ensures the snapshot was taken from the given graph before restoring it there.
+    pub(in crate::graph) fn check_source(&self, graph: &Arc<Graph>) -> ImplicaResult<()> {
+        if Arc::ptr_eq(&self.graph, graph) {
+            Ok(())
+        } else {
+            Err(ImplicaError::SnapshotMismatch {
+                reason: "a snapshot can only be restored into the graph it was taken from"
+                    .to_string(),
+                context: Some(ctx!("snapshot - check source")),
+            }
+            .into())
+        }
+    }
+}
+
+#[pymethods]
+impl PySnapshot {
+    pub fn __repr__(&self) -> String {
+        format!(
+            "Snapshot(nodes={}, edges={})",
+            self.snapshot.nodes.len(),
+            self.snapshot.edges.len()
+        )
+    }
+}
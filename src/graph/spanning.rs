@@ -0,0 +1,102 @@
+use std::collections::HashMap;
+
+use error_stack::ResultExt;
+
+use crate::ctx;
+use crate::errors::{ImplicaError, ImplicaResult};
+use crate::graph::base::{Graph, Uid};
+use crate::utils::numeric_value;
+
+/// Union-find over the nodes of the graph, with path halving.
+struct Forest {
+    parent: HashMap<Uid, Uid>,
+}
+
+impl Forest {
+    fn find(&mut self, node: Uid) -> Uid {
+        let mut current = node;
+
+        loop {
+            let parent = *self.parent.entry(current).or_insert(current);
+            if parent == current {
+                return current;
+            }
+
+            let grandparent = *self.parent.entry(parent).or_insert(parent);
+            self.parent.insert(current, grandparent);
+            current = grandparent;
+        }
+    }
+
+    /// Joins the trees of two nodes, returning whether they were apart.
+    fn union(&mut self, a: Uid, b: Uid) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return false;
+        }
+
+        self.parent.insert(a, b);
+        true
+    }
+}
+
+impl Graph {
+    /// Computes a minimum spanning forest of the graph with Kruskal's algorithm, ignoring
+    /// edge directions.
+    ///
+    /// Edges are weighted by their `weight` property. An edge lacking it, or holding a
+    /// non-numeric value, takes `default` when one is given and is an error otherwise. Ties
+    /// are broken by edge uid, so the same graph always yields the same forest. Returns the
+    /// selected edges and their total weight.
+    pub(crate) fn minimum_spanning_tree(
+        &self,
+        weight: &str,
+        default: Option<f64>,
+    ) -> ImplicaResult<(Vec<(Uid, Uid)>, f64)> {
+        let mut weighted: Vec<(f64, (Uid, Uid))> = Vec::with_capacity(self.edges.len());
+
+        for entry in self.edges.iter() {
+            let value = entry
+                .value()
+                .get(weight)
+                .attach(ctx!("graph - minimum spanning tree"))?;
+
+            let w = match (value.as_ref().and_then(numeric_value), default) {
+                (Some(w), _) => w,
+                (None, Some(default)) => default,
+                (None, None) => {
+                    return Err(ImplicaError::TypeMismatch {
+                        expected: "int or float".to_string(),
+                        got: value
+                            .map(|v| v.type_name().to_string())
+                            .unwrap_or_else(|| "no value".to_string()),
+                        context: Some(ctx!(format!(
+                            "graph - minimum spanning tree - weight property '{}'",
+                            weight
+                        ))),
+                    }
+                    .into())
+                }
+            };
+
+            weighted.push((w, *entry.key()));
+        }
+
+        weighted.sort_by(|a, b| a.0.total_cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+
+        let mut forest = Forest {
+            parent: HashMap::new(),
+        };
+        let mut selected = Vec::new();
+        let mut total = 0.0;
+
+        for (w, edge) in weighted {
+            if forest.union(edge.0, edge.1) {
+                selected.push(edge);
+                total += w;
+            }
+        }
+
+        Ok((selected, total))
+    }
+}
@@ -0,0 +1,109 @@
+use error_stack::ResultExt;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::atomic::Ordering;
+
+use crate::ctx;
+use crate::errors::ImplicaResult;
+use crate::graph::base::{Graph, Uid};
+
+/// Aggregate figures describing the size and shape of a graph.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct GraphStats {
+    pub nodes: usize,
+    pub edges: usize,
+    pub types: usize,
+    pub terms: usize,
+    pub average_degree: f64,
+    pub max_degree: usize,
+}
+
+impl Graph {
+    /// Summarises the graph in a single pass over its nodes and edges.
+    ///
+    /// The degree of a node counts both its outgoing and incoming edges. `types` counts every
+    /// type registered in the graph, component types of arrows included, while `terms` counts
+    /// the nodes that carry a term.
+    pub(crate) fn stats(&self) -> GraphStats {
+        let mut degrees: HashMap<Uid, usize> = HashMap::new();
+        let mut edges = 0;
+
+        for entry in self.edges.iter() {
+            let (start, end) = *entry.key();
+            *degrees.entry(start).or_default() += 1;
+            *degrees.entry(end).or_default() += 1;
+            edges += 1;
+        }
+
+        let nodes = self.nodes.len();
+        let terms = self
+            .nodes
+            .iter()
+            .filter(|entry| self.term_index.contains_key(entry.key()))
+            .count();
+
+        GraphStats {
+            nodes,
+            edges,
+            types: self.type_index.len(),
+            terms,
+            average_degree: if nodes == 0 {
+                0.0
+            } else {
+                (2 * edges) as f64 / nodes as f64
+            },
+            max_degree: degrees.values().copied().max().unwrap_or(0),
+        }
+    }
+
+    /// Groups the edges by the string form of their type or, with `by_term`, of their term.
+    ///
+    /// An edge is identified by its endpoints, which fix its arrow type, so grouping by type
+    /// yields one edge per group; edges built from the same constant share a term and are
+    /// grouped together. Groups and the edges within them are sorted.
+    pub(crate) fn edges_grouped(
+        &self,
+        by_term: bool,
+    ) -> ImplicaResult<BTreeMap<String, Vec<(Uid, Uid)>>> {
+        let mut groups: BTreeMap<String, Vec<(Uid, Uid)>> = BTreeMap::new();
+
+        for entry in self.edges.iter() {
+            let edge = *entry.key();
+            let r#type = self
+                .get_edge_type(&edge)
+                .attach(ctx!("graph - edges grouped"))?;
+            let key = if by_term {
+                self.term_to_string(&r#type)
+            } else {
+                self.type_to_string(&r#type)
+            }
+            .attach(ctx!("graph - edges grouped"))?;
+
+            groups.entry(key).or_default().push(edge);
+        }
+
+        for edges in groups.values_mut() {
+            edges.sort();
+        }
+
+        Ok(groups)
+    }
+
+    /// Number of candidate nodes examined by node-pattern matching so far.
+    pub(crate) fn nodes_scanned(&self) -> u64 {
+        self.nodes_scanned.load(Ordering::Relaxed)
+    }
+
+    pub(in crate::graph) fn record_node_scanned(&self) {
+        self.nodes_scanned.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Number of edges edge-pattern matching has checked against a type or term schema so
+    /// far, i.e. the ones that passed its cheaper arity and property checks.
+    pub(crate) fn edge_schemas_checked(&self) -> u64 {
+        self.edge_schemas_checked.load(Ordering::Relaxed)
+    }
+
+    pub(in crate::graph) fn record_edge_schema_checked(&self) {
+        self.edge_schemas_checked.fetch_add(1, Ordering::Relaxed);
+    }
+}
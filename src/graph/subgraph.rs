@@ -0,0 +1,216 @@
+use dashmap::DashSet;
+use error_stack::ResultExt;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::ctx;
+use crate::errors::{ImplicaError, ImplicaResult};
+use crate::graph::base::{Graph, TermRep, TypeRep, Uid};
+
+use super::__snapshot::copy_properties;
+
+impl Graph {
+    /// Copies the given nodes and every edge among them into a new graph.
+    ///
+    /// The copy has the constants, sum types, subtype declarations, identity keys, property
+    /// schemas and numeric normalization of this graph, and only the types and terms its elements refer to. Properties are
+    /// copied, so the two graphs can be mutated independently.
+    pub(crate) fn induced_subgraph(&self, nodes: &HashSet<Uid>) -> ImplicaResult<Graph> {
+        let mut edges = HashSet::new();
+
+        for node in nodes.iter() {
+            let out_edges = self
+                .out_edges(node)
+                .attach(ctx!("graph - induced subgraph"))?;
+
+            edges.extend(out_edges.into_iter().filter(|(_, end)| nodes.contains(end)));
+        }
+
+        self.copy_subgraph(nodes, &edges)
+            .attach(ctx!("graph - induced subgraph"))
+    }
+
+    /// Copies the whole graph into a new one, like `copy_subgraph` over every node and edge.
+    pub(crate) fn copy(&self) -> ImplicaResult<Graph> {
+        let nodes = self.nodes.iter().map(|entry| *entry.key()).collect();
+        let edges = self.edges.iter().map(|entry| *entry.key()).collect();
+
+        self.copy_subgraph(&nodes, &edges)
+            .attach(ctx!("graph - copy"))
+    }
+
+    /// Copies the given nodes and edges into a new graph, like `induced_subgraph` but keeping
+    /// only the listed edges. Edges whose endpoints are not among the nodes are skipped.
+    pub(crate) fn copy_subgraph(
+        &self,
+        nodes: &HashSet<Uid>,
+        edges: &HashSet<(Uid, Uid)>,
+    ) -> ImplicaResult<Graph> {
+        let subgraph = Graph::new(
+            Vec::new(),
+            Vec::new(),
+            self.normalize_numerics,
+            self.directed,
+        );
+
+        for entry in self.constants.iter() {
+            subgraph
+                .constants
+                .insert(entry.key().clone(), entry.value().clone());
+        }
+        for entry in self.sums.iter() {
+            subgraph
+                .sums
+                .insert(entry.key().clone(), entry.value().clone());
+        }
+        for entry in self.declared_types.iter() {
+            subgraph
+                .declared_types
+                .insert(*entry.key(), entry.value().clone());
+        }
+        for entry in self.supertypes.iter() {
+            subgraph
+                .supertypes
+                .insert(*entry.key(), entry.value().clone());
+        }
+        for entry in self.identity_keys.iter() {
+            subgraph
+                .identity_keys
+                .insert(*entry.key(), entry.value().clone());
+        }
+        for entry in self.property_schemas.iter() {
+            subgraph
+                .property_schemas
+                .insert(*entry.key(), entry.value().clone());
+        }
+
+        for node in nodes.iter() {
+            let properties = match self.nodes.get(node) {
+                Some(entry) => {
+                    copy_properties(entry.value()).attach(ctx!("graph - copy subgraph"))?
+                }
+                None => {
+                    return Err(ImplicaError::NodeNotFound {
+                        uid: *node,
+                        context: Some(ctx!("graph - copy subgraph")),
+                    }
+                    .into())
+                }
+            };
+
+            self.copy_type_into(&subgraph, node)
+                .attach(ctx!("graph - copy subgraph"))?;
+            if self.term_index.contains_key(node) {
+                self.copy_term_into(&subgraph, node)
+                    .attach(ctx!("graph - copy subgraph"))?;
+            }
+
+            subgraph.nodes.insert(*node, properties);
+            subgraph
+                .start_to_edge_index
+                .insert(*node, Arc::new(DashSet::new()));
+            subgraph
+                .end_to_edge_index
+                .insert(*node, Arc::new(DashSet::new()));
+            if let Some(timestamps) = self.node_timestamps.get(node) {
+                subgraph.node_timestamps.insert(*node, *timestamps.value());
+            }
+        }
+
+        for edge in edges
+            .iter()
+            .filter(|(start, end)| nodes.contains(start) && nodes.contains(end))
+        {
+            let properties = match self.edges.get(edge) {
+                Some(entry) => {
+                    copy_properties(entry.value()).attach(ctx!("graph - copy subgraph"))?
+                }
+                // Removed since the edges were read
+                None => continue,
+            };
+            let r#type = self
+                .get_edge_type(edge)
+                .attach(ctx!("graph - copy subgraph"))?;
+
+            self.copy_type_into(&subgraph, &r#type)
+                .attach(ctx!("graph - copy subgraph"))?;
+            self.copy_term_into(&subgraph, &r#type)
+                .attach(ctx!("graph - copy subgraph"))?;
+
+            subgraph.edges.insert(*edge, properties);
+            subgraph.type_to_edge_index.insert(r#type, *edge);
+            subgraph.edge_to_type_index.insert(*edge, r#type);
+            if let Some(entry) = subgraph.start_to_edge_index.get(&edge.0) {
+                entry.value().insert(*edge);
+            }
+            if let Some(entry) = subgraph.end_to_edge_index.get(&edge.1) {
+                entry.value().insert(*edge);
+            }
+            if let Some(timestamps) = self.edge_timestamps.get(edge) {
+                subgraph.edge_timestamps.insert(*edge, *timestamps.value());
+            }
+        }
+
+        Ok(subgraph)
+    }
+
+    /// Copies a type and the types it is built from into another graph.
+    fn copy_type_into(&self, target: &Graph, r#type: &Uid) -> ImplicaResult<()> {
+        if target.type_index.contains_key(r#type) {
+            return Ok(());
+        }
+
+        let type_rep = match self.type_index.get(r#type) {
+            Some(entry) => entry.value().clone(),
+            None => {
+                return Err(ImplicaError::TypeNotFound {
+                    uid: *r#type,
+                    context: Some(ctx!("graph - copy type into")),
+                }
+                .into())
+            }
+        };
+
+        if let TypeRep::Arrow(left, right) = &type_rep {
+            self.copy_type_into(target, left)
+                .attach(ctx!("graph - copy type into"))?;
+            self.copy_type_into(target, right)
+                .attach(ctx!("graph - copy type into"))?;
+        }
+
+        target.type_index.insert(*r#type, type_rep);
+        Ok(())
+    }
+
+    /// Copies the term stored under a type, and the terms it is applied to, into another
+    /// graph together with their types.
+    fn copy_term_into(&self, target: &Graph, r#type: &Uid) -> ImplicaResult<()> {
+        if target.term_index.contains_key(r#type) {
+            return Ok(());
+        }
+
+        let term_rep = match self.term_index.get(r#type) {
+            Some(entry) => entry.value().clone(),
+            None => {
+                return Err(ImplicaError::TermNotFound {
+                    uid: *r#type,
+                    context: Some(ctx!("graph - copy term into")),
+                }
+                .into())
+            }
+        };
+
+        self.copy_type_into(target, r#type)
+            .attach(ctx!("graph - copy term into"))?;
+
+        if let TermRep::Application(function, argument) = &term_rep {
+            self.copy_term_into(target, function)
+                .attach(ctx!("graph - copy term into"))?;
+            self.copy_term_into(target, argument)
+                .attach(ctx!("graph - copy term into"))?;
+        }
+
+        target.term_index.insert(*r#type, term_rep);
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use std::collections::{HashSet, VecDeque};
+use std::sync::Arc;
+
+use error_stack::ResultExt;
+
+use crate::ctx;
+use crate::errors::ImplicaResult;
+use crate::graph::base::{Graph, Uid};
+use crate::matches::Match;
+use crate::patterns::{NodePattern, TypeSchema};
+
+impl Graph {
+    /// Records that `sub` is a subtype of `sup`.
+    ///
+    /// The relation is transitive and is only consulted by the `subtype_of` node modifier;
+    /// type schemas keep matching types exactly.
+    pub(crate) fn declare_subtype(&self, sub: &TypeSchema, sup: &TypeSchema) -> ImplicaResult<()> {
+        self.check_not_frozen("declare subtype")
+            .attach(ctx!("graph - declare subtype"))?;
+
+        let sub = self
+            .type_schema_to_type(sub, Arc::new(Match::new(None)))
+            .attach(ctx!("graph - declare subtype"))?;
+        let sup = self
+            .type_schema_to_type(sup, Arc::new(Match::new(None)))
+            .attach(ctx!("graph - declare subtype"))?;
+
+        let sub_uid = self.declare_type(&sub);
+        let sup_uid = self.declare_type(&sup);
+
+        self.supertypes.entry(sub_uid).or_default().insert(sup_uid);
+        self.bump_version();
+
+        Ok(())
+    }
+
+    /// Returns a type followed by every type it was declared a subtype of, directly or
+    /// through a chain of declarations.
+    fn supertype_closure(&self, r#type: &Uid) -> Vec<Uid> {
+        let mut seen: HashSet<Uid> = HashSet::from([*r#type]);
+        let mut closure = vec![*r#type];
+        let mut queue = VecDeque::from([*r#type]);
+
+        while let Some(current) = queue.pop_front() {
+            let direct: Vec<Uid> = match self.supertypes.get(&current) {
+                Some(entry) => entry.value().iter().copied().collect(),
+                None => continue,
+            };
+
+            for sup in direct {
+                if seen.insert(sup) {
+                    closure.push(sup);
+                    queue.push_back(sup);
+                }
+            }
+        }
+
+        closure
+    }
+
+    /// Checks the `subtype_of` modifier of a node pattern: the node's type or one of its
+    /// supertypes must match the schema.
+    pub(in crate::graph) fn check_node_subtype(
+        &self,
+        node: &Uid,
+        pattern: &NodePattern,
+    ) -> ImplicaResult<bool> {
+        let schema = match pattern.subtype_of {
+            Some(ref schema) => schema,
+            None => return Ok(true),
+        };
+
+        for r#type in self.supertype_closure(node) {
+            if self
+                .check_type_schema_matches(&r#type, schema, Arc::new(Match::new(None)))
+                .attach(ctx!("graph - check node subtype"))?
+                .is_some()
+            {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+}
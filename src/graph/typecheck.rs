@@ -0,0 +1,236 @@
+use error_stack::ResultExt;
+
+use crate::ctx;
+use crate::errors::ImplicaResult;
+use crate::graph::base::{Graph, TermRep, TypeRep, Uid};
+
+/// The element a type violation was found on.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum TypedElement {
+    Node(Uid),
+    Edge((Uid, Uid)),
+}
+
+/// A node or edge whose type disagrees with its term or its endpoints.
+#[derive(Debug, Clone)]
+pub(crate) struct TypeViolation {
+    pub element: TypedElement,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl Graph {
+    /// Checks every node and edge against the types they are built from.
+    ///
+    /// An edge must have an arrow type going from the type of its start node to the type of
+    /// its end node. The term of a node or edge must have its type: a constant must be
+    /// declared with a schema matching it, and an application `f x` held by a type `B` needs
+    /// `f` to have type `A -> B` where `A` is the type of `x`. Only the outermost application
+    /// of a term is checked; its parts are checked on the nodes that hold them.
+    pub(crate) fn type_check(&self) -> ImplicaResult<Vec<TypeViolation>> {
+        let mut violations = Vec::new();
+
+        for entry in self.nodes.iter() {
+            let node = *entry.key();
+
+            if let Some((expected, actual)) = self
+                .check_term_type(&node)
+                .attach(ctx!("graph - type check"))?
+            {
+                violations.push(TypeViolation {
+                    element: TypedElement::Node(node),
+                    expected,
+                    actual,
+                });
+            }
+        }
+
+        for entry in self.edges.iter() {
+            let edge = *entry.key();
+            let (start, end) = edge;
+
+            let expected = format!(
+                "({} -> {})",
+                self.type_to_string(&start)
+                    .attach(ctx!("graph - type check"))?,
+                self.type_to_string(&end)
+                    .attach(ctx!("graph - type check"))?
+            );
+
+            let r#type = match self.edge_to_type_index.get(&edge) {
+                Some(r#type) => *r#type.value(),
+                None => {
+                    violations.push(TypeViolation {
+                        element: TypedElement::Edge(edge),
+                        expected,
+                        actual: "no type".to_string(),
+                    });
+                    continue;
+                }
+            };
+
+            let endpoints_match = matches!(
+                self.type_index.get(&r#type).as_deref(),
+                Some(TypeRep::Arrow(left, right)) if *left == start && *right == end
+            );
+            if !endpoints_match {
+                violations.push(TypeViolation {
+                    element: TypedElement::Edge(edge),
+                    expected,
+                    actual: self
+                        .type_to_string(&r#type)
+                        .attach(ctx!("graph - type check"))?,
+                });
+                continue;
+            }
+
+            if !self.term_index.contains_key(&r#type) {
+                violations.push(TypeViolation {
+                    element: TypedElement::Edge(edge),
+                    expected: format!("a term of type {}", expected),
+                    actual: "no term".to_string(),
+                });
+                continue;
+            }
+
+            if let Some((expected, actual)) = self
+                .check_term_type(&r#type)
+                .attach(ctx!("graph - type check"))?
+            {
+                violations.push(TypeViolation {
+                    element: TypedElement::Edge(edge),
+                    expected,
+                    actual,
+                });
+            }
+        }
+
+        Ok(violations)
+    }
+
+    /// Checks the term held by a type, if any, returning the expected and actual types when
+    /// they disagree.
+    fn check_term_type(&self, r#type: &Uid) -> ImplicaResult<Option<(String, String)>> {
+        let term_rep = match self.term_index.get(r#type) {
+            Some(entry) => entry.value().clone(),
+            None => return Ok(None),
+        };
+
+        let expected = self
+            .type_to_string(r#type)
+            .attach(ctx!("graph - check term type"))?;
+
+        match term_rep {
+            TermRep::Base(name) => {
+                let constant = match self.constants.get(&name) {
+                    Some(constant) => constant.value().clone(),
+                    None => return Ok(Some((expected, format!("undeclared constant '{}'", name)))),
+                };
+
+                if self
+                    .constant_inhabits(&constant, r#type)
+                    .attach(ctx!("graph - check term type"))?
+                {
+                    Ok(None)
+                } else {
+                    Ok(Some((expected, constant.type_schema.pattern.clone())))
+                }
+            }
+            TermRep::Application(function, argument) => {
+                let well_typed = matches!(
+                    self.type_index.get(&function).as_deref(),
+                    Some(TypeRep::Arrow(left, right)) if *left == argument && right == r#type
+                );
+
+                if well_typed {
+                    Ok(None)
+                } else {
+                    Ok(Some((
+                        format!(
+                            "a function of type ({} -> {})",
+                            self.type_to_string(&argument)
+                                .attach(ctx!("graph - check term type"))?,
+                            expected
+                        ),
+                        self.type_to_string(&function)
+                            .attach(ctx!("graph - check term type"))?,
+                    )))
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::TypedElement;
+    use crate::constants::Constant;
+    use crate::graph::base::{Graph, TermRep};
+    use crate::properties::PropertyMap;
+    use crate::typing::{Arrow, BasicTerm, Term, Type, Variable};
+
+    fn variable(name: &str) -> Type {
+        Type::Variable(Variable::new(name.to_string()).unwrap())
+    }
+
+    /// A graph holding `f : A -> B` as the edge between the nodes `A` and `B`.
+    fn graph() -> Graph {
+        let graph = Graph::new(
+            vec![Constant::new("f".to_string(), "A -> B".to_string()).unwrap()],
+            Vec::new(),
+            false,
+            true,
+        );
+        let arrow = Arc::new(Type::Arrow(Arrow::new(
+            Arc::new(variable("A")),
+            Arc::new(variable("B")),
+        )));
+        let f = Term::Basic(BasicTerm::new("f".to_string(), arrow.clone()).unwrap());
+
+        graph
+            .add_node(
+                arrow.as_ref().clone(),
+                Some(f),
+                PropertyMap::default(),
+                None,
+            )
+            .unwrap();
+        graph
+    }
+
+    #[test]
+    fn consistent_graph_has_no_violations() {
+        assert!(graph().type_check().unwrap().is_empty());
+    }
+
+    #[test]
+    fn edge_indexed_under_another_type_is_reported() {
+        let graph = graph();
+        let a = graph.insert_type(&variable("A"));
+        let b = graph.insert_type(&variable("B"));
+        graph.edge_to_type_index.insert((a, b), a);
+
+        let violations = graph.type_check().unwrap();
+
+        assert_eq!(violations.len(), 1);
+        assert!(matches!(violations[0].element, TypedElement::Edge(edge) if edge == (a, b)));
+        assert_eq!(violations[0].expected, "(A -> B)");
+        assert_eq!(violations[0].actual, "A");
+    }
+
+    #[test]
+    fn term_of_another_type_is_reported() {
+        let graph = graph();
+        let b = graph.insert_type(&variable("B"));
+        graph.term_index.insert(b, TermRep::Base("f".to_string()));
+
+        let violations = graph.type_check().unwrap();
+
+        assert_eq!(violations.len(), 1);
+        assert!(matches!(violations[0].element, TypedElement::Node(node) if node == b));
+        assert_eq!(violations[0].expected, "B");
+        assert_eq!(violations[0].actual, "A -> B");
+    }
+}
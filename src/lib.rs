@@ -11,16 +11,19 @@ mod query;
 mod typing;
 mod utils;
 
-pub use constants::Constant;
-pub use graph::PyGraph;
+pub use constants::{Constant, Sum};
+pub use graph::{PyGraph, PySnapshot};
 pub use query::references::*;
-pub use query::Query;
+pub use query::{GraphBuilder, Query, ResultIterator};
 
 #[pymodule]
 fn implica(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyGraph>()?;
+    m.add_class::<PySnapshot>()?;
 
     m.add_class::<Query>()?;
+    m.add_class::<GraphBuilder>()?;
+    m.add_class::<ResultIterator>()?;
 
     m.add_class::<EdgeRef>()?;
     m.add_class::<NodeRef>()?;
@@ -28,6 +31,9 @@ fn implica(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<TypeRef>()?;
 
     m.add_class::<Constant>()?;
+    m.add_class::<Sum>()?;
+
+    m.add_class::<patterns::PathPattern>()?;
 
     Ok(())
 }
@@ -2,17 +2,36 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 use dashmap::DashMap;
+use rhai::Dynamic;
 
 use crate::ctx;
 use crate::errors::ImplicaResult;
+use crate::utils::{compare_values, order_values};
 use crate::{errors::ImplicaError, graph::Uid};
 
+/// A value computed per row, e.g. by LET. Values compare as in WHERE, ints equal to
+/// floats of the same value.
+#[derive(Debug, Clone)]
+pub struct ComputedValue(pub Dynamic);
+
+impl PartialEq for ComputedValue {
+    fn eq(&self, other: &Self) -> bool {
+        compare_values(&self.0, &other.0)
+    }
+}
+
+impl Eq for ComputedValue {}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum MatchElement {
     Type(Uid),
     Term(Uid),
     Node(Uid),
     Edge((Uid, Uid)),
+    /// Elements collected per row, e.g. by a pattern comprehension.
+    List(Vec<MatchElement>),
+    /// A value computed per row rather than an element of the graph.
+    Value(ComputedValue),
 }
 
 impl MatchElement {
@@ -40,6 +59,20 @@ impl MatchElement {
                 context,
             }
             .into()),
+            MatchElement::List(_) => Err(ImplicaError::ContextConflict {
+                name: var.to_string(),
+                original: "list".to_string(),
+                new: "type".to_string(),
+                context,
+            }
+            .into()),
+            MatchElement::Value(_) => Err(ImplicaError::ContextConflict {
+                name: var.to_string(),
+                original: "value".to_string(),
+                new: "type".to_string(),
+                context,
+            }
+            .into()),
         }
     }
     pub fn as_term(&self, var: &str, context: Option<String>) -> ImplicaResult<Uid> {
@@ -66,6 +99,20 @@ impl MatchElement {
                 context,
             }
             .into()),
+            MatchElement::List(_) => Err(ImplicaError::ContextConflict {
+                name: var.to_string(),
+                original: "list".to_string(),
+                new: "term".to_string(),
+                context,
+            }
+            .into()),
+            MatchElement::Value(_) => Err(ImplicaError::ContextConflict {
+                name: var.to_string(),
+                original: "value".to_string(),
+                new: "term".to_string(),
+                context,
+            }
+            .into()),
         }
     }
     pub fn as_node(&self, var: &str, context: Option<String>) -> ImplicaResult<Uid> {
@@ -92,6 +139,20 @@ impl MatchElement {
                 context,
             }
             .into()),
+            MatchElement::List(_) => Err(ImplicaError::ContextConflict {
+                name: var.to_string(),
+                original: "list".to_string(),
+                new: "node".to_string(),
+                context,
+            }
+            .into()),
+            MatchElement::Value(_) => Err(ImplicaError::ContextConflict {
+                name: var.to_string(),
+                original: "value".to_string(),
+                new: "node".to_string(),
+                context,
+            }
+            .into()),
         }
     }
     pub fn as_edge(&self, var: &str, context: Option<String>) -> ImplicaResult<(Uid, Uid)> {
@@ -118,6 +179,52 @@ impl MatchElement {
                 context,
             }
             .into()),
+            MatchElement::List(_) => Err(ImplicaError::ContextConflict {
+                name: var.to_string(),
+                original: "list".to_string(),
+                new: "edge".to_string(),
+                context,
+            }
+            .into()),
+            MatchElement::Value(_) => Err(ImplicaError::ContextConflict {
+                name: var.to_string(),
+                original: "value".to_string(),
+                new: "edge".to_string(),
+                context,
+            }
+            .into()),
+        }
+    }
+
+    /// A total order over elements: by kind first, then graph elements by uid, lists element
+    /// by element and values as in ORDER BY. It only serves to break ties reproducibly.
+    pub(crate) fn cmp_uids(&self, other: &MatchElement) -> std::cmp::Ordering {
+        match (self, other) {
+            (MatchElement::Type(a), MatchElement::Type(b))
+            | (MatchElement::Term(a), MatchElement::Term(b))
+            | (MatchElement::Node(a), MatchElement::Node(b)) => a.cmp(b),
+            (MatchElement::Edge(a), MatchElement::Edge(b)) => a.cmp(b),
+            (MatchElement::List(a), MatchElement::List(b)) => a
+                .iter()
+                .zip(b.iter())
+                .map(|(a, b)| a.cmp_uids(b))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or_else(|| a.len().cmp(&b.len())),
+            (MatchElement::Value(a), MatchElement::Value(b)) => {
+                order_values(&a.0, &b.0).unwrap_or(std::cmp::Ordering::Equal)
+            }
+            _ => self.kind_rank().cmp(&other.kind_rank()),
+        }
+    }
+
+    fn kind_rank(&self) -> u8 {
+        match self {
+            MatchElement::Type(_) => 0,
+            MatchElement::Term(_) => 1,
+            MatchElement::Node(_) => 2,
+            MatchElement::Edge(_) => 3,
+            MatchElement::List(_) => 4,
+            MatchElement::Value(_) => 5,
         }
     }
 }
@@ -169,6 +276,34 @@ impl Match {
         Ok(())
     }
 
+    /// Returns every variable bound in this match or any of its predecessors.
+    pub fn elements(&self) -> Vec<(String, MatchElement)> {
+        let mut elements: Vec<(String, MatchElement)> = self
+            .elements
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect();
+
+        if let Some(ref previous) = self.previous {
+            for (key, element) in previous.elements() {
+                match elements.iter_mut().find(|(k, _)| k == &key) {
+                    Some(slot) => slot.1 = element,
+                    None => elements.push((key, element)),
+                }
+            }
+        }
+
+        elements
+    }
+
+    /// The bindings of the row sorted by variable name, to compare rows with
+    /// [`cmp_bindings`].
+    pub(crate) fn sorted_elements(&self) -> Vec<(String, MatchElement)> {
+        let mut elements = self.elements();
+        elements.sort_by(|a, b| a.0.cmp(&b.0));
+        elements
+    }
+
     pub fn remove(&self, key: &str) -> Option<MatchElement> {
         if let Some((_, element)) = self.elements.remove(key) {
             Some(element)
@@ -180,6 +315,23 @@ impl Match {
     }
 }
 
+/// Orders two rows by their sorted bindings, compared variable by variable. Rows that tie on
+/// every ORDER BY key fall back to it, so the order does not depend on the order rows were
+/// matched in.
+pub(crate) fn cmp_bindings(
+    elements_1: &[(String, MatchElement)],
+    elements_2: &[(String, MatchElement)],
+) -> std::cmp::Ordering {
+    elements_1
+        .iter()
+        .zip(elements_2.iter())
+        .map(|((key_1, element_1), (key_2, element_2))| {
+            key_1.cmp(key_2).then_with(|| element_1.cmp_uids(element_2))
+        })
+        .find(|ordering| ordering.is_ne())
+        .unwrap_or_else(|| elements_1.len().cmp(&elements_2.len()))
+}
+
 pub type MatchSet = Arc<DashMap<u64, (Uid, Arc<Match>)>>;
 
 pub(crate) fn default_match_set() -> MatchSet {
@@ -188,6 +340,21 @@ pub(crate) fn default_match_set() -> MatchSet {
     mset
 }
 
+/// Rows of a match set in the order of their ids.
+///
+/// Ids are handed out in increasing order, so ORDER BY gives the rows it sorts new ids in
+/// sorted order and operations that only drop rows keep their ids; reading the rows this
+/// way is what makes an ordering visible.
+pub(crate) fn ordered_rows(mset: &MatchSet) -> Vec<(Uid, Arc<Match>)> {
+    let mut rows: Vec<(u64, (Uid, Arc<Match>))> = mset
+        .iter()
+        .map(|entry| (*entry.key(), entry.value().clone()))
+        .collect();
+    rows.sort_by_key(|(id, _)| *id);
+
+    rows.into_iter().map(|(_, row)| row).collect()
+}
+
 pub static MATCH_ID_COUNTER: AtomicU64 = AtomicU64::new(0);
 
 pub fn next_match_id() -> u64 {
@@ -7,7 +7,7 @@ use crate::errors::{ImplicaError, ImplicaResult};
 use crate::patterns::term_schema::TermSchema;
 use crate::patterns::type_schema::TypeSchema;
 use crate::properties::PropertyMap;
-use crate::utils::validate_variable_name;
+use crate::utils::validate_query_variable;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum CompiledDirection {
@@ -17,7 +17,7 @@ pub enum CompiledDirection {
 }
 
 impl CompiledDirection {
-    fn from_string(s: &str) -> ImplicaResult<Self> {
+    pub(crate) fn from_string(s: &str) -> ImplicaResult<Self> {
         match s {
             "forward" => Ok(CompiledDirection::Forward),
             "backward" => Ok(CompiledDirection::Backward),
@@ -30,7 +30,7 @@ impl CompiledDirection {
         }
     }
 
-    fn to_string(&self) -> &'static str {
+    pub(crate) fn to_string(&self) -> &'static str {
         match self {
             CompiledDirection::Forward => "forward",
             CompiledDirection::Backward => "backward",
@@ -38,6 +38,49 @@ impl CompiledDirection {
         }
     }
 }
+/// How a variable-length edge may revisit parts of the graph while expanding.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum CycleMode {
+    /// No node appears twice in a path.
+    #[default]
+    NoRepeatedNodes,
+    /// No edge appears twice in a path, though nodes may.
+    NoRepeatedRelationships,
+    /// Anything goes; requires an upper bound on the number of hops.
+    Any,
+}
+
+impl CycleMode {
+    pub(crate) fn from_string(s: &str) -> ImplicaResult<Self> {
+        match s {
+            "none" => Ok(CycleMode::NoRepeatedNodes),
+            "relationships" => Ok(CycleMode::NoRepeatedRelationships),
+            "any" => Ok(CycleMode::Any),
+            _ => Err(ImplicaError::SchemaValidation {
+                schema: s.to_string(),
+                reason: "Cycles must be 'none', 'relationships', or 'any'".to_string(),
+            }
+            .into()),
+        }
+    }
+
+    fn to_string(self) -> &'static str {
+        match self {
+            CycleMode::NoRepeatedNodes => "none",
+            CycleMode::NoRepeatedRelationships => "relationships",
+            CycleMode::Any => "any",
+        }
+    }
+}
+
+/// Match-only options of an edge pattern, written after a `|` in its brackets.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct EdgeModifiers {
+    pub min_arity: Option<usize>,
+    pub hops: Option<(usize, Option<usize>)>,
+    pub cycles: Option<CycleMode>,
+}
+
 #[derive(Debug)]
 pub struct EdgePattern {
     pub variable: Option<String>,
@@ -45,6 +88,13 @@ pub struct EdgePattern {
     pub type_schema: Option<TypeSchema>,
     pub term_schema: Option<TermSchema>,
     pub properties: Option<PropertyMap>,
+    /// Minimum number of arguments of the edge's type, counted along its chain of arrows:
+    /// `A -> B` has arity 1 and `A -> B -> C` has arity 2.
+    pub min_arity: Option<usize>,
+    /// Inclusive `(min, max)` number of consecutive edges a variable-length edge spans; an
+    /// unbounded range has no max.
+    pub hops: Option<(usize, Option<usize>)>,
+    pub cycles: CycleMode,
 }
 
 impl Clone for EdgePattern {
@@ -55,6 +105,9 @@ impl Clone for EdgePattern {
             type_schema: self.type_schema.clone(),
             term_schema: self.term_schema.clone(),
             properties: self.properties.clone(),
+            min_arity: self.min_arity,
+            hops: self.hops,
+            cycles: self.cycles,
         }
     }
 }
@@ -75,6 +128,18 @@ impl Display for EdgePattern {
             content.push(format!("term_schema={}", term_schema));
         }
 
+        if let Some(min_arity) = self.min_arity {
+            content.push(format!("min_arity={}", min_arity));
+        }
+
+        if let Some((min, max)) = self.hops {
+            match max {
+                Some(max) => content.push(format!("hops={}..{}", min, max)),
+                None => content.push(format!("hops={}..", min)),
+            }
+            content.push(format!("cycles='{}'", self.cycles.to_string()));
+        }
+
         content.push(format!(
             "direction='{}'",
             self.compiled_direction.to_string()
@@ -85,15 +150,16 @@ impl Display for EdgePattern {
 }
 
 impl EdgePattern {
-    pub fn new(
+    pub(crate) fn new(
         variable: Option<String>,
         type_schema: Option<TypeSchema>,
         term_schema: Option<TermSchema>,
         direction: String,
         properties: Option<PropertyMap>,
+        modifiers: EdgeModifiers,
     ) -> ImplicaResult<Self> {
         if let Some(ref var) = variable {
-            validate_variable_name(var).attach(ctx!("edge pattern - new"))?;
+            validate_query_variable(var).attach(ctx!("edge pattern - new"))?;
         }
 
         let compiled_direction =
@@ -105,6 +171,9 @@ impl EdgePattern {
             type_schema,
             term_schema,
             properties,
+            min_arity: modifiers.min_arity,
+            hops: modifiers.hops,
+            cycles: modifiers.cycles.unwrap_or_default(),
         })
     }
 }
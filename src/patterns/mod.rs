@@ -5,8 +5,8 @@ mod path;
 mod term_schema;
 mod type_schema;
 
-pub use edge::{CompiledDirection, EdgePattern};
-pub use node::NodePattern;
+pub use edge::{CompiledDirection, CycleMode, EdgePattern};
+pub use node::{Comparison, NodePattern, PropertyMatch, QuantifiedPredicate, Quantifier};
 pub use path::PathPattern;
 pub use term_schema::{TermPattern, TermSchema};
 pub use type_schema::{TypePattern, TypeSchema};
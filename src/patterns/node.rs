@@ -1,13 +1,161 @@
 use std::fmt::Display;
 
 use error_stack::ResultExt;
+use rhai::Dynamic;
 
 use crate::ctx;
-use crate::errors::ImplicaResult;
+use crate::errors::{ImplicaError, ImplicaResult};
 use crate::patterns::term_schema::TermSchema;
 use crate::patterns::type_schema::TypeSchema;
 use crate::properties::PropertyMap;
-use crate::utils::validate_variable_name;
+use crate::utils::{compare_values, order_values, validate_query_variable};
+
+/// How the properties written in a node pattern compare with those of a node.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum PropertyMatch {
+    /// The node has every property of the pattern, and possibly others.
+    #[default]
+    Superset,
+    /// The node has exactly the properties of the pattern.
+    Exact,
+    /// Every property of the node is in the pattern, which may list others.
+    Subset,
+}
+
+impl PropertyMatch {
+    pub(crate) fn from_string(s: &str) -> ImplicaResult<Self> {
+        match s {
+            "superset" => Ok(PropertyMatch::Superset),
+            "exact" => Ok(PropertyMatch::Exact),
+            "subset" => Ok(PropertyMatch::Subset),
+            _ => Err(ImplicaError::SchemaValidation {
+                schema: s.to_string(),
+                reason: "Property match must be 'superset', 'exact', or 'subset'".to_string(),
+            }
+            .into()),
+        }
+    }
+
+    pub(crate) fn to_string(self) -> &'static str {
+        match self {
+            PropertyMatch::Superset => "superset",
+            PropertyMatch::Exact => "exact",
+            PropertyMatch::Subset => "subset",
+        }
+    }
+}
+
+/// Whether a quantified predicate must hold for some or for every element of a list.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Quantifier {
+    Any,
+    All,
+}
+
+impl Quantifier {
+    pub(crate) fn to_string(self) -> &'static str {
+        match self {
+            Quantifier::Any => "ANY",
+            Quantifier::All => "ALL",
+        }
+    }
+}
+
+/// An operator comparing the elements of a list property with a value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Comparison {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Comparison {
+    pub(crate) fn from_string(s: &str) -> Option<Self> {
+        match s {
+            "==" => Some(Comparison::Eq),
+            "!=" => Some(Comparison::Ne),
+            "<" => Some(Comparison::Lt),
+            "<=" => Some(Comparison::Le),
+            ">" => Some(Comparison::Gt),
+            ">=" => Some(Comparison::Ge),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn to_string(self) -> &'static str {
+        match self {
+            Comparison::Eq => "==",
+            Comparison::Ne => "!=",
+            Comparison::Lt => "<",
+            Comparison::Le => "<=",
+            Comparison::Gt => ">",
+            Comparison::Ge => ">=",
+        }
+    }
+
+    /// Whether `value` stands in this relation to `operand`. Values that cannot be ordered
+    /// against the operand, like a string and a number, only satisfy `!=`.
+    pub(crate) fn holds(self, value: &Dynamic, operand: &Dynamic) -> bool {
+        match self {
+            Comparison::Eq => compare_values(value, operand),
+            Comparison::Ne => !compare_values(value, operand),
+            _ => order_values(value, operand).is_some_and(|ordering| match self {
+                Comparison::Lt => ordering.is_lt(),
+                Comparison::Le => ordering.is_le(),
+                Comparison::Gt => ordering.is_gt(),
+                _ => ordering.is_ge(),
+            }),
+        }
+    }
+}
+
+/// A predicate over a list property: some, or every, element must satisfy all of the
+/// comparisons, e.g. `ANY(scores > 90)`.
+#[derive(Clone, Debug)]
+pub struct QuantifiedPredicate {
+    pub key: String,
+    pub quantifier: Quantifier,
+    pub comparisons: Vec<(Comparison, Dynamic)>,
+}
+
+impl QuantifiedPredicate {
+    /// Whether the elements of a list satisfy the predicate. `ALL` holds for an empty list
+    /// and `ANY` does not.
+    pub(crate) fn holds(&self, list: &[Dynamic]) -> bool {
+        let satisfies = |item: &Dynamic| {
+            self.comparisons
+                .iter()
+                .all(|(comparison, operand)| comparison.holds(item, operand))
+        };
+
+        match self.quantifier {
+            Quantifier::Any => list.iter().any(satisfies),
+            Quantifier::All => list.iter().all(satisfies),
+        }
+    }
+}
+
+impl Display for QuantifiedPredicate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let comparisons: Vec<String> = self
+            .comparisons
+            .iter()
+            .map(|(comparison, operand)| {
+                format!("{} {} {:?}", self.key, comparison.to_string(), operand)
+            })
+            .collect();
+
+        write!(
+            f,
+            "{}({})",
+            self.quantifier.to_string(),
+            comparisons.join(", ")
+        )
+    }
+}
 
 #[derive(Debug)]
 pub struct NodePattern {
@@ -15,6 +163,21 @@ pub struct NodePattern {
     pub type_schema: Option<TypeSchema>,
     pub term_schema: Option<TermSchema>,
     pub properties: Option<PropertyMap>,
+    /// Keys, possibly dotted paths, the node must not have among its properties.
+    pub missing_properties: Vec<String>,
+    /// Type schema that the node's type, or one of its declared supertypes, must match.
+    pub subtype_of: Option<TypeSchema>,
+    /// How `properties` compare with those of a node.
+    pub property_match: PropertyMatch,
+    /// Predicates over the elements of list properties, set when matching from Python.
+    pub quantified: Vec<QuantifiedPredicate>,
+}
+
+/// Match-only options of a node pattern, written after a `|` in its parentheses.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct NodeModifiers {
+    pub missing_properties: Vec<String>,
+    pub subtype_of: Option<TypeSchema>,
 }
 
 impl Clone for NodePattern {
@@ -24,6 +187,10 @@ impl Clone for NodePattern {
             type_schema: self.type_schema.clone(),
             term_schema: self.term_schema.clone(),
             properties: self.properties.clone(),
+            missing_properties: self.missing_properties.clone(),
+            subtype_of: self.subtype_of.clone(),
+            property_match: self.property_match,
+            quantified: self.quantified.clone(),
         }
     }
 }
@@ -44,19 +211,42 @@ impl Display for NodePattern {
             content.push(format!("term_schema={}", term_schema));
         }
 
+        if !self.missing_properties.is_empty() {
+            content.push(format!(
+                "missing_properties=[{}]",
+                self.missing_properties.join(", ")
+            ));
+        }
+
+        if let Some(ref subtype_of) = self.subtype_of {
+            content.push(format!("subtype_of={}", subtype_of));
+        }
+
+        if self.property_match != PropertyMatch::Superset {
+            content.push(format!(
+                "property_match={}",
+                self.property_match.to_string()
+            ));
+        }
+
+        for predicate in self.quantified.iter() {
+            content.push(predicate.to_string());
+        }
+
         write!(f, "NodePattern({})", content.join(", "))
     }
 }
 
 impl NodePattern {
-    pub fn new(
+    pub(crate) fn new(
         variable: Option<String>,
         type_schema: Option<TypeSchema>,
         term_schema: Option<TermSchema>,
         properties: Option<PropertyMap>,
+        modifiers: NodeModifiers,
     ) -> ImplicaResult<Self> {
         if let Some(ref var) = variable {
-            validate_variable_name(var).attach(ctx!("node pattern - new"))?;
+            validate_query_variable(var).attach(ctx!("node pattern - new"))?;
         }
 
         Ok(NodePattern {
@@ -64,6 +254,10 @@ impl NodePattern {
             type_schema,
             term_schema,
             properties,
+            missing_properties: modifiers.missing_properties,
+            subtype_of: modifiers.subtype_of,
+            property_match: PropertyMatch::default(),
+            quantified: Vec::new(),
         })
     }
 }
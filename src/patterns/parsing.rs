@@ -6,7 +6,10 @@ use crate::errors::{ImplicaError, ImplicaResult};
 use crate::ctx;
 use crate::patterns::term_schema::TermSchema;
 use crate::patterns::type_schema::TypeSchema;
-use crate::patterns::{edge::EdgePattern, node::NodePattern};
+use crate::patterns::{
+    edge::{CycleMode, EdgeModifiers, EdgePattern},
+    node::{NodeModifiers, NodePattern},
+};
 use crate::properties::PropertyMap;
 
 #[derive(Debug, PartialEq)]
@@ -66,6 +69,13 @@ pub(in crate::patterns) fn tokenize_pattern(pattern: &str) -> ImplicaResult<Vec<
                     ' ' | '\t' | '\n' | '\r' => {
                         // Skip whitespace
                     }
+                    ',' => {
+                        return Err(ImplicaError::InvalidPattern {
+                            pattern: pattern.to_string(),
+                            reason: "',' separates disconnected components, which MATCH accepts but a single path pattern cannot hold".to_string(),
+                        }
+                        .into());
+                    }
                     _ => {
                         return Err(ImplicaError::InvalidPattern {
                             pattern: pattern.to_string(),
@@ -188,6 +198,54 @@ pub(in crate::patterns) fn tokenize_pattern(pattern: &str) -> ImplicaResult<Vec<
     Ok(tokens)
 }
 
+/// Splits a pattern into its comma-separated components, e.g. `(a:Person), (b:Company)`,
+/// skipping the commas inside nodes, edges, properties and strings.
+pub(in crate::patterns) fn split_pattern_components(pattern: &str) -> ImplicaResult<Vec<String>> {
+    let mut components = Vec::new();
+    let mut current = String::new();
+    let mut in_string = false;
+    let mut string_char = ' ';
+    let mut depth = 0;
+
+    let empty_component = || ImplicaError::InvalidPattern {
+        pattern: pattern.to_string(),
+        reason: "Pattern components cannot be empty".to_string(),
+    };
+
+    for c in pattern.chars() {
+        match c {
+            '"' | '\'' => {
+                if !in_string {
+                    in_string = true;
+                    string_char = c;
+                } else if c == string_char {
+                    in_string = false;
+                }
+            }
+            '(' | '[' | '{' if !in_string => depth += 1,
+            ')' | ']' | '}' if !in_string => depth -= 1,
+            ',' if !in_string && depth == 0 => {
+                let component = std::mem::take(&mut current);
+                if component.trim().is_empty() {
+                    return Err(empty_component().into());
+                }
+                components.push(component.trim().to_string());
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+
+    // A lone empty pattern is left for the path parser to reject
+    if current.trim().is_empty() && !components.is_empty() {
+        return Err(empty_component().into());
+    }
+    components.push(current.trim().to_string());
+
+    Ok(components)
+}
+
 pub(in crate::patterns) fn parse_properties(props_str: &str) -> ImplicaResult<PropertyMap> {
     let props_str = props_str.trim();
 
@@ -712,7 +770,16 @@ pub(in crate::patterns) fn parse_node_pattern(s: &str) -> ImplicaResult<NodePatt
         .into());
     }
 
-    let inner = &s[1..s.len() - 1].trim();
+    let inner = s[1..s.len() - 1].trim();
+
+    // Modifiers follow a top level '|': (var:type:term {props} | missing_properties=[a, b])
+    let (inner, modifiers) = match find_modifiers_start(inner) {
+        Some(bar_idx) => (
+            inner[..bar_idx].trim(),
+            parse_node_modifiers(&inner[bar_idx + 1..]).attach(ctx!("parse node pattern"))?,
+        ),
+        None => (inner, NodeModifiers::default()),
+    };
 
     // Parse: (var:type:term {props}) or (var:type:term) or (var:type) or (var) or (:type:term) or (:type)
     let mut variable = None;
@@ -722,7 +789,7 @@ pub(in crate::patterns) fn parse_node_pattern(s: &str) -> ImplicaResult<NodePatt
 
     if inner.is_empty() {
         // Empty node pattern - matches any node
-        return NodePattern::new(None, None, None, None);
+        return NodePattern::new(None, None, None, None, modifiers);
     }
 
     // Check for properties - need to find the LAST { that's not inside parentheses
@@ -799,7 +866,7 @@ pub(in crate::patterns) fn parse_node_pattern(s: &str) -> ImplicaResult<NodePatt
         }
     }
 
-    NodePattern::new(variable, type_schema, term_schema, properties)
+    NodePattern::new(variable, type_schema, term_schema, properties, modifiers)
 }
 
 pub(in crate::patterns) fn parse_edge_pattern(s: &str) -> ImplicaResult<EdgePattern> {
@@ -842,7 +909,16 @@ pub(in crate::patterns) fn parse_edge_pattern(s: &str) -> ImplicaResult<EdgePatt
         "any"
     };
 
-    let inner = &s[bracket_start + 1..bracket_end].trim();
+    let inner = s[bracket_start + 1..bracket_end].trim();
+
+    // Modifiers follow a top level '|': [var:type:term {props} | min_arity=2, hops=1..3]
+    let (inner, modifiers) = match find_modifiers_start(inner) {
+        Some(bar_idx) => (
+            inner[..bar_idx].trim(),
+            parse_edge_modifiers(&inner[bar_idx + 1..]).attach(ctx!("parse edge pattern"))?,
+        ),
+        None => (inner, EdgeModifiers::default()),
+    };
 
     let mut variable = None;
     let mut type_schema = None;
@@ -921,11 +997,217 @@ pub(in crate::patterns) fn parse_edge_pattern(s: &str) -> ImplicaResult<EdgePatt
             }
         }
     }
+    if modifiers.hops.is_none() {
+        if modifiers.cycles.is_some() {
+            return Err(ImplicaError::InvalidPattern {
+                pattern: s.to_string(),
+                reason: "cycles only applies to variable-length edges, set hops as well"
+                    .to_string(),
+            }
+            .into());
+        }
+    } else {
+        if variable.is_some() {
+            return Err(ImplicaError::InvalidPattern {
+                pattern: s.to_string(),
+                reason: "A variable-length edge cannot be bound to a variable".to_string(),
+            }
+            .into());
+        }
+
+        // Whether an undirected variable-length edge can be walked depends on the graph,
+        // so the matcher decides.
+        if let (Some((_, None)), Some(CycleMode::Any)) = (modifiers.hops, modifiers.cycles) {
+            return Err(ImplicaError::InvalidPattern {
+                pattern: s.to_string(),
+                reason: "cycles=any needs an upper bound on hops".to_string(),
+            }
+            .into());
+        }
+    }
+
     EdgePattern::new(
         variable,
         type_schema,
         term_schema,
         direction.to_string(),
         properties,
+        modifiers,
     )
 }
+
+fn find_modifiers_start(s: &str) -> Option<usize> {
+    // Find the '|' opening the modifiers of a node or edge, ignoring any inside nested groups or strings
+    let mut depth = 0;
+    let mut quote: Option<char> = None;
+
+    for (i, c) in s.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None => match c {
+                '\'' | '"' => quote = Some(c),
+                '(' | '[' | '{' => depth += 1,
+                ')' | ']' | '}' => depth -= 1,
+                '|' if depth == 0 => return Some(i),
+                _ => {}
+            },
+        }
+    }
+
+    None
+}
+
+fn split_modifiers(s: &str) -> ImplicaResult<Vec<(String, String)>> {
+    // Split "key=value, key=[a, b]" on the commas that are not inside a list
+    let mut items = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0;
+
+    for c in s.chars().chain(std::iter::once(',')) {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            ',' if depth == 0 => {
+                items.push(std::mem::take(&mut current));
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+
+    items
+        .into_iter()
+        .map(|item| match item.split_once('=') {
+            Some((key, value)) => Ok((key.trim().to_string(), value.trim().to_string())),
+            None => Err(ImplicaError::InvalidPattern {
+                pattern: s.to_string(),
+                reason: format!("Modifier '{}' must be of the form key=value", item.trim()),
+            }
+            .into()),
+        })
+        .collect()
+}
+
+fn parse_edge_modifiers(s: &str) -> ImplicaResult<EdgeModifiers> {
+    let mut modifiers = EdgeModifiers::default();
+
+    for (key, value) in split_modifiers(s)? {
+        match key.as_str() {
+            "min_arity" => {
+                let arity = value
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|a| *a > 0)
+                    .ok_or_else(|| ImplicaError::InvalidPattern {
+                        pattern: s.to_string(),
+                        reason: format!("min_arity must be a positive integer, got '{}'", value),
+                    })?;
+                modifiers.min_arity = Some(arity);
+            }
+            "hops" => {
+                modifiers.hops = Some(parse_hops(&value).ok_or_else(|| {
+                    ImplicaError::InvalidPattern {
+                        pattern: s.to_string(),
+                        reason: format!(
+                            "hops must be n, min..max, ..max or min.. with 0 <= min <= max and max >= 1, got '{}'",
+                            value
+                        ),
+                    }
+                })?);
+            }
+            "cycles" => {
+                modifiers.cycles =
+                    Some(CycleMode::from_string(&value).attach(ctx!("parse edge modifiers"))?);
+            }
+            other => {
+                return Err(ImplicaError::InvalidPattern {
+                    pattern: s.to_string(),
+                    reason: format!("Unknown edge modifier '{}'", other),
+                }
+                .into())
+            }
+        }
+    }
+
+    Ok(modifiers)
+}
+
+fn parse_hops(s: &str) -> Option<(usize, Option<usize>)> {
+    let (min, max) = match s.split_once("..") {
+        Some((min, max)) => {
+            let min = match min.trim() {
+                "" => 1,
+                min => min.parse().ok()?,
+            };
+            let max = match max.trim() {
+                "" => None,
+                max => Some(max.parse().ok()?),
+            };
+            (min, max)
+        }
+        None => {
+            let hops = s.trim().parse().ok()?;
+            (hops, Some(hops))
+        }
+    };
+
+    // A minimum of zero also yields the start node itself, but a path can never be zero
+    // hops at most
+    if max.is_some_and(|max| max == 0 || max < min) {
+        return None;
+    }
+
+    Some((min, max))
+}
+
+fn parse_node_modifiers(s: &str) -> ImplicaResult<NodeModifiers> {
+    let mut modifiers = NodeModifiers::default();
+
+    for (key, value) in split_modifiers(s)? {
+        match key.as_str() {
+            "missing_properties" => {
+                let list = match value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+                    Some(list) => list,
+                    None => value.as_str(),
+                };
+
+                for name in list.split(',').map(str::trim) {
+                    if name.is_empty() {
+                        return Err(ImplicaError::InvalidPattern {
+                            pattern: s.to_string(),
+                            reason: "missing_properties cannot list an empty key".to_string(),
+                        }
+                        .into());
+                    }
+                    modifiers.missing_properties.push(name.to_string());
+                }
+            }
+            "subtype_of" => {
+                let schema = TypeSchema::new(value.clone()).attach(ctx!("parse node modifiers"))?;
+
+                // The schema is checked against each supertype on its own, so it cannot bind
+                // anything in the row
+                if schema.compiled.has_captures() {
+                    return Err(ImplicaError::InvalidPattern {
+                        pattern: s.to_string(),
+                        reason: "subtype_of cannot capture types".to_string(),
+                    }
+                    .into());
+                }
+
+                modifiers.subtype_of = Some(schema);
+            }
+            other => {
+                return Err(ImplicaError::InvalidPattern {
+                    pattern: s.to_string(),
+                    reason: format!("Unknown node modifier '{}'", other),
+                }
+                .into())
+            }
+        }
+    }
+
+    Ok(modifiers)
+}
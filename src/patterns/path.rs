@@ -1,15 +1,21 @@
+use std::collections::HashSet;
 use std::fmt::Display;
 
 use error_stack::ResultExt;
+use pyo3::prelude::*;
 
 use crate::ctx;
-use crate::errors::{ImplicaError, ImplicaResult};
+use crate::errors::{ImplicaError, ImplicaResult, IntoPyResult};
 use crate::patterns::{
     edge::EdgePattern,
-    node::NodePattern,
-    parsing::{parse_edge_pattern, parse_node_pattern, tokenize_pattern, TokenKind},
+    node::{NodePattern, PropertyMatch, QuantifiedPredicate},
+    parsing::{
+        parse_edge_pattern, parse_node_pattern, split_pattern_components, tokenize_pattern,
+        TokenKind,
+    },
 };
 
+#[pyclass]
 #[derive(Clone, Debug)]
 pub struct PathPattern {
     pattern: String,
@@ -41,14 +47,179 @@ impl PathPattern {
             }
             .into());
         }
+
+        let node_variables: HashSet<&String> = self
+            .nodes
+            .iter()
+            .filter_map(|node| node.variable.as_ref())
+            .collect();
+        if let Some(var) = self
+            .edges
+            .iter()
+            .filter_map(|edge| edge.variable.as_ref())
+            .find(|var| node_variables.contains(var))
+        {
+            return Err(ImplicaError::InvalidPattern {
+                pattern: self.to_string(),
+                reason: format!("'{}' names both a node and an edge", var),
+            }
+            .into());
+        }
+
         Ok(())
     }
 }
 
+#[pymethods]
+impl PathPattern {
+    /// Parses a path pattern without running it.
+    #[staticmethod]
+    #[pyo3(name = "parse")]
+    pub fn py_parse(pattern: String) -> PyResult<PathPattern> {
+        PathPattern::parse(pattern).into_py_result()
+    }
+
+    /// Checks the pattern is a well-formed path: it alternates nodes and edges, starting and
+    /// ending with a node, and no variable names both a node and an edge.
+    #[pyo3(name = "validate")]
+    pub fn py_validate(&self) -> PyResult<()> {
+        self.validate().into_py_result()
+    }
+
+    fn __str__(&self) -> String {
+        self.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PathPattern({} nodes, {} edges)",
+            self.nodes.len(),
+            self.edges.len()
+        )
+    }
+}
+
+impl PathPattern {
+    /// Returns every identifier the pattern refers to: node and edge variables as well as the
+    /// names used inside their type and term schemas.
+    pub(crate) fn identifiers(&self) -> HashSet<String> {
+        let mut out = HashSet::new();
+
+        for node in self.nodes.iter() {
+            if let Some(ref var) = node.variable {
+                out.insert(var.clone());
+            }
+            if let Some(ref type_schema) = node.type_schema {
+                type_schema.compiled.collect_identifiers(&mut out);
+            }
+            if let Some(ref term_schema) = node.term_schema {
+                term_schema.compiled.collect_identifiers(&mut out);
+            }
+        }
+
+        for edge in self.edges.iter() {
+            if let Some(ref var) = edge.variable {
+                out.insert(var.clone());
+            }
+            if let Some(ref type_schema) = edge.type_schema {
+                type_schema.compiled.collect_identifiers(&mut out);
+            }
+            if let Some(ref term_schema) = edge.term_schema {
+                term_schema.compiled.collect_identifiers(&mut out);
+            }
+        }
+
+        out
+    }
+}
+
+impl PathPattern {
+    /// Sets how the properties written in every node pattern compare with those of a node.
+    pub(crate) fn set_property_match(&mut self, property_match: PropertyMatch) {
+        for node in self.nodes.iter_mut() {
+            node.property_match = property_match;
+        }
+    }
+
+    pub(crate) fn property_match(&self) -> PropertyMatch {
+        self.nodes
+            .first()
+            .map(|node| node.property_match)
+            .unwrap_or_default()
+    }
+
+    /// Adds predicates over the list properties of the pattern's node. Only a pattern of a
+    /// single node takes them.
+    pub(crate) fn add_quantified(
+        &mut self,
+        predicates: Vec<QuantifiedPredicate>,
+    ) -> ImplicaResult<()> {
+        match self.nodes.as_mut_slice() {
+            [node] => {
+                node.quantified.extend(predicates);
+                Ok(())
+            }
+            _ => Err(ImplicaError::InvalidPattern {
+                pattern: self.to_string(),
+                reason: "quantified property predicates need a pattern of a single node"
+                    .to_string(),
+            }
+            .into()),
+        }
+    }
+
+    /// Adds keys the single node of the pattern must not have, like its `missing_properties`
+    /// modifier.
+    pub(crate) fn add_missing_properties(&mut self, keys: Vec<String>) -> ImplicaResult<()> {
+        if keys.iter().any(|key| key.is_empty()) {
+            return Err(ImplicaError::InvalidPattern {
+                pattern: self.to_string(),
+                reason: "missing_properties cannot list an empty key".to_string(),
+            }
+            .into());
+        }
+
+        match self.nodes.as_mut_slice() {
+            [node] => {
+                node.missing_properties.extend(keys);
+                Ok(())
+            }
+            _ => Err(ImplicaError::InvalidPattern {
+                pattern: self.to_string(),
+                reason: "missing_properties needs a pattern of a single node".to_string(),
+            }
+            .into()),
+        }
+    }
+
+    pub(crate) fn quantified(&self) -> impl Iterator<Item = &QuantifiedPredicate> {
+        self.nodes.iter().flat_map(|node| node.quantified.iter())
+    }
+
+    pub(crate) fn missing_properties(&self) -> impl Iterator<Item = &String> {
+        self.nodes
+            .iter()
+            .flat_map(|node| node.missing_properties.iter())
+    }
+}
+
 impl PathPattern {
     pub fn new(pattern: String) -> ImplicaResult<Self> {
         PathPattern::parse(pattern).attach(ctx!("path pattern - new"))
     }
+
+    /// Parses a pattern made of comma-separated components, e.g. `(a:Person), (b:Company)`,
+    /// into a path pattern for each. Matching them in turn joins their rows on the variables
+    /// they share, and takes the cartesian product of components that share none.
+    pub(crate) fn parse_components(pattern: String) -> ImplicaResult<Vec<Self>> {
+        split_pattern_components(&pattern)
+            .attach(ctx!("path pattern - parse components"))?
+            .into_iter()
+            .map(|component| {
+                PathPattern::parse(component).attach(ctx!("path pattern - parse components"))
+            })
+            .collect()
+    }
     pub fn parse(pattern: String) -> ImplicaResult<Self> {
         // Enhanced parser for Cypher-like path patterns
         // Supports: (n)-[e]->(m), (n:A)-[e:term]->(m:B), etc.
@@ -111,12 +282,4 @@ impl PathPattern {
             edges,
         })
     }
-
-    fn __repr__(&self) -> String {
-        format!(
-            "PathPattern({} nodes, {} edges)",
-            self.nodes.len(),
-            self.edges.len()
-        )
-    }
 }
@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::fmt::Display;
 
 use error_stack::ResultExt;
@@ -5,7 +6,7 @@ use error_stack::ResultExt;
 use crate::ctx;
 use crate::errors::{ImplicaError, ImplicaResult};
 use crate::patterns::TypeSchema;
-use crate::utils::validate_variable_name;
+use crate::utils::validate_query_variable;
 
 #[derive(Clone, Debug)]
 pub enum TermPattern {
@@ -21,6 +22,29 @@ pub enum TermPattern {
     },
 }
 
+impl TermPattern {
+    /// Collects every term variable, constant name and type argument identifier mentioned by
+    /// the pattern.
+    pub(crate) fn collect_identifiers(&self, out: &mut HashSet<String>) {
+        match self {
+            TermPattern::Wildcard => (),
+            TermPattern::Variable(name) => {
+                out.insert(name.clone());
+            }
+            TermPattern::Application { function, argument } => {
+                function.collect_identifiers(out);
+                argument.collect_identifiers(out);
+            }
+            TermPattern::Constant { name, args } => {
+                out.insert(name.clone());
+                for arg in args.iter() {
+                    arg.compiled.collect_identifiers(out);
+                }
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct TermSchema {
     pub pattern: String,
@@ -90,7 +114,7 @@ impl TermSchema {
             .into());
         }
 
-        validate_variable_name(trimmed).attach(ctx!("term schema - parse pattern"))?;
+        validate_query_variable(trimmed).attach(ctx!("term schema - parse pattern"))?;
         Ok(TermPattern::Variable(trimmed.to_string()))
     }
 
@@ -1,15 +1,20 @@
+use std::collections::HashSet;
 use std::fmt::Display;
+use std::sync::OnceLock;
 
 use error_stack::ResultExt;
 
 use crate::ctx;
 use crate::errors::{ImplicaError, ImplicaResult};
-use crate::utils::validate_variable_name;
+use crate::graph::Uid;
+use crate::utils::{validate_query_variable, validate_variable_name};
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum TypePattern {
     Wildcard,
     Variable(String),
+    /// A sum type registered in the graph, written `$name$`.
+    Sum(String),
     Arrow {
         left: Box<TypePattern>,
         right: Box<TypePattern>,
@@ -20,11 +25,43 @@ pub enum TypePattern {
     },
 }
 
+impl TypePattern {
+    /// Collects every type name, type variable and capture name mentioned by the pattern.
+    pub(crate) fn collect_identifiers(&self, out: &mut HashSet<String>) {
+        match self {
+            TypePattern::Wildcard => (),
+            TypePattern::Variable(name) | TypePattern::Sum(name) => {
+                out.insert(name.clone());
+            }
+            TypePattern::Arrow { left, right } => {
+                left.collect_identifiers(out);
+                right.collect_identifiers(out);
+            }
+            TypePattern::Capture { name, pattern } => {
+                out.insert(name.clone());
+                pattern.collect_identifiers(out);
+            }
+        }
+    }
+
+    pub(crate) fn has_captures(&self) -> bool {
+        match self {
+            TypePattern::Wildcard | TypePattern::Variable(_) | TypePattern::Sum(_) => false,
+            TypePattern::Arrow { left, right } => left.has_captures() || right.has_captures(),
+            TypePattern::Capture { .. } => true,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct TypeSchema {
     pub pattern: String,
 
     pub compiled: TypePattern,
+
+    /// Uid of the type the pattern spells out when it only names types and arrows,
+    /// resolved by the graph the first time it is matched.
+    pub(crate) interned: OnceLock<Option<Uid>>,
 }
 
 impl Display for TypeSchema {
@@ -37,7 +74,11 @@ impl TypeSchema {
     pub fn new(pattern: String) -> ImplicaResult<Self> {
         let compiled = Self::parse_pattern(&pattern).attach(ctx!("type schema - new"))?;
 
-        Ok(TypeSchema { pattern, compiled })
+        Ok(TypeSchema {
+            pattern,
+            compiled,
+            interned: OnceLock::new(),
+        })
     }
 
     fn parse_pattern(input: &str) -> ImplicaResult<TypePattern> {
@@ -97,6 +138,14 @@ impl TypeSchema {
             return Ok(TypePattern::Wildcard);
         }
 
+        // Sum type: $name$
+        if input.len() >= 2 && input.starts_with('$') && input.ends_with('$') {
+            let name = input[1..input.len() - 1].trim();
+            validate_variable_name(name).attach(ctx!("type schema - parse pattern recursive"))?;
+
+            return Ok(TypePattern::Sum(name.to_string()));
+        }
+
         // Check for Arrow pattern FIRST (at top level): left -> right
         // This must be done before checking for captures to handle patterns like "(in:*) -> (out:*)"
         if let Some(arrow_pos) = find_arrow(input) {
@@ -135,7 +184,7 @@ impl TypeSchema {
 
                 // Otherwise it's a named capture
 
-                validate_variable_name(name_part)
+                validate_query_variable(name_part)
                     .attach(ctx!("type schema - parse pattern recursive"))?;
 
                 return Ok(TypePattern::Capture {
@@ -6,7 +6,8 @@ use rayon::prelude::*;
 use rhai::{Dynamic, Map};
 use std::convert::Infallible;
 use std::fmt::Display;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, RwLock, RwLockReadGuard, TryLockError};
+use std::time::Duration;
 
 use crate::ctx;
 use crate::errors::{ImplicaError, ImplicaResult, IntoPyResult};
@@ -20,6 +21,13 @@ impl Clone for PyOpaque {
     }
 }
 
+/// Bounded retry for reading a property map whose lock is momentarily held by a writer.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LockRetry {
+    pub attempts: u32,
+    pub backoff: Duration,
+}
+
 #[derive(Debug, Clone)]
 pub struct PropertyMap {
     data: Arc<RwLock<Map>>,
@@ -97,6 +105,12 @@ impl PropertyMap {
         })
     }
 
+    pub fn from_map(map: Map) -> Self {
+        PropertyMap {
+            data: Arc::new(RwLock::new(map)),
+        }
+    }
+
     pub fn empty() -> Self {
         PropertyMap {
             data: Arc::new(RwLock::new(Map::new())),
@@ -124,14 +138,149 @@ impl PropertyMap {
         Ok(())
     }
 
+    /// Takes the read lock, blocking until it is free or, given a retry, trying it again
+    /// after an exponentially growing backoff until the attempts run out. A poisoned lock
+    /// fails straight away either way.
+    fn read_data(&self, retry: Option<LockRetry>) -> ImplicaResult<RwLockReadGuard<'_, Map>> {
+        let Some(retry) = retry else {
+            return Ok(self.data.read().map_err(|e| ImplicaError::LockError {
+                rw: "read".to_string(),
+                message: e.to_string(),
+                context: Some(ctx!("property map - read data").to_string()),
+            })?);
+        };
+
+        let mut backoff = retry.backoff;
+        for attempt in 0..=retry.attempts {
+            match self.data.try_read() {
+                Ok(data_lock) => return Ok(data_lock),
+                Err(TryLockError::Poisoned(e)) => {
+                    return Err(ImplicaError::LockError {
+                        rw: "read".to_string(),
+                        message: e.to_string(),
+                        context: Some(ctx!("property map - read data").to_string()),
+                    }
+                    .into())
+                }
+                Err(TryLockError::WouldBlock) if attempt < retry.attempts => {
+                    std::thread::sleep(backoff);
+                    backoff = backoff.saturating_mul(2);
+                }
+                Err(TryLockError::WouldBlock) => break,
+            }
+        }
+
+        Err(ImplicaError::LockError {
+            rw: "read".to_string(),
+            message: format!("lock still held after {} retries", retry.attempts),
+            context: Some(ctx!("property map - read data").to_string()),
+        }
+        .into())
+    }
+
     pub fn get(&self, key: &str) -> ImplicaResult<Option<Dynamic>> {
+        self.get_retrying(key, None)
+            .attach(ctx!("property map - get"))
+    }
+
+    pub(crate) fn get_retrying(
+        &self,
+        key: &str,
+        retry: Option<LockRetry>,
+    ) -> ImplicaResult<Option<Dynamic>> {
+        let data_lock = self
+            .read_data(retry)
+            .attach(ctx!("property map - get retrying"))?;
+
+        Ok(data_lock.get(key).cloned())
+    }
+
+    /// Looks up a property by a dotted path such as `address.city`, walking nested maps one
+    /// segment at a time. A key containing the dots literally takes precedence over the path,
+    /// and a missing intermediate key yields `None`.
+    pub fn get_path(&self, path: &str) -> ImplicaResult<Option<Dynamic>> {
+        self.get_path_retrying(path, None)
+            .attach(ctx!("property map - get path"))
+    }
+
+    /// Like `get_path`, retrying each read of the lock as `retry` says.
+    pub(crate) fn get_path_retrying(
+        &self,
+        path: &str,
+        retry: Option<LockRetry>,
+    ) -> ImplicaResult<Option<Dynamic>> {
+        if let Some(value) = self
+            .get_retrying(path, retry)
+            .attach(ctx!("property map - get path retrying"))?
+        {
+            return Ok(Some(value));
+        }
+
+        let mut segments = path.split('.');
+        let mut current = match segments.next() {
+            Some(first) if first != path => {
+                match self
+                    .get_retrying(first, retry)
+                    .attach(ctx!("property map - get path retrying"))?
+                {
+                    Some(value) => value,
+                    None => return Ok(None),
+                }
+            }
+            _ => return Ok(None),
+        };
+
+        for segment in segments {
+            current = match current.try_cast::<Map>() {
+                Some(map) => match map.get(segment) {
+                    Some(value) => value.clone(),
+                    None => return Ok(None),
+                },
+                None => return Ok(None),
+            };
+        }
+
+        Ok(Some(current))
+    }
+
+    /// Returns a copy of the properties as a rhai map, e.g. to expose them to an expression.
+    pub fn to_map(&self) -> ImplicaResult<Map> {
         let data_lock = self.data.read().map_err(|e| ImplicaError::LockError {
             rw: "read".to_string(),
             message: e.to_string(),
-            context: Some(ctx!("property map - get").to_string()),
+            context: Some(ctx!("property map - to map").to_string()),
         })?;
 
-        Ok(data_lock.get(key).cloned())
+        Ok(data_lock.clone())
+    }
+
+    pub fn is_empty(&self) -> ImplicaResult<bool> {
+        let data_lock = self.data.read().map_err(|e| ImplicaError::LockError {
+            rw: "read".to_string(),
+            message: e.to_string(),
+            context: Some(ctx!("property map - is empty").to_string()),
+        })?;
+
+        Ok(data_lock.is_empty())
+    }
+
+    /// Returns a copy of the properties with every integer, nested ones included, widened to
+    /// a float, so numbers compare alike whichever way they were written.
+    pub fn with_widened_numerics(&self) -> ImplicaResult<PropertyMap> {
+        let data_lock = self.data.read().map_err(|e| ImplicaError::LockError {
+            rw: "read".to_string(),
+            message: e.to_string(),
+            context: Some(ctx!("property map - with widened numerics").to_string()),
+        })?;
+
+        let map: Map = data_lock
+            .iter()
+            .map(|(k, v)| (k.clone(), widen_numerics(v.clone())))
+            .collect();
+
+        Ok(PropertyMap {
+            data: Arc::new(RwLock::new(map)),
+        })
     }
 
     pub fn try_par_compare<F>(&self, func: F) -> ImplicaResult<bool>
@@ -181,7 +330,35 @@ impl PropertyMap {
     }
 }
 
-fn py_to_rhai(obj: &Bound<PyAny>) -> ImplicaResult<Dynamic> {
+/// Widens integers, nested ones included, to floats. Integers beyond 2^53 have no exact
+/// float and are rounded to the nearest one.
+fn widen_numerics(value: Dynamic) -> Dynamic {
+    if let Some(v) = value.clone().try_cast::<i64>() {
+        return Dynamic::from(v as f64);
+    }
+
+    if value.is_array() {
+        if let Some(list) = value.clone().try_cast::<rhai::Array>() {
+            return Dynamic::from(
+                list.into_iter()
+                    .map(widen_numerics)
+                    .collect::<rhai::Array>(),
+            );
+        }
+    }
+
+    if let Some(map) = value.clone().try_cast::<Map>() {
+        return Dynamic::from(
+            map.into_iter()
+                .map(|(k, v)| (k, widen_numerics(v)))
+                .collect::<Map>(),
+        );
+    }
+
+    value
+}
+
+pub(crate) fn py_to_rhai(obj: &Bound<PyAny>) -> ImplicaResult<Dynamic> {
     if obj.is_instance_of::<PyBool>() {
         let val: bool = obj
             .extract()
@@ -234,7 +411,7 @@ fn py_to_rhai(obj: &Bound<PyAny>) -> ImplicaResult<Dynamic> {
     Ok(Dynamic::from(PyOpaque(obj.clone().unbind())))
 }
 
-fn rhai_to_py<'py>(val: Dynamic, py: Python<'py>) -> ImplicaResult<Bound<'py, PyAny>> {
+pub(crate) fn rhai_to_py<'py>(val: Dynamic, py: Python<'py>) -> ImplicaResult<Bound<'py, PyAny>> {
     if val.is::<PyOpaque>() {
         let opaque = val.cast::<PyOpaque>();
         return Ok(opaque.0.bind(py).clone());
@@ -1,33 +1,103 @@
-use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
 use std::ops::ControlFlow;
-use std::sync::Arc;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
+use dashmap::DashMap;
 use error_stack::{Report, ResultExt};
+use pyo3::exceptions::{PyAssertionError, PyIndexError};
 use pyo3::prelude::*;
-use pyo3::types::PyList;
-use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use pyo3::types::{PyDict, PyList, PySlice, PyTuple};
+use rayon::iter::{
+    IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator, ParallelIterator,
+};
+use rhai::{Dynamic, Map, Scope};
 
 use crate::ctx;
 use crate::errors::{ImplicaResult, IntoPyResult};
-use crate::matches::{default_match_set, MatchElement};
-use crate::properties::PropertyMap;
+use crate::matches::{
+    cmp_bindings, default_match_set, next_match_id, ordered_rows, ComputedValue, Match,
+    MatchElement,
+};
+use crate::properties::{py_to_rhai, rhai_to_py, PropertyMap};
+use crate::query::parquet::{write_parquet, Column};
 use crate::query::references::*;
-use crate::{errors::ImplicaError, graph::Graph, matches::MatchSet, patterns::PathPattern};
+use crate::query::ResultIterator;
+use crate::{
+    errors::ImplicaError,
+    graph::{CreateOptions, Creations, Graph, PyGraph, Uid},
+    matches::MatchSet,
+    patterns::{
+        Comparison, CompiledDirection, PathPattern, PropertyMatch, QuantifiedPredicate, Quantifier,
+    },
+    utils::{
+        compare_values, edge_variable, numeric_value, order_values, sorts_last,
+        validate_query_variable, validate_variable_name, Evaluator,
+    },
+};
 
 #[derive(Debug, Clone)]
 enum QueryOperation {
-    Create(PathPattern),
+    Create(PathPattern, CreateOptions),
     Match(PathPattern),
+    MatchWithin(String, Vec<Uid>, usize, CompiledDirection),
     Remove(Vec<String>),
+    ClearTerm(Vec<String>, bool),
     Set(String, PropertyMap, bool),
+    SetExpr(String, String, String),
+    Filter(Arc<Py<PyAny>>),
+    Where(String),
+    WhereDistinct(Vec<String>),
+    Let(String, String),
+    With(Vec<(String, String)>),
+    CollectPattern(String, PathPattern, String, String),
+    OrderBy(Vec<String>, bool),
+    Limit(usize),
 }
 
 impl Display for QueryOperation {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            QueryOperation::Create(pattern) => write!(f, "CREATE {}", pattern),
-            QueryOperation::Match(pattern) => write!(f, "MATCH {}", pattern),
+            QueryOperation::Create(pattern, options) => {
+                write!(f, "CREATE {}", pattern)?;
+                if options.merge_properties && options.overwrite {
+                    write!(f, " OVERWRITING PROPERTIES")?;
+                } else if options.merge_properties {
+                    write!(f, " MERGING PROPERTIES")?;
+                }
+                if options.property_fn.is_some() {
+                    write!(f, " WITH <python property fn>")?;
+                }
+                Ok(())
+            }
+            QueryOperation::Match(pattern) => {
+                write!(f, "MATCH {}", pattern)?;
+                for predicate in pattern.quantified() {
+                    write!(f, " {}", predicate)?;
+                }
+                let missing: Vec<&str> = pattern.missing_properties().map(String::as_str).collect();
+                if !missing.is_empty() {
+                    write!(f, " MISSING [{}]", missing.join(", "))?;
+                }
+                match pattern.property_match() {
+                    PropertyMatch::Superset => Ok(()),
+                    mode => write!(f, " PROPERTIES {}", mode.to_string().to_uppercase()),
+                }
+            }
+            QueryOperation::MatchWithin(variable, seeds, k, direction) => {
+                let seeds: Vec<String> = seeds.iter().map(hex::encode).collect();
+                write!(
+                    f,
+                    "MATCH {} WITHIN {} {} HOPS OF [{}]",
+                    variable,
+                    k,
+                    direction.to_string().to_uppercase(),
+                    seeds.join(", ")
+                )
+            }
             QueryOperation::Remove(variables) => {
                 write!(f, "REMOVE ")?;
                 let mut is_first = true;
@@ -42,6 +112,13 @@ impl Display for QueryOperation {
 
                 Ok(())
             }
+            QueryOperation::ClearTerm(variables, cascade) => {
+                write!(f, "CLEAR TERM {}", variables.join(", "))?;
+                if *cascade {
+                    write!(f, " CASCADE")?;
+                }
+                Ok(())
+            }
             QueryOperation::Set(variable, properties, overwrite) => {
                 write!(
                     f,
@@ -51,15 +128,143 @@ impl Display for QueryOperation {
                     properties
                 )
             }
+            QueryOperation::SetExpr(variable, key, expression) => {
+                write!(f, "SET {}.{} = {}", variable, key, expression)
+            }
+            QueryOperation::Filter(_) => write!(f, "FILTER <python callback>"),
+            QueryOperation::Where(expression) => write!(f, "WHERE {}", expression),
+            QueryOperation::WhereDistinct(variables) => {
+                write!(f, "WHERE DISTINCT {}", variables.join(", "))
+            }
+            QueryOperation::Let(variable, expression) => {
+                write!(f, "LET {} = {}", variable, expression)
+            }
+            QueryOperation::OrderBy(expressions, descending) => {
+                write!(f, "ORDER BY {}", expressions.join(", "))?;
+                if *descending {
+                    write!(f, " DESC")?;
+                }
+                Ok(())
+            }
+            QueryOperation::Limit(limit) => write!(f, "LIMIT {}", limit),
+            QueryOperation::CollectPattern(anchor, pattern, collect, alias) => {
+                write!(
+                    f,
+                    "COLLECT [{} | {}] PER {} AS {}",
+                    pattern, collect, anchor, alias
+                )
+            }
+            QueryOperation::With(items) => {
+                write!(f, "WITH ")?;
+                let mut is_first = true;
+
+                for (source, alias) in items.iter() {
+                    if !is_first {
+                        write!(f, ", ")?;
+                    }
+                    is_first = false;
+                    if source == alias {
+                        write!(f, "{}", source)?;
+                    } else {
+                        write!(f, "{} AS {}", source, alias)?;
+                    }
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+/// An item of RETURN.
+#[derive(Debug, Clone)]
+enum Projection {
+    Variable(String),
+    Property {
+        key: String,
+        variable: String,
+        path: String,
+        default: Option<Arc<Py<PyAny>>>,
+    },
+    /// The value the first matching case maps an expression's value to.
+    Case {
+        key: String,
+        expression: String,
+        cases: Vec<(Dynamic, Dynamic)>,
+        default: Option<Arc<Py<PyAny>>>,
+    },
+}
+
+impl Projection {
+    fn key(&self) -> &str {
+        match self {
+            Projection::Variable(key)
+            | Projection::Property { key, .. }
+            | Projection::Case { key, .. } => key,
+        }
+    }
+}
+
+/// The value of an item of RETURN in one row, before it is handed to Python.
+enum Projected {
+    Element(Reference),
+    Value(Dynamic),
+    Missing,
+}
+
+/// Rows already converted to Python objects.
+type PyRows<'py> = Vec<Bound<'py, PyAny>>;
+
+/// Pairs every expected row with an equal actual row, each actual row being used at most
+/// once so that rows are compared as multisets. Returns the paired rows, the actual rows
+/// left over and the expected rows that found no match.
+fn pair_rows<'py>(
+    actual_rows: &Bound<'py, PyList>,
+    expected_rows: &[Bound<'py, PyDict>],
+) -> PyResult<(PyRows<'py>, PyRows<'py>, PyRows<'py>)> {
+    let mut unmatched: PyRows<'py> = actual_rows.iter().collect();
+    let mut paired = Vec::new();
+    let mut missing = Vec::new();
+
+    for row in expected_rows.iter() {
+        let mut found = None;
+        for (idx, actual) in unmatched.iter().enumerate() {
+            if actual.eq(row)? {
+                found = Some(idx);
+                break;
+            }
+        }
+
+        match found {
+            Some(idx) => paired.push(unmatched.remove(idx)),
+            None => missing.push(row.clone().into_any()),
         }
     }
+
+    Ok((paired, unmatched, missing))
 }
 
+/// A row of bindings as handed to Python, keyed by variable name.
+type Row = HashMap<String, Reference>;
+
+/// Operations run by a traced execution, each with the rows it left and the time it took.
+type Trace = Vec<(String, usize, Duration)>;
+
 #[pyclass]
 #[derive(Debug, Clone)]
 pub struct Query {
     graph: Arc<Graph>,
     operations: Vec<QueryOperation>,
+    optimize: bool,
+    cached: bool,
+    functions: Vec<(String, Arc<Py<PyAny>>)>,
+    /// Most rows a MATCH may leave behind before the query is aborted.
+    max_intermediate_rows: Option<usize>,
+    /// Rows read through `len` and indexing, with the number of operations they were
+    /// computed for so that adding operations afterwards runs the query again.
+    rows: Option<(usize, Arc<Vec<Row>>)>,
+    /// Where executed operations are recorded while `explain_analyze` runs the query.
+    trace: Option<Arc<Mutex<Trace>>>,
 }
 
 impl Display for Query {
@@ -77,25 +282,177 @@ impl Query {
         Query {
             graph,
             operations: Vec::new(),
+            optimize: false,
+            cached: false,
+            functions: Vec::new(),
+            max_intermediate_rows: None,
+            rows: None,
+            trace: None,
+        }
+    }
+
+    /// Returns the operations in the order they will be executed.
+    ///
+    /// Without optimization this is the order in which they were added. With optimization,
+    /// every run of consecutive MATCH operations is reordered so that the most selective
+    /// pattern runs first. A match never moves ahead of an earlier match it shares an
+    /// identifier with, as the meaning of a name depends on whether it is already bound.
+    fn plan(&self) -> Vec<QueryOperation> {
+        if !self.optimize {
+            return self.operations.clone();
         }
+
+        let mut plan = Vec::with_capacity(self.operations.len());
+        let mut run: Vec<&PathPattern> = Vec::new();
+
+        for op in self.operations.iter() {
+            match op {
+                QueryOperation::Match(pattern) => run.push(pattern),
+                _ => {
+                    plan.extend(self.reorder_matches(&run));
+                    run.clear();
+                    plan.push(op.clone());
+                }
+            }
+        }
+        plan.extend(self.reorder_matches(&run));
+
+        plan
+    }
+
+    fn reorder_matches(&self, run: &[&PathPattern]) -> Vec<QueryOperation> {
+        let mut pending: Vec<(&PathPattern, HashSet<String>, usize)> = run
+            .iter()
+            .map(|pattern| {
+                (
+                    *pattern,
+                    pattern.identifiers(),
+                    self.graph.estimate_path_pattern(pattern),
+                )
+            })
+            .collect();
+        let mut ordered = Vec::with_capacity(pending.len());
+
+        while !pending.is_empty() {
+            let next = (0..pending.len())
+                .filter(|&idx| {
+                    pending[..idx]
+                        .iter()
+                        .all(|(_, ids, _)| ids.is_disjoint(&pending[idx].1))
+                })
+                .min_by_key(|&idx| (pending[idx].2, idx))
+                .unwrap_or(0);
+
+            let (pattern, _, _) = pending.remove(next);
+            ordered.push(QueryOperation::Match(pattern.clone()));
+        }
+
+        ordered
     }
 
     fn execute_operations(&self) -> ImplicaResult<MatchSet> {
+        // Only read-only queries can be served from the cache, as replaying a cached result
+        // would skip the mutations of any other operation.
+        let cacheable = self.cached
+            && self
+                .operations
+                .iter()
+                .all(|op| matches!(op, QueryOperation::Match(_)));
+
+        if !cacheable {
+            let mset = self.run_operations();
+
+            self.graph
+                .notify_observers()
+                .attach(ctx!("query - execute operations"))?;
+
+            return mset;
+        }
+
+        let key = self.to_string();
+        if let Some(mset) = self
+            .graph
+            .cached_matches(&key)
+            .attach(ctx!("query - execute operations"))?
+        {
+            return Ok(mset);
+        }
+
+        let version = self.graph.version();
+        let mset = self.run_operations()?;
+        self.graph
+            .store_matches(key, version, mset.clone())
+            .attach(ctx!("query - execute operations"))?;
+
+        Ok(mset)
+    }
+
+    fn run_operations(&self) -> ImplicaResult<MatchSet> {
+        let plan = self.plan();
+
+        // A lone node or single-hop path pattern followed only by a LIMIT can stop scanning
+        // once it has enough rows; anything in between, or after, needs the full match set.
+        // Variable-length edges and longer paths always run in full.
+        if let [QueryOperation::Match(pattern), QueryOperation::Limit(limit)] = plan.as_slice() {
+            let pushable = match (pattern.nodes.as_slice(), pattern.edges.as_slice()) {
+                ([_], []) => true,
+                ([_, _], [edge]) => edge.hops.is_none(),
+                _ => false,
+            };
+
+            if pushable {
+                pattern
+                    .validate()
+                    .attach(ctx!("query - execute operations"))?;
+
+                let start = Instant::now();
+                let mset = match pattern.nodes.as_slice() {
+                    [node] => self.graph.match_node_pattern_limited(node, *limit),
+                    _ => self.graph.match_edge_path_limited(pattern, *limit),
+                }
+                .attach(ctx!(format!(
+                    "query - execute operation - {}",
+                    self.to_string()
+                )))?;
+                self.record_trace(
+                    format!("{} {}", plan[0], plan[1]),
+                    mset.len(),
+                    start.elapsed(),
+                )?;
+
+                return Ok(mset);
+            }
+        }
+
         let mut mset: MatchSet = default_match_set();
 
-        for op in self.operations.iter() {
+        for op in plan.iter() {
+            let start = Instant::now();
+
             match op {
-                QueryOperation::Create(pattern) => {
-                    mset = self.execute_create(pattern, mset).attach(ctx!(format!(
-                        "query - execute operation - {}",
-                        self.to_string()
-                    )))?;
+                QueryOperation::Create(pattern, options) => {
+                    mset = self
+                        .execute_create(pattern, options, mset)
+                        .attach(ctx!(format!(
+                            "query - execute operation - {}",
+                            self.to_string()
+                        )))?;
                 }
                 QueryOperation::Match(pattern) => {
                     mset = self.execute_match(pattern, mset).attach(ctx!(format!(
                         "query - execute operation - {}",
                         self.to_string()
                     )))?;
+                    self.check_intermediate_rows(&mset)?;
+                }
+                QueryOperation::MatchWithin(variable, seeds, k, direction) => {
+                    mset = self
+                        .execute_match_within(variable, seeds, *k, direction, mset)
+                        .attach(ctx!(format!(
+                            "query - execute operation - {}",
+                            self.to_string()
+                        )))?;
+                    self.check_intermediate_rows(&mset)?;
                 }
                 QueryOperation::Remove(variables) => {
                     mset = self.execute_remove(variables, mset).attach(ctx!(format!(
@@ -103,6 +460,14 @@ impl Query {
                         self.to_string()
                     )))?;
                 }
+                QueryOperation::ClearTerm(variables, cascade) => {
+                    mset = self
+                        .execute_clear_term(variables, *cascade, mset)
+                        .attach(ctx!(format!(
+                            "query - execute operation - {}",
+                            self.to_string()
+                        )))?;
+                }
                 QueryOperation::Set(variable, properties, overwrite) => {
                     mset = self
                         .execute_set(variable, properties, *overwrite, mset)
@@ -111,24 +476,156 @@ impl Query {
                             self.to_string()
                         )))?;
                 }
+                QueryOperation::SetExpr(variable, key, expression) => {
+                    mset = self
+                        .execute_set_expr(variable, key, expression, mset)
+                        .attach(ctx!(format!(
+                            "query - execute operation - {}",
+                            self.to_string()
+                        )))?;
+                }
+                QueryOperation::Filter(callback) => {
+                    mset = self.execute_filter(callback, mset).attach(ctx!(format!(
+                        "query - execute operation - {}",
+                        self.to_string()
+                    )))?;
+                }
+                QueryOperation::Where(expression) => {
+                    mset = self.execute_where(expression, mset).attach(ctx!(format!(
+                        "query - execute operation - {}",
+                        self.to_string()
+                    )))?;
+                }
+                QueryOperation::WhereDistinct(variables) => {
+                    mset = self
+                        .execute_where_distinct(variables, mset)
+                        .attach(ctx!(format!(
+                            "query - execute operation - {}",
+                            self.to_string()
+                        )))?;
+                }
+                QueryOperation::Let(variable, expression) => {
+                    mset = self
+                        .execute_let(variable, expression, mset)
+                        .attach(ctx!(format!(
+                            "query - execute operation - {}",
+                            self.to_string()
+                        )))?;
+                }
+                QueryOperation::With(items) => {
+                    mset = self.execute_with(items, mset).attach(ctx!(format!(
+                        "query - execute operation - {}",
+                        self.to_string()
+                    )))?;
+                }
+                QueryOperation::CollectPattern(anchor, pattern, collect, alias) => {
+                    mset = self
+                        .execute_collect_pattern(anchor, pattern, collect, alias, mset)
+                        .attach(ctx!(format!(
+                            "query - execute operation - {}",
+                            self.to_string()
+                        )))?;
+                }
+                QueryOperation::OrderBy(expressions, descending) => {
+                    mset = self
+                        .execute_order_by(expressions, *descending, mset)
+                        .attach(ctx!(format!(
+                            "query - execute operation - {}",
+                            self.to_string()
+                        )))?;
+                }
+                QueryOperation::Limit(limit) => {
+                    mset = self.execute_limit(*limit, mset);
+                }
             }
+
+            self.record_trace(op.to_string(), mset.len(), start.elapsed())?;
         }
 
         Ok(mset)
     }
 
-    fn execute_create(&self, pattern: &PathPattern, matches: MatchSet) -> ImplicaResult<MatchSet> {
-        self.graph
-            .create_path(pattern, matches)
+    /// Records an executed operation when the query is being traced.
+    fn record_trace(&self, operation: String, rows: usize, elapsed: Duration) -> ImplicaResult<()> {
+        if let Some(ref trace) = self.trace {
+            trace
+                .lock()
+                .map_err(|e| ImplicaError::LockError {
+                    rw: "write".to_string(),
+                    message: e.to_string(),
+                    context: Some(ctx!("query - record trace")),
+                })?
+                .push((operation, rows, elapsed));
+        }
+
+        Ok(())
+    }
+
+    /// Aborts the query once a MATCH has left more rows than `max_intermediate_rows` allows.
+    fn check_intermediate_rows(&self, matches: &MatchSet) -> ImplicaResult<()> {
+        match self.max_intermediate_rows {
+            Some(max) if matches.len() > max => Err(ImplicaError::InvalidQuery {
+                query: self.to_string(),
+                reason: format!("intermediate result set exceeded {} rows", max),
+                context: Some(ctx!("query - check intermediate rows")),
+            }
+            .into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Makes every CREATE of the query record the nodes and edges it inserts in `creations`.
+    pub(crate) fn record_creations(&mut self, creations: &Arc<Creations>) {
+        for operation in self.operations.iter_mut() {
+            if let QueryOperation::Create(_, options) = operation {
+                options.creations = Some(creations.clone());
+            }
+        }
+    }
+
+    fn execute_create(
+        &self,
+        pattern: &PathPattern,
+        options: &CreateOptions,
+        matches: MatchSet,
+    ) -> ImplicaResult<MatchSet> {
+        if options.property_fn.is_none() {
+            return self
+                .graph
+                .create_path(pattern, options, matches)
+                .attach(ctx!(format!("query - execute create - {}", pattern)));
+        }
+
+        // The property callback runs on the worker threads creating the nodes, so the GIL
+        // is released here for them to take it.
+        Python::attach(|py| py.detach(|| self.graph.create_path(pattern, options, matches)))
             .attach(ctx!(format!("query - execute create - {}", pattern)))
     }
 
     fn execute_match(&self, pattern: &PathPattern, matches: MatchSet) -> ImplicaResult<MatchSet> {
-        self.graph
-            .match_path_pattern(pattern, matches)
+        // Matching never calls into Python, so the GIL is released to let queries from other
+        // threads run alongside it.
+        Python::attach(|py| py.detach(|| self.graph.match_path_pattern(pattern, matches)))
             .attach(ctx!(format!("query - execute match - {}", pattern)))
     }
 
+    fn execute_match_within(
+        &self,
+        variable: &str,
+        seeds: &[Uid],
+        k: usize,
+        direction: &CompiledDirection,
+        matches: MatchSet,
+    ) -> ImplicaResult<MatchSet> {
+        Python::attach(|py| {
+            py.detach(|| {
+                self.graph
+                    .match_within(variable, seeds, k, direction, matches)
+            })
+        })
+        .attach(ctx!(format!("query - execute match within - {}", variable)))
+    }
+
     fn execute_remove(&self, variables: &[String], matches: MatchSet) -> ImplicaResult<MatchSet> {
         for var in variables.iter() {
             let result = matches.par_iter().try_for_each(|entry| {
@@ -160,6 +657,28 @@ impl Query {
                             }
                             .into(),
                         ),
+                        MatchElement::List(_) => ControlFlow::Break(
+                            ImplicaError::InvalidQuery {
+                                query: self.to_string(),
+                                reason: format!(
+                                    "'{}' is bound to a list, not to an element of the graph",
+                                    var
+                                ),
+                                context: Some("execute remove".to_string()),
+                            }
+                            .into(),
+                        ),
+                        MatchElement::Value(_) => ControlFlow::Break(
+                            ImplicaError::InvalidQuery {
+                                query: self.to_string(),
+                                reason: format!(
+                                    "'{}' is bound to a value, not to an element of the graph",
+                                    var
+                                ),
+                                context: Some("execute remove".to_string()),
+                            }
+                            .into(),
+                        ),
                     }
                 } else {
                     ControlFlow::Break(
@@ -186,6 +705,53 @@ impl Query {
         Ok(matches)
     }
 
+    fn execute_clear_term(
+        &self,
+        variables: &[String],
+        cascade: bool,
+        matches: MatchSet,
+    ) -> ImplicaResult<MatchSet> {
+        for var in variables.iter() {
+            let result = matches.par_iter().try_for_each(|entry| {
+                let (_, r#match) = entry.value().clone();
+
+                match r#match.get(var) {
+                    Some(MatchElement::Node(n)) => match self.graph.clear_term(&n, cascade) {
+                        Ok(_) => ControlFlow::Continue(()),
+                        Err(e) => ControlFlow::Break(e),
+                    },
+                    Some(_) => ControlFlow::Break(
+                        ImplicaError::InvalidQuery {
+                            query: self.to_string(),
+                            reason: format!(
+                                "You can only clear the term of a node, '{}' is not one",
+                                var
+                            ),
+                            context: Some("execute clear term".to_string()),
+                        }
+                        .into(),
+                    ),
+                    None => ControlFlow::Break(
+                        ImplicaError::VariableNotFound {
+                            name: var.clone(),
+                            context: Some("execute clear term".to_string()),
+                        }
+                        .into(),
+                    ),
+                }
+            });
+
+            if let ControlFlow::Break(e) = result {
+                return Err(e.attach(ctx!(format!(
+                    "query - execute clear term - {}",
+                    variables.join(", ")
+                ))));
+            }
+        }
+
+        Ok(matches)
+    }
+
     fn execute_set(
         &self,
         variable: &str,
@@ -213,16 +779,34 @@ impl Query {
                     }
                     MatchElement::Type(_) => ControlFlow::Break(ImplicaError::InvalidQuery {
                         query: self.to_string(),
-                        reason:
-                            "You cannot set the properties of a type, types do not have properties"
-                                .to_string(),
+                        reason: format!(
+                            "cannot set properties on '{}', it is bound to a type and types do not have properties",
+                            variable
+                        ),
                         context: Some("execute set".to_string()),
                     }.into()),
                     MatchElement::Term(_) => ControlFlow::Break(ImplicaError::InvalidQuery {
                         query: self.to_string(),
-                        reason:
-                            "You cannot set the properties of a type, types do not have properties"
-                                .to_string(),
+                        reason: format!(
+                            "cannot set properties on '{}', it is bound to a term and terms do not have properties",
+                            variable
+                        ),
+                        context: Some("execute set".to_string()),
+                    }.into()),
+                    MatchElement::List(_) => ControlFlow::Break(ImplicaError::InvalidQuery {
+                        query: self.to_string(),
+                        reason: format!(
+                            "cannot set properties on '{}', it is bound to a list",
+                            variable
+                        ),
+                        context: Some("execute set".to_string()),
+                    }.into()),
+                    MatchElement::Value(_) => ControlFlow::Break(ImplicaError::InvalidQuery {
+                        query: self.to_string(),
+                        reason: format!(
+                            "cannot set properties on '{}', it is bound to a value",
+                            variable
+                        ),
                         context: Some("execute set".to_string()),
                     }.into()),
                 }
@@ -247,48 +831,1649 @@ impl Query {
             )))),
         }
     }
-}
 
-#[pymethods]
-impl Query {
-    pub fn create(&mut self, pattern: String) -> PyResult<Query> {
-        let path_pattern = PathPattern::new(pattern)
-            .attach(ctx!("query - create"))
-            .into_py_result()?;
+    fn execute_set_expr(
+        &self,
+        variable: &str,
+        key: &str,
+        expression: &str,
+        matches: MatchSet,
+    ) -> ImplicaResult<MatchSet> {
+        // As for WHERE, the GIL is released for registered Python functions.
+        Python::attach(|py| py.detach(|| self.set_expr_rows(variable, key, expression, matches)))
+            .attach(ctx!(format!(
+                "query - execute set expr - {}.{} = {}",
+                variable, key, expression
+            )))
+    }
 
-        self.operations.push(QueryOperation::Create(path_pattern));
+    /// Evaluates the expression for every row and then writes the values, so each row sees
+    /// the properties as they were before the operation even when rows share an element.
+    fn set_expr_rows(
+        &self,
+        variable: &str,
+        key: &str,
+        expression: &str,
+        matches: MatchSet,
+    ) -> ImplicaResult<MatchSet> {
+        let evaluator = self.evaluator().attach(ctx!("query - execute set expr"))?;
 
-        Ok(self.clone())
-    }
+        let updates = matches
+            .par_iter()
+            .map(|entry| -> ImplicaResult<(MatchElement, Dynamic)> {
+                let (_, r#match) = entry.value().clone();
 
-    pub fn r#match(&mut self, pattern: String) -> PyResult<Query> {
+                let element = r#match
+                    .get(variable)
+                    .ok_or(ImplicaError::VariableNotFound {
+                        name: variable.to_string(),
+                        context: Some(ctx!("query - execute set expr")),
+                    })?;
+
+                let mut scope = self
+                    .row_scope(&r#match)
+                    .attach(ctx!("query - execute set expr"))?;
+                let value = evaluator
+                    .eval_value(&mut scope, expression)
+                    .attach(ctx!("query - execute set expr"))?;
+
+                Ok((element, value))
+            })
+            .collect::<ImplicaResult<Vec<_>>>()?;
+
+        updates
+            .into_par_iter()
+            .try_for_each(|(element, value)| -> ImplicaResult<()> {
+                let mut map = Map::new();
+                map.insert(key.into(), value);
+                let properties = PropertyMap::from_map(map);
+
+                match element {
+                    MatchElement::Node(n) => self.graph.set_node_properties(&n, properties, false),
+                    MatchElement::Edge(e) => self.graph.set_edge_properties(&e, properties, false),
+                    _ => Err(ImplicaError::InvalidQuery {
+                        query: self.to_string(),
+                        reason: format!(
+                            "cannot set properties on '{}', it is not bound to a node or an edge",
+                            variable
+                        ),
+                        context: Some(ctx!("query - execute set expr")),
+                    }
+                    .into()),
+                }
+            })?;
+
+        Ok(matches)
+    }
+
+    fn execute_filter(&self, callback: &Py<PyAny>, matches: MatchSet) -> ImplicaResult<MatchSet> {
+        // The callback needs the GIL, so rows are visited sequentially on the calling thread.
+        Python::attach(|py| {
+            let out_map: MatchSet = Arc::new(DashMap::new());
+
+            for entry in matches.iter() {
+                let (prev_uid, r#match) = entry.value().clone();
+
+                let variables: Vec<String> =
+                    r#match.elements().into_iter().map(|(k, _)| k).collect();
+                let row = self
+                    .collect_row(&r#match, &variables)
+                    .attach(ctx!("query - execute filter"))?;
+
+                let keep = row
+                    .into_pyobject(py)
+                    .and_then(|row| callback.call1(py, (row,)))
+                    .and_then(|result| result.is_truthy(py))
+                    .map_err(|e| {
+                        Report::new(ImplicaError::from(e.clone_ref(py)))
+                            .attach_opaque(e)
+                            .attach(ctx!("query - execute filter"))
+                    })?;
+
+                if keep {
+                    out_map.insert(*entry.key(), (prev_uid, r#match));
+                }
+            }
+
+            Ok(out_map)
+        })
+    }
+
+    /// Builds an evaluator knowing the Python functions registered on the query and the
+    /// endpoints of the graph's edges.
+    fn evaluator(&self) -> ImplicaResult<Evaluator> {
+        let mut evaluator = Evaluator::new().attach(ctx!("query - evaluator"))?;
+
+        let graph = self.graph.clone();
+        evaluator.register_edge_endpoints(move |node| {
+            graph
+                .node_properties(node)
+                .and_then(|props| props.to_map())
+                .attach(ctx!("query - edge endpoint"))
+        });
+
+        for (name, callback) in self.functions.iter() {
+            evaluator.register_python_function(name, callback.clone());
+        }
+
+        Ok(evaluator)
+    }
+
+    fn execute_where(&self, expression: &str, matches: MatchSet) -> ImplicaResult<MatchSet> {
+        // Registered Python functions are called from the worker threads evaluating the
+        // rows, so the GIL is released here for them to take it.
+        Python::attach(|py| py.detach(|| self.where_rows(expression, matches)))
+    }
+
+    fn where_rows(&self, expression: &str, matches: MatchSet) -> ImplicaResult<MatchSet> {
+        let evaluator = self.evaluator().attach(ctx!("query - execute where"))?;
+        let out_map: MatchSet = Arc::new(DashMap::new());
+
+        matches
+            .par_iter()
+            .try_for_each(|entry| -> ImplicaResult<()> {
+                let (prev_uid, r#match) = entry.value().clone();
+
+                let mut scope = self
+                    .row_scope(&r#match)
+                    .attach(ctx!("query - execute where"))?;
+
+                if evaluator
+                    .eval(&mut scope, expression)
+                    .attach(ctx!("query - execute where"))?
+                {
+                    out_map.insert(*entry.key(), (prev_uid, r#match));
+                }
+
+                Ok(())
+            })?;
+
+        Ok(out_map)
+    }
+
+    /// Keeps the rows whose named bindings are pairwise different elements.
+    fn execute_where_distinct(
+        &self,
+        variables: &[String],
+        matches: MatchSet,
+    ) -> ImplicaResult<MatchSet> {
+        let out_map: MatchSet = Arc::new(DashMap::new());
+
+        matches
+            .par_iter()
+            .try_for_each(|entry| -> ImplicaResult<()> {
+                let (prev_uid, r#match) = entry.value().clone();
+
+                let mut seen: Vec<MatchElement> = Vec::with_capacity(variables.len());
+                for var in variables.iter() {
+                    let element = r#match.get(var).ok_or(ImplicaError::VariableNotFound {
+                        name: var.clone(),
+                        context: Some(ctx!("query - execute where distinct")),
+                    })?;
+
+                    if seen.contains(&element) {
+                        return Ok(());
+                    }
+                    seen.push(element);
+                }
+
+                out_map.insert(*entry.key(), (prev_uid, r#match));
+                Ok(())
+            })?;
+
+        Ok(out_map)
+    }
+
+    fn execute_let(
+        &self,
+        variable: &str,
+        expression: &str,
+        matches: MatchSet,
+    ) -> ImplicaResult<MatchSet> {
+        // As for WHERE, the GIL is released for registered Python functions.
+        Python::attach(|py| py.detach(|| self.let_rows(variable, expression, matches)))
+    }
+
+    /// Binds `variable` in every row to the value of `expression` in that row. Missing
+    /// values are bound as well, as `None`.
+    fn let_rows(
+        &self,
+        variable: &str,
+        expression: &str,
+        matches: MatchSet,
+    ) -> ImplicaResult<MatchSet> {
+        let evaluator = self.evaluator().attach(ctx!("query - execute let"))?;
+        let out_map: MatchSet = Arc::new(DashMap::new());
+
+        matches
+            .par_iter()
+            .try_for_each(|entry| -> ImplicaResult<()> {
+                let (prev_uid, r#match) = entry.value().clone();
+
+                let mut scope = self
+                    .row_scope(&r#match)
+                    .attach(ctx!("query - execute let"))?;
+                let value = evaluator
+                    .eval_value(&mut scope, expression)
+                    .attach(ctx!(format!("query - execute let - {}", expression)))?;
+
+                let extended = Match::new(Some(r#match));
+                extended
+                    .insert(variable, MatchElement::Value(ComputedValue(value)))
+                    .attach(ctx!("query - execute let"))?;
+
+                out_map.insert(*entry.key(), (prev_uid, Arc::new(extended)));
+                Ok(())
+            })?;
+
+        Ok(out_map)
+    }
+
+    fn execute_with(
+        &self,
+        items: &[(String, String)],
+        matches: MatchSet,
+    ) -> ImplicaResult<MatchSet> {
+        let out_map: MatchSet = Arc::new(DashMap::new());
+
+        matches
+            .par_iter()
+            .try_for_each(|entry| -> ImplicaResult<()> {
+                let (prev_uid, r#match) = entry.value().clone();
+                let projected = Match::new(None);
+
+                for (source, alias) in items.iter() {
+                    let element = r#match.get(source).ok_or(ImplicaError::VariableNotFound {
+                        name: source.clone(),
+                        context: Some(ctx!("query - execute with")),
+                    })?;
+
+                    projected
+                        .insert(alias, element)
+                        .attach(ctx!("query - execute with"))?;
+                }
+
+                out_map.insert(*entry.key(), (prev_uid, Arc::new(projected)));
+                Ok(())
+            })?;
+
+        Ok(out_map)
+    }
+
+    /// Binds `alias` in every row to the list of elements `collect` is bound to across the
+    /// matches of `pattern` starting from the row's `anchor`. Only the anchor is carried
+    /// into the pattern; a row without any match gets an empty list.
+    fn execute_collect_pattern(
+        &self,
+        anchor: &str,
+        pattern: &PathPattern,
+        collect: &str,
+        alias: &str,
+        matches: MatchSet,
+    ) -> ImplicaResult<MatchSet> {
+        let out_map: MatchSet = Arc::new(DashMap::new());
+
+        let collect_row = |entry: &(Uid, Arc<Match>)| -> ImplicaResult<Arc<Match>> {
+            let (prev_uid, r#match) = entry;
+
+            let element = r#match.get(anchor).ok_or(ImplicaError::VariableNotFound {
+                name: anchor.to_string(),
+                context: Some(ctx!("query - execute collect pattern")),
+            })?;
+            let seed = Match::new(None);
+            seed.insert(anchor, element)
+                .attach(ctx!("query - execute collect pattern"))?;
+
+            let seed_set: MatchSet = Arc::new(DashMap::new());
+            seed_set.insert(next_match_id(), (*prev_uid, Arc::new(seed)));
+
+            let found = self
+                .graph
+                .match_path_pattern(pattern, seed_set)
+                .attach(ctx!("query - execute collect pattern"))?;
+
+            let collected = ordered_rows(&found)
+                .into_iter()
+                .map(|(_, m)| {
+                    m.get(collect).ok_or(
+                        ImplicaError::VariableNotFound {
+                            name: collect.to_string(),
+                            context: Some(ctx!("query - execute collect pattern")),
+                        }
+                        .into(),
+                    )
+                })
+                .collect::<ImplicaResult<Vec<_>>>()?;
+
+            let extended = Match::new(Some(r#match.clone()));
+            extended
+                .insert(alias, MatchElement::List(collected))
+                .attach(ctx!("query - execute collect pattern"))?;
+
+            Ok(Arc::new(extended))
+        };
+
+        // Matching never calls into Python, so the GIL is released as for MATCH.
+        Python::attach(|py| {
+            py.detach(|| {
+                matches
+                    .par_iter()
+                    .try_for_each(|entry| -> ImplicaResult<()> {
+                        let extended = collect_row(entry.value())?;
+                        // Rows keep their ids, so an earlier ORDER BY still holds.
+                        out_map.insert(*entry.key(), (entry.value().0, extended));
+                        Ok(())
+                    })
+            })
+        })?;
+
+        Ok(out_map)
+    }
+
+    /// Sorts the rows by the values of the expressions, compared in turn. Missing values and
+    /// NaN go last in either direction. Rows that tie are ordered by the uids of their
+    /// bindings, so the order, and the rows a later LIMIT keeps, is the same on every run.
+    fn execute_order_by(
+        &self,
+        expressions: &[String],
+        descending: bool,
+        matches: MatchSet,
+    ) -> ImplicaResult<MatchSet> {
+        // As for WHERE, the GIL is released for registered Python functions.
+        Python::attach(|py| py.detach(|| self.order_rows(expressions, descending, matches)))
+    }
+
+    fn order_rows(
+        &self,
+        expressions: &[String],
+        descending: bool,
+        matches: MatchSet,
+    ) -> ImplicaResult<MatchSet> {
+        let evaluator = self.evaluator().attach(ctx!("query - execute order by"))?;
+
+        let mut rows = ordered_rows(&matches)
+            .into_par_iter()
+            .map(|(prev_uid, r#match)| {
+                let mut scope = self
+                    .row_scope(&r#match)
+                    .attach(ctx!("query - execute order by"))?;
+
+                let keys = expressions
+                    .iter()
+                    .map(|expression| {
+                        evaluator
+                            .eval_value(&mut scope, expression)
+                            .attach(ctx!(format!("query - execute order by - {}", expression)))
+                    })
+                    .collect::<ImplicaResult<Vec<Dynamic>>>()?;
+                let bindings = r#match.sorted_elements();
+
+                Ok(((keys, bindings), (prev_uid, r#match)))
+            })
+            .collect::<ImplicaResult<Vec<_>>>()?;
+
+        let mut mismatch: Option<Report<ImplicaError>> = None;
+
+        rows.sort_by(|((keys_1, bindings_1), _), ((keys_2, bindings_2), _)| {
+            for (idx, (key_1, key_2)) in keys_1.iter().zip(keys_2.iter()).enumerate() {
+                let ordering = match (sorts_last(key_1), sorts_last(key_2)) {
+                    (true, true) => Ordering::Equal,
+                    (true, false) => Ordering::Greater,
+                    (false, true) => Ordering::Less,
+                    (false, false) => match order_values(key_1, key_2) {
+                        Some(ordering) if descending => ordering.reverse(),
+                        Some(ordering) => ordering,
+                        None => {
+                            mismatch.get_or_insert_with(|| {
+                                ImplicaError::TypeMismatch {
+                                    expected: format!(
+                                        "a value comparable with {}",
+                                        key_1.type_name()
+                                    ),
+                                    got: key_2.type_name().to_string(),
+                                    context: Some(ctx!(format!(
+                                        "query - execute order by - {}",
+                                        expressions[idx]
+                                    ))),
+                                }
+                                .into()
+                            });
+                            Ordering::Equal
+                        }
+                    },
+                };
+
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+
+            cmp_bindings(bindings_1, bindings_2)
+        });
+
+        if let Some(report) = mismatch {
+            return Err(report);
+        }
+
+        // Fresh ids are increasing, so handing them out in sorted order records the ordering
+        let out_map: MatchSet = Arc::new(DashMap::new());
+        for (_, row) in rows {
+            out_map.insert(next_match_id(), row);
+        }
+
+        Ok(out_map)
+    }
+
+    /// The `k` rows whose value of `expression` is closest to `target`, nearest first. Rows
+    /// where the value is missing or NaN come after every other one; ties keep the order of
+    /// the rows.
+    fn nearest_rows(
+        &self,
+        expression: &str,
+        target: f64,
+        k: usize,
+        matches: MatchSet,
+    ) -> ImplicaResult<Vec<Arc<Match>>> {
+        let evaluator = self.evaluator().attach(ctx!("query - nearest"))?;
+
+        let mut rows = ordered_rows(&matches)
+            .into_par_iter()
+            .enumerate()
+            .map(|(position, (_prev_uid, r#match))| {
+                let mut scope = self.row_scope(&r#match).attach(ctx!("query - nearest"))?;
+
+                let value = evaluator
+                    .eval_value(&mut scope, expression)
+                    .attach(ctx!(format!("query - nearest - {}", expression)))?;
+
+                let distance = if sorts_last(&value) {
+                    None
+                } else {
+                    match numeric_value(&value) {
+                        Some(number) => Some((number - target).abs()),
+                        None => {
+                            return Err(ImplicaError::TypeMismatch {
+                                expected: "a number".to_string(),
+                                got: value.type_name().to_string(),
+                                context: Some(ctx!(format!("query - nearest - {}", expression))),
+                            }
+                            .into())
+                        }
+                    }
+                };
+
+                Ok(((distance.filter(|d| !d.is_nan()), position), r#match))
+            })
+            .collect::<ImplicaResult<Vec<_>>>()?;
+
+        let closest_first =
+            |((d1, p1), _): &((Option<f64>, usize), Arc<Match>),
+             ((d2, p2), _): &((Option<f64>, usize), Arc<Match>)| {
+                let ordering = match (d1, d2) {
+                    (Some(d1), Some(d2)) => d1.total_cmp(d2),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                };
+                ordering.then(p1.cmp(p2))
+            };
+
+        if k < rows.len() {
+            rows.select_nth_unstable_by(k, closest_first);
+            rows.truncate(k);
+        }
+        rows.sort_unstable_by(closest_first);
+
+        Ok(rows.into_iter().map(|(_, r#match)| r#match).collect())
+    }
+
+    fn execute_limit(&self, limit: usize, matches: MatchSet) -> MatchSet {
+        let out_map: MatchSet = Arc::new(DashMap::new());
+
+        for row in ordered_rows(&matches).into_iter().take(limit) {
+            out_map.insert(next_match_id(), row);
+        }
+
+        out_map
+    }
+
+    /// Exposes the bindings of a row to WHERE expressions: nodes and edges as maps of their
+    /// properties (lists become arrays) plus their `created_at` and `modified_at` timestamps,
+    /// types and terms as their string form. Edges are also kept by uid for `startNode` and
+    /// `endNode`.
+    fn row_scope(&self, r#match: &Match) -> ImplicaResult<Scope<'static>> {
+        let mut scope = Scope::new();
+
+        for (variable, element) in r#match.elements() {
+            match element {
+                MatchElement::Edge(edge) => scope.push(edge_variable(&variable), edge),
+                _ => scope.push(edge_variable(&variable), ()),
+            };
+
+            let timestamps = match element {
+                MatchElement::Node(uid) => self.graph.node_timestamps(&uid).ok(),
+                MatchElement::Edge(uid) => self.graph.edge_timestamps(&uid).ok(),
+                _ => None,
+            };
+
+            let mut value = self
+                .element_value(element)
+                .attach(ctx!("query - row scope"))?;
+
+            // Timestamps read like properties, which win when they share the name
+            if let (Some((created_at, modified_at)), Some(mut map)) =
+                (timestamps, value.write_lock::<Map>())
+            {
+                map.entry("created_at".into())
+                    .or_insert_with(|| Dynamic::from_float(created_at));
+                map.entry("modified_at".into())
+                    .or_insert_with(|| Dynamic::from_float(modified_at));
+            }
+
+            scope.push_dynamic(variable, value);
+        }
+
+        Ok(scope)
+    }
+
+    fn element_value(&self, element: MatchElement) -> ImplicaResult<Dynamic> {
+        Ok(match element {
+            MatchElement::Node(uid) => Dynamic::from_map(
+                self.graph
+                    .node_properties(&uid)
+                    .and_then(|props| props.to_map())
+                    .attach(ctx!("query - element value"))?,
+            ),
+            MatchElement::Edge(uid) => Dynamic::from_map(
+                self.graph
+                    .edge_properties(&uid)
+                    .and_then(|props| props.to_map())
+                    .attach(ctx!("query - element value"))?,
+            ),
+            MatchElement::Type(uid) => Dynamic::from(
+                self.graph
+                    .type_to_string(&uid)
+                    .attach(ctx!("query - element value"))?,
+            ),
+            MatchElement::Term(uid) => Dynamic::from(
+                self.graph
+                    .term_to_string(&uid)
+                    .attach(ctx!("query - element value"))?,
+            ),
+            MatchElement::List(elements) => Dynamic::from_array(
+                elements
+                    .into_iter()
+                    .map(|element| self.element_value(element))
+                    .collect::<ImplicaResult<Vec<_>>>()?,
+            ),
+            MatchElement::Value(value) => value.0,
+        })
+    }
+
+    /// Checks user-supplied variable names before they reach the operations, so that an
+    /// invalid or reserved name fails when the query is built rather than while it runs.
+    fn validate_variables(&self, variables: &[String], context: String) -> ImplicaResult<()> {
+        for v in variables.iter() {
+            validate_query_variable(v).map_err(|e| {
+                let reason = match e.current_context() {
+                    ImplicaError::InvalidIdentifier { name, reason } => {
+                        format!("invalid variable name '{}': {}", name, reason)
+                    }
+                    other => other.to_string(),
+                };
+
+                e.change_context(ImplicaError::InvalidQuery {
+                    query: self.to_string(),
+                    reason,
+                    context: Some(context.clone()),
+                })
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads the `any_property` or `all_property` argument of MATCH, e.g.
+    /// `{"scores": {">": 90, "<=": 100}}`: each key names a list property and maps to the
+    /// comparisons its elements are checked against.
+    fn quantified_predicates(
+        &self,
+        predicates: &Bound<PyDict>,
+        quantifier: Quantifier,
+    ) -> PyResult<Vec<QuantifiedPredicate>> {
+        let mut out = Vec::with_capacity(predicates.len());
+
+        for (key, comparisons) in predicates.iter() {
+            let key: String = key.extract()?;
+            let comparisons = comparisons.cast::<PyDict>()?;
+            if comparisons.is_empty() {
+                return Err(ImplicaError::InvalidQuery {
+                    query: self.to_string(),
+                    reason: format!(
+                        "{} over property '{}' needs at least one comparison",
+                        quantifier.to_string(),
+                        key
+                    ),
+                    context: Some(ctx!("query - quantified predicates")),
+                }
+                .into())
+                .into_py_result();
+            }
+
+            let mut parsed = Vec::with_capacity(comparisons.len());
+            for (operator, operand) in comparisons.iter() {
+                let operator: String = operator.extract()?;
+                let comparison = match Comparison::from_string(&operator) {
+                    Some(comparison) => comparison,
+                    None => {
+                        return Err(ImplicaError::InvalidQuery {
+                            query: self.to_string(),
+                            reason: format!(
+                                "unknown comparison '{}' over property '{}', expected one of ==, !=, <, <=, >, >=",
+                                operator, key
+                            ),
+                            context: Some(ctx!("query - quantified predicates")),
+                        }
+                        .into())
+                        .into_py_result()
+                    }
+                };
+                let operand = py_to_rhai(&operand)
+                    .attach(ctx!("query - quantified predicates"))
+                    .into_py_result()?;
+
+                parsed.push((comparison, operand));
+            }
+
+            out.push(QuantifiedPredicate {
+                key,
+                quantifier,
+                comparisons: parsed,
+            });
+        }
+
+        Ok(out)
+    }
+
+    /// Parses the items of RETURN. A single list of items is accepted as well, e.g.
+    /// `return_([("n.score", 0)])`.
+    fn parse_projections(&self, items: &[Bound<PyAny>]) -> PyResult<Vec<Projection>> {
+        let mut projections: Vec<Projection> = Vec::with_capacity(items.len());
+
+        for item in items.iter() {
+            match item.cast::<PyList>() {
+                Ok(list) => {
+                    for inner in list.iter() {
+                        projections.push(self.parse_projection(&inner)?);
+                    }
+                }
+                Err(_) => projections.push(self.parse_projection(item)?),
+            }
+        }
+
+        Ok(projections)
+    }
+
+    /// Parses an item of RETURN: a variable, a property path, or a `(path, default)` pair.
+    fn parse_projection(&self, item: &Bound<PyAny>) -> PyResult<Projection> {
+        let (key, default) = match item.extract::<String>() {
+            Ok(key) => (key, None),
+            Err(_) => {
+                let (key, default) = item.extract::<(String, Bound<PyAny>)>()?;
+                (key, Some(Arc::new(default.unbind())))
+            }
+        };
+
+        match key.split_once('.') {
+            Some((variable, path)) if !path.is_empty() => {
+                self.validate_variables(&[variable.to_string()], ctx!("query - return"))
+                    .into_py_result()?;
+
+                Ok(Projection::Property {
+                    variable: variable.to_string(),
+                    path: path.to_string(),
+                    key,
+                    default,
+                })
+            }
+            _ => {
+                if default.is_some() {
+                    return Err(ImplicaError::InvalidQuery {
+                        query: self.to_string(),
+                        reason: format!(
+                            "a default can only be given for a property, not for '{}'",
+                            key
+                        ),
+                        context: Some(ctx!("query - return")),
+                    }
+                    .into())
+                    .into_py_result();
+                }
+
+                self.validate_variables(std::slice::from_ref(&key), ctx!("query - return"))
+                    .into_py_result()?;
+
+                Ok(Projection::Variable(key))
+            }
+        }
+    }
+
+    /// Computes the items of RETURN for a row. `evaluator` is needed by CASE items only.
+    fn collect_projected_row(
+        &self,
+        r#match: &Match,
+        projections: &[Projection],
+        evaluator: Option<&Evaluator>,
+    ) -> ImplicaResult<Vec<Projected>> {
+        let mut row = Vec::with_capacity(projections.len());
+
+        for projection in projections.iter() {
+            match projection {
+                Projection::Variable(variable) => {
+                    let mut map = self
+                        .collect_row(r#match, std::slice::from_ref(variable))
+                        .attach(ctx!("query - collect projected row"))?;
+
+                    match map.remove(variable) {
+                        Some(reference) => row.push(Projected::Element(reference)),
+                        None => row.push(Projected::Missing),
+                    }
+                }
+                Projection::Property { variable, path, .. } => {
+                    let properties = match r#match.get(variable) {
+                        Some(MatchElement::Node(uid)) => self.graph.node_properties(&uid),
+                        Some(MatchElement::Edge(uid)) => self.graph.edge_properties(&uid),
+                        Some(_) => {
+                            return Err(ImplicaError::InvalidQuery {
+                                query: self.to_string(),
+                                reason: format!(
+                                    "'{}' is not a node or an edge, so it has no properties",
+                                    variable
+                                ),
+                                context: Some(ctx!("query - collect projected row")),
+                            }
+                            .into())
+                        }
+                        None => {
+                            return Err(ImplicaError::VariableNotFound {
+                                name: variable.clone(),
+                                context: Some(ctx!("query - collect projected row")),
+                            }
+                            .into())
+                        }
+                    }
+                    .attach(ctx!("query - collect projected row"))?;
+
+                    match properties
+                        .get_path(path)
+                        .attach(ctx!("query - collect projected row"))?
+                    {
+                        Some(value) if !value.is_unit() => row.push(Projected::Value(value)),
+                        _ => row.push(Projected::Missing),
+                    }
+                }
+                Projection::Case {
+                    expression, cases, ..
+                } => {
+                    let evaluator = evaluator.ok_or(ImplicaError::RuntimeError {
+                        message: "a CASE item was projected without an evaluator".to_string(),
+                        context: Some(ctx!("query - collect projected row")),
+                    })?;
+
+                    let mut scope = self
+                        .row_scope(r#match)
+                        .attach(ctx!("query - collect projected row"))?;
+                    let value = evaluator
+                        .eval_value(&mut scope, expression)
+                        .attach(ctx!("query - collect projected row"))?;
+
+                    match cases.iter().find(|(case, _)| compare_values(case, &value)) {
+                        Some((_, result)) => row.push(Projected::Value(result.clone())),
+                        None => row.push(Projected::Missing),
+                    }
+                }
+            }
+        }
+
+        Ok(row)
+    }
+
+    /// Returns the rows with only the given variables, as RETURN with plain names does.
+    pub(crate) fn return_variables<'py>(
+        &mut self,
+        py: Python<'py>,
+        variables: Vec<String>,
+        as_: &str,
+    ) -> PyResult<Bound<'py, PyList>> {
+        self.validate_variables(&variables, ctx!("query - return"))
+            .into_py_result()?;
+
+        let projections = variables.into_iter().map(Projection::Variable).collect();
+        self.return_projections(py, projections, as_)
+    }
+
+    fn return_projections<'py>(
+        &mut self,
+        py: Python<'py>,
+        projections: Vec<Projection>,
+        as_: &str,
+    ) -> PyResult<Bound<'py, PyList>> {
+        let keys: Vec<String> = projections.iter().map(|p| p.key().to_string()).collect();
+
+        // Rows are dicts by default; a namedtuple class is built once and filled per row
+        let row_type = match as_ {
+            "dict" => None,
+            "namedtuple" => Some(
+                py.import("collections")?
+                    .getattr("namedtuple")?
+                    .call1(("Row", keys.clone()))?,
+            ),
+            other => {
+                return Err(ImplicaError::InvalidQuery {
+                    query: self.to_string(),
+                    reason: format!(
+                        "rows can be returned as 'dict' or 'namedtuple', not '{}'",
+                        other
+                    ),
+                    context: Some(ctx!("query - return")),
+                }
+                .into())
+                .into_py_result()
+            }
+        };
+
+        let results = self.project_rows(py, &projections)?;
+
+        let py_results = PyList::empty(py);
+
+        for row in results {
+            let mut fields: Vec<Bound<'py, PyAny>> = Vec::with_capacity(row.len());
+
+            for (value, projection) in row.into_iter().zip(projections.iter()) {
+                fields.push(match value {
+                    Projected::Element(reference) => reference.into_pyobject(py)?,
+                    Projected::Value(value) => rhai_to_py(value, py)
+                        .attach(ctx!("query - return"))
+                        .into_py_result()?,
+                    Projected::Missing => match projection {
+                        Projection::Property {
+                            default: Some(default),
+                            ..
+                        }
+                        | Projection::Case {
+                            default: Some(default),
+                            ..
+                        } => default.bind(py).clone(),
+                        _ => py.None().into_bound(py),
+                    },
+                });
+            }
+
+            match row_type {
+                Some(ref row_type) => {
+                    py_results.append(row_type.call1(PyTuple::new(py, fields)?)?)?;
+                }
+                None => {
+                    let dict = PyDict::new(py);
+                    for (key, field) in keys.iter().zip(fields) {
+                        dict.set_item(key, field)?;
+                    }
+                    py_results.append(dict)?;
+                }
+            }
+        }
+
+        Ok(py_results)
+    }
+
+    /// Executes the query and computes the items of RETURN for every row, in order.
+    fn project_rows(
+        &mut self,
+        py: Python<'_>,
+        projections: &[Projection],
+    ) -> PyResult<Vec<Vec<Projected>>> {
+        let mset = self
+            .execute_operations()
+            .attach(ctx!("query - project rows"))
+            .into_py_result()?;
+
+        let evaluator = if projections
+            .iter()
+            .any(|p| matches!(p, Projection::Case { .. }))
+        {
+            Some(
+                self.evaluator()
+                    .attach(ctx!("query - project rows"))
+                    .into_py_result()?,
+            )
+        } else {
+            None
+        };
+
+        // Registered Python functions may be called by CASE expressions from the worker
+        // threads, so the GIL is released while the rows are projected.
+        py.detach(|| {
+            ordered_rows(&mset)
+                .into_par_iter()
+                .map(|(_prev_uid, r#match)| {
+                    self.collect_projected_row(&r#match, projections, evaluator.as_ref())
+                })
+                .collect::<ImplicaResult<Vec<_>>>()
+        })
+        .into_py_result()
+    }
+
+    fn collect_row(
+        &self,
+        r#match: &Match,
+        variables: &[String],
+    ) -> ImplicaResult<HashMap<String, Reference>> {
+        let mut map = HashMap::new();
+
+        for v in variables.iter() {
+            if let Some(element) = r#match.get(v) {
+                map.insert(v.clone(), self.element_reference(element));
+            } else {
+                return Err(ImplicaError::VariableNotFound {
+                    name: v.clone(),
+                    context: Some(ctx!("query return - data collection").to_string()),
+                }
+                .into());
+            }
+        }
+
+        Ok(map)
+    }
+
+    /// Returns the rows of the query restricted to the variables named in any expected row.
+    fn return_expected_variables<'py>(
+        &mut self,
+        py: Python<'py>,
+        expected_rows: &[Bound<'py, PyDict>],
+    ) -> PyResult<Bound<'py, PyList>> {
+        let mut variables: Vec<String> = Vec::new();
+        for row in expected_rows.iter() {
+            for key in row.keys().iter() {
+                let key: String = key.extract()?;
+                if !variables.contains(&key) {
+                    variables.push(key);
+                }
+            }
+        }
+
+        self.return_variables(py, variables, "dict")
+    }
+
+    /// Executes the query on first use and returns its rows with every variable bound in
+    /// them, reusing them until more operations are added.
+    fn result_rows(&mut self) -> ImplicaResult<Arc<Vec<Row>>> {
+        if let Some((operations, ref rows)) = self.rows {
+            if operations == self.operations.len() {
+                return Ok(rows.clone());
+            }
+        }
+
+        let mset = self
+            .execute_operations()
+            .attach(ctx!("query - result rows"))?;
+
+        let rows = Arc::new(
+            ordered_rows(&mset)
+                .into_iter()
+                .map(|(_prev_uid, r#match)| {
+                    r#match
+                        .elements()
+                        .into_iter()
+                        .map(|(name, element)| (name, self.element_reference(element)))
+                        .collect()
+                })
+                .collect::<Vec<_>>(),
+        );
+
+        self.rows = Some((self.operations.len(), rows.clone()));
+        Ok(rows)
+    }
+
+    fn element_reference(&self, element: MatchElement) -> Reference {
+        match element {
+            MatchElement::Edge(uid) => Reference::Edge(EdgeRef::new(self.graph.clone(), uid)),
+            MatchElement::Node(uid) => Reference::Node(NodeRef::new(self.graph.clone(), uid)),
+            MatchElement::Term(uid) => Reference::Term(TermRef::new(self.graph.clone(), uid)),
+            MatchElement::Type(uid) => Reference::Type(TypeRef::new(self.graph.clone(), uid)),
+            MatchElement::List(elements) => Reference::List(
+                elements
+                    .into_iter()
+                    .map(|element| self.element_reference(element))
+                    .collect(),
+            ),
+            MatchElement::Value(value) => Reference::Value(value),
+        }
+    }
+
+    fn element_to_string(&self, element: MatchElement) -> ImplicaResult<String> {
+        match element {
+            MatchElement::Node(uid) => self.graph.node_to_string(&uid),
+            MatchElement::Edge(uid) => self.graph.edge_to_string(&uid),
+            MatchElement::Type(uid) => self.graph.type_to_string(&uid),
+            MatchElement::Term(uid) => self.graph.term_to_string(&uid),
+            MatchElement::List(elements) => {
+                let mut items = elements
+                    .into_iter()
+                    .map(|element| self.element_to_string(element))
+                    .collect::<ImplicaResult<Vec<_>>>()?;
+                items.sort();
+                Ok(format!("[{}]", items.join(", ")))
+            }
+            MatchElement::Value(value) => Ok(value.0.to_string()),
+        }
+    }
+}
+
+#[pymethods]
+impl Query {
+    #[pyo3(signature = (pattern, merge_properties=false, property_fn=None, property_fn_wins=false, overwrite=false))]
+    pub fn create(
+        &mut self,
+        pattern: String,
+        merge_properties: bool,
+        property_fn: Option<Py<PyAny>>,
+        property_fn_wins: bool,
+        overwrite: bool,
+    ) -> PyResult<Query> {
         let path_pattern = PathPattern::new(pattern)
+            .attach(ctx!("query - create"))
+            .into_py_result()?;
+
+        self.operations.push(QueryOperation::Create(
+            path_pattern,
+            CreateOptions {
+                merge_properties,
+                overwrite,
+                property_fn: property_fn.map(Arc::new),
+                property_fn_wins,
+                creations: None,
+            },
+        ));
+
+        Ok(self.clone())
+    }
+
+    /// Adds a CREATE and executes the query right away, returning the requested variables.
+    /// Without variables, every node and edge variable of the pattern is returned.
+    #[pyo3(signature = (pattern, *variables, merge_properties=false, property_fn=None, property_fn_wins=false, overwrite=false))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_return<'py>(
+        &mut self,
+        py: Python<'py>,
+        pattern: String,
+        variables: Vec<String>,
+        merge_properties: bool,
+        property_fn: Option<Py<PyAny>>,
+        property_fn_wins: bool,
+        overwrite: bool,
+    ) -> PyResult<Bound<'py, PyList>> {
+        self.create(
+            pattern,
+            merge_properties,
+            property_fn,
+            property_fn_wins,
+            overwrite,
+        )?;
+
+        let mut variables = variables;
+        if variables.is_empty() {
+            if let Some(QueryOperation::Create(pattern, _)) = self.operations.last() {
+                let created = pattern
+                    .nodes
+                    .iter()
+                    .filter_map(|node| node.variable.as_ref())
+                    .chain(
+                        pattern
+                            .edges
+                            .iter()
+                            .filter_map(|edge| edge.variable.as_ref()),
+                    );
+
+                for var in created {
+                    if !variables.contains(var) {
+                        variables.push(var.clone());
+                    }
+                }
+            }
+        }
+
+        self.return_variables(py, variables, "dict")
+    }
+
+    /// Adds a MATCH of `pattern`, or, given `edge` and `incident` instead, binds `edge` to
+    /// every edge starting or ending at the node `incident`, whichever its direction.
+    /// `from_node` and `to_node` narrow this to the edges leaving or entering the node.
+    ///
+    /// A pattern may list comma-separated components, e.g. `(a:Person), (b:Company)`. They
+    /// are matched in turn, so components that share no variable give every combination of
+    /// their rows.
+    ///
+    /// `property_match` sets how the properties written in the node patterns compare with a
+    /// node's: `"superset"` (the default) lets the node have more, `"exact"` requires the same
+    /// keys and `"subset"` requires every property of the node to be listed with its value.
+    ///
+    /// `node` binds a variable to every node, like the pattern `(node)`. `any_property` and
+    /// `all_property` map list properties of a single node pattern to comparisons, such as
+    /// `{"scores": {">": 90}}`, that some or every element must satisfy. `missing_properties`
+    /// lists keys the node of a single node pattern must not have.
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (pattern=None, edge=None, incident=None, from_node=None, to_node=None, property_match="superset", node=None, any_property=None, all_property=None, missing_properties=None))]
+    pub fn r#match(
+        &mut self,
+        pattern: Option<String>,
+        edge: Option<String>,
+        incident: Option<String>,
+        from_node: Option<String>,
+        to_node: Option<String>,
+        property_match: &str,
+        node: Option<String>,
+        any_property: Option<&Bound<PyDict>>,
+        all_property: Option<&Bound<PyDict>>,
+        missing_properties: Option<Vec<String>>,
+    ) -> PyResult<Query> {
+        let property_match = PropertyMatch::from_string(property_match)
+            .attach(ctx!("query - match"))
+            .into_py_result()?;
+
+        let endpoint = match (incident, from_node, to_node) {
+            (None, None, None) => None,
+            (Some(node), None, None) => Some((node, "-", "-")),
+            (None, Some(node), None) => Some((node, "-", "->")),
+            (None, None, Some(node)) => Some((node, "<-", "-")),
+            _ => {
+                return Err(ImplicaError::InvalidQuery {
+                    query: self.to_string(),
+                    reason: "MATCH takes only one of incident, from_node and to_node".to_string(),
+                    context: Some(ctx!("query - match")),
+                }
+                .into())
+                .into_py_result()
+            }
+        };
+
+        let pattern =
+            match (pattern, edge, endpoint, node) {
+                (Some(pattern), None, None, None) => pattern,
+                (None, None, None, Some(node)) => {
+                    self.validate_variables(std::slice::from_ref(&node), ctx!("query - match"))
+                        .into_py_result()?;
+                    format!("({})", node)
+                }
+                (None, Some(edge), Some((node, left, right)), None) => {
+                    self.validate_variables(&[edge.clone(), node.clone()], ctx!("query - match"))
+                        .into_py_result()?;
+                    format!("({}){}[{}]{}()", node, left, edge, right)
+                }
+                _ => return Err(ImplicaError::InvalidQuery {
+                    query: self.to_string(),
+                    reason:
+                        "MATCH takes either a pattern, a node or both an edge and an incident node"
+                            .to_string(),
+                    context: Some(ctx!("query - match")),
+                }
+                .into())
+                .into_py_result(),
+            };
+
+        let mut components = PathPattern::parse_components(pattern.clone())
             .attach(ctx!("query - match"))
             .into_py_result()?;
-        self.operations.push(QueryOperation::Match(path_pattern));
+        for component in components.iter_mut() {
+            component.set_property_match(property_match);
+        }
+
+        let mut quantified = Vec::new();
+        for (predicates, quantifier) in [
+            (any_property, Quantifier::Any),
+            (all_property, Quantifier::All),
+        ] {
+            if let Some(predicates) = predicates {
+                quantified.extend(self.quantified_predicates(predicates, quantifier)?);
+            }
+        }
+        if !quantified.is_empty() {
+            match components.as_mut_slice() {
+                [path_pattern] => path_pattern
+                    .add_quantified(quantified)
+                    .attach(ctx!("query - match"))
+                    .into_py_result()?,
+                _ => {
+                    return Err(ImplicaError::InvalidPattern {
+                        pattern,
+                        reason: "quantified property predicates need a pattern of a single node"
+                            .to_string(),
+                    }
+                    .into())
+                    .into_py_result()
+                }
+            }
+        }
+
+        if let Some(keys) = missing_properties {
+            match components.as_mut_slice() {
+                [path_pattern] => path_pattern
+                    .add_missing_properties(keys)
+                    .attach(ctx!("query - match"))
+                    .into_py_result()?,
+                _ => {
+                    return Err(ImplicaError::InvalidPattern {
+                        pattern,
+                        reason: "missing_properties needs a pattern of a single node".to_string(),
+                    }
+                    .into())
+                    .into_py_result()
+                }
+            }
+        }
+
+        // Components are matched in turn, joining on shared variables
+        self.operations
+            .extend(components.into_iter().map(QueryOperation::Match));
+        Ok(self.clone())
+    }
+
+    /// Binds `variable` to every node reachable within `k` hops of any of the seed nodes,
+    /// the seeds themselves included. `direction` is `"forward"`, `"backward"` or `"any"`.
+    #[pyo3(signature = (seed, k, direction="forward".to_string(), variable="n".to_string()))]
+    pub fn match_within(
+        &mut self,
+        seed: Vec<Bound<PyAny>>,
+        k: usize,
+        direction: String,
+        variable: String,
+    ) -> PyResult<Query> {
+        self.validate_variables(
+            std::slice::from_ref(&variable),
+            ctx!("query - match within"),
+        )
+        .into_py_result()?;
+
+        let direction = CompiledDirection::from_string(&direction)
+            .attach(ctx!("query - match within"))
+            .into_py_result()?;
+
+        let seeds = seed
+            .iter()
+            .map(PyGraph::node_uid)
+            .collect::<PyResult<Vec<Uid>>>()?;
+
+        self.operations
+            .push(QueryOperation::MatchWithin(variable, seeds, k, direction));
+        Ok(self.clone())
+    }
+
+    /// Returns a new graph holding the nodes within `radius` hops of `node`, the node
+    /// included, and every edge among them. `direction` is `"any"` (the default),
+    /// `"forward"` or `"backward"`.
+    #[pyo3(signature = (node, radius=1, direction="any".to_string()))]
+    pub fn ego(&self, node: &Bound<PyAny>, radius: usize, direction: String) -> PyResult<PyGraph> {
+        let direction = CompiledDirection::from_string(&direction)
+            .attach(ctx!("query - ego"))
+            .into_py_result()?;
+        let center = PyGraph::node_uid(node)?;
+
+        let nodes: HashSet<Uid> = self
+            .graph
+            .reachable_within(&[center], radius, &direction)
+            .attach(ctx!("query - ego"))
+            .into_py_result()?
+            .into_iter()
+            .collect();
+
+        let subgraph = self
+            .graph
+            .induced_subgraph(&nodes)
+            .attach(ctx!("query - ego"))
+            .into_py_result()?;
+
+        Ok(PyGraph::from_graph(subgraph))
+    }
+
+    #[pyo3(signature=(*variables))]
+    pub fn remove(&mut self, variables: Vec<String>) -> PyResult<Query> {
+        self.validate_variables(&variables, ctx!("query - remove"))
+            .into_py_result()?;
+
+        self.operations.push(QueryOperation::Remove(variables));
+        Ok(self.clone())
+    }
+
+    #[pyo3(signature = (*variables, cascade=false))]
+    pub fn clear_term(&mut self, variables: Vec<String>, cascade: bool) -> PyResult<Query> {
+        self.validate_variables(&variables, ctx!("query - clear term"))
+            .into_py_result()?;
+
+        self.operations
+            .push(QueryOperation::ClearTerm(variables, cascade));
+        Ok(self.clone())
+    }
+
+    #[pyo3(signature = (variable, properties, overwrite=true))]
+    pub fn set(
+        &mut self,
+        variable: String,
+        properties: &Bound<PyAny>,
+        overwrite: bool,
+    ) -> PyResult<Query> {
+        self.validate_variables(std::slice::from_ref(&variable), ctx!("query - set"))
+            .into_py_result()?;
+
+        let map = PropertyMap::new(properties)
+            .attach(ctx!("query - set"))
+            .into_py_result()?;
+
+        self.operations
+            .push(QueryOperation::Set(variable, map, overwrite));
+        Ok(self.clone())
+    }
+
+    /// Sets a property to the value of an expression evaluated for every row, e.g.
+    /// `set_expr("a.score", "a.score * 1.1")`. The expression is written like a WHERE
+    /// expression and sees the properties as they were before the operation.
+    pub fn set_expr(&mut self, property: String, expression: String) -> PyResult<Query> {
+        let (variable, key) = match property.split_once('.') {
+            Some((variable, key)) if !key.is_empty() && !key.contains('.') => {
+                (variable.to_string(), key.to_string())
+            }
+            _ => {
+                return Err(ImplicaError::InvalidQuery {
+                    query: self.to_string(),
+                    reason: format!("expected a property as 'variable.key', got '{}'", property),
+                    context: Some(ctx!("query - set expr")),
+                }
+                .into())
+                .into_py_result()
+            }
+        };
+
+        self.validate_variables(std::slice::from_ref(&variable), ctx!("query - set expr"))
+            .into_py_result()?;
+
+        self.operations
+            .push(QueryOperation::SetExpr(variable, key, expression));
+        Ok(self.clone())
+    }
+
+    #[pyo3(signature = (enabled=true))]
+    pub fn cached(&mut self, enabled: bool) -> Query {
+        self.cached = enabled;
+        self.clone()
+    }
+
+    #[pyo3(signature = (enabled=true))]
+    pub fn optimize(&mut self, enabled: bool) -> Query {
+        self.optimize = enabled;
+        self.clone()
+    }
+
+    /// Makes the query fail with a ValueError as soon as a MATCH leaves more than `count`
+    /// rows, instead of carrying an exploding cartesian product on to later clauses.
+    pub fn max_intermediate_rows(&mut self, count: usize) -> Query {
+        self.max_intermediate_rows = Some(count);
+        self.clone()
+    }
+
+    pub fn run_on(&self, graph: &PyGraph) -> Query {
+        let mut query = graph.query();
+        query.operations = self.operations.clone();
+        query.optimize = self.optimize;
+        query.cached = self.cached;
+        query.functions = self.functions.clone();
+        query.max_intermediate_rows = self.max_intermediate_rows;
+        query
+    }
+
+    pub fn filter(&mut self, callback: Py<PyAny>) -> Query {
+        self.operations
+            .push(QueryOperation::Filter(Arc::new(callback)));
+        self.clone()
+    }
+
+    /// Projects every row onto the given bindings, each either a variable name or a
+    /// `(source, alias)` pair carrying the binding forward under a new name.
+    pub fn with_(&mut self, items: Vec<Bound<PyAny>>) -> PyResult<Query> {
+        let mut projection: Vec<(String, String)> = Vec::with_capacity(items.len());
+
+        for item in items.iter() {
+            let (source, alias) = match item.extract::<String>() {
+                Ok(name) => (name.clone(), name),
+                Err(_) => item.extract::<(String, String)>()?,
+            };
+
+            self.validate_variables(&[source.clone(), alias.clone()], ctx!("query - with"))
+                .into_py_result()?;
+
+            if projection.iter().any(|(_, a)| a == &alias) {
+                return Err(ImplicaError::InvalidQuery {
+                    query: self.to_string(),
+                    reason: format!("variable '{}' is projected more than once", alias),
+                    context: Some(ctx!("query - with")),
+                }
+                .into())
+                .into_py_result();
+            }
+
+            projection.push((source, alias));
+        }
+
+        self.operations.push(QueryOperation::With(projection));
+        Ok(self.clone())
+    }
+
+    /// Binds `as_` (by default `collect`) in every row to the list of elements `collect`
+    /// takes across the matches of `pattern` from the row's `variable`, like a pattern
+    /// comprehension.
+    #[pyo3(signature = (variable, pattern, collect, as_=None))]
+    pub fn collect_pattern(
+        &mut self,
+        variable: String,
+        pattern: String,
+        collect: String,
+        as_: Option<String>,
+    ) -> PyResult<Query> {
+        let alias = as_.unwrap_or_else(|| collect.clone());
+        self.validate_variables(
+            &[variable.clone(), collect.clone(), alias.clone()],
+            ctx!("query - collect pattern"),
+        )
+        .into_py_result()?;
+
+        let path_pattern = PathPattern::new(pattern)
+            .attach(ctx!("query - collect pattern"))
+            .into_py_result()?;
+
+        let identifiers = path_pattern.identifiers();
+        for name in [&variable, &collect] {
+            if !identifiers.contains(name) {
+                return Err(ImplicaError::InvalidQuery {
+                    query: self.to_string(),
+                    reason: format!("'{}' does not appear in the pattern {}", name, path_pattern),
+                    context: Some(ctx!("query - collect pattern")),
+                }
+                .into())
+                .into_py_result();
+            }
+        }
+
+        self.operations.push(QueryOperation::CollectPattern(
+            variable,
+            path_pattern,
+            collect,
+            alias,
+        ));
+        Ok(self.clone())
+    }
+
+    /// Makes a Python callable available to WHERE and ORDER BY expressions under `name`.
+    pub fn register_function(&mut self, name: String, callback: Py<PyAny>) -> PyResult<Query> {
+        validate_variable_name(&name)
+            .attach(ctx!("query - register function"))
+            .into_py_result()?;
+
+        let callable = Python::attach(|py| callback.bind(py).is_callable());
+        if !callable {
+            return Err(ImplicaError::InvalidQuery {
+                query: self.to_string(),
+                reason: format!("the function registered as '{}' is not callable", name),
+                context: Some(ctx!("query - register function")),
+            }
+            .into())
+            .into_py_result();
+        }
+
+        self.functions.retain(|(other, _)| other != &name);
+        self.functions.push((name, Arc::new(callback)));
+        Ok(self.clone())
+    }
+
+    pub fn where_(&mut self, expression: String) -> Query {
+        self.operations.push(QueryOperation::Where(expression));
+        self.clone()
+    }
+
+    /// Binds `variable` in every row to the value of `expression`, evaluated once per row
+    /// like a WHERE expression, for later clauses to use.
+    pub fn let_(&mut self, variable: String, expression: String) -> PyResult<Query> {
+        self.validate_variables(std::slice::from_ref(&variable), ctx!("query - let"))
+            .into_py_result()?;
+
+        self.operations
+            .push(QueryOperation::Let(variable, expression));
+        Ok(self.clone())
+    }
+
+    /// Keeps only the rows in which the given variables are bound to pairwise different
+    /// elements.
+    pub fn where_distinct(&mut self, variables: Vec<String>) -> PyResult<Query> {
+        self.validate_variables(&variables, ctx!("query - where distinct"))
+            .into_py_result()?;
+
+        if variables.len() < 2 {
+            return Err(ImplicaError::InvalidQuery {
+                query: self.to_string(),
+                reason: "WHERE DISTINCT needs at least two variables".to_string(),
+                context: Some(ctx!("query - where distinct")),
+            }
+            .into())
+            .into_py_result();
+        }
+
+        self.operations
+            .push(QueryOperation::WhereDistinct(variables));
         Ok(self.clone())
     }
 
-    #[pyo3(signature=(*variables))]
-    pub fn remove(&mut self, variables: Vec<String>) -> Query {
-        self.operations.push(QueryOperation::Remove(variables));
+    /// Sorts the rows by one or more expressions, evaluated per row like a WHERE clause.
+    #[pyo3(signature = (*expressions, descending=false))]
+    pub fn order_by(&mut self, expressions: Vec<String>, descending: bool) -> PyResult<Query> {
+        if expressions.is_empty() {
+            return Err(ImplicaError::InvalidQuery {
+                query: self.to_string(),
+                reason: "ORDER BY needs at least one expression".to_string(),
+                context: Some(ctx!("query - order by")),
+            }
+            .into())
+            .into_py_result();
+        }
+
+        self.operations
+            .push(QueryOperation::OrderBy(expressions, descending));
+        Ok(self.clone())
+    }
+
+    /// Keeps at most `count` of the rows matched so far.
+    pub fn limit(&mut self, count: usize) -> Query {
+        self.operations.push(QueryOperation::Limit(count));
         self.clone()
     }
 
-    #[pyo3(signature = (variable, properties, overwrite=true))]
-    pub fn set(
-        &mut self,
-        variable: String,
-        properties: &Bound<PyAny>,
-        overwrite: bool,
-    ) -> PyResult<Query> {
-        let map = PropertyMap::new(properties)
-            .attach(ctx!("query - set"))
+    /// Executes the query and returns the `k` rows whose value of `expression` (e.g.
+    /// `"a.score"`) is closest to `target`, nearest first, each a dict of every variable bound
+    /// in it. Rows missing the value come last.
+    #[pyo3(signature = (expression, target, k))]
+    pub fn nearest(&mut self, expression: String, target: f64, k: usize) -> PyResult<Vec<Row>> {
+        let mset = self
+            .execute_operations()
+            .attach(ctx!("query - nearest"))
             .into_py_result()?;
 
-        self.operations
-            .push(QueryOperation::Set(variable, map, overwrite));
-        Ok(self.clone())
+        // As for ORDER BY, the GIL is released for registered Python functions.
+        let rows =
+            Python::attach(|py| py.detach(|| self.nearest_rows(&expression, target, k, mset)))
+                .into_py_result()?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r#match| {
+                r#match
+                    .elements()
+                    .into_iter()
+                    .map(|(name, element)| (name, self.element_reference(element)))
+                    .collect()
+            })
+            .collect())
+    }
+
+    /// Describes the matched rows in a form that does not depend on uids or execution order:
+    /// one line per row listing its bindings by name, with the lines sorted.
+    pub fn canonical(&mut self) -> PyResult<String> {
+        let mset = self
+            .execute_operations()
+            .attach(ctx!("query - canonical"))
+            .into_py_result()?;
+
+        let mut rows: Vec<String> = mset
+            .par_iter()
+            .map(|entry| {
+                let (_prev_uid, r#match) = entry.value().clone();
+
+                let mut elements = r#match.elements();
+                elements.sort_by(|a, b| a.0.cmp(&b.0));
+
+                let bindings = elements
+                    .into_iter()
+                    .map(|(variable, element)| {
+                        let repr = self
+                            .element_to_string(element)
+                            .attach(ctx!("query - canonical"))?;
+
+                        Ok(format!("{}: {}", variable, repr))
+                    })
+                    .collect::<ImplicaResult<Vec<_>>>()?;
+
+                Ok(format!("{{{}}}", bindings.join(", ")))
+            })
+            .collect::<ImplicaResult<Vec<_>>>()
+            .into_py_result()?;
+
+        rows.sort();
+        Ok(rows.join("\n"))
+    }
+
+    pub fn exists(&mut self) -> PyResult<bool> {
+        // A lone node pattern can stop at the first matching node; anything else needs the
+        // full match set.
+        if let [QueryOperation::Match(pattern)] = self.operations.as_slice() {
+            if let ([node], []) = (pattern.nodes.as_slice(), pattern.edges.as_slice()) {
+                return self
+                    .graph
+                    .node_pattern_exists(node)
+                    .attach(ctx!("query - exists"))
+                    .into_py_result();
+            }
+        }
+
+        let mset = self
+            .execute_operations()
+            .attach(ctx!("query - exists"))
+            .into_py_result()?;
+
+        Ok(!mset.is_empty())
     }
 
     pub fn execute(&mut self) -> PyResult<()> {
@@ -298,52 +2483,461 @@ impl Query {
         Ok(())
     }
 
-    #[pyo3(signature=(*variables))]
+    /// Runs the query, matches `pattern` and deletes every node and edge it matched, whether
+    /// bound to a variable or not. Returns how many were deleted.
+    ///
+    /// Matched edges go first, then the matched nodes, detached from their remaining edges.
+    /// Edges removed only because one of their endpoints was deleted are not counted.
+    /// Variable-length edges are rejected, as they match paths rather than single edges.
+    pub fn delete_matching(&mut self, pattern: String) -> PyResult<usize> {
+        let mut path_pattern = PathPattern::new(pattern.clone())
+            .attach(ctx!("query - delete matching"))
+            .into_py_result()?;
+
+        if path_pattern.edges.iter().any(|edge| edge.hops.is_some()) {
+            return Err(ImplicaError::InvalidPattern {
+                pattern,
+                reason: "delete_matching does not support variable-length edges".to_string(),
+            }
+            .into())
+            .into_py_result();
+        }
+
+        // Unnamed elements get reserved names so the rows record them as well
+        for (idx, node) in path_pattern.nodes.iter_mut().enumerate() {
+            node.variable
+                .get_or_insert_with(|| format!("__delete_node_{}", idx));
+        }
+        for (idx, edge) in path_pattern.edges.iter_mut().enumerate() {
+            edge.variable
+                .get_or_insert_with(|| format!("__delete_edge_{}", idx));
+        }
+
+        let node_variables: Vec<String> = path_pattern
+            .nodes
+            .iter()
+            .filter_map(|node| node.variable.clone())
+            .collect();
+        let edge_variables: Vec<String> = path_pattern
+            .edges
+            .iter()
+            .filter_map(|edge| edge.variable.clone())
+            .collect();
+
+        let mut query = self.clone();
+        query.operations.push(QueryOperation::Match(path_pattern));
+
+        let mset = query
+            .execute_operations()
+            .attach(ctx!("query - delete matching"))
+            .into_py_result()?;
+
+        let mut nodes: HashSet<Uid> = HashSet::new();
+        let mut edges: HashSet<(Uid, Uid)> = HashSet::new();
+
+        for entry in mset.iter() {
+            let (_, r#match) = entry.value();
+
+            for var in node_variables.iter() {
+                if let Some(MatchElement::Node(uid)) = r#match.get(var) {
+                    nodes.insert(uid);
+                }
+            }
+            for var in edge_variables.iter() {
+                if let Some(MatchElement::Edge(uid)) = r#match.get(var) {
+                    edges.insert(uid);
+                }
+            }
+        }
+
+        let mut deleted = 0;
+
+        for edge in edges.iter() {
+            if self
+                .graph
+                .remove_edge(edge)
+                .attach(ctx!("query - delete matching"))
+                .into_py_result()?
+                .is_some()
+            {
+                deleted += 1;
+            }
+        }
+        for node in nodes.iter() {
+            if self
+                .graph
+                .remove_node(node)
+                .attach(ctx!("query - delete matching"))
+                .into_py_result()?
+                .is_some()
+            {
+                deleted += 1;
+            }
+        }
+
+        self.graph
+            .notify_observers()
+            .attach(ctx!("query - delete matching"))
+            .into_py_result()?;
+
+        Ok(deleted)
+    }
+
+    /// Returns one row per match, each holding the requested items.
+    ///
+    /// An item is a variable name, a property path such as `"n.score"`, or a
+    /// `(path, default)` pair giving the value to use where the property is absent. A path
+    /// without a default yields `None` for those rows.
+    #[pyo3(signature=(*items, as_="dict"))]
     pub fn return_<'py>(
         &mut self,
         py: Python<'py>,
-        variables: Vec<String>,
+        items: Vec<Bound<'py, PyAny>>,
+        as_: &str,
+    ) -> PyResult<Bound<'py, PyList>> {
+        let projections = self.parse_projections(&items)?;
+
+        self.return_projections(py, projections, as_)
+    }
+
+    /// Returns one row per match like `return_`, adding under `as_` the value `cases` maps
+    /// the expression's value to, or `default` when no case equals it.
+    ///
+    /// The expression is written like a WHERE expression, so it can be a property such as
+    /// `"a.status"` or any computed value, e.g. `"a.age >= 18"` mapped from `True`/`False`.
+    #[pyo3(signature=(expression, cases, *items, default=None, as_="case"))]
+    pub fn return_case<'py>(
+        &mut self,
+        py: Python<'py>,
+        expression: String,
+        cases: &Bound<'py, PyDict>,
+        items: Vec<Bound<'py, PyAny>>,
+        default: Option<Bound<'py, PyAny>>,
+        as_: &str,
     ) -> PyResult<Bound<'py, PyList>> {
+        let mut projections = self.parse_projections(&items)?;
+
+        let cases = cases
+            .iter()
+            .map(|(case, value)| Ok((py_to_rhai(&case)?, py_to_rhai(&value)?)))
+            .collect::<ImplicaResult<Vec<_>>>()
+            .attach(ctx!("query - return case"))
+            .into_py_result()?;
+
+        projections.push(Projection::Case {
+            key: as_.to_string(),
+            expression,
+            cases,
+            default: default.map(|d| Arc::new(d.unbind())),
+        });
+
+        self.return_projections(py, projections, "dict")
+    }
+
+    /// Executes the query like `return_`, returning its plan, what each operation actually
+    /// did and the rows.
+    ///
+    /// The result is a dict with `plan`, the operations in execution order, `operations`, one
+    /// `{"operation", "rows", "seconds"}` dict per executed operation giving the rows left
+    /// after it and the time it took, `total_seconds` and `rows`. The match cache is
+    /// bypassed so that every operation really runs.
+    #[pyo3(signature=(*items, as_="dict"))]
+    pub fn explain_analyze<'py>(
+        &mut self,
+        py: Python<'py>,
+        items: Vec<Bound<'py, PyAny>>,
+        as_: &str,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let plan: Vec<String> = self.plan().iter().map(|op| op.to_string()).collect();
+
+        let trace = Arc::new(Mutex::new(Trace::new()));
+        let mut traced = self.clone();
+        traced.cached = false;
+        traced.trace = Some(trace.clone());
+
+        let start = Instant::now();
+        let rows = traced.return_(py, items, as_)?;
+        let total = start.elapsed();
+
+        let operations = PyList::empty(py);
+        let trace = trace
+            .lock()
+            .map_err(|e| {
+                ImplicaError::LockError {
+                    rw: "read".to_string(),
+                    message: e.to_string(),
+                    context: Some(ctx!("query - explain analyze")),
+                }
+                .into()
+            })
+            .into_py_result()?;
+        for (operation, count, elapsed) in trace.iter() {
+            let entry = PyDict::new(py);
+            entry.set_item("operation", operation)?;
+            entry.set_item("rows", count)?;
+            entry.set_item("seconds", elapsed.as_secs_f64())?;
+            operations.append(entry)?;
+        }
+
+        let analysis = PyDict::new(py);
+        analysis.set_item("plan", plan)?;
+        analysis.set_item("operations", operations)?;
+        analysis.set_item("total_seconds", total.as_secs_f64())?;
+        analysis.set_item("rows", rows)?;
+        Ok(analysis)
+    }
+
+    /// Number of rows matched by the query, which is executed on first use.
+    pub fn __len__(&mut self) -> PyResult<usize> {
+        let rows = self
+            .result_rows()
+            .attach(ctx!("query - len"))
+            .into_py_result()?;
+
+        Ok(rows.len())
+    }
+
+    /// Returns the row at an index, or a list of the rows in a slice, each a dict of every
+    /// variable bound in it. The query is executed on first use.
+    pub fn __getitem__<'py>(
+        &mut self,
+        py: Python<'py>,
+        index: &Bound<'py, PyAny>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let rows = self
+            .result_rows()
+            .attach(ctx!("query - get item"))
+            .into_py_result()?;
+
+        if let Ok(slice) = index.cast::<PySlice>() {
+            let indices = slice.indices(rows.len() as isize)?;
+            let selected = (0..indices.slicelength)
+                .map(|i| rows[(indices.start + i as isize * indices.step) as usize].clone())
+                .collect::<Vec<_>>();
+
+            return Ok(selected.into_pyobject(py)?.into_any());
+        }
+
+        let index: isize = index.extract()?;
+        let position = if index < 0 {
+            index + rows.len() as isize
+        } else {
+            index
+        };
+
+        if position < 0 || position as usize >= rows.len() {
+            return Err(PyIndexError::new_err(format!(
+                "row index {} out of range for {} rows",
+                index,
+                rows.len()
+            )));
+        }
+
+        Ok(rows[position as usize]
+            .clone()
+            .into_pyobject(py)?
+            .into_any())
+    }
+
+    /// Executes the query and returns an iterator over its rows, each a dict of the given
+    /// variables.
+    #[pyo3(signature=(*variables))]
+    pub fn iter_(&mut self, variables: Vec<String>) -> PyResult<ResultIterator> {
+        self.validate_variables(&variables, ctx!("query - iter"))
+            .into_py_result()?;
+
         let mset = self
             .execute_operations()
-            .attach(ctx!("query - return"))
+            .attach(ctx!("query - iter"))
             .into_py_result()?;
 
-        let results: Vec<HashMap<String, Reference>> = mset
-            .par_iter()
-            .map(|entry| {
-                let (_prev_uid, r#match) = entry.value().clone();
+        let rows = ordered_rows(&mset)
+            .into_par_iter()
+            .map(|(_prev_uid, r#match)| self.collect_row(&r#match, &variables))
+            .collect::<ImplicaResult<Vec<_>>>()
+            .attach(ctx!("query - iter"))
+            .into_py_result()?;
+
+        Ok(ResultIterator::new(rows))
+    }
 
-                let mut map = HashMap::new();
+    #[pyo3(signature=(*variables))]
+    pub fn first<'py>(
+        &mut self,
+        py: Python<'py>,
+        variables: Vec<String>,
+    ) -> PyResult<Option<Bound<'py, PyAny>>> {
+        self.validate_variables(&variables, ctx!("query - first"))
+            .into_py_result()?;
 
-                for v in variables.iter() {
-                    if let Some(element) = r#match.get(v) {
-                        let reference = match element {
-                            MatchElement::Edge(uid) => {
-                                Reference::Edge(EdgeRef::new(self.graph.clone(), uid))
-                            }
-                            MatchElement::Node(uid) => {
-                                Reference::Node(NodeRef::new(self.graph.clone(), uid))
-                            }
-                            MatchElement::Term(uid) => {
-                                Reference::Term(TermRef::new(self.graph.clone(), uid))
-                            }
-                            MatchElement::Type(uid) => {
-                                Reference::Type(TypeRef::new(self.graph.clone(), uid))
-                            }
-                        };
+        // Only the first row is kept, so a LIMIT 1 changes nothing but lets a lone pattern
+        // match stop scanning at its first row.
+        let mut limited = self.clone();
+        limited.operations.push(QueryOperation::Limit(1));
 
-                        map.insert(v.clone(), reference);
-                    } else {
+        let mset = limited
+            .execute_operations()
+            .attach(ctx!("query - first"))
+            .into_py_result()?;
+
+        let r#match = match ordered_rows(&mset).into_iter().next() {
+            Some((_prev_uid, r#match)) => r#match,
+            None => return Ok(None),
+        };
+
+        let row = self
+            .collect_row(&r#match, &variables)
+            .attach(ctx!("query - first"))
+            .into_py_result()?;
+
+        Ok(Some(row.into_pyobject(py)?.into_any()))
+    }
+
+    #[pyo3(signature=(*variables))]
+    pub fn one<'py>(
+        &mut self,
+        py: Python<'py>,
+        variables: Vec<String>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        self.validate_variables(&variables, ctx!("query - one"))
+            .into_py_result()?;
+
+        let mset = self
+            .execute_operations()
+            .attach(ctx!("query - one"))
+            .into_py_result()?;
+
+        if mset.len() != 1 {
+            return Err(ImplicaError::InvalidQuery {
+                query: self.to_string(),
+                reason: format!("expected exactly one row, got {}", mset.len()),
+                context: Some(ctx!("query - one")),
+            }
+            .into())
+            .into_py_result();
+        }
+
+        let r#match = match mset.iter().next() {
+            Some(entry) => entry.value().1.clone(),
+            None => {
+                return Err(ImplicaError::RuntimeError {
+                    message: "match set reported one row but yielded none".to_string(),
+                    context: Some(ctx!("query - one")),
+                }
+                .into())
+                .into_py_result()
+            }
+        };
+
+        let row = self
+            .collect_row(&r#match, &variables)
+            .attach(ctx!("query - one"))
+            .into_py_result()?;
+
+        Ok(row.into_pyobject(py)?.into_any())
+    }
+
+    pub fn assert_count(&mut self, expected: usize) -> PyResult<()> {
+        let mset = self
+            .execute_operations()
+            .attach(ctx!("query - assert count"))
+            .into_py_result()?;
+
+        if mset.len() != expected {
+            return Err(PyAssertionError::new_err(format!(
+                "{}\n\nexpected {} rows, got {}",
+                self,
+                expected,
+                mset.len()
+            )));
+        }
+
+        Ok(())
+    }
+
+    pub fn assert_returns<'py>(
+        &mut self,
+        py: Python<'py>,
+        expected_rows: Vec<Bound<'py, PyDict>>,
+    ) -> PyResult<()> {
+        let actual_rows = self.return_expected_variables(py, &expected_rows)?;
+        let (_, only_actual, only_expected) = pair_rows(&actual_rows, &expected_rows)?;
+
+        if !only_actual.is_empty() || !only_expected.is_empty() {
+            return Err(PyAssertionError::new_err(format!(
+                "{}\n\nexpected rows: {}\nactual rows:   {}",
+                self,
+                PyList::new(py, expected_rows.iter())?.repr()?,
+                actual_rows.repr()?
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Compares the rows of the query with the expected ones, as `assert_returns` does, and
+    /// reports the rows found only in the result, only in the expectation and in both.
+    pub fn compare_to<'py>(
+        &mut self,
+        py: Python<'py>,
+        expected_rows: Vec<Bound<'py, PyDict>>,
+    ) -> PyResult<Bound<'py, PyDict>> {
+        let actual_rows = self.return_expected_variables(py, &expected_rows)?;
+        let (in_both, only_actual, only_expected) = pair_rows(&actual_rows, &expected_rows)?;
+
+        let report = PyDict::new(py);
+        report.set_item("only_in_result", only_actual)?;
+        report.set_item("only_in_expected", only_expected)?;
+        report.set_item("in_both", in_both)?;
+        Ok(report)
+    }
+
+    pub fn return_triples<'py>(
+        &mut self,
+        py: Python<'py>,
+        variable: String,
+    ) -> PyResult<Bound<'py, PyList>> {
+        self.validate_variables(
+            std::slice::from_ref(&variable),
+            ctx!("query - return triples"),
+        )
+        .into_py_result()?;
+
+        let mset = self
+            .execute_operations()
+            .attach(ctx!("query - return triples"))
+            .into_py_result()?;
+
+        let results: Vec<HashMap<&str, Reference>> = ordered_rows(&mset)
+            .into_par_iter()
+            .map(|(_prev_uid, r#match)| {
+                let edge = match r#match.get(&variable) {
+                    Some(element) => element
+                        .as_edge(&variable, Some(ctx!("query - return triples")))
+                        .attach(ctx!("query - return triples"))?,
+                    None => {
                         return Err(ImplicaError::VariableNotFound {
-                            name: v.clone(),
-                            context: Some(ctx!("query return - data collection").to_string()),
+                            name: variable.clone(),
+                            context: Some(ctx!("query - return triples")),
                         }
-                        .into());
+                        .into())
                     }
-                }
+                };
 
-                Ok(map)
+                Ok(HashMap::from([
+                    (
+                        "start",
+                        Reference::Node(NodeRef::new(self.graph.clone(), edge.0)),
+                    ),
+                    (
+                        "edge",
+                        Reference::Edge(EdgeRef::new(self.graph.clone(), edge)),
+                    ),
+                    (
+                        "end",
+                        Reference::Node(NodeRef::new(self.graph.clone(), edge.1)),
+                    ),
+                ]))
             })
             .collect::<ImplicaResult<Vec<_>>>()
             .into_py_result()?;
@@ -351,12 +2945,168 @@ impl Query {
         let py_results = PyList::empty(py);
 
         for map in results {
-            py_results.append(map.into_pyobject(py)?)?; // TODO: attach something here
+            py_results.append(map.into_pyobject(py)?)?;
         }
 
         Ok(py_results)
     }
 
+    /// Runs the query and returns a new graph holding every node and edge bound in any row,
+    /// along with the endpoints of the edges. Elements bound in several rows are copied once.
+    pub fn to_graph(&mut self) -> PyResult<PyGraph> {
+        let mset = self
+            .execute_operations()
+            .attach(ctx!("query - to graph"))
+            .into_py_result()?;
+
+        let mut nodes = HashSet::new();
+        let mut edges = HashSet::new();
+
+        fn collect(
+            element: MatchElement,
+            nodes: &mut HashSet<Uid>,
+            edges: &mut HashSet<(Uid, Uid)>,
+        ) {
+            match element {
+                MatchElement::Node(node) => {
+                    nodes.insert(node);
+                }
+                MatchElement::Edge(edge) => {
+                    nodes.insert(edge.0);
+                    nodes.insert(edge.1);
+                    edges.insert(edge);
+                }
+                MatchElement::List(elements) => {
+                    for element in elements {
+                        collect(element, nodes, edges);
+                    }
+                }
+                MatchElement::Type(_) | MatchElement::Term(_) | MatchElement::Value(_) => (),
+            }
+        }
+
+        for row in mset.iter() {
+            for (_, element) in row.value().1.elements() {
+                collect(element, &mut nodes, &mut edges);
+            }
+        }
+
+        let graph = self
+            .graph
+            .copy_subgraph(&nodes, &edges)
+            .attach(ctx!("query - to graph"))
+            .into_py_result()?;
+
+        Ok(PyGraph::from_graph(graph))
+    }
+
+    /// Executes the query and writes the given items, taken as by `return_`, to a Parquet
+    /// file with one column per item. A column is typed from its values: int, float, string
+    /// or bool, with missing values as nulls. Node, type and term variables are written as
+    /// their uid and LET variables as their value. Returns the number of rows written.
+    #[pyo3(signature = (path, *columns))]
+    pub fn to_parquet(
+        &mut self,
+        py: Python<'_>,
+        path: PathBuf,
+        columns: Vec<Bound<PyAny>>,
+    ) -> PyResult<usize> {
+        let projections = self.parse_projections(&columns)?;
+        if projections.is_empty() {
+            return Err(ImplicaError::InvalidQuery {
+                query: self.to_string(),
+                reason: "a Parquet file needs at least one column".to_string(),
+                context: Some(ctx!("query - to parquet")),
+            }
+            .into())
+            .into_py_result();
+        }
+
+        let results = self.project_rows(py, &projections)?;
+        let rows = results.len();
+
+        let mut table: Vec<Column> = projections
+            .iter()
+            .map(|projection| Column::new(projection.key().to_string(), rows))
+            .collect();
+
+        for row in results {
+            for ((value, projection), column) in row
+                .into_iter()
+                .zip(projections.iter())
+                .zip(table.iter_mut())
+            {
+                let value = match value {
+                    Projected::Value(value)
+                    | Projected::Element(Reference::Value(ComputedValue(value))) => Some(
+                        self.graph
+                            .export_value(value)
+                            .attach(ctx!("query - to parquet"))
+                            .into_py_result()?,
+                    ),
+                    Projected::Element(reference) => Some(Dynamic::from(match reference {
+                        Reference::Node(node) => node.uid(),
+                        Reference::Type(r#type) => r#type.uid(),
+                        Reference::Term(term) => term.uid(),
+                        Reference::Edge(_) | Reference::List(_) | Reference::Value(_) => {
+                            return Err(ImplicaError::TypeMismatch {
+                                expected: "a node, type or term variable, or a property"
+                                    .to_string(),
+                                got: if matches!(reference, Reference::Edge(_)) {
+                                    "an edge".to_string()
+                                } else {
+                                    "a list".to_string()
+                                },
+                                context: Some(ctx!(format!(
+                                    "query - to parquet - column '{}'",
+                                    projection.key()
+                                ))),
+                            }
+                            .into())
+                            .into_py_result()
+                        }
+                    })),
+                    Projected::Missing => match projection {
+                        Projection::Property {
+                            default: Some(default),
+                            ..
+                        }
+                        | Projection::Case {
+                            default: Some(default),
+                            ..
+                        } => Some(
+                            py_to_rhai(default.bind(py))
+                                .attach(ctx!("query - to parquet"))
+                                .into_py_result()?,
+                        ),
+                        _ => None,
+                    },
+                };
+
+                column
+                    .push(value)
+                    .attach(ctx!("query - to parquet"))
+                    .into_py_result()?;
+            }
+        }
+
+        py.detach(|| write_parquet(&path, table))
+            .attach(ctx!("query - to parquet"))
+            .into_py_result()?;
+
+        Ok(rows)
+    }
+
+    /// Number of operations queued so far.
+    pub fn pending_operations(&self) -> usize {
+        self.operations.len()
+    }
+
+    /// Describes each queued operation, in the order they were added.
+    pub fn describe_operations(&self) -> Vec<String> {
+        self.operations.iter().map(|op| op.to_string()).collect()
+    }
+
     pub fn __str__(&self) -> String {
         self.to_string()
     }
@@ -0,0 +1,208 @@
+use error_stack::ResultExt;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::sync::Arc;
+
+use crate::ctx;
+use crate::errors::{ImplicaError, IntoPyResult};
+use crate::graph::{Creations, Graph};
+use crate::query::Query;
+use crate::utils::validate_query_variable;
+
+#[derive(Debug, Clone)]
+struct NodeSpec {
+    name: String,
+    r#type: Option<String>,
+    term: Option<String>,
+    properties: Option<Arc<Py<PyAny>>>,
+}
+
+#[derive(Debug, Clone)]
+struct EdgeSpec {
+    start: String,
+    end: String,
+    name: Option<String>,
+    term: Option<String>,
+    properties: Option<Arc<Py<PyAny>>>,
+}
+
+/// Fluent builder collecting named nodes and the edges between them, created together by
+/// `commit`.
+#[pyclass]
+#[derive(Debug, Clone)]
+pub struct GraphBuilder {
+    graph: Arc<Graph>,
+    nodes: Vec<NodeSpec>,
+    edges: Vec<EdgeSpec>,
+}
+
+impl GraphBuilder {
+    pub(crate) fn new(graph: Arc<Graph>) -> Self {
+        GraphBuilder {
+            graph,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    fn check_new_name(&self, name: &str) -> PyResult<()> {
+        validate_query_variable(name)
+            .attach(ctx!("graph builder - check new name"))
+            .into_py_result()?;
+
+        let taken = self.nodes.iter().any(|n| n.name == name)
+            || self.edges.iter().any(|e| e.name.as_deref() == Some(name));
+
+        if taken {
+            return Err(ImplicaError::VariableAlreadyExists {
+                name: name.to_string(),
+                context: Some(ctx!("graph builder - check new name")),
+            }
+            .into())
+            .into_py_result();
+        }
+
+        Ok(())
+    }
+
+    fn check_node_name(&self, name: &str) -> PyResult<()> {
+        if self.nodes.iter().any(|n| n.name == name) {
+            Ok(())
+        } else {
+            Err(ImplicaError::VariableNotFound {
+                name: name.to_string(),
+                context: Some(ctx!("graph builder - check node name")),
+            }
+            .into())
+            .into_py_result()
+        }
+    }
+
+    /// Translates the collected specs into a query of CREATE and SET operations.
+    fn to_query(&self, py: Python) -> PyResult<Query> {
+        let mut query = Query::new(self.graph.clone());
+
+        for node in self.nodes.iter() {
+            let pattern = match (&node.r#type, &node.term) {
+                (Some(r#type), Some(term)) => format!("({}:{}:{})", node.name, r#type, term),
+                (Some(r#type), None) => format!("({}:{})", node.name, r#type),
+                (None, Some(term)) => format!("({}::{})", node.name, term),
+                (None, None) => format!("({})", node.name),
+            };
+            query.create(pattern, false, None, false, false)?;
+
+            if let Some(ref properties) = node.properties {
+                query.set(node.name.clone(), properties.bind(py), true)?;
+            }
+        }
+
+        for edge in self.edges.iter() {
+            let name = edge.name.clone().unwrap_or_default();
+            let pattern = match edge.term {
+                Some(ref term) => format!("({})-[{}::{}]->({})", edge.start, name, term, edge.end),
+                None => format!("({})-[{}]->({})", edge.start, name, edge.end),
+            };
+            query.create(pattern, false, None, false, false)?;
+
+            if let (Some(ref name), Some(ref properties)) = (&edge.name, &edge.properties) {
+                query.set(name.clone(), properties.bind(py), true)?;
+            }
+        }
+
+        Ok(query)
+    }
+}
+
+#[pymethods]
+impl GraphBuilder {
+    #[pyo3(signature = (name, r#type=None, term=None, properties=None))]
+    pub fn node(
+        &mut self,
+        name: String,
+        r#type: Option<String>,
+        term: Option<String>,
+        properties: Option<Py<PyAny>>,
+    ) -> PyResult<GraphBuilder> {
+        self.check_new_name(&name)?;
+
+        self.nodes.push(NodeSpec {
+            name,
+            r#type,
+            term,
+            properties: properties.map(Arc::new),
+        });
+        Ok(self.clone())
+    }
+
+    #[pyo3(signature = (start, end, term=None, name=None, properties=None))]
+    pub fn edge(
+        &mut self,
+        start: String,
+        end: String,
+        term: Option<String>,
+        name: Option<String>,
+        properties: Option<Py<PyAny>>,
+    ) -> PyResult<GraphBuilder> {
+        self.check_node_name(&start)?;
+        self.check_node_name(&end)?;
+        if let Some(ref name) = name {
+            self.check_new_name(name)?;
+        }
+        if properties.is_some() && name.is_none() {
+            return Err(ImplicaError::InvalidQuery {
+                query: format!("({})-[]->({})", start, end),
+                reason: "an edge needs a name to be given properties".to_string(),
+                context: Some(ctx!("graph builder - edge")),
+            }
+            .into())
+            .into_py_result();
+        }
+
+        self.edges.push(EdgeSpec {
+            start,
+            end,
+            name,
+            term,
+            properties: properties.map(Arc::new),
+        });
+        Ok(self.clone())
+    }
+
+    /// Creates every collected node and edge, returning the created elements by name.
+    ///
+    /// If any creation fails, the nodes and edges this commit inserted are removed again,
+    /// so either all of them are added or none is, and the error is raised. Elements that
+    /// already existed, and changes made by other writers meanwhile, are left alone.
+    pub fn commit<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
+        let mut names: Vec<String> = self.nodes.iter().map(|n| n.name.clone()).collect();
+        names.extend(self.edges.iter().filter_map(|e| e.name.clone()));
+
+        if names.is_empty() {
+            return Ok(PyDict::new(py).into_any());
+        }
+
+        let mut query = self.to_query(py)?;
+        let creations = Arc::new(Creations::default());
+        query.record_creations(&creations);
+
+        match query.return_variables(py, names, "dict") {
+            Ok(rows) => rows.get_item(0),
+            Err(e) => {
+                // The error that made the commit fail is the one worth reporting, so a
+                // failure to roll back does not replace it
+                if self.graph.undo_creations(&creations).is_ok() {
+                    let _ = self.graph.notify_observers();
+                }
+                Err(e)
+            }
+        }
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "GraphBuilder(nodes={}, edges={})",
+            self.nodes.len(),
+            self.edges.len()
+        )
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use pyo3::prelude::*;
+
+use crate::ctx;
+use crate::errors::{ImplicaError, IntoPyResult};
+use crate::query::references::Reference;
+
+/// Rows of an executed query, yielded one at a time.
+///
+/// The rows are collected when the iterator is created, so `seek` can move to any of them
+/// without re-running the query or yielding the rows in between.
+#[pyclass(name = "ResultIterator")]
+#[derive(Debug)]
+pub struct ResultIterator {
+    rows: Vec<HashMap<String, Reference>>,
+    position: usize,
+}
+
+impl ResultIterator {
+    pub(crate) fn new(rows: Vec<HashMap<String, Reference>>) -> Self {
+        ResultIterator { rows, position: 0 }
+    }
+}
+
+#[pymethods]
+impl ResultIterator {
+    pub fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    pub fn __next__(&mut self) -> Option<HashMap<String, Reference>> {
+        let row = self.rows.get(self.position).cloned();
+        if row.is_some() {
+            self.position += 1;
+        }
+        row
+    }
+
+    /// Moves to the row at `index`, the next one to be yielded. Seeking to the number of
+    /// rows exhausts the iterator.
+    pub fn seek(&mut self, index: usize) -> PyResult<()> {
+        if index > self.rows.len() {
+            return Err(ImplicaError::IndexOutOfRange {
+                index,
+                max_len: self.rows.len(),
+                context: Some(ctx!("result iterator - seek")),
+            }
+            .into())
+            .into_py_result();
+        }
+
+        self.position = index;
+        Ok(())
+    }
+
+    /// Index of the next row to be yielded.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn __len__(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn __repr__(&self) -> String {
+        format!(
+            "ResultIterator(rows={}, position={})",
+            self.rows.len(),
+            self.position
+        )
+    }
+}
@@ -1,4 +1,9 @@
 mod base;
+mod builder;
+mod iterator;
+mod parquet;
 pub mod references;
 
 pub use base::Query;
+pub use builder::GraphBuilder;
+pub use iterator::ResultIterator;
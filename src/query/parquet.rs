@@ -0,0 +1,161 @@
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, BooleanArray, Float64Array, Int64Array, RecordBatch, StringArray};
+use arrow_schema::{Field, Schema};
+use error_stack::ResultExt;
+use parquet::arrow::ArrowWriter;
+use rhai::Dynamic;
+
+use crate::ctx;
+use crate::errors::{ImplicaError, ImplicaResult};
+use crate::utils::numeric_value;
+
+/// The Arrow type a column is written with, inferred from its values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColumnKind {
+    Int,
+    Float,
+    String,
+    Bool,
+}
+
+impl ColumnKind {
+    fn of(value: &Dynamic) -> Option<ColumnKind> {
+        if value.is::<i64>() {
+            Some(ColumnKind::Int)
+        } else if value.is::<f64>() {
+            Some(ColumnKind::Float)
+        } else if value.is_string() {
+            Some(ColumnKind::String)
+        } else if value.is::<bool>() {
+            Some(ColumnKind::Bool)
+        } else {
+            None
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            ColumnKind::Int => "int",
+            ColumnKind::Float => "float",
+            ColumnKind::String => "string",
+            ColumnKind::Bool => "bool",
+        }
+    }
+}
+
+/// Accumulates the values of one column, row by row, and builds its Arrow array.
+///
+/// The type of the column is that of its values; ints and floats mixed give a float column
+/// and a column with no value at all is written as strings. Missing values become nulls.
+pub(crate) struct Column {
+    name: String,
+    kind: Option<ColumnKind>,
+    values: Vec<Option<Dynamic>>,
+}
+
+impl Column {
+    pub(crate) fn new(name: String, rows: usize) -> Self {
+        Column {
+            name,
+            kind: None,
+            values: Vec::with_capacity(rows),
+        }
+    }
+
+    pub(crate) fn push(&mut self, value: Option<Dynamic>) -> ImplicaResult<()> {
+        if let Some(ref value) = value {
+            let kind = ColumnKind::of(value).ok_or_else(|| ImplicaError::TypeMismatch {
+                expected: "an int, float, string or bool".to_string(),
+                got: value.type_name().to_string(),
+                context: Some(ctx!(format!("parquet column '{}'", self.name))),
+            })?;
+
+            self.kind = match (self.kind, kind) {
+                (None, kind) => Some(kind),
+                (Some(current), kind) if current == kind => Some(kind),
+                (Some(ColumnKind::Int), ColumnKind::Float)
+                | (Some(ColumnKind::Float), ColumnKind::Int) => Some(ColumnKind::Float),
+                (Some(current), kind) => {
+                    return Err(ImplicaError::TypeMismatch {
+                        expected: current.name().to_string(),
+                        got: kind.name().to_string(),
+                        context: Some(ctx!(format!("parquet column '{}'", self.name))),
+                    }
+                    .into())
+                }
+            };
+        }
+
+        self.values.push(value);
+        Ok(())
+    }
+
+    fn finish(self) -> (Field, ArrayRef) {
+        let values = self.values;
+
+        let array: ArrayRef = match self.kind {
+            Some(ColumnKind::Int) => Arc::new(Int64Array::from_iter(
+                values
+                    .into_iter()
+                    .map(|v| v.and_then(|v| v.try_cast::<i64>())),
+            )),
+            Some(ColumnKind::Float) => Arc::new(Float64Array::from_iter(
+                values
+                    .into_iter()
+                    .map(|v| v.as_ref().and_then(numeric_value)),
+            )),
+            Some(ColumnKind::Bool) => Arc::new(BooleanArray::from_iter(
+                values
+                    .into_iter()
+                    .map(|v| v.and_then(|v| v.try_cast::<bool>())),
+            )),
+            Some(ColumnKind::String) | None => Arc::new(StringArray::from_iter(
+                values
+                    .into_iter()
+                    .map(|v| v.and_then(|v| v.into_immutable_string().ok())),
+            )),
+        };
+
+        let field = Field::new(self.name, array.data_type().clone(), true);
+        (field, array)
+    }
+}
+
+/// Writes the columns as a single row group of a Parquet file, replacing any file at `path`.
+pub(crate) fn write_parquet(path: &Path, columns: Vec<Column>) -> ImplicaResult<()> {
+    let (fields, arrays): (Vec<Field>, Vec<ArrayRef>) =
+        columns.into_iter().map(Column::finish).unzip();
+
+    let schema = Arc::new(Schema::new(fields));
+    let batch =
+        RecordBatch::try_new(schema.clone(), arrays).map_err(|e| ImplicaError::RuntimeError {
+            message: e.to_string(),
+            context: Some(ctx!("write parquet - record batch")),
+        })?;
+
+    let io_error = |message: String| ImplicaError::IoError {
+        path: path.display().to_string(),
+        message,
+    };
+
+    let file = File::create(path)
+        .map_err(|e| io_error(e.to_string()))
+        .attach(ctx!("write parquet"))?;
+
+    let mut writer = ArrowWriter::try_new(file, schema, None)
+        .map_err(|e| io_error(e.to_string()))
+        .attach(ctx!("write parquet"))?;
+    writer
+        .write(&batch)
+        .map_err(|e| io_error(e.to_string()))
+        .attach(ctx!("write parquet"))?;
+    writer
+        .close()
+        .map_err(|e| io_error(e.to_string()))
+        .attach(ctx!("write parquet"))?;
+
+    Ok(())
+}
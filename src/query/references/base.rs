@@ -1,13 +1,18 @@
+use crate::errors::IntoPyResult;
+use crate::matches::ComputedValue;
+use crate::properties::rhai_to_py;
 use crate::query::references::{EdgeRef, NodeRef, TermRef, TypeRef};
 use pyo3::prelude::*;
 use pyo3::IntoPyObject;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Reference {
     Edge(EdgeRef),
     Node(NodeRef),
     Term(TermRef),
     Type(TypeRef),
+    List(Vec<Reference>),
+    Value(ComputedValue),
 }
 
 impl<'py> IntoPyObject<'py> for Reference {
@@ -21,6 +26,8 @@ impl<'py> IntoPyObject<'py> for Reference {
             Reference::Term(v) => Ok(v.into_pyobject(py)?.into_any()),
             Reference::Node(v) => Ok(v.into_pyobject(py)?.into_any()),
             Reference::Edge(v) => Ok(v.into_pyobject(py)?.into_any()),
+            Reference::List(v) => Ok(v.into_pyobject(py)?.into_any()),
+            Reference::Value(v) => rhai_to_py(v.0, py).into_py_result(),
         }
     }
 }
@@ -64,6 +64,66 @@ impl EdgeRef {
         Ok(TermRef::new(self.graph.clone(), edge_type))
     }
 
+    /// Types of the start and end nodes. A node's uid is that of its type.
+    pub fn endpoint_types(&self) -> PyResult<(TypeRef, TypeRef)> {
+        let (start, end) = self
+            .graph
+            .edge_endpoints(&self.uid)
+            .attach(ctx!("edge reference - endpoint types"))
+            .into_py_result()?;
+
+        Ok((
+            TypeRef::new(self.graph.clone(), start),
+            TypeRef::new(self.graph.clone(), end),
+        ))
+    }
+
+    /// Terms of the start and end nodes, `None` for a node without one.
+    pub fn endpoint_terms(&self) -> PyResult<(Option<TermRef>, Option<TermRef>)> {
+        let (start, end) = self
+            .graph
+            .edge_endpoints(&self.uid)
+            .attach(ctx!("edge reference - endpoint terms"))
+            .into_py_result()?;
+
+        let term = |node: Uid| {
+            self.graph
+                .contains_term_of_type(&node)
+                .then(|| TermRef::new(self.graph.clone(), node))
+        };
+
+        Ok((term(start), term(end)))
+    }
+
+    pub fn created_at(&self) -> PyResult<f64> {
+        let (created_at, _) = self
+            .graph
+            .edge_timestamps(&self.uid)
+            .attach(ctx!("edge reference - created at"))
+            .into_py_result()?;
+
+        Ok(created_at)
+    }
+
+    pub fn modified_at(&self) -> PyResult<f64> {
+        let (_, modified_at) = self
+            .graph
+            .edge_timestamps(&self.uid)
+            .attach(ctx!("edge reference - modified at"))
+            .into_py_result()?;
+
+        Ok(modified_at)
+    }
+
+    /// Whether `other` has the same type, term and properties and structurally equal endpoints,
+    /// even if it belongs to another graph.
+    pub fn structurally_equals(&self, other: &Self) -> PyResult<bool> {
+        self.graph
+            .edge_structurally_equals(&self.uid, &other.graph, &other.uid)
+            .attach(ctx!("edge reference - structurally equals"))
+            .into_py_result()
+    }
+
     pub fn __str__(&self) -> PyResult<String> {
         self.graph
             .edge_to_string(&self.uid)
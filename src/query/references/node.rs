@@ -1,5 +1,7 @@
 use error_stack::ResultExt;
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 
 use crate::ctx;
@@ -58,6 +60,83 @@ impl NodeRef {
         }
     }
 
+    /// What the node's term is built from, or `None` for a node without a term: its
+    /// `"constants"` and free `"variables"`, each mapped to the types it occurs at, and the
+    /// `"types"` of all its subterms. Types are listed by uid.
+    pub fn context<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        if !self.graph.contains_term_of_type(&self.uid) {
+            return Ok(None);
+        }
+
+        let context = self
+            .graph
+            .term_context(&self.uid)
+            .attach(ctx!("node reference - context"))
+            .into_py_result()?;
+
+        let types = |uids: BTreeSet<Uid>| -> Vec<TypeRef> {
+            uids.into_iter()
+                .map(|uid| TypeRef::new(self.graph.clone(), uid))
+                .collect()
+        };
+        let names = |names: BTreeMap<String, BTreeSet<Uid>>| -> BTreeMap<String, Vec<TypeRef>> {
+            names
+                .into_iter()
+                .map(|(name, uids)| (name, types(uids)))
+                .collect()
+        };
+
+        let dict = PyDict::new(py);
+        dict.set_item("constants", names(context.constants))?;
+        dict.set_item("variables", names(context.variables))?;
+        dict.set_item("types", types(context.types))?;
+
+        Ok(Some(dict))
+    }
+
+    #[pyo3(signature = (cascade=false))]
+    pub fn clear_term(&self, cascade: bool) -> PyResult<bool> {
+        let result = self.graph.clear_term(&self.uid, cascade);
+
+        self.graph
+            .notify_observers()
+            .attach(ctx!("node reference - clear term"))
+            .into_py_result()?;
+
+        result
+            .attach(ctx!("node reference - clear term"))
+            .into_py_result()
+    }
+
+    pub fn created_at(&self) -> PyResult<f64> {
+        let (created_at, _) = self
+            .graph
+            .node_timestamps(&self.uid)
+            .attach(ctx!("node reference - created at"))
+            .into_py_result()?;
+
+        Ok(created_at)
+    }
+
+    pub fn modified_at(&self) -> PyResult<f64> {
+        let (_, modified_at) = self
+            .graph
+            .node_timestamps(&self.uid)
+            .attach(ctx!("node reference - modified at"))
+            .into_py_result()?;
+
+        Ok(modified_at)
+    }
+
+    /// Whether `other` has the same type, term and properties,
+    /// even if it belongs to another graph.
+    pub fn structurally_equals(&self, other: &Self) -> PyResult<bool> {
+        self.graph
+            .node_structurally_equals(&self.uid, &other.graph, &other.uid)
+            .attach(ctx!("node reference - structurally equals"))
+            .into_py_result()
+    }
+
     pub fn __str__(&self) -> PyResult<String> {
         self.graph
             .node_to_string(&self.uid)
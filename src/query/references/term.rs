@@ -4,8 +4,9 @@ use pyo3::prelude::*;
 use std::sync::Arc;
 
 use crate::ctx;
-use crate::errors::IntoPyResult;
-use crate::graph::{Graph, Uid};
+use crate::errors::{ImplicaError, IntoPyResult};
+use crate::graph::{Graph, PyGraph, Uid};
+use crate::TypeRef;
 
 #[pyclass(name = "Term")]
 #[derive(Debug, Clone)]
@@ -35,6 +36,48 @@ impl TermRef {
         hex::encode(self.uid)
     }
 
+    /// Type of the term. A term is stored under the uid of its type.
+    pub fn r#type(&self) -> TypeRef {
+        TypeRef::new(self.graph.clone(), self.uid)
+    }
+
+    /// Applies the term to `argument`, whose type must be the domain of the term's first
+    /// arrow. Applying one argument to a curried `A -> B -> C` yields a `B -> C` term, ready
+    /// to label a new edge.
+    pub fn partial_apply(&self, argument: &TermRef) -> PyResult<TermRef> {
+        if !Arc::ptr_eq(&self.graph, &argument.graph) {
+            return Err(ImplicaError::InvalidTerm {
+                reason: "the argument term belongs to another graph".to_string(),
+            }
+            .into())
+            .into_py_result();
+        }
+
+        let uid = self
+            .graph
+            .apply_term(&self.uid, &argument.uid)
+            .attach(ctx!("term reference - partial apply"))
+            .into_py_result()?;
+
+        Ok(TermRef::new(self.graph.clone(), uid))
+    }
+
+    /// The term as an S-expression, e.g. `(app succ zero)`.
+    pub fn to_sexpr(&self) -> PyResult<String> {
+        self.graph
+            .term_to_sexpr(&self.uid)
+            .map(|sexpr| sexpr.to_string())
+            .attach(ctx!("term reference - to sexpr"))
+            .into_py_result()
+    }
+
+    /// Reads a term written as an S-expression over the constants of `graph` and stores it
+    /// there, failing if its type already holds a different term.
+    #[staticmethod]
+    pub fn from_sexpr(sexpr: &str, graph: &PyGraph) -> PyResult<TermRef> {
+        graph.term_from_sexpr(sexpr)
+    }
+
     pub fn __str__(&self) -> PyResult<String> {
         self.graph
             .term_to_string(&self.uid)
@@ -4,9 +4,10 @@ use pyo3::prelude::*;
 use std::sync::Arc;
 
 use crate::{
+    constants::Constant,
     ctx,
     errors::IntoPyResult,
-    graph::{Graph, Uid},
+    graph::{Graph, PyGraph, Uid},
 };
 
 #[pyclass(name = "Type")]
@@ -29,6 +30,13 @@ impl TypeRef {
     pub fn new(graph: Arc<Graph>, uid: Uid) -> Self {
         TypeRef { graph, uid }
     }
+
+    pub(crate) fn is_inhabited_by(&self, constant: &Constant) -> PyResult<bool> {
+        self.graph
+            .constant_inhabits(constant, &self.uid)
+            .attach(ctx!("type reference - is inhabited by"))
+            .into_py_result()
+    }
 }
 
 #[pymethods]
@@ -37,6 +45,21 @@ impl TypeRef {
         hex::encode(self.uid)
     }
 
+    /// The type as an S-expression, e.g. `(arrow A (arrow B C))` for `A -> B -> C`.
+    pub fn to_sexpr(&self) -> PyResult<String> {
+        self.graph
+            .type_to_sexpr(&self.uid)
+            .map(|sexpr| sexpr.to_string())
+            .attach(ctx!("type reference - to sexpr"))
+            .into_py_result()
+    }
+
+    /// Reads a type written as an S-expression and adds it to `graph`.
+    #[staticmethod]
+    pub fn from_sexpr(sexpr: &str, graph: &PyGraph) -> PyResult<TypeRef> {
+        graph.type_from_sexpr(sexpr)
+    }
+
     pub fn __str__(&self) -> PyResult<String> {
         self.graph
             .type_to_string(&self.uid)
@@ -1,5 +1,7 @@
+mod sexpr;
 mod term;
 mod types;
 
+pub use sexpr::SExpr;
 pub use term::{Application, BasicTerm, Term};
 pub use types::{Arrow, Type, Variable};
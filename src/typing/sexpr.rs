@@ -0,0 +1,112 @@
+use std::fmt::Display;
+
+/// An S-expression: an atom, or a parenthesised list of S-expressions.
+///
+/// Types are written `A` and `(arrow A B)`, terms `f` and `(app f x)`, so that
+/// `A -> B -> C` is `(arrow A (arrow B C))` and `((f x) y)` is `(app (app f x) y)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SExpr {
+    Atom(String),
+    List(Vec<SExpr>),
+}
+
+impl SExpr {
+    /// Parses a single S-expression. Atoms are runs of characters other than whitespace and
+    /// parentheses; anything after the expression is rejected. Errors are returned as the
+    /// reason the input is invalid.
+    pub fn parse(input: &str) -> Result<SExpr, String> {
+        let tokens = Self::tokenize(input);
+        let mut position = 0;
+
+        let expr = Self::parse_tokens(&tokens, &mut position)?;
+
+        if position != tokens.len() {
+            return Err(format!(
+                "unexpected '{}' after the expression",
+                tokens[position]
+            ));
+        }
+
+        Ok(expr)
+    }
+
+    fn tokenize(input: &str) -> Vec<String> {
+        let mut tokens = Vec::new();
+        let mut atom = String::new();
+
+        for c in input.chars() {
+            if c == '(' || c == ')' || c.is_whitespace() {
+                if !atom.is_empty() {
+                    tokens.push(std::mem::take(&mut atom));
+                }
+                if !c.is_whitespace() {
+                    tokens.push(c.to_string());
+                }
+            } else {
+                atom.push(c);
+            }
+        }
+
+        if !atom.is_empty() {
+            tokens.push(atom);
+        }
+
+        tokens
+    }
+
+    fn parse_tokens(tokens: &[String], position: &mut usize) -> Result<SExpr, String> {
+        let token = match tokens.get(*position) {
+            Some(token) => token,
+            None => return Err("unexpected end of input".to_string()),
+        };
+        *position += 1;
+
+        match token.as_str() {
+            "(" => {
+                let mut items = Vec::new();
+
+                loop {
+                    match tokens.get(*position).map(String::as_str) {
+                        Some(")") => {
+                            *position += 1;
+                            return Ok(SExpr::List(items));
+                        }
+                        Some(_) => items.push(Self::parse_tokens(tokens, position)?),
+                        None => return Err("missing ')'".to_string()),
+                    }
+                }
+            }
+            ")" => Err("unexpected ')'".to_string()),
+            atom => Ok(SExpr::Atom(atom.to_string())),
+        }
+    }
+
+    /// The operands of a list headed by `head`, e.g. `["A", "B"]` for `(arrow A B)`.
+    pub fn operands(&self, head: &str) -> Option<&[SExpr]> {
+        match self {
+            SExpr::List(items) => match items.split_first() {
+                Some((SExpr::Atom(name), rest)) if name == head => Some(rest),
+                _ => None,
+            },
+            SExpr::Atom(_) => None,
+        }
+    }
+}
+
+impl Display for SExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SExpr::Atom(atom) => write!(f, "{}", atom),
+            SExpr::List(items) => {
+                write!(f, "(")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
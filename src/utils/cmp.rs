@@ -1,7 +1,22 @@
+use std::cmp::Ordering;
+
 use rhai::{Dynamic, Map};
 
 use crate::properties::PyOpaque;
 
+fn mixed_numbers(value_1: &Dynamic, value_2: &Dynamic) -> Option<(f64, f64)> {
+    match (
+        value_1.clone().try_cast::<i64>(),
+        value_1.clone().try_cast::<f64>(),
+        value_2.clone().try_cast::<i64>(),
+        value_2.clone().try_cast::<f64>(),
+    ) {
+        (Some(v1), _, _, Some(v2)) => Some((v1 as f64, v2)),
+        (_, Some(v1), Some(v2), _) => Some((v1, v2 as f64)),
+        _ => None,
+    }
+}
+
 pub(crate) fn compare_values(value_1: &Dynamic, value_2: &Dynamic) -> bool {
     // Handle PyOpaque - compare Python object identity
     if value_1.is::<PyOpaque>() && value_2.is::<PyOpaque>() {
@@ -18,11 +33,22 @@ pub(crate) fn compare_values(value_1: &Dynamic, value_2: &Dynamic) -> bool {
         return v1 == v2;
     }
 
-    // Handle f64
+    // Handle f64 (NaN is never equal to anything, itself included)
     if let (Some(v1), Some(v2)) = (
         value_1.clone().try_cast::<f64>(),
         value_2.clone().try_cast::<f64>(),
     ) {
+        if v1.is_nan() || v2.is_nan() {
+            return false;
+        }
+        return (v1 - v2).abs() < f64::EPSILON;
+    }
+
+    // Handle an i64 against an f64 by widening the integer
+    if let Some((v1, v2)) = mixed_numbers(value_1, value_2) {
+        if v1.is_nan() || v2.is_nan() {
+            return false;
+        }
         return (v1 - v2).abs() < f64::EPSILON;
     }
 
@@ -82,3 +108,79 @@ pub(crate) fn compare_values(value_1: &Dynamic, value_2: &Dynamic) -> bool {
     // Types don't match or unknown type
     false
 }
+
+/// Whether a value goes after every other one when sorting, whatever the direction: a
+/// missing value (unit) or a NaN.
+pub(crate) fn sorts_last(value: &Dynamic) -> bool {
+    value.is_unit() || value.clone().try_cast::<f64>().is_some_and(|v| v.is_nan())
+}
+
+/// The value of a number as a float, whether it is stored as an int or a float.
+pub(crate) fn numeric_value(value: &Dynamic) -> Option<f64> {
+    value
+        .clone()
+        .try_cast::<i64>()
+        .map(|v| v as f64)
+        .or_else(|| value.clone().try_cast::<f64>())
+}
+
+/// Orders two values for sorting: numbers by value, ints and floats mixed, strings
+/// lexicographically and booleans with `false` first. Any other pair is not comparable.
+pub(crate) fn order_values(value_1: &Dynamic, value_2: &Dynamic) -> Option<Ordering> {
+    if let (Some(v1), Some(v2)) = (
+        value_1.clone().try_cast::<i64>(),
+        value_2.clone().try_cast::<i64>(),
+    ) {
+        return Some(v1.cmp(&v2));
+    }
+
+    if let (Some(v1), Some(v2)) = (
+        value_1.clone().try_cast::<f64>(),
+        value_2.clone().try_cast::<f64>(),
+    ) {
+        return v1.partial_cmp(&v2);
+    }
+
+    if let Some((v1, v2)) = mixed_numbers(value_1, value_2) {
+        return v1.partial_cmp(&v2);
+    }
+
+    if let (Some(v1), Some(v2)) = (
+        value_1.clone().try_cast::<String>(),
+        value_2.clone().try_cast::<String>(),
+    ) {
+        return Some(v1.cmp(&v2));
+    }
+
+    if let (Some(v1), Some(v2)) = (
+        value_1.clone().try_cast::<bool>(),
+        value_2.clone().try_cast::<bool>(),
+    ) {
+        return Some(v1.cmp(&v2));
+    }
+
+    None
+}
+
+/// Checks whether a stored property value satisfies the value given in a pattern.
+///
+/// Plain values are compared with [`compare_values`]. A single-key map whose key is an
+/// operator (e.g. `{ contains: 'x' }`) is treated as a predicate over the stored value
+/// when a plain comparison does not already succeed.
+pub(crate) fn matches_value(expected: &Dynamic, actual: &Dynamic) -> bool {
+    if compare_values(expected, actual) {
+        return true;
+    }
+
+    if let Some(map) = expected.clone().try_cast::<Map>() {
+        if map.len() == 1 {
+            if let Some(needle) = map.get("contains") {
+                if let Some(list) = actual.clone().try_cast::<Vec<Dynamic>>() {
+                    return list.iter().any(|item| compare_values(needle, item));
+                }
+            }
+        }
+    }
+
+    false
+}
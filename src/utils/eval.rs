@@ -1,19 +1,29 @@
-use fancy_regex::Regex;
+use error_stack::Report;
+use fancy_regex::{Captures, Regex};
 use pyo3::prelude::*;
-use pyo3::types::{PyBool, PyDict, PyFloat, PyInt, PyList, PyString};
-use rhai::{Dynamic, Engine, EvalAltResult, Map, Scope};
-use std::collections::HashMap;
+use pyo3::types::PyTuple;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, ImmutableString, Map, Position, Scope};
+use std::any::TypeId;
+use std::sync::Arc;
 
-use crate::errors::ImplicaError;
+use crate::errors::{ImplicaError, ImplicaResult};
+use crate::graph::Uid;
+use crate::properties::{py_to_rhai, rhai_to_py};
+use crate::utils::numeric_value;
+
+/// Most arguments a Python function registered on the evaluator can be called with.
+const MAX_PYTHON_FUNCTION_ARITY: usize = 8;
 
 #[derive(Debug)]
 pub struct Evaluator {
     engine: Engine,
     replacements: Vec<(Regex, String)>,
+    /// Single-quoted string literals, which rhai reserves for characters.
+    single_quoted: Regex,
 }
 
 impl Evaluator {
-    pub fn new() -> Result<Self, ImplicaError> {
+    pub fn new() -> ImplicaResult<Self> {
         let replacements = vec![
             (Regex::new(r"(?i)\bAND\b").unwrap(), "&&".to_string()),
             (Regex::new(r"(?i)\bOR\b").unwrap(), "||".to_string()),
@@ -21,26 +31,39 @@ impl Evaluator {
             (Regex::new(r"(?i)\bXOR\b").unwrap(), "^".to_string()),
             (Regex::new(r"(?<![<>=!])=(?!=)").unwrap(), "==".to_string()),
             (
-                Regex::new(r"(\w+)\s+(?i)STARTS WITH\s+('[\w\s]+')").unwrap(),
+                Regex::new(r"([\w.]+)\s+(?i)STARTS WITH\s+('[\w\s]+')").unwrap(),
                 "starts_with($1, $2)".to_string(),
             ),
             (
-                Regex::new(r"(\w+)\s+(?i)ENDS WITH\s+('[\w\s]+')").unwrap(),
+                Regex::new(r"([\w.]+)\s+(?i)ENDS WITH\s+('[\w\s]+')").unwrap(),
                 "ends_with($1, $2)".to_string(),
             ),
             (
-                Regex::new(r"(\w+)\s+(?i)CONTAINS\s+('[\w\s]+')").unwrap(),
+                Regex::new(r"([\w.]+)\s+(?i)CONTAINS\s+('[\w\s]+')").unwrap(),
                 "contains($1, $2)".to_string(),
             ),
+            // startNode(e) and endNode(e) read the endpoints of the edge bound to e
+            (
+                Regex::new(r"(?i)\bstartNode\s*\(\s*(\w+)\s*\)").unwrap(),
+                "__start_node(__edge_$1)".to_string(),
+            ),
+            (
+                Regex::new(r"(?i)\bendNode\s*\(\s*(\w+)\s*\)").unwrap(),
+                "__end_node(__edge_$1)".to_string(),
+            ),
         ];
 
         let mut engine = Engine::new();
+        // Let the float comparisons registered below take precedence over the built-in ones.
+        engine.set_fast_operators(false);
 
         Self::register_custom_functions(&mut engine);
+        Self::register_float_comparisons(&mut engine);
 
         Ok(Evaluator {
             engine,
             replacements,
+            single_quoted: Regex::new(r"'([^'\\]*)'").unwrap(),
         })
     }
 
@@ -51,7 +74,14 @@ impl Evaluator {
                 .replace_all(&processed, replacement.clone())
                 .to_string();
         }
-        processed
+
+        // Single-quoted literals become double-quoted ones, so the double quotes they hold
+        // are escaped.
+        self.single_quoted
+            .replace_all(&processed, |caps: &Captures| {
+                format!("\"{}\"", caps[1].replace('"', "\\\""))
+            })
+            .to_string()
     }
 
     fn register_custom_functions(engine: &mut Engine) {
@@ -87,97 +117,181 @@ impl Evaluator {
 
             s_str.contains(&pat_str)
         });
+
+        // size() of a list, a string or a dict; a missing value has size 0
+        engine.register_fn("size", |list: Array| list.len() as i64);
+        engine.register_fn("size", |s: ImmutableString| s.chars().count() as i64);
+        engine.register_fn("size", |map: Map| map.len() as i64);
+        engine.register_fn("size", |_: ()| 0_i64);
+
+        // approx_eq(a, b, eps) compares numbers within a tolerance; NaN is never equal
+        engine.register_fn("approx_eq", |a: Dynamic, b: Dynamic, eps: Dynamic| {
+            match (numeric_value(&a), numeric_value(&b), numeric_value(&eps)) {
+                (Some(a), Some(b), Some(eps)) => (a - b).abs() <= eps,
+                _ => false,
+            }
+        });
     }
 
-    pub fn eval(&self, scope: &mut Scope, query: &str) -> Result<bool, ImplicaError> {
-        let transpiled_query = self.transpile(query);
+    /// Makes a Python callable available to expressions under the given name, with any
+    /// number of arguments up to [`MAX_PYTHON_FUNCTION_ARITY`].
+    ///
+    /// Arguments and the returned value are converted like property values. An exception
+    /// raised by the callable aborts the evaluation and is re-raised unchanged.
+    pub fn register_python_function(&mut self, name: &str, callback: Arc<Py<PyAny>>) {
+        for arity in 0..=MAX_PYTHON_FUNCTION_ARITY {
+            let callback = callback.clone();
 
-        match self
-            .engine
-            .eval_with_scope::<bool>(scope, &transpiled_query)
-        {
-            Ok(result) => Ok(result),
-            Err(e) => match e.as_ref() {
-                EvalAltResult::ErrorMismatchOutputType(output, _, _) => Ok(output != "()"),
-                _ => Err(ImplicaError::EvaluationError {
-                    message: e.to_string(),
-                }),
-            },
+            self.engine.register_raw_fn(
+                name,
+                vec![TypeId::of::<Dynamic>(); arity],
+                move |_, args: &mut [&mut Dynamic]| {
+                    Python::attach(|py| {
+                        let arguments = args
+                            .iter()
+                            .map(|arg| rhai_to_py((**arg).clone(), py))
+                            .collect::<ImplicaResult<Vec<_>>>()
+                            .map_err(|e| {
+                                Box::new(EvalAltResult::ErrorRuntime(
+                                    e.current_context().to_string().into(),
+                                    Position::NONE,
+                                ))
+                            })?;
+
+                        let result = PyTuple::new(py, arguments)
+                            .and_then(|arguments| callback.call1(py, arguments))
+                            .map_err(|e| {
+                                Box::new(EvalAltResult::ErrorRuntime(
+                                    Dynamic::from(Arc::new(e)),
+                                    Position::NONE,
+                                ))
+                            })?;
+
+                        py_to_rhai(result.bind(py)).map_err(|e| {
+                            Box::new(EvalAltResult::ErrorRuntime(
+                                e.current_context().to_string().into(),
+                                Position::NONE,
+                            ))
+                        })
+                    })
+                },
+            );
         }
     }
-}
 
-pub fn props_as_map(prop: &HashMap<String, Py<PyAny>>) -> Result<Map, ImplicaError> {
-    let mut map = Map::new();
+    /// Makes `startNode(e)` and `endNode(e)` evaluate to the properties of the endpoints of
+    /// the edge bound to `e`, read with `node_properties`. The scope must hold the edge under
+    /// [`edge_variable`].
+    pub fn register_edge_endpoints<F>(&mut self, node_properties: F)
+    where
+        F: Fn(&Uid) -> ImplicaResult<Map> + Send + Sync + 'static,
+    {
+        let node_properties = Arc::new(node_properties);
 
-    Python::attach(|py| {
-        for (k, obj_ref) in prop.iter() {
-            let bound_obj = obj_ref.bind(py);
-            let dynamic_val = to_dynamic(bound_obj);
+        for (name, start) in [("__start_node", true), ("__end_node", false)] {
+            let node_properties = node_properties.clone();
 
-            map.insert(k.clone().into(), dynamic_val);
-        }
-    });
+            self.engine.register_fn(
+                name,
+                move |edge: (Uid, Uid)| -> Result<Map, Box<EvalAltResult>> {
+                    let node = if start { edge.0 } else { edge.1 };
 
-    Ok(map)
-}
+                    node_properties(&node).map_err(|e| {
+                        Box::new(EvalAltResult::ErrorRuntime(
+                            e.current_context().to_string().into(),
+                            Position::NONE,
+                        ))
+                    })
+                },
+            );
 
-fn to_dynamic(obj: &Bound<'_, PyAny>) -> Dynamic {
-    if obj.is_none() {
-        return Dynamic::UNIT;
+            // Variables bound to anything but an edge are held as ()
+            self.engine
+                .register_fn(name, |_: ()| -> Result<Map, Box<EvalAltResult>> {
+                    Err(Box::new(EvalAltResult::ErrorRuntime(
+                        "startNode() and endNode() take an edge variable".into(),
+                        Position::NONE,
+                    )))
+                });
+        }
     }
 
-    if obj.is_instance_of::<PyBool>() {
-        return match obj.extract::<bool>() {
-            Ok(b) => Dynamic::from(b),
-            Err(_) => Dynamic::FALSE,
-        };
+    /// Replaces rhai's tolerant float comparisons with plain IEEE ones, so NaN compares
+    /// unequal to everything (itself included) and never sorts before or after any value.
+    /// Use `approx_eq` to compare floats within a tolerance.
+    fn register_float_comparisons(engine: &mut Engine) {
+        engine.register_fn("==", |a: f64, b: f64| a == b);
+        engine.register_fn("!=", |a: f64, b: f64| a != b);
+        engine.register_fn("<", |a: f64, b: f64| a < b);
+        engine.register_fn("<=", |a: f64, b: f64| a <= b);
+        engine.register_fn(">", |a: f64, b: f64| a > b);
+        engine.register_fn(">=", |a: f64, b: f64| a >= b);
+
+        engine.register_fn("==", |a: f64, b: i64| a == b as f64);
+        engine.register_fn("!=", |a: f64, b: i64| a != b as f64);
+        engine.register_fn("<", |a: f64, b: i64| a < b as f64);
+        engine.register_fn("<=", |a: f64, b: i64| a <= b as f64);
+        engine.register_fn(">", |a: f64, b: i64| a > b as f64);
+        engine.register_fn(">=", |a: f64, b: i64| a >= b as f64);
+
+        engine.register_fn("==", |a: i64, b: f64| a as f64 == b);
+        engine.register_fn("!=", |a: i64, b: f64| a as f64 != b);
+        engine.register_fn("<", |a: i64, b: f64| (a as f64) < b);
+        engine.register_fn("<=", |a: i64, b: f64| a as f64 <= b);
+        engine.register_fn(">", |a: i64, b: f64| a as f64 > b);
+        engine.register_fn(">=", |a: i64, b: f64| a as f64 >= b);
     }
 
-    if obj.is_instance_of::<PyInt>() {
-        return match obj.extract::<i64>() {
-            Ok(i) => Dynamic::from(i),
-            Err(_) => match obj.extract::<f64>() {
-                Ok(f) => Dynamic::from(f),
-                Err(_) => Dynamic::from(obj.to_string()),
+    pub fn eval(&self, scope: &mut Scope, query: &str) -> ImplicaResult<bool> {
+        let transpiled_query = self.transpile(query);
+
+        match self
+            .engine
+            .eval_with_scope::<bool>(scope, &transpiled_query)
+        {
+            Ok(result) => Ok(result),
+            Err(e) => match e.as_ref() {
+                EvalAltResult::ErrorMismatchOutputType(output, _, _) => Ok(output != "()"),
+                _ => Err(evaluation_error(e)),
             },
-        };
+        }
     }
 
-    if obj.is_instance_of::<PyFloat>() {
-        return match obj.extract::<f64>() {
-            Ok(f) => Dynamic::from(f),
-            Err(_) => Dynamic::from(0.0),
-        };
+    /// Evaluates an expression to whatever value it produces, for uses other than filtering.
+    pub fn eval_value(&self, scope: &mut Scope, expression: &str) -> ImplicaResult<Dynamic> {
+        let transpiled_expression = self.transpile(expression);
+
+        self.engine
+            .eval_with_scope::<Dynamic>(scope, &transpiled_expression)
+            .map_err(evaluation_error)
     }
+}
 
-    if obj.is_instance_of::<PyString>() {
-        return match obj.extract::<String>() {
-            Ok(s) => Dynamic::from(s),
-            Err(_) => Dynamic::from(""),
-        };
+/// Turns a failed evaluation into an error, keeping the exception of a registered Python
+/// function that raised so it reaches the caller unchanged.
+fn evaluation_error(error: Box<EvalAltResult>) -> Report<ImplicaError> {
+    let mut cause = error.as_ref();
+    while let EvalAltResult::ErrorInFunctionCall(_, _, inner, _) = cause {
+        cause = inner.as_ref();
     }
 
-    if let Ok(list) = obj.cast::<PyList>() {
-        let mut arr = Vec::with_capacity(list.len());
-        for item in list {
-            arr.push(to_dynamic(&item));
+    if let EvalAltResult::ErrorRuntime(value, _) = cause {
+        if let Some(exception) = value.clone().try_cast::<Arc<PyErr>>() {
+            return Python::attach(|py| {
+                Report::new(ImplicaError::from(exception.clone_ref(py)))
+                    .attach_opaque(exception.clone_ref(py))
+            });
         }
-        return Dynamic::from_array(arr);
     }
 
-    // 7. Check for Dict (Recursive)
-    if let Ok(dict) = obj.cast::<PyDict>() {
-        let mut map = Map::new();
-        for (k, v) in dict {
-            // Rhai keys must be strings. Force conversion of non-string keys.
-            let key_str = k.extract::<String>().unwrap_or_else(|_| k.to_string());
-            map.insert(key_str.into(), to_dynamic(&v));
-        }
-        return Dynamic::from_map(map);
+    ImplicaError::EvaluationError {
+        message: error.to_string(),
     }
+    .into()
+}
 
-    // 8. Fallback: Any other Python object (Classes, Dates, etc.)
-    // We convert them to their string representation to allow basic comparisons.
-    Dynamic::from(obj.to_string())
+/// Name under which a scope holds the edge bound to `variable`, for `startNode` and `endNode`;
+/// other elements are held there as `()`.
+pub(crate) fn edge_variable(variable: &str) -> String {
+    format!("__edge_{}", variable)
 }
@@ -1,11 +1,11 @@
 mod cmp;
-//mod eval;
 mod data_queue;
+mod eval;
 mod hex_to_uid;
 mod validation;
 
-pub(crate) use cmp::compare_values;
-//pub(crate) use eval::{props_as_map, Evaluator};
+pub(crate) use cmp::{compare_values, matches_value, numeric_value, order_values, sorts_last};
 pub(crate) use data_queue::{DataQueue, QueueItem};
+pub(crate) use eval::{edge_variable, Evaluator};
 pub(crate) use hex_to_uid::hex_str_to_uid;
-pub(crate) use validation::validate_variable_name;
+pub(crate) use validation::{validate_query_variable, validate_variable_name};
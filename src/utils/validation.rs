@@ -2,6 +2,7 @@ use crate::errors::{ImplicaError, ImplicaResult};
 
 const MAX_NAME_LENGTH: usize = 255;
 const RESERVED_NAMES: &[&str] = &["None", "True", "False"];
+const RESERVED_PREFIX: &str = "__";
 
 pub(crate) fn validate_variable_name(name: &str) -> ImplicaResult<()> {
     // Longitud
@@ -51,3 +52,19 @@ pub(crate) fn validate_variable_name(name: &str) -> ImplicaResult<()> {
 
     Ok(())
 }
+
+/// Validates a name bound to a query variable. On top of the general identifier rules, names
+/// starting with `__` are rejected as they are reserved for internal bindings.
+pub(crate) fn validate_query_variable(name: &str) -> ImplicaResult<()> {
+    validate_variable_name(name)?;
+
+    if name.starts_with(RESERVED_PREFIX) {
+        return Err(ImplicaError::InvalidIdentifier {
+            name: name.to_string(),
+            reason: format!("Names starting with '{}' are reserved", RESERVED_PREFIX),
+        }
+        .into());
+    }
+
+    Ok(())
+}
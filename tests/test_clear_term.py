@@ -0,0 +1,88 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph(
+        constants=[implica.Constant("a", "A"), implica.Constant("f", "A -> B")]
+    )
+    graph.query().create("(:A:@a() { x: 1 })").create("(:A -> B:@f())").execute()
+    return graph
+
+
+class TestClearTerm:
+    def test_query_clear_term_keeps_node(self):
+        graph = implica.Graph(constants=[implica.Constant("a", "A")])
+        graph.query().create("(:A:@a() { x: 1 })").execute()
+
+        graph.query().match("(n:A)").clear_term("n").execute()
+
+        result = graph.query().match("(n:A)").return_("n")
+        assert len(result) == 1
+        assert result[0]["n"].term() is None
+        assert result[0]["n"].properties() == {"x": 1}
+        assert graph.query().match("(n::@a())").return_("n") == []
+
+    def test_node_clear_term(self):
+        graph = implica.Graph(constants=[implica.Constant("a", "A")])
+        graph.query().create("(:A:@a())").execute()
+        node = graph.nodes()[0]
+
+        assert node.clear_term() is True
+        assert node.term() is None
+        assert node.clear_term() is False
+
+    def test_clear_term_of_edge_label_requires_cascade(self):
+        graph = _graph()
+
+        with pytest.raises(ValueError):
+            graph.query().match("(n:A -> B)").clear_term("n").execute()
+
+        assert len(graph.edges()) == 1
+        assert graph.query().match("(n::@f())").return_("n") != []
+
+    def test_clear_term_cascade_removes_dependent_edge(self):
+        graph = _graph()
+
+        graph.query().match("(n:A -> B)").clear_term("n", cascade=True).execute()
+
+        assert graph.edges() == []
+        assert len(graph.query().match("(n:A -> B)").return_("n")) == 1
+        assert graph.query().match("(n::@f())").return_("n") == []
+        assert graph.query().match("(n:B)").one("n")["n"].term() is None
+        assert graph.query().match("(n:A)").one("n")["n"].term() is not None
+
+    def test_clear_term_of_applied_term_requires_cascade(self):
+        graph = _graph()
+
+        with pytest.raises(ValueError):
+            graph.query().match("(n:A)").clear_term("n").execute()
+
+        assert str(graph.query().match("(n:A)").one("n")["n"].term()) == "a"
+        assert str(graph.query().match("(n:B)").one("n")["n"].term()) == "(f a)"
+
+    def test_clear_term_cascade_clears_applications(self):
+        graph = _graph()
+
+        graph.query().match("(n:A)").clear_term("n", cascade=True).execute()
+
+        assert graph.query().match("(n:A)").one("n")["n"].term() is None
+        assert graph.query().match("(n:B)").one("n")["n"].term() is None
+        assert str(graph.query().match("(n:A -> B)").one("n")["n"].term()) == "f"
+        assert len(graph.edges()) == 1
+
+    def test_clear_term_rejects_non_nodes(self):
+        graph = _graph()
+
+        with pytest.raises(ValueError):
+            graph.query().match("()-[e]->()").clear_term("e").execute()
+
+    def test_clear_term_emits_event(self):
+        graph = implica.Graph(constants=[implica.Constant("a", "A")])
+        graph.query().create("(:A:@a())").execute()
+        events = []
+        graph.on_change(events.append)
+
+        graph.nodes()[0].clear_term()
+
+        assert [event["kind"] for event in events] == ["node_term_cleared"]
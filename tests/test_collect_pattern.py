@@ -0,0 +1,69 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph(
+        constants=[
+            implica.Constant("ab", "Alice -> Bob"),
+            implica.Constant("ac", "Alice -> Carol"),
+            implica.Constant("bc", "Bob -> Carol"),
+        ]
+    )
+    (
+        graph.query()
+        .create("(:Alice { name: 'alice' })-[::@ab() { since: 2020 }]->(:Bob { name: 'bob' })")
+        .create("(:Alice)-[::@ac()]->(:Carol { name: 'carol' })")
+        .create("(:Bob)-[::@bc()]->(:Carol)")
+        .execute()
+    )
+    return graph
+
+
+def _known(rows, variable="b"):
+    return {
+        row["a"].properties()["name"]: sorted(n.properties()["name"] for n in row[variable]) for row in rows
+    }
+
+
+class TestCollectPattern:
+    def test_collects_known_people_per_person(self):
+        rows = _graph().query().match("(a)").collect_pattern("a", "(a)-[]->(b)", collect="b").return_("a", "b")
+
+        assert _known(rows) == {"alice": ["bob", "carol"], "bob": ["carol"], "carol": []}
+
+    def test_alias_keeps_collected_name_free(self):
+        rows = (
+            _graph()
+            .query()
+            .match("(a:Alice)")
+            .collect_pattern("a", "(a)-[e]->(b)", collect="e", as_="links")
+            .return_("a", "links")
+        )
+
+        assert len(rows) == 1
+        assert sorted(str(e.type()) for e in rows[0]["links"]) == ["(Alice -> Bob)", "(Alice -> Carol)"]
+
+    def test_collected_list_is_visible_to_where(self):
+        rows = (
+            _graph()
+            .query()
+            .match("(a)")
+            .collect_pattern("a", "(a)-[]->(b)", collect="b")
+            .where_("b.len() >= 1")
+            .return_("a", "b")
+        )
+
+        assert set(_known(rows)) == {"alice", "bob"}
+
+    def test_list_binding_cannot_be_set(self):
+        query = _graph().query().match("(a)").collect_pattern("a", "(a)-[]->(b)", collect="b").set("b", {"x": 1})
+
+        with pytest.raises(ValueError, match="bound to a list"):
+            query.execute()
+
+    def test_names_must_appear_in_pattern(self):
+        with pytest.raises(ValueError):
+            _graph().query().match("(a)").collect_pattern("a", "(x)-[]->(b)", collect="b")
+        with pytest.raises(ValueError):
+            _graph().query().match("(a)").collect_pattern("a", "(a)-[]->(b)", collect="c")
@@ -0,0 +1,162 @@
+import threading
+
+import implica
+
+
+def _graph():
+    graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+    graph.query().create("(:A { x: 0 })-[::@f()]->(:B)").execute()
+    return graph
+
+
+def _run(workers, timeout=60):
+    errors = []
+
+    def guard(worker):
+        def run():
+            try:
+                worker()
+            except Exception as e:  # pragma: no cover - reported below
+                errors.append(e)
+
+        return run
+
+    threads = [threading.Thread(target=guard(worker), daemon=True) for worker in workers]
+    for thread in threads:
+        thread.start()
+    for thread in threads:
+        thread.join(timeout)
+
+    assert not any(thread.is_alive() for thread in threads), "queries deadlocked"
+    assert errors == []
+
+
+class TestConcurrentQueries:
+    def test_concurrent_matches_and_creates(self):
+        graph = _graph()
+
+        def writer(offset):
+            def run():
+                for i in range(100):
+                    graph.query().create(f"(:T{offset}_{i} {{ i: {i} }})").execute()
+
+            return run
+
+        def reader():
+            for _ in range(100):
+                graph.query().match("(n)").return_("n")
+                graph.query().match("(n:A)-[e]->(m)").return_("e")
+                graph.query().match("(n:*)").limit(3).return_("n")
+
+        _run([writer(0), writer(1), reader, reader])
+
+        assert len(graph.nodes()) == 202
+
+    def test_concurrent_matches_and_removals(self):
+        graph = _graph()
+        for i in range(200):
+            graph.query().create(f"(:R{i} {{ i: {i} }})").execute()
+
+        def remover(parity):
+            def run():
+                for i in range(parity, 200, 2):
+                    graph.query().match(f"(n:R{i})").remove("n").execute()
+
+            return run
+
+        def reader():
+            for _ in range(100):
+                for row in graph.query().match("(n { i: 1 })").return_("n"):
+                    assert row["n"] is not None
+
+        _run([remover(0), remover(1), reader, reader])
+
+        assert len(graph.nodes()) == 2
+
+    def test_concurrent_property_updates(self):
+        graph = _graph()
+
+        def updater(value):
+            def run():
+                for _ in range(100):
+                    graph.query().match("(n:A)").set("n", {"x": value}).execute()
+                    graph.query().match("(n:A { x: 0 })").return_("n")
+
+            return run
+
+        _run([updater(1), updater(2), updater(3)])
+
+        assert graph.query().match("(n:A)").one("n")["n"].properties()["x"] in (1, 2, 3)
+
+    def test_edges_of_removed_nodes_are_never_matched(self):
+        constants = [implica.Constant(f"f{i}", f"S{i} -> E{i}") for i in range(100)]
+        graph = implica.Graph(constants=constants)
+        for i in range(100):
+            graph.query().create(f"(:S{i})-[::@f{i}()]->(:E{i})").execute()
+
+        def remover():
+            for i in range(100):
+                graph.query().match(f"(n:E{i})").remove("n").execute()
+
+        def reader():
+            for _ in range(50):
+                rows = graph.query().match("(a)-[e]->(b)").return_("a", "e", "b")
+                for row in rows:
+                    start, end = row["e"].uid()
+                    assert (start, end) == (row["a"].uid(), row["b"].uid())
+
+        _run([remover, reader, reader])
+
+        assert graph.edges() == []
+        assert graph.query().match("(a)-[e]->(b)").return_("e") == []
+
+
+class TestLockRetry:
+    def test_retried_reads_succeed_under_contention(self):
+        graph = _graph()
+        graph.set_lock_retry(50, backoff_ms=1)
+
+        def updater(value):
+            def run():
+                for _ in range(200):
+                    graph.set_node_properties({uid: {"x": value, "y": value}})
+
+            return run
+
+        def reader():
+            for _ in range(200):
+                graph.query().match("(n:A { x: 1 })").return_("n")
+                graph.query().match("(n:A)-[e { w: 1 }]->(m)").return_("e")
+
+        uid = graph.query().match("(n:A)").one("n")["n"].uid()
+        _run([updater(1), updater(2), reader, reader])
+
+        assert graph.query().match("(n:A)").one("n")["n"].properties()["x"] in (1, 2)
+
+    def test_disabling_retries_restores_blocking_reads(self):
+        graph = _graph()
+        graph.set_lock_retry(3)
+        graph.set_lock_retry(0)
+
+        def updater():
+            for i in range(100):
+                graph.query().match("(n:A)").set("n", {"x": i}).execute()
+
+        def reader():
+            for _ in range(100):
+                graph.query().match("(n:A { x: 0 })").return_("n")
+
+        _run([updater, reader, reader])
+
+
+class TestStrictEdges:
+    def test_strict_edges_leave_consistent_matches_alone(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+        graph.query().create("(:A)-[::@f()]->(:B)").execute()
+        graph.set_strict_edges(True)
+
+        assert len(graph.query().match("(a)-[e]->(b)").return_("e")) == 1
+
+        graph.query().match("(n:B)").remove("n").execute()
+
+        assert graph.query().match("(a)-[e]->(b)").return_("e") == []
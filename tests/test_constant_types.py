@@ -0,0 +1,74 @@
+import implica
+import pytest
+
+
+def _graph():
+    constants = [
+        implica.Constant("f", "A -> B"),
+        implica.Constant("a", "A"),
+        implica.Constant("id", "(X:*) -> X"),
+    ]
+    graph = implica.Graph(constants=constants)
+    graph.query().create("(:A)-[::@f()]->(:B)").create("(:(C -> C))").execute()
+    return graph, {c.name: c for c in constants}
+
+
+def _type(graph, name):
+    return graph.query().match(f"(n:{name})").one("n")["n"].type()
+
+
+class TestConstantTypes:
+    def test_constant_type(self):
+        graph, constants = _graph()
+
+        assert str(constants["f"].type(graph)) == "(A -> B)"
+        assert constants["a"].type(graph) == _type(graph, "A")
+
+    def test_constant_inhabits_its_own_type(self):
+        graph, constants = _graph()
+
+        assert constants["a"].inhabits(_type(graph, "A"))
+        assert constants["f"].inhabits(constants["f"].type(graph))
+
+    def test_constant_does_not_inhabit_unrelated_type(self):
+        graph, constants = _graph()
+
+        assert not constants["a"].inhabits(_type(graph, "B"))
+        assert not constants["f"].inhabits(_type(graph, "A"))
+
+    def test_parametric_constant_inhabits_its_instances(self):
+        graph, constants = _graph()
+
+        assert constants["id"].inhabits(_type(graph, "C -> C"))
+        assert not constants["id"].inhabits(constants["f"].type(graph))
+
+    def test_parametric_constant_has_no_type(self):
+        graph, constants = _graph()
+
+        with pytest.raises(ValueError, match="parametric"):
+            constants["id"].type(graph)
+
+    def test_type_missing_from_graph_raises(self):
+        graph = implica.Graph()
+
+        with pytest.raises(KeyError):
+            implica.Constant("g", "B -> C").type(graph)
+
+    def test_reading_the_type_leaves_the_graph_untouched(self):
+        graph, constants = _graph()
+        graph.freeze()
+        version = graph.version()
+        types = graph.stats()["types"]
+
+        assert str(constants["f"].type(graph)) == "(A -> B)"
+        with pytest.raises(KeyError):
+            implica.Constant("g", "B -> C").type(graph)
+
+        assert graph.version() == version
+        assert graph.stats()["types"] == types
+
+    def test_wildcard_constant_has_no_type(self):
+        graph = implica.Graph()
+
+        with pytest.raises(ValueError):
+            implica.Constant("any", "*").type(graph)
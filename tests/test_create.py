@@ -260,6 +260,36 @@ class TestCreateEdgeQuery:
         assert len(edges) == 2
         assert {str(e) for e in edges} == {"Edge((A -> B):f {})", "Edge((B -> C):g {})"}
 
+    def test_edge_cannot_carry_a_second_term(self):
+        graph = implica.Graph(
+            constants=[implica.Constant("f", "A -> B"), implica.Constant("g", "A -> B")]
+        )
+        graph.query().create("(:A)-[::@f()]->(:B)").execute()
+
+        # An edge holds the single term of its type, so g cannot join or replace f
+        with pytest.raises(ValueError, match="already holds the term 'f'"):
+            graph.query().create("(:A)-[::@g()]->(:B)").execute()
+        with pytest.raises(ValueError, match="already holds the term 'f'"):
+            graph.query().create(
+                "(:A)-[::@g() {w: 1}]->(:B)", merge_properties=True
+            ).execute()
+
+        edges = graph.edges()
+        assert [str(e) for e in edges] == ["Edge((A -> B):f {})"]
+        assert not hasattr(edges[0], "terms")
+
+    def test_rejected_edge_leaves_the_graph_unchanged(self):
+        graph = implica.Graph(
+            constants=[implica.Constant("f", "A -> B"), implica.Constant("g", "A -> B")]
+        )
+        graph.query().create("(:A)-[::@f()]->(:B)").execute()
+        before = graph.stats()
+
+        with pytest.raises(ValueError, match="already holds the term 'f'"):
+            graph.query().create("(:A)-[::@g()]->(:B)").execute()
+
+        assert graph.stats() == before
+
 
 class TestCreateInference:
     def test_create_infers_term_for_node_if_constant_of_that_type_exists(self):
@@ -271,6 +301,25 @@ class TestCreateInference:
         assert len(nodes) == 1
         assert str(nodes[0]) == "Node(A:f {})"
 
+    def test_create_does_not_infer_term_when_several_constants_fit(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "A"), implica.Constant("g", "A")])
+
+        graph.query().create("(:A)").execute()
+
+        nodes = graph.nodes()
+        assert len(nodes) == 1
+        assert str(nodes[0]) == "Node(A: {})"
+        assert nodes[0].term() is None
+
+    def test_create_explicit_term_wins_over_inference(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "A"), implica.Constant("g", "A")])
+
+        graph.query().create("(:A:@g())").execute()
+
+        nodes = graph.nodes()
+        assert len(nodes) == 1
+        assert str(nodes[0]) == "Node(A:g {})"
+
     def test_create_infers_term_for_edge_if_constant_of_that_type_exists(self):
         graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
 
@@ -315,3 +364,180 @@ class TestCreateInference:
 
         result = graph.query().match("(:A)-[E::x y]->(:B)").return_("E")
         assert len(result) == 1
+
+
+class TestCreateMergeProperties:
+    def test_create_drops_properties_of_existing_node_by_default(self):
+        graph = implica.Graph()
+        graph.query().create("(:A { x: 1 })").execute()
+        graph.query().create("(:A { y: 2 })").execute()
+
+        nodes = graph.nodes()
+        assert len(nodes) == 1
+        assert nodes[0].properties() == {"x": 1}
+
+    def test_create_merges_properties_into_existing_node(self):
+        graph = implica.Graph()
+        graph.query().create("(:A { x: 1 })").execute()
+        graph.query().create("(:A { y: 2 })", merge_properties=True).execute()
+
+        nodes = graph.nodes()
+        assert len(nodes) == 1
+        assert nodes[0].properties() == {"x": 1, "y": 2}
+
+    def test_create_merge_overrides_conflicting_node_properties(self):
+        graph = implica.Graph()
+        graph.query().create("(:A { x: 1, y: 1 })").execute()
+        graph.query().create("(:A { y: 2 })", merge_properties=True).execute()
+
+        assert graph.nodes()[0].properties() == {"x": 1, "y": 2}
+
+    def test_create_merge_sets_properties_on_new_node(self):
+        graph = implica.Graph()
+        graph.query().create("(:A { x: 1 })", merge_properties=True).execute()
+
+        assert graph.nodes()[0].properties() == {"x": 1}
+
+    def test_create_merge_applies_to_reused_endpoint_of_path(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+        graph.query().create("(:A { x: 1 })").execute()
+        graph.query().create(
+            "(:A { y: 2 })-[::@f()]->(:B { z: 3 })", merge_properties=True
+        ).execute()
+
+        props = {str(n.type()): n.properties() for n in graph.nodes()}
+        assert props == {"A": {"x": 1, "y": 2}, "B": {"z": 3}}
+
+    def test_create_merges_properties_into_existing_edge(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+        graph.query().create("(:A)-[::@f() { w: 1 }]->(:B)").execute()
+        graph.query().create("(:A)-[::@f() { v: 2 }]->(:B)", merge_properties=True).execute()
+
+        edges = graph.edges()
+        assert len(edges) == 1
+        assert edges[0].properties() == {"w": 1, "v": 2}
+
+    def test_repeated_edge_creation_never_duplicates(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+        query = graph.query()
+        for i in range(5):
+            query = query.create(f"(:A)-[::@f() {{ w{i}: {i} }}]->(:B)", merge_properties=True)
+        query.execute()
+        graph.query().create("()-[::@f()]->()", merge_properties=True).execute()
+
+        edges = graph.edges()
+        assert len(edges) == 1
+        assert edges[0].properties() == {f"w{i}": i for i in range(5)}
+
+    def test_create_overwrite_replaces_properties_of_existing_node(self):
+        graph = implica.Graph()
+        graph.query().create("(:A { x: 1, y: 1 })").execute()
+        graph.query().create("(:A { y: 2 })", merge_properties=True, overwrite=True).execute()
+
+        nodes = graph.nodes()
+        assert len(nodes) == 1
+        assert nodes[0].properties() == {"y": 2}
+
+    def test_create_overwrite_replaces_properties_of_existing_edge(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+        graph.query().create("(:A)-[::@f() { w: 1 }]->(:B)").execute()
+        graph.query().create(
+            "(:A)-[::@f() { v: 2 }]->(:B)", merge_properties=True, overwrite=True
+        ).execute()
+
+        edges = graph.edges()
+        assert len(edges) == 1
+        assert edges[0].properties() == {"v": 2}
+
+    def test_create_overwrite_without_merge_keeps_existing_properties(self):
+        graph = implica.Graph()
+        graph.query().create("(:A { x: 1 })").execute()
+        graph.query().create("(:A { y: 2 })", overwrite=True).execute()
+
+        assert graph.nodes()[0].properties() == {"x": 1}
+
+
+class TestCreatePropertyFn:
+    def test_property_fn_sets_property_from_type(self):
+        graph = implica.Graph()
+
+        graph.query().create(
+            "(:A)", property_fn=lambda type, term: {"label": f"node of {type}"}
+        ).execute()
+
+        assert graph.nodes()[0].properties() == {"label": "node of A"}
+
+    def test_property_fn_receives_term(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+        calls = []
+
+        def property_fn(type, term):
+            calls.append((type, term))
+            return {}
+
+        graph.query().create("(:A -> B:@f())", property_fn=property_fn).execute()
+
+        assert ("(A -> B)", "f") in calls
+
+    def test_property_fn_is_called_for_every_new_node_of_a_path(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+
+        graph.query().create(
+            "(:A)-[::@f()]->(:B)", property_fn=lambda type, term: {"name": type}
+        ).execute()
+
+        assert {n.properties()["name"] for n in graph.nodes()} == {"A", "B"}
+
+    def test_static_properties_win_by_default(self):
+        graph = implica.Graph()
+
+        graph.query().create(
+            "(:A { x: 1 })", property_fn=lambda type, term: {"x": 2, "y": 3}
+        ).execute()
+
+        assert graph.nodes()[0].properties() == {"x": 1, "y": 3}
+
+    def test_property_fn_wins_when_requested(self):
+        graph = implica.Graph()
+
+        graph.query().create(
+            "(:A { x: 1 })",
+            property_fn=lambda type, term: {"x": 2},
+            property_fn_wins=True,
+        ).execute()
+
+        assert graph.nodes()[0].properties() == {"x": 2}
+
+    def test_property_fn_is_not_called_for_existing_nodes(self):
+        graph = implica.Graph()
+        graph.query().create("(:A)").execute()
+        calls = []
+
+        graph.query().create("(:A)", property_fn=lambda t, term: calls.append(t) or {}).execute()
+
+        assert calls == []
+
+    def test_property_fn_errors_are_raised(self):
+        graph = implica.Graph()
+
+        def property_fn(type, term):
+            raise RuntimeError("boom")
+
+        with pytest.raises(RuntimeError, match="boom"):
+            graph.query().create("(:A)", property_fn=property_fn).execute()
+
+
+class TestCreateConstantResolution:
+    def test_constants_are_pinned_when_the_graph_is_built(self):
+        constants = [implica.Constant("f", "A")]
+        graph = implica.Graph(constants=constants)
+        query = graph.query().create("(:B:@g())")
+
+        constants.append(implica.Constant("g", "B"))
+        constants.pop(0)
+
+        with pytest.raises(KeyError):
+            query.execute()
+
+        graph.query().create("(:A:@f())").execute()
+        assert [str(n) for n in graph.nodes()] == ["Node(A:f {})"]
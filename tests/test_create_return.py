@@ -0,0 +1,46 @@
+import implica
+
+
+class TestCreateReturn:
+    def test_create_return_gives_back_the_created_node(self):
+        graph = implica.Graph()
+
+        rows = graph.query().create_return("(n:A { x: 1 })")
+
+        assert len(rows) == 1
+        node = rows[0]["n"]
+        assert str(node.type()) == "A"
+        assert node.properties() == {"x": 1}
+        assert node in graph.nodes()
+
+    def test_create_return_defaults_to_node_and_edge_variables(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+
+        rows = graph.query().create_return("(a:A)-[e::@f()]->(b:B)")
+
+        assert sorted(rows[0].keys()) == ["a", "b", "e"]
+        assert rows[0]["e"].uid() == (rows[0]["a"].uid(), rows[0]["b"].uid())
+
+    def test_create_return_selected_variables(self):
+        graph = implica.Graph()
+
+        rows = graph.query().create("(a:A)").create_return("(b:B)", "a", "b")
+
+        assert sorted(rows[0].keys()) == ["a", "b"]
+        assert len(graph.nodes()) == 2
+
+    def test_create_return_after_match(self):
+        graph = implica.Graph()
+        graph.query().create("(:A)").execute()
+
+        rows = graph.query().match("(a:A)").create_return("(b:B { y: 2 })")
+
+        assert [str(row["b"].type()) for row in rows] == ["B"]
+
+    def test_create_return_accepts_create_options(self):
+        graph = implica.Graph()
+        graph.query().create("(:A { x: 1 })").execute()
+
+        rows = graph.query().create_return("(n:A { y: 2 })", merge_properties=True)
+
+        assert rows[0]["n"].properties() == {"x": 1, "y": 2}
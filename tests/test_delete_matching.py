@@ -0,0 +1,100 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph(
+        constants=[
+            implica.Constant("f", "Temp -> Keep"),
+            implica.Constant("g", "Keep -> Other"),
+            implica.Constant("h", "Other -> Temp"),
+        ]
+    )
+    (
+        graph.query()
+        .create("(:Temp)-[::@f()]->(:Keep)")
+        .create("(:Keep)-[::@g()]->(:Other)")
+        .create("(:Other)-[::@h()]->(:Temp)")
+        .create("(:Temp -> Temp { tmp: true })")
+        .execute()
+    )
+    return graph
+
+
+class TestDeleteMatching:
+    def test_deletes_nodes_of_a_type_and_their_edges(self):
+        graph = _graph()
+
+        deleted = graph.query().delete_matching("(n:Temp)")
+
+        assert deleted == 1
+        assert graph.query().match("(n:Temp)").return_("n") == []
+        assert len(graph.nodes()) == 3
+        assert len(graph.edges()) == 1
+        assert graph.query().match("(a:Keep)-[]->(b:Other)").exists()
+
+    def test_unnamed_elements_are_deleted(self):
+        graph = _graph()
+
+        deleted = graph.query().delete_matching("(:Keep)-[]->()")
+
+        assert deleted == 3
+        assert sorted(str(node.type()) for node in graph.nodes()) == ["(Temp -> Temp)", "Temp"]
+        assert graph.edges() == []
+
+    def test_edge_pattern_deletes_the_edge_and_its_endpoints(self):
+        graph = _graph()
+
+        deleted = graph.query().delete_matching("()-[e:Keep -> Other]->()")
+
+        # The anonymous endpoints are matched too, so they go with the edge
+        assert deleted == 3
+        assert sorted(str(node.type()) for node in graph.nodes()) == ["(Temp -> Temp)", "Temp"]
+        assert graph.edges() == []
+
+    def test_variable_length_edges_are_rejected(self):
+        graph = _graph()
+
+        with pytest.raises(ValueError, match="variable-length"):
+            graph.query().delete_matching("(:Temp)-[| hops=1..2]->()")
+
+        assert len(graph.nodes()) == 4
+        assert len(graph.edges()) == 3
+
+    def test_nothing_matched(self):
+        graph = _graph()
+
+        assert graph.query().delete_matching("(n:Missing)") == 0
+        assert len(graph.nodes()) == 4
+        assert len(graph.edges()) == 3
+
+    def test_runs_after_previous_operations(self):
+        graph = _graph()
+
+        deleted = graph.query().match("(n { tmp: true })").delete_matching("(n)")
+
+        assert deleted == 1
+        assert len(graph.nodes()) == 3
+        assert len(graph.edges()) == 3
+
+    def test_shared_elements_are_counted_once(self):
+        graph = _graph()
+
+        deleted = graph.query().delete_matching("(a)-[]->(b)")
+
+        assert deleted == 6
+        assert [str(node.type()) for node in graph.nodes()] == ["(Temp -> Temp)"]
+
+    def test_observers_are_notified(self):
+        graph = _graph()
+        edge_uid = next(
+            e.uid() for e in graph.edges() if str(e.type()) == "(Temp -> Keep)"
+        )
+        events = []
+        graph.on_change(events.append)
+
+        graph.query().delete_matching("(:Temp)-[]->(:Keep)")
+
+        # Detaching the two nodes also removes their other two edges
+        assert sorted(e["kind"] for e in events) == ["edge_removed"] * 3 + ["node_removed"] * 2
+        assert events[0] == {"kind": "edge_removed", "uid": edge_uid}
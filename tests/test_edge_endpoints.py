@@ -0,0 +1,40 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph(constants=[implica.Constant("f", "A -> B"), implica.Constant("x", "A")])
+    graph.query().create("(:A:@x())-[::@f()]->(:B)").execute()
+    return graph
+
+
+class TestEdgeEndpoints:
+    def test_endpoint_types_line_up_with_the_arrow(self):
+        edge = _graph().edges()[0]
+
+        start, end = edge.endpoint_types()
+
+        assert str(edge.type()) == "(A -> B)"
+        assert (str(start), str(end)) == ("A", "B")
+
+    def test_endpoint_terms(self):
+        start, end = _graph().edges()[0].endpoint_terms()
+
+        assert (str(start), str(end)) == ("x", "(f x)")
+
+    def test_endpoint_without_a_term_is_none(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+        graph.query().create("(:A)").create("(:B)").execute()
+        graph.query().match("(a:A)").match("(b:B)").create("(a)-[::@f()]->(b)").execute()
+
+        start, _ = graph.edges()[0].endpoint_terms()
+
+        assert start is None
+
+    def test_endpoints_of_a_removed_edge_raise(self):
+        graph = _graph()
+        edge = graph.edges()[0]
+        graph.query().match("()-[e]->()").remove("e").execute()
+
+        with pytest.raises(KeyError):
+            edge.endpoint_types()
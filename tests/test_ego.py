@@ -0,0 +1,93 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph(
+        constants=[
+            implica.Constant("f", "A -> B"),
+            implica.Constant("g", "C -> A"),
+            implica.Constant("h", "B -> D"),
+            implica.Constant("k", "C -> B"),
+            implica.Constant("x", "C"),
+        ]
+    )
+    (
+        graph.query()
+        .create("(:A { name: 'a' })-[::@f() { w: 1 }]->(:B)-[::@h()]->(:D)")
+        .create("(:C:@x())-[::@g()]->(:A)")
+        .create("(:C)-[::@k()]->(:B)")
+        .create("(:E)")
+        .execute()
+    )
+    return graph
+
+
+def _node(graph, type_name):
+    return next(n for n in graph.nodes() if str(n.type()) == type_name)
+
+
+def _types(elements):
+    return sorted(str(e.type()) for e in elements)
+
+
+class TestEgo:
+    def test_radius_one_holds_neighbours_and_edges_among_them(self):
+        graph = _graph()
+
+        ego = graph.query().ego(_node(graph, "A"))
+
+        assert _types(ego.nodes()) == ["A", "B", "C"]
+        assert _types(ego.edges()) == ["(A -> B)", "(C -> A)", "(C -> B)"]
+
+    def test_direction_limits_the_expansion(self):
+        graph = _graph()
+
+        ego = graph.query().ego(_node(graph, "A"), radius=2, direction="forward")
+
+        assert _types(ego.nodes()) == ["A", "B", "D"]
+        assert _types(ego.edges()) == ["(A -> B)", "(B -> D)"]
+
+    def test_radius_zero_holds_only_the_node(self):
+        graph = _graph()
+
+        ego = graph.query().ego(_node(graph, "A"), radius=0)
+
+        assert _types(ego.nodes()) == ["A"]
+        assert ego.edges() == []
+
+    def test_ego_network_is_a_copy(self):
+        graph = _graph()
+        ego = graph.query().ego(_node(graph, "A"))
+
+        ego.query().match("(n:A)").set("n", {"name": "changed"}).execute()
+        ego.query().match("(n:B)").remove("n").execute()
+
+        assert _node(graph, "A").properties() == {"name": "a"}
+        assert len(graph.nodes()) == 5
+        assert _node(ego, "A").properties() == {"name": "changed"}
+
+    def test_ego_network_keeps_terms_and_properties(self):
+        graph = _graph()
+
+        ego = graph.query().ego(_node(graph, "A"))
+
+        assert str(_node(ego, "C").term()) == "x"
+        assert str(_node(ego, "A").term()) == "(g x)"
+        edge = next(e for e in ego.edges() if str(e.type()) == "(A -> B)")
+        assert edge.properties() == {"w": 1}
+        assert str(edge.term()) == "f"
+
+    def test_ego_network_can_be_queried_and_extended(self):
+        graph = _graph()
+        ego = graph.query().ego(_node(graph, "A"))
+
+        assert len(ego.query().match("(a:A)-[e]->(b:B)").return_("e")) == 1
+        ego.query().match("(b:B)").create("(b)-[::@h()]->(:D)").execute()
+        assert _types(ego.nodes()) == ["A", "B", "C", "D"]
+
+    def test_unknown_direction_raises(self):
+        graph = _graph()
+
+        with pytest.raises(ValueError):
+            graph.query().ego(_node(graph, "A"), direction="sideways")
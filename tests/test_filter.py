@@ -0,0 +1,62 @@
+import pytest
+import implica
+
+
+class TestQueryFilter:
+    """Tests for filtering rows with a Python callback."""
+
+    def test_filter_by_property(self):
+        graph = implica.Graph()
+        (
+            graph.query()
+            .create("(:A { age: 20 })")
+            .create("(:B { age: 35 })")
+            .create("(:C { age: 50 })")
+            .execute()
+        )
+
+        result = (
+            graph.query().match("(N)").filter(lambda row: row["N"].properties()["age"] > 30)
+        ).return_("N")
+
+        assert {str(r["N"].type()) for r in result} == {"B", "C"}
+
+    def test_filter_receives_all_bound_references(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "(A:*)->(B:*)")])
+        graph.query().create("(:A)").create("(:B)").create("(:C)").execute()
+        (
+            graph.query()
+            .create("()-[::@f(A, B)]->()")
+            .create("()-[::@f(A, C)]->()")
+            .execute()
+        )
+
+        seen = []
+
+        def predicate(row):
+            seen.append(set(row.keys()))
+            return isinstance(row["E"], implica.Edge) and str(row["M"].type()) == "C"
+
+        result = graph.query().match("(N)-[E]->(M)").filter(predicate).return_("N", "E", "M")
+
+        assert len(result) == 1
+        assert str(result[0]["E"]) == "Edge((A -> C):f {})"
+        assert all({"N", "E", "M"} <= keys for keys in seen)
+
+    def test_filter_uses_truthiness(self):
+        graph = implica.Graph()
+        graph.query().create("(:A { tags: [] })").create("(:B { tags: ['x'] })").execute()
+
+        result = graph.query().match("(N)").filter(lambda row: row["N"].properties()["tags"])
+
+        assert [str(r["N"].type()) for r in result.return_("N")] == ["B"]
+
+    def test_filter_exception_propagates(self):
+        graph = implica.Graph()
+        graph.query().create("(:A)").execute()
+
+        def predicate(row):
+            raise ZeroDivisionError("boom")
+
+        with pytest.raises(ZeroDivisionError):
+            graph.query().match("(N)").filter(predicate).return_("N")
@@ -0,0 +1,105 @@
+import implica
+import pytest
+
+
+def _node(graph, type_name):
+    return next(n for n in graph.nodes() if str(n.type()) == type_name)
+
+
+class TestGraphInOutEdges:
+    def _graph(self):
+        graph = implica.Graph(
+            constants=[
+                implica.Constant("f", "A -> B"),
+                implica.Constant("g", "B -> C"),
+                implica.Constant("h", "C -> B"),
+            ]
+        )
+        graph.query().create("(:A)-[::@f()]->(:B)-[::@g()]->(:C)").execute()
+        graph.query().create("(:C)-[::@h()]->(:B)").execute()
+        return graph
+
+    def test_out_edges_of_node_with_mixed_direction_edges(self):
+        graph = self._graph()
+        b = _node(graph, "B")
+
+        assert [str(e) for e in graph.out_edges(b)] == ["Edge((B -> C):g {})"]
+
+    def test_in_edges_of_node_with_mixed_direction_edges(self):
+        graph = self._graph()
+        b = _node(graph, "B")
+
+        assert {str(e) for e in graph.in_edges(b)} == {
+            "Edge((A -> B):f {})",
+            "Edge((C -> B):h {})",
+        }
+
+    def test_in_and_out_edges_accept_node_uid(self):
+        graph = self._graph()
+        a = _node(graph, "A")
+
+        assert len(graph.out_edges(a.uid())) == 1
+        assert graph.in_edges(a.uid()) == []
+
+    def test_removing_edge_updates_in_and_out_edges(self):
+        graph = self._graph()
+        graph.query().match("(:C)-[e]->(:B)").remove("e").execute()
+
+        assert len(graph.in_edges(_node(graph, "B"))) == 1
+        assert graph.out_edges(_node(graph, "C")) == []
+
+    def test_removing_node_updates_neighbour_edges(self):
+        graph = self._graph()
+        graph.query().match("(n:A)").remove("n").execute()
+
+        assert {str(e) for e in graph.in_edges(_node(graph, "B"))} == {"Edge((C -> B):h {})"}
+
+    def test_in_edges_of_missing_node_raises_key_error(self):
+        graph = self._graph()
+        a = _node(graph, "A")
+        graph.query().match("(n:A)").remove("n").execute()
+
+        with pytest.raises(KeyError):
+            graph.in_edges(a)
+
+
+class TestGraphSourcesAndSinks:
+    def _dag(self):
+        graph = implica.Graph(
+            constants=[
+                implica.Constant("f", "A -> B"),
+                implica.Constant("g", "A -> C"),
+                implica.Constant("h", "B -> D"),
+                implica.Constant("k", "C -> D"),
+                implica.Constant("m", "E -> D"),
+            ]
+        )
+        (
+            graph.query()
+            .create("(:A)-[::@f()]->(:B)-[::@h()]->(:D)")
+            .create("(:A)-[::@g()]->(:C)-[::@k()]->(:D)")
+            .create("(:E)-[::@m()]->(:D)")
+            .create("(:X)")
+            .create("(:Y)")
+            .execute()
+        )
+        return graph
+
+    def _types(self, nodes):
+        return sorted(str(n.type()) for n in nodes)
+
+    def test_sources_have_no_incoming_edges(self):
+        assert self._types(self._dag().sources()) == ["A", "E", "X", "Y"]
+
+    def test_sinks_have_no_outgoing_edges(self):
+        assert self._types(self._dag().sinks()) == ["D", "X", "Y"]
+
+    def test_isolated_nodes_have_no_edges(self):
+        assert self._types(self._dag().isolated()) == ["X", "Y"]
+
+    def test_removing_edges_updates_sources_and_sinks(self):
+        graph = self._dag()
+        graph.query().match("(:E)-[e]->()").remove("e").execute()
+
+        assert self._types(graph.isolated()) == ["E", "X", "Y"]
+        assert self._types(graph.sinks()) == ["D", "E", "X", "Y"]
@@ -0,0 +1,64 @@
+import pytest
+import implica
+
+
+class TestGraphAdjacencyMatrix:
+    def test_graph_adjacency_matrix_empty_graph(self):
+        graph = implica.Graph()
+
+        matrix, nodes = graph.adjacency_matrix()
+
+        assert matrix == []
+        assert nodes == []
+
+    def test_graph_adjacency_matrix_entries(self):
+        graph = implica.Graph(
+            constants=[implica.Constant("f", "A -> B"), implica.Constant("g", "B -> C")]
+        )
+        graph.query().create("(:A)-[::@f()]->(:B)-[::@g()]->(:C)").execute()
+
+        matrix, nodes = graph.adjacency_matrix()
+        index = {str(n.type()): i for i, n in enumerate(nodes)}
+
+        assert len(matrix) == len(nodes)
+        assert all(len(row) == len(nodes) for row in matrix)
+        assert matrix[index["A"]][index["B"]] == 1
+        assert matrix[index["B"]][index["C"]] == 1
+        assert matrix[index["B"]][index["A"]] == 0
+        assert matrix[index["A"]][index["C"]] == 0
+        assert sum(sum(row) for row in matrix) == len(graph.edges())
+
+    def test_graph_adjacency_matrix_node_order_is_stable(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+        graph.query().create("(:A)-[::@f()]->(:B)").create("(:C)").execute()
+
+        first_matrix, first_nodes = graph.adjacency_matrix()
+        second_matrix, second_nodes = graph.adjacency_matrix()
+
+        assert first_matrix == second_matrix
+        assert [n.uid() for n in first_nodes] == [n.uid() for n in second_nodes]
+
+    def test_graph_adjacency_matrix_with_weight(self):
+        graph = implica.Graph(
+            constants=[implica.Constant("f", "A -> B"), implica.Constant("g", "B -> C")]
+        )
+        (
+            graph.query()
+            .create("(:A)-[::@f() { cost: 2.5 }]->(:B)")
+            .create("(:B)-[::@g()]->(:C)")
+            .execute()
+        )
+
+        matrix, nodes = graph.adjacency_matrix(weight="cost")
+        index = {str(n.type()): i for i, n in enumerate(nodes)}
+
+        assert matrix[index["A"]][index["B"]] == 2.5
+        assert matrix[index["B"]][index["C"]] == 1.0
+        assert matrix[index["C"]][index["A"]] == 0.0
+
+    def test_graph_adjacency_matrix_with_non_numeric_weight_raises(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+        graph.query().create("(:A)-[::@f() { cost: 'high' }]->(:B)").execute()
+
+        with pytest.raises(TypeError):
+            graph.adjacency_matrix(weight="cost")
@@ -0,0 +1,94 @@
+import implica
+import pytest
+
+
+def _graph():
+    return implica.Graph(
+        constants=[implica.Constant("f", "A -> B"), implica.Constant("a", "A")]
+    )
+
+
+class TestGraphBuilder:
+    def test_build_nodes_and_edges(self):
+        graph = _graph()
+
+        created = (
+            graph.build()
+            .node("x", type="A", term="@a()", properties={"k": 1})
+            .node("y", type="B")
+            .edge("x", "y", term="@f()", name="e", properties={"w": 2})
+            .commit()
+        )
+
+        assert set(created) == {"x", "y", "e"}
+        assert str(created["x"]) == "Node(A:a {k: 1})"
+        assert created["e"].properties() == {"w": 2}
+        assert len(graph.nodes()) == 2
+        assert len(graph.edges()) == 1
+        assert graph.query().match("(:A)-[e]->(:B)").one("e")["e"] == created["e"]
+
+    def test_unnamed_edges_are_created_but_not_returned(self):
+        graph = _graph()
+
+        created = graph.build().node("x", type="A").node("y", type="B").edge("x", "y", term="@f()").commit()
+
+        assert set(created) == {"x", "y"}
+        assert len(graph.edges()) == 1
+
+    def test_empty_builder_commits_nothing(self):
+        graph = _graph()
+
+        assert graph.build().commit() == {}
+        assert graph.nodes() == []
+
+    def test_failed_commit_leaves_graph_untouched(self):
+        graph = _graph()
+        graph.query().create("(:C)").execute()
+
+        builder = graph.build().node("x", type="A").node("y", type="B").edge("y", "x")
+        with pytest.raises(ValueError):
+            builder.commit()
+
+        assert [str(n.type()) for n in graph.nodes()] == ["C"]
+        assert graph.edges() == []
+
+    def test_failed_commit_keeps_existing_elements(self):
+        graph = _graph()
+        graph.query().create("(:A { keep: true })").execute()
+
+        builder = graph.build().node("x", type="A").node("y", type="B").edge("y", "x")
+        with pytest.raises(ValueError):
+            builder.commit()
+
+        assert [(str(n.type()), n.properties()) for n in graph.nodes()] == [("A", {"keep": True})]
+
+    def test_failed_commit_reports_its_removals(self):
+        graph = _graph()
+        events = []
+        graph.on_change(events.append)
+
+        builder = graph.build().node("x", type="A").node("y", type="B").edge("y", "x")
+        with pytest.raises(ValueError):
+            builder.commit()
+
+        added = sorted(e["uid"] for e in events if e["kind"] == "node_added")
+        removed = sorted(e["uid"] for e in events if e["kind"] == "node_removed")
+        assert len(added) == 2
+        assert removed == added
+
+    def test_commit_on_frozen_graph_raises_the_original_error(self):
+        graph = _graph()
+        graph.freeze()
+
+        with pytest.raises(ValueError, match="add node"):
+            graph.build().node("x", type="A").commit()
+
+        assert graph.nodes() == []
+
+    def test_edge_between_unknown_nodes_raises(self):
+        with pytest.raises(KeyError):
+            _graph().build().node("x", type="A").edge("x", "z")
+
+    def test_duplicate_names_raise(self):
+        with pytest.raises(KeyError):
+            _graph().build().node("x", type="A").node("x", type="B")
@@ -0,0 +1,74 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+    graph.query().create("(:A { x: 1 })-[::@f()]->(:B)").execute()
+    return graph
+
+
+class TestGraphFreeze:
+    def test_freeze_rejects_mutations_until_unfrozen(self):
+        graph = _graph()
+        graph.freeze()
+
+        with pytest.raises(ValueError, match="graph is frozen"):
+            graph.query().create("(:C)").execute()
+
+        graph.unfreeze()
+        graph.query().create("(:C)").execute()
+
+        assert len(graph.query().match("(n:C)").return_("n")) == 1
+
+    def test_every_mutation_is_rejected(self):
+        graph = _graph()
+        uid = graph.query().match("(n:A)").one("n")["n"].uid()
+        edge = graph.edges()[0].uid()
+        graph.freeze()
+
+        mutations = [
+            lambda: graph.query().match("(n:A)").set("n", {"x": 2}).execute(),
+            lambda: graph.query().match("(n:A)").remove("n").execute(),
+            lambda: graph.query().match("()-[e]->()").remove("e").execute(),
+            lambda: graph.query().match("(n:B)").clear_term("n").execute(),
+            lambda: graph.set_node_properties({uid: {"x": 2}}),
+            lambda: graph.set_edge_properties({edge: {"w": 1}}),
+            lambda: graph.declare_subtype("B", "A"),
+            lambda: implica.Type.from_sexpr("(arrow B A)", graph),
+            lambda: implica.Term.from_sexpr("f", graph),
+        ]
+        for mutation in mutations:
+            with pytest.raises(ValueError, match="graph is frozen"):
+                mutation()
+
+        assert graph.query().match("(n:A)").one("n")["n"].properties() == {"x": 1}
+        assert len(graph.nodes()) == 2
+        assert len(graph.edges()) == 1
+
+    def test_reads_work_while_frozen(self):
+        graph = _graph()
+        version = graph.version()
+        graph.freeze()
+
+        assert graph.is_frozen()
+        assert len(graph.query().match("(a)-[e]->(b)").return_("e")) == 1
+        assert graph.stats()["nodes"] == 2
+        assert graph.version() == version
+
+    def test_restore_is_rejected_while_frozen(self):
+        graph = _graph()
+        checkpoint = graph.snapshot()
+        graph.freeze()
+
+        with pytest.raises(ValueError, match="graph is frozen"):
+            graph.restore(checkpoint)
+
+    def test_unfreeze_restores_mutability(self):
+        graph = _graph()
+        graph.freeze()
+        graph.unfreeze()
+
+        assert not graph.is_frozen()
+        graph.query().match("(n:A)").set("n", {"x": 2}).execute()
+        assert graph.query().match("(n:A)").one("n")["n"].properties() == {"x": 2}
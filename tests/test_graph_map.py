@@ -0,0 +1,136 @@
+import pytest
+import implica
+
+
+def _graph():
+    graph = implica.Graph(
+        constants=[
+            implica.Constant("a", "A"),
+            implica.Constant("f", "A -> B"),
+            implica.Constant("g", "B -> C"),
+        ]
+    )
+    (
+        graph.query()
+        .create("(:A:@a() {name: 'alice'})")
+        .create("(:B {name: 'bob'})")
+        .create("(:C {name: 'carol'})")
+        .create("(:A)-[::@f() {label: 'knows'}]->(:B)")
+        .create("(:B)-[::@g() {label: 'likes'}]->(:C)")
+        .execute()
+    )
+    return graph
+
+
+def _topology(graph):
+    nodes = sorted(str(n.type()) for n in graph.nodes())
+    edges = sorted(str(e.type()) for e in graph.edges())
+    return nodes, edges
+
+
+class TestGraphMapNodes:
+    def test_map_nodes_uppercases_a_property(self):
+        graph = _graph()
+
+        mapped = graph.map_nodes(
+            lambda n: {**n.properties(), "name": n.properties()["name"].upper()}
+            if "name" in n.properties()
+            else n.properties()
+        )
+
+        names = sorted(
+            n.properties()["name"] for n in mapped.nodes() if "name" in n.properties()
+        )
+        assert names == ["ALICE", "BOB", "CAROL"]
+
+    def test_map_nodes_keeps_topology(self):
+        graph = _graph()
+
+        mapped = graph.map_nodes(lambda n: {"seen": True})
+
+        assert _topology(mapped) == _topology(graph)
+        assert sorted(e.uid() for e in mapped.edges()) == sorted(
+            e.uid() for e in graph.edges()
+        )
+        assert sorted(e.properties()["label"] for e in mapped.edges()) == [
+            "knows",
+            "likes",
+        ]
+
+    def test_map_nodes_leaves_original_untouched(self):
+        graph = _graph()
+
+        mapped = graph.map_nodes(lambda n: {})
+
+        assert all(n.properties() == {} for n in mapped.nodes())
+        assert any("name" in n.properties() for n in graph.nodes())
+
+    def test_map_nodes_keeps_terms(self):
+        graph = _graph()
+
+        mapped = graph.map_nodes(lambda n: {})
+
+        terms = sorted(str(n.term()) for n in mapped.nodes() if n.term())
+        assert terms == sorted(str(n.term()) for n in graph.nodes() if n.term())
+        assert "(g (f a))" in terms
+
+    def test_map_nodes_rejects_non_dict(self):
+        graph = _graph()
+
+        with pytest.raises(TypeError):
+            graph.map_nodes(lambda n: "nope")
+
+    def test_map_nodes_propagates_callback_errors(self):
+        graph = _graph()
+
+        def fail(n):
+            raise ValueError("boom")
+
+        with pytest.raises(ValueError, match="boom"):
+            graph.map_nodes(fail)
+
+    def test_map_nodes_keeps_declarations(self):
+        graph = _graph()
+        graph.set_identity_key("A", ["name"])
+        graph.declare_schema("Person", {"name": "str"})
+
+        mapped = graph.map_nodes(lambda n: n.properties())
+
+        with pytest.raises(ValueError, match="missing required property 'name'"):
+            mapped.query().create("(:Person)").execute()
+        with pytest.raises(KeyError):
+            mapped.query().create("(:A { name: 'bob' })").execute()
+
+
+class TestGraphMapEdges:
+    def test_map_edges_uppercases_a_property(self):
+        graph = _graph()
+
+        mapped = graph.map_edges(
+            lambda e: {"label": e.properties()["label"].upper()}
+        )
+
+        assert sorted(e.properties()["label"] for e in mapped.edges()) == [
+            "KNOWS",
+            "LIKES",
+        ]
+        assert sorted(e.properties()["label"] for e in graph.edges()) == [
+            "knows",
+            "likes",
+        ]
+
+    def test_map_edges_keeps_topology_and_node_properties(self):
+        graph = _graph()
+
+        mapped = graph.map_edges(lambda e: {})
+
+        assert _topology(mapped) == _topology(graph)
+        assert sorted(
+            n.properties().get("name", "") for n in mapped.nodes()
+        ) == sorted(n.properties().get("name", "") for n in graph.nodes())
+
+    def test_map_edges_rejects_non_dict(self):
+        graph = _graph()
+
+        with pytest.raises(TypeError):
+            graph.map_edges(lambda e: None)
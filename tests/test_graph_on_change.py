@@ -0,0 +1,80 @@
+import implica
+import pytest
+
+
+def _collect(graph):
+    events = []
+    graph.on_change(events.append)
+    return events
+
+
+class TestGraphOnChange:
+    def test_create_emits_added_events(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+        events = _collect(graph)
+
+        graph.query().create("(:A)-[::@f()]->(:B)").execute()
+
+        assert sorted(e["kind"] for e in events) == ["edge_added", "node_added", "node_added"]
+
+        uids = {n.uid() for n in graph.nodes()}
+        assert {e["uid"] for e in events if e["kind"] == "node_added"} == uids
+        assert [e["uid"] for e in events if e["kind"] == "edge_added"] == [graph.edges()[0].uid()]
+
+    def test_set_emits_set_events(self):
+        graph = implica.Graph()
+        graph.query().create("(:A)").execute()
+        events = _collect(graph)
+
+        graph.query().match("(n:A)").set("n", {"x": 1}).execute()
+        graph.set_node_properties({graph.nodes()[0].uid(): {"y": 2}}, overwrite=False)
+
+        node_uid = graph.nodes()[0].uid()
+        assert events == [
+            {"kind": "node_set", "uid": node_uid},
+            {"kind": "node_set", "uid": node_uid},
+        ]
+
+    def test_remove_emits_removed_events(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+        graph.query().create("(:A)-[::@f()]->(:B)").execute()
+        edge_uid = graph.edges()[0].uid()
+        events = _collect(graph)
+
+        graph.query().match("(n:A)").remove("n").execute()
+
+        assert [e["kind"] for e in events] == ["edge_removed", "node_removed"]
+        assert events[0]["uid"] == edge_uid
+
+    def test_existing_node_emits_no_event(self):
+        graph = implica.Graph()
+        graph.query().create("(:A)").execute()
+        events = _collect(graph)
+
+        graph.query().create("(:A)").execute()
+        graph.query().match("(n:A)").execute()
+
+        assert events == []
+
+    def test_multiple_observers_receive_events(self):
+        graph = implica.Graph()
+        first = _collect(graph)
+        second = _collect(graph)
+
+        graph.query().create("(:A)").execute()
+
+        assert len(first) == 1
+        assert first == second
+
+    def test_callback_errors_are_raised(self):
+        graph = implica.Graph()
+
+        def fail(event):
+            raise RuntimeError("boom")
+
+        graph.on_change(fail)
+
+        with pytest.raises(RuntimeError, match="boom"):
+            graph.query().create("(:A)").execute()
+
+        assert len(graph.nodes()) == 1
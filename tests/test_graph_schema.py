@@ -0,0 +1,79 @@
+import json
+
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph(
+        constants=[implica.Constant("f", "A -> B"), implica.Constant("id", "(X:*) -> X")],
+        sums=[implica.Sum("Color", [implica.Constant("red", "Color"), implica.Constant("blue", "Color")])],
+    )
+    graph.declare_subtype("Dog", "Animal")
+    graph.declare_subtype("A -> B", "Arrow")
+    graph.query().create("(:A)-[::@f()]->(:B)").execute()
+    return graph
+
+
+class TestGraphSchema:
+    def test_export_describes_vocabulary_only(self):
+        schema = _graph().export_schema()
+
+        assert schema["constants"] == [
+            {"name": "blue", "type": "Color"},
+            {"name": "f", "type": "A -> B"},
+            {"name": "id", "type": "(X:*) -> X"},
+            {"name": "red", "type": "Color"},
+        ]
+        assert schema["sums"] == [{"name": "Color", "cases": ["red", "blue"]}]
+        assert schema["subtypes"] == [
+            {"sub": "(A -> B)", "sup": "Arrow"},
+            {"sub": "Dog", "sup": "Animal"},
+        ]
+
+    def test_loaded_schema_reproduces_vocabulary_without_data(self):
+        schema = json.loads(json.dumps(_graph().export_schema()))
+
+        graph = implica.Graph.load_schema(schema)
+
+        assert graph.nodes() == []
+        assert graph.edges() == []
+        assert graph.export_schema() == schema
+
+    def test_loaded_vocabulary_is_usable(self):
+        graph = implica.Graph.load_schema(_graph().export_schema())
+
+        graph.query().create("(:A)-[::@f()]->(:B)").create("(::@red())").create("(:Dog)").execute()
+
+        assert len(graph.query().match("(a:A)-[e]->(b:B)").return_("e")) == 1
+        assert len(graph.query().match("(n:$Color$)").return_("n")) == 1
+        assert len(graph.query().match("(n | subtype_of=Animal)").return_("n")) == 1
+
+    def test_invalid_names_are_rejected(self):
+        with pytest.raises(ValueError):
+            implica.Graph.load_schema({"constants": [{"name": "not a name", "type": "A"}]})
+
+    def test_unknown_sum_case_is_rejected(self):
+        with pytest.raises(KeyError):
+            implica.Graph.load_schema({"sums": [{"name": "Color", "cases": ["green"]}]})
+
+    def test_identity_keys_and_property_schemas_round_trip(self):
+        graph = _graph()
+        graph.set_identity_key("Person", ["name"])
+        graph.declare_schema("Person", {"name": "str", "age": "int?"})
+
+        schema = json.loads(json.dumps(graph.export_schema()))
+
+        assert schema["identity_keys"] == [{"type": "Person", "keys": ["name"]}]
+        assert schema["property_schemas"] == [
+            {"type": "Person", "properties": {"age": "int?", "name": "str"}}
+        ]
+
+        loaded = implica.Graph.load_schema(schema)
+        assert loaded.export_schema() == schema
+        with pytest.raises(ValueError, match="missing required property 'name'"):
+            loaded.query().create("(:Person)").execute()
+
+        loaded.query().create("(:Person { name: 'Ann' })").execute()
+        with pytest.raises(KeyError):
+            loaded.query().create("(:Person { name: 'Bo' })").execute()
@@ -96,3 +96,46 @@ class TestGraphSetNodeProperties:
         edges = graph.edges()
         assert len(edges) == 3
         assert all([e.properties() == {"foo": "var", "number": 0.3} for e in edges])
+
+
+class TestGraphSetPropertiesByUid:
+    def _graph(self):
+        graph = implica.Graph()
+        graph.query().create("(:A {foo: 'var'})").create("(:B {foo: 'var'})").create(
+            "(:C {foo: 'var'})"
+        ).execute()
+        return graph
+
+    def test_properties_are_merged_into_each_node(self):
+        graph = self._graph()
+        mapping = {n.uid(): {"score": i} for i, n in enumerate(graph.nodes())}
+
+        missing = graph.set_properties_by_uid(mapping)
+
+        assert missing == []
+        for node in graph.nodes():
+            assert node.properties() == {"foo": "var", "score": mapping[node.uid()]["score"]}
+
+    def test_unknown_uids_are_reported(self):
+        graph = self._graph()
+        known = graph.nodes()[0]
+        unknown = "ab" * 32
+
+        missing = graph.set_properties_by_uid(
+            {known.uid(): {"score": 1}, unknown: {"score": 2}}
+        )
+
+        assert missing == [unknown]
+        assert graph.query().match("(n)").where_("n.score == 1").exists()
+        assert len(graph.nodes()) == 3
+
+    def test_sequential_writes_match_parallel_ones(self):
+        graph = self._graph()
+        mapping = {n.uid(): {"score": 0.5} for n in graph.nodes()}
+
+        assert graph.set_properties_by_uid(mapping, parallel=False) == []
+        assert all(n.properties()["score"] == 0.5 for n in graph.nodes())
+
+    def test_malformed_uid_raises(self):
+        with pytest.raises(ValueError):
+            self._graph().set_properties_by_uid({"not-a-uid": {"score": 1}})
@@ -0,0 +1,73 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+    graph.query().create("(:A { x: 1 })-[::@f() { w: 2 }]->(:B)").execute()
+    return graph
+
+
+class TestGraphSnapshot:
+    def test_restore_brings_back_original_contents(self):
+        graph = _graph()
+        checkpoint = graph.snapshot()
+
+        graph.query().match("(n:A)").set("n", {"x": 10, "y": 1}).execute()
+        graph.query().match("()-[e]->()").remove("e").execute()
+        graph.query().match("(n:B)").remove("n").execute()
+        graph.query().create("(:C)").create("(:D)").execute()
+        assert len(graph.nodes()) == 3
+        assert len(graph.edges()) == 0
+
+        graph.restore(checkpoint)
+
+        assert len(graph.nodes()) == 2
+        assert len(graph.edges()) == 1
+        result = graph.query().match("(n:A)-[e]->(m:B)").return_("n", "e")
+        assert len(result) == 1
+        assert result[0]["n"].properties() == {"x": 1}
+        assert result[0]["e"].properties() == {"w": 2}
+        assert graph.query().match("(n:C)").return_("n") == []
+
+    def test_references_created_after_snapshot_are_invalid(self):
+        graph = _graph()
+        checkpoint = graph.snapshot()
+
+        graph.query().create("(:C)").execute()
+        node = graph.query().match("(n:C)").return_("n")[0]["n"]
+
+        graph.restore(checkpoint)
+
+        with pytest.raises(KeyError):
+            node.properties()
+
+    def test_snapshot_can_be_restored_twice(self):
+        graph = _graph()
+        checkpoint = graph.snapshot()
+
+        graph.query().match("(n:A)").set("n", {"x": 5}).execute()
+        graph.restore(checkpoint)
+        graph.query().match("(n:A)").set("n", {"x": 7}).execute()
+        graph.restore(checkpoint)
+
+        result = graph.query().match("(n:A)").return_("n")
+        assert result[0]["n"].properties() == {"x": 1}
+
+    def test_restore_invalidates_cached_matches(self):
+        graph = _graph()
+        checkpoint = graph.snapshot()
+        graph.query().create("(:C)").execute()
+        assert len(graph.query().match("(n)").return_("n")) == 3
+
+        version = graph.version()
+        graph.restore(checkpoint)
+
+        assert graph.version() > version
+        assert len(graph.query().match("(n)").return_("n")) == 2
+
+    def test_restore_into_another_graph_fails(self):
+        checkpoint = _graph().snapshot()
+
+        with pytest.raises(ValueError):
+            implica.Graph().restore(checkpoint)
@@ -0,0 +1,77 @@
+import implica
+import pytest
+
+
+class TestGraphStats:
+    def test_stats_of_empty_graph(self):
+        graph = implica.Graph()
+
+        assert graph.stats() == {
+            "nodes": 0,
+            "edges": 0,
+            "types": 0,
+            "terms": 0,
+            "average_degree": 0.0,
+            "max_degree": 0,
+        }
+
+    def test_stats_of_small_graph(self):
+        graph = implica.Graph(
+            constants=[
+                implica.Constant("f", "A -> B"),
+                implica.Constant("g", "A -> C"),
+                implica.Constant("d", "D"),
+            ]
+        )
+        (
+            graph.query()
+            .create("(:A)-[::@f()]->(:B)")
+            .create("(:A)-[::@g()]->(:C)")
+            .create("(:D:@d())")
+            .execute()
+        )
+
+        stats = graph.stats()
+        assert stats["nodes"] == 4
+        assert stats["edges"] == 2
+        assert stats["types"] == 6
+        assert stats["terms"] == 1
+        assert stats["average_degree"] == 1.0
+        assert stats["max_degree"] == 2
+
+
+def _vocabulary_graph():
+    graph = implica.Graph(
+        constants=[implica.Constant("f", "(A:*) -> (B:*)"), implica.Constant("g", "C -> D")]
+    )
+    graph.query().create("(:A)").create("(:B)").create("(:C)").execute()
+    (
+        graph.query()
+        .create("()-[::@f(A, B)]->()")
+        .create("()-[::@f(A, C)]->()")
+        .create("(:C)-[::@g()]->(:D)")
+        .execute()
+    )
+    return graph
+
+
+class TestEdgesByType:
+    def test_groups_by_type(self):
+        groups = _vocabulary_graph().edges_by_type()
+
+        assert sorted(groups) == ["(A -> B)", "(A -> C)", "(C -> D)"]
+        assert all(len(edges) == 1 for edges in groups.values())
+        assert all(str(edges[0].type()) == key for key, edges in groups.items())
+
+    def test_groups_by_term(self):
+        groups = _vocabulary_graph().edges_by_type(by="term")
+
+        assert {key: len(edges) for key, edges in groups.items()} == {"f": 2, "g": 1}
+        assert sorted(str(edge.type()) for edge in groups["f"]) == ["(A -> B)", "(A -> C)"]
+
+    def test_empty_graph(self):
+        assert implica.Graph().edges_by_type() == {}
+
+    def test_unknown_grouping_raises(self):
+        with pytest.raises(ValueError):
+            _vocabulary_graph().edges_by_type(by="constant")
@@ -0,0 +1,66 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph()
+    graph.set_identity_key("User", ["email"])
+    return graph
+
+
+def _users(graph):
+    return graph.query().match("(u:User)").return_("u")
+
+
+class TestIdentityKeys:
+    def test_same_key_dedups_to_one_node(self):
+        graph = _graph()
+        graph.query().create("(:User { email: 'a@x.org', name: 'Ann' })").execute()
+        graph.query().create("(:User { email: 'a@x.org', name: 'Annie' })").execute()
+
+        users = _users(graph)
+        assert len(users) == 1
+        assert users[0]["u"].properties() == {"email": "a@x.org", "name": "Ann"}
+
+    def test_same_key_merges_when_asked(self):
+        graph = _graph()
+        graph.query().create("(:User { email: 'a@x.org', name: 'Ann' })").execute()
+        graph.query().create("(:User { email: 'a@x.org', age: 30 })", merge_properties=True).execute()
+
+        assert _users(graph)[0]["u"].properties() == {"email": "a@x.org", "name": "Ann", "age": 30}
+
+    def test_different_key_raises(self):
+        graph = _graph()
+        graph.query().create("(:User { email: 'a@x.org' })").execute()
+
+        with pytest.raises(KeyError, match="email"):
+            graph.query().create("(:User { email: 'b@x.org' })").execute()
+
+        assert _users(graph)[0]["u"].properties() == {"email": "a@x.org"}
+
+    def test_pattern_without_the_key_refers_to_the_node(self):
+        graph = _graph()
+        graph.query().create("(:User { email: 'a@x.org' })").execute()
+        graph.query().create("(:User)").execute()
+
+        assert len(_users(graph)) == 1
+
+    def test_types_without_a_key_keep_plain_dedup(self):
+        graph = _graph()
+        graph.query().create("(:Team { name: 'a' })").execute()
+        graph.query().create("(:Team { name: 'b' })").execute()
+
+        assert graph.query().match("(t:Team)").one("t")["t"].properties() == {"name": "a"}
+
+    def test_empty_key_list_removes_the_key(self):
+        graph = _graph()
+        graph.set_identity_key("User", [])
+        graph.query().create("(:User { email: 'a@x.org' })").execute()
+        graph.query().create("(:User { email: 'b@x.org' })").execute()
+
+        assert len(_users(graph)) == 1
+
+    def test_setting_a_key_adds_no_types(self):
+        graph = _graph()
+
+        assert graph.stats()["types"] == 0
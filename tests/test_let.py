@@ -0,0 +1,85 @@
+import pytest
+import implica
+
+
+def _graph():
+    graph = implica.Graph()
+    (
+        graph.query()
+        .create("(:A { name: 'a', price: 2, qty: 10 })")
+        .create("(:B { name: 'b', price: 5, qty: 1 })")
+        .create("(:C { name: 'c', price: 3.5, qty: 2 })")
+        .execute()
+    )
+    return graph
+
+
+class TestLet:
+    """Tests for Query.let_."""
+
+    def test_computed_value_is_filtered_and_returned(self):
+        rows = (
+            _graph()
+            .query()
+            .match("(n)")
+            .let_("total", "n.price * n.qty")
+            .where_("total > 6")
+            .order_by("total")
+            .return_("n.name", "total")
+        )
+
+        assert rows == [{"n.name": "c", "total": 7.0}, {"n.name": "a", "total": 20}]
+
+    def test_value_is_bound_in_every_row(self):
+        rows = _graph().query().match("(n)").let_("double", "n.qty * 2")[:]
+
+        assert sorted(row["double"] for row in rows) == [2, 4, 20]
+        assert all(set(row) == {"n", "double"} for row in rows)
+
+    def test_later_let_can_use_an_earlier_one(self):
+        rows = (
+            _graph()
+            .query()
+            .match("(n)")
+            .let_("total", "n.price * n.qty")
+            .let_("discounted", "total - 1")
+            .where_("n.name == 'b'")
+            .return_("discounted")
+        )
+
+        assert rows == [{"discounted": 4}]
+
+    def test_let_survives_with(self):
+        rows = (
+            _graph()
+            .query()
+            .match("(n)")
+            .let_("total", "n.price * n.qty")
+            .with_(["total"])
+            .order_by("total", descending=True)
+            .return_("total")
+        )
+
+        assert [row["total"] for row in rows] == [20, 7.0, 5]
+
+    def test_missing_value_is_none(self):
+        rows = _graph().query().match("(n { name: 'a' })").let_("w", "n.weight").return_("w")
+
+        assert rows == [{"w": None}]
+
+    def test_rebinding_a_variable_raises(self):
+        with pytest.raises(KeyError):
+            _graph().query().match("(n)").let_("n", "1").execute()
+
+    def test_invalid_variable_name_raises(self):
+        with pytest.raises(ValueError):
+            _graph().query().match("(n)").let_("1x", "1")
+
+    def test_value_variable_cannot_be_removed(self):
+        with pytest.raises(ValueError):
+            _graph().query().match("(n)").let_("t", "1").remove("t").execute()
+
+    def test_let_is_described(self):
+        query = _graph().query().match("(n)").let_("total", "n.price * n.qty")
+
+        assert query.describe_operations()[-1] == "LET total = n.price * n.qty"
@@ -10,6 +10,32 @@ import implica
 class TestMatchNodeBasic:
     """Tests for basic node pattern matching."""
 
+    @pytest.mark.parametrize("pattern", ["", "   ", "-[e]->", "(n)-[e]->"])
+    def test_match_rejects_patterns_without_a_trailing_node(self, pattern):
+        """Empty patterns and patterns ending in an edge are rejected when the query is built."""
+        graph = implica.Graph()
+
+        with pytest.raises(ValueError):
+            graph.query().match(pattern)
+
+    @pytest.mark.parametrize("op", ["match", "create"])
+    def test_empty_pattern_is_rejected_by_create_and_match(self, op):
+        """Neither create nor match accept an empty pattern."""
+        graph = implica.Graph()
+
+        with pytest.raises(ValueError, match="empty"):
+            getattr(graph.query(), op)("")
+
+    def test_single_node_pattern_without_edges(self):
+        """A lone node pattern matches without any edge traversal."""
+        graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+        graph.query().create("(:A)-[::@f()]->(:B)").execute()
+
+        result = graph.query().match("(n:A)").return_("n")
+
+        assert len(result) == 1
+        assert str(result[0]["n"].type()) == "A"
+
     def test_empty_match_node_pattern_matches_all_nodes(self):
         """Empty pattern () matches all nodes in the graph."""
         graph = implica.Graph()
@@ -274,6 +300,21 @@ class TestMatchNodeTermSchema:
         assert len(result) == 1
         assert str(result[0]["N"]) == "Node(B:(f a) {})"
 
+    def test_match_node_term_is_structural(self):
+        """A term built by an edge matches the same application written in the pattern."""
+        graph = implica.Graph(
+            constants=[
+                implica.Constant("f", "A -> B"),
+                implica.Constant("a", "A"),
+            ]
+        )
+        graph.query().create("(:A:@a())").create("()-[::@f()]->()").execute()
+
+        result = graph.query().match("(N::@f() @a())").return_("N")
+        assert len(result) == 1
+        assert str(result[0]["N"]) == "Node(B:(f a) {})"
+        assert graph.query().match("(N::@f() @f())").return_("N") == []
+
 
 class TestMatchNodeProperties:
     """Tests for node matching with property constraints."""
@@ -351,6 +392,79 @@ class TestMatchNodeProperties:
         result = graph.query().match("(N { baz: 'qux' })").return_("N")
         assert len(result) == 0
 
+    def test_match_node_with_list_property_equality(self):
+        """A list literal in the pattern matches an equal list property."""
+        graph = implica.Graph()
+        graph.query().create("(:A { tags: ['x', 'y'] })").create("(:B { tags: ['y'] })").execute()
+
+        result = graph.query().match("(N { tags: ['x', 'y'] })").return_("N")
+        assert len(result) == 1
+        assert result[0]["N"].properties()["tags"] == ["x", "y"]
+
+    def test_match_node_with_list_property_contains(self):
+        """Pattern { tags: { contains: 'x' } } matches nodes whose tags include 'x'."""
+        graph = implica.Graph()
+        (
+            graph.query()
+            .create("(:A { tags: ['x', 'y'] })")
+            .create("(:B { tags: ['y', 'z'] })")
+            .create("(:C { tags: ['x'] })")
+            .execute()
+        )
+
+        result = graph.query().match("(N { tags: { contains: 'x' } })").return_("N")
+        assert len(result) == 2
+        assert {str(d["N"].type()) for d in result} == {"A", "C"}
+
+    def test_match_node_with_list_property_contains_excludes_non_members(self):
+        """Nodes whose list does not hold the value, or that lack a list, are excluded."""
+        graph = implica.Graph()
+        (
+            graph.query()
+            .create("(:A { tags: [1, 2, 3] })")
+            .create("(:B { tags: 'x' })")
+            .create("(:C)")
+            .execute()
+        )
+
+        assert len(graph.query().match("(N { tags: { contains: 4 } })").return_("N")) == 0
+        assert len(graph.query().match("(N { tags: { contains: 'x' } })").return_("N")) == 0
+        assert len(graph.query().match("(N { tags: { contains: 2 } })").return_("N")) == 1
+
+    def test_match_node_with_nested_property_path(self):
+        """Dotted keys such as address.city reach into nested property dicts."""
+        graph = implica.Graph()
+        (
+            graph.query()
+            .create("(:A { address: { city: 'Madrid', zip: 28001 } })")
+            .create("(:B { address: { city: 'Paris' } })")
+            .execute()
+        )
+
+        result = graph.query().match("(N { address.city: 'Madrid' })").return_("N")
+        assert len(result) == 1
+        assert str(result[0]["N"].type()) == "A"
+
+        result = graph.query().match("(N { address.zip: 28001 })").return_("N")
+        assert len(result) == 1
+
+    def test_match_node_with_nested_property_path_excludes_nodes_lacking_it(self):
+        """Nodes missing an intermediate key, or whose value is not a dict, are excluded."""
+        graph = implica.Graph()
+        (
+            graph.query()
+            .create("(:A { address: { city: 'Madrid' } })")
+            .create("(:B { address: 'Madrid' })")
+            .create("(:C { name: 'Madrid' })")
+            .create("(:D)")
+            .execute()
+        )
+
+        result = graph.query().match("(N { address.city: 'Madrid' })").return_("N")
+        assert [str(r["N"].type()) for r in result] == ["A"]
+
+        assert len(graph.query().match("(N { address.country: 'ES' })").return_("N")) == 0
+
 
 class TestMatchNodeCombined:
     """Tests for node matching with combined type, term, and property constraints."""
@@ -713,6 +827,42 @@ class TestMatchEdgeProperties:
         result = graph.query().match("()-[E { weight: 10, label: 'test' }]->()").return_("E")
         assert len(result) == 1
 
+    def test_match_edge_with_type_and_properties(self):
+        """Type schema and inline properties must both hold."""
+        graph = implica.Graph(
+            constants=[implica.Constant("f", "A -> B"), implica.Constant("g", "A -> C")]
+        )
+        graph.query().create("(:A)-[::@f() { since: 2020 }]->(:B)").execute()
+        graph.query().create("(:A)-[::@g() { since: 2020 }]->(:C)").execute()
+
+        assert len(graph.query().match("()-[E]->()").return_("E")) == 2
+        assert len(graph.query().match("()-[E { since: 2020 }]->()").return_("E")) == 2
+        assert len(graph.query().match("()-[E:A -> B { since: 2020 }]->()").return_("E")) == 1
+        assert len(graph.query().match("()-[E:A -> B { since: 2021 }]->()").return_("E")) == 0
+
+    def test_match_edge_properties_skip_edges_lacking_the_key(self):
+        """An inline property is an equality filter, so an edge without it does not match."""
+        graph = implica.Graph(
+            constants=[implica.Constant("f", "A -> B"), implica.Constant("g", "A -> C")]
+        )
+        graph.query().create("(:A)-[::@f() { since: 2020 }]->(:B)").execute()
+        graph.query().create("(:A)-[::@g()]->(:C)").execute()
+
+        result = graph.query().match("(a)-[e { since: 2020 }]->(b)").return_("b")
+        assert [str(row["b"].type()) for row in result] == ["B"]
+
+    def test_match_backward_edge_with_properties(self):
+        """Inline properties apply to edges matched against the arrow."""
+        graph = implica.Graph(
+            constants=[implica.Constant("f", "A -> B"), implica.Constant("g", "A -> C")]
+        )
+        graph.query().create("(:A)-[::@f() { since: 2020 }]->(:B)").execute()
+        graph.query().create("(:A)-[::@g() { since: 2021 }]->(:C)").execute()
+
+        result = graph.query().match("(x)<-[E { since: 2021 }]-(y:A)").return_("x")
+        assert len(result) == 1
+        assert str(result[0]["x"].type()) == "C"
+
 
 class TestMatchEdgeTermSchema:
     """Tests for edge matching with term schemas."""
@@ -886,6 +1036,39 @@ class TestVariableReuse:
         assert len(result) == 1
         assert str(result[0]["E"]) == "Edge((A -> B):f {})"
 
+    def test_bound_node_constrains_start_of_longer_path(self):
+        """A path starting at a bound variable only yields paths from that node."""
+        graph = implica.Graph(constants=[implica.Constant("f", "(A:*)->(B:*)")])
+        graph.query().create("(:A { start: true })").create("(:B)").create("(:C)").create("(:D)").execute()
+        graph.query().create("()-[::@f(A, B)]->()").create("()-[::@f(C, B)]->()").execute()
+        graph.query().create("()-[::@f(B, D)]->()").execute()
+
+        result = (
+            graph.query()
+            .match("(N { start: true })")
+            .match("(N)-[E]->(M)-[F]->(O)")
+            .return_("N", "M", "O")
+        )
+        assert len(result) == 1
+        assert [str(result[0][v].type()) for v in ("N", "M", "O")] == ["A", "B", "D"]
+
+    def test_bound_path_start_is_not_rescanned(self):
+        """The bound start node is checked, not searched for among every node."""
+        graph = implica.Graph(constants=[implica.Constant("f", "(A:*)->(B:*)")])
+        graph.query().create("(:A)").create("(:B)").create("(:C)").create("(:D)").execute()
+        graph.query().create("()-[::@f(A, B)]->()").create("()-[::@f(C, D)]->()").execute()
+
+        before = graph.scan_info()["nodes_scanned"]
+        graph.query().match("(N:A)").return_("N")
+        node_scan = graph.scan_info()["nodes_scanned"] - before
+
+        before = graph.scan_info()["nodes_scanned"]
+        result = graph.query().match("(N:A)").match("(N)-[E]->(M)").return_("M")
+        path_scan = graph.scan_info()["nodes_scanned"] - before
+
+        assert [str(row["M"].type()) for row in result] == ["B"]
+        assert path_scan == node_scan + 1
+
 
 # =============================================================================
 # TEST RETURN VARIATIONS
@@ -0,0 +1,78 @@
+import pytest
+import implica
+
+
+def _graph():
+    graph = implica.Graph(
+        constants=[
+            implica.Constant("works_at", "Person -> Company"),
+        ]
+    )
+    (
+        graph.query()
+        .create("(:Person {name: 'alice'})")
+        .create("(:Company {name: 'acme'})")
+        .create("(:City {name: 'madrid'})")
+        .create("(:Country {name: 'spain'})")
+        .create("(:Person)-[::@works_at() {since: 2020}]->(:Company)")
+        .execute()
+    )
+    return graph
+
+
+class TestMatchComponents:
+    """Tests for comma-separated components in MATCH patterns."""
+
+    def test_disconnected_nodes_give_the_cartesian_product(self):
+        graph = _graph()
+
+        rows = graph.query().match("(a), (b)").return_("a", "b")
+
+        assert len(rows) == 4 * 4
+
+    def test_product_of_typed_components(self):
+        graph = _graph()
+
+        rows = graph.query().match("(a:Person), (b:*), (c:City)").return_("a", "b", "c")
+
+        assert len(rows) == 1 * 4 * 1
+        assert {str(row["b"].type()) for row in rows} == {"Person", "Company", "City", "Country"}
+
+    def test_shared_variables_join_components(self):
+        graph = _graph()
+
+        rows = graph.query().match("(p:Person), (p)-[e]->(c)").return_("p", "e", "c")
+
+        assert len(rows) == 1
+        assert rows[0]["c"].properties() == {"name": "acme"}
+
+    def test_commas_inside_properties_are_not_separators(self):
+        graph = _graph()
+        graph.query().create("(:Tag {label: 'a, b', values: [1, 2]})").execute()
+
+        rows = graph.query().match("(t {label: 'a, b', values: [1, 2]}), (c:City)").return_("t", "c")
+
+        assert len(rows) == 1
+
+    def test_components_with_property_match(self):
+        graph = _graph()
+
+        rows = (
+            graph.query()
+            .match("(a {name: 'alice'}), (b {name: 'madrid'})", property_match="exact")
+            .return_("a", "b")
+        )
+
+        assert len(rows) == 1
+
+    def test_empty_component_raises_value_error(self):
+        with pytest.raises(ValueError, match="empty"):
+            _graph().query().match("(a), , (b)")
+
+    def test_quantified_predicates_need_a_single_component(self):
+        with pytest.raises(ValueError, match="single node"):
+            _graph().query().match("(a), (b)", any_property={"scores": {">": 1}})
+
+    def test_path_pattern_parse_rejects_components(self):
+        with pytest.raises(ValueError, match="disconnected components"):
+            implica.PathPattern.parse("(a), (b)")
@@ -0,0 +1,66 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph(
+        constants=[implica.Constant("f", "A -> B"), implica.Constant("g", "A -> (B -> C)")]
+    )
+    (
+        graph.query()
+        .create("(:A)-[::@f()]->(:B)")
+        .create("(:A)-[::@g()]->(:B -> C)")
+        .execute()
+    )
+    return graph
+
+
+def _edge_types(result, variable="e"):
+    return sorted(str(row[variable].type()) for row in result)
+
+
+class TestMatchEdgeArity:
+    def test_without_modifier_matches_every_edge(self):
+        graph = _graph()
+
+        result = graph.query().match("(:A)-[e]->()").return_("e")
+        assert len(result) == 2
+
+    def test_min_arity_keeps_curried_edges(self):
+        graph = _graph()
+
+        result = graph.query().match("(:A)-[e | min_arity=2]->()").return_("e")
+        assert len(result) == 1
+        assert result[0]["e"].term() is not None
+        assert str(result[0]["e"].term()) == "g"
+
+    def test_min_arity_one_matches_every_edge(self):
+        graph = _graph()
+
+        result = graph.query().match("(:A)-[e | min_arity=1]->()").return_("e")
+        assert len(result) == 2
+
+    def test_min_arity_combines_with_schemas_and_properties(self):
+        graph = _graph()
+        graph.query().match("()-[e::@g()]->()").set("e", {"w": 1}).execute()
+
+        result = graph.query().match("()-[e::@g() { w: 1 } | min_arity=2]->()").return_("e")
+        assert len(result) == 1
+
+        result = graph.query().match("()-[e::@f() | min_arity=2]->()").return_("e")
+        assert result == []
+
+    def test_invalid_modifiers_raise(self):
+        graph = _graph()
+
+        with pytest.raises(ValueError):
+            graph.query().match("()-[e | min_arity=0]->()")
+
+        with pytest.raises(ValueError):
+            graph.query().match("()-[e | max_depth=2]->()")
+
+    def test_create_rejects_arity_modifier(self):
+        graph = _graph()
+
+        with pytest.raises(ValueError):
+            graph.query().create("(:A)-[::@f() | min_arity=1]->(:B)").execute()
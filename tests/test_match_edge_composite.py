@@ -0,0 +1,57 @@
+import implica
+
+
+def _graph(size=20):
+    constants = [implica.Constant(f"f{i}", f"H -> E{i}") for i in range(size)]
+    graph = implica.Graph(constants=constants)
+    graph.query().create("(:H)").execute()
+    for i in range(size):
+        graph.query().match("(h:H)").create(f"(h)-[::@f{i}() {{ w: {i % 2} }}]->(:E{i})").execute()
+    return graph
+
+
+def _edge_types(rows, variable="e"):
+    return sorted(str(row[variable].type()) for row in rows)
+
+
+def _schema_checks(graph):
+    return graph.scan_info()["edge_schemas_checked"]
+
+
+class TestMatchEdgeComposite:
+    def test_type_and_properties_combine_with_and(self):
+        graph = _graph()
+
+        both = graph.query().match("(h:H)-[e:H -> E3 { w: 1 }]->(x)").return_("e")
+        wrong_property = graph.query().match("(h:H)-[e:H -> E3 { w: 0 }]->(x)").return_("e")
+        wrong_type = graph.query().match("(h:H)-[e:H -> E4 { w: 1 }]->(x)").return_("e")
+
+        assert _edge_types(both) == ["(H -> E3)"]
+        assert wrong_property == []
+        assert wrong_type == []
+
+    def test_properties_prune_before_the_type_schema(self):
+        graph = _graph()
+
+        before = _schema_checks(graph)
+        rows = graph.query().match("(h:H)-[e:H -> * { w: 1 }]->(x)").return_("e")
+
+        assert len(rows) == 10
+        assert _schema_checks(graph) - before == 10
+
+    def test_property_failing_everywhere_skips_the_type_schema(self):
+        graph = _graph()
+
+        before = _schema_checks(graph)
+        rows = graph.query().match("(h:H)-[e:H -> E3 { w: 7 }]->(x)").return_("e")
+
+        assert rows == []
+        assert _schema_checks(graph) == before
+
+    def test_every_candidate_reaches_the_schema_without_properties(self):
+        graph = _graph()
+
+        before = _schema_checks(graph)
+        graph.query().match("(h:H)-[e:H -> *]->(x)").return_("e")
+
+        assert _schema_checks(graph) - before == 20
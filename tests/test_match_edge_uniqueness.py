@@ -0,0 +1,52 @@
+import implica
+
+
+def _graph():
+    """A and B joined by an edge each way."""
+    graph = implica.Graph(constants=[implica.Constant("f", "A -> B"), implica.Constant("g", "B -> A")])
+    graph.query().create("(:A)-[::@f()]->(:B)").execute()
+    graph.query().create("(:B)-[::@g()]->(:A)").execute()
+    return graph
+
+
+class TestEdgeUniqueness:
+    """Tests that distinct edge patterns of a path never bind the same edge."""
+
+    def test_named_edges_bind_distinct_edges(self):
+        """Walking back over the edge just taken is not a match."""
+        graph = _graph()
+
+        rows = graph.query().match("(x:A)-[e1]-(y)-[e2]-(z)").return_("e1", "e2")
+
+        assert len(rows) == 2
+        for row in rows:
+            assert row["e1"].uid() != row["e2"].uid()
+
+    def test_anonymous_edges_bind_distinct_edges(self):
+        graph = _graph()
+
+        rows = graph.query().match("(x:A)-[]-(y)-[]-(z)").return_("x", "y", "z")
+
+        assert len(rows) == 2
+        assert all(str(row["z"].type()) == "A" for row in rows)
+
+    def test_anonymous_edge_names_do_not_leak_into_rows(self):
+        graph = _graph()
+
+        query = graph.query().match("(x:A)-[]-(y)-[e]-(z)")
+
+        assert len(query) == 2
+        assert all(set(row.keys()) == {"x", "y", "z", "e"} for row in query[:])
+
+    def test_repeated_variable_names_the_same_edge(self):
+        """A variable used in two segments may bind the same edge in both."""
+        graph = _graph()
+
+        rows = graph.query().match("(x:A)-[e]->(y)<-[e]-(z)").return_("e")
+
+        assert len(rows) == 1
+
+    def test_single_edge_patterns_are_unaffected(self):
+        graph = _graph()
+
+        assert len(graph.query().match("(x)-[e]-(y)").return_("e")) == 4
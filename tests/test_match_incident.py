@@ -0,0 +1,71 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph(
+        constants=[
+            implica.Constant("f", "A -> B"),
+            implica.Constant("g", "C -> A"),
+            implica.Constant("h", "A -> A"),
+            implica.Constant("k", "B -> C"),
+        ]
+    )
+    (
+        graph.query()
+        .create("(:A)-[::@f()]->(:B)")
+        .create("(:C)-[::@g()]->(:A)")
+        .create("(:A)-[::@h()]->(:A)")
+        .create("(:B)-[::@k()]->(:C)")
+        .execute()
+    )
+    return graph
+
+
+def _types(rows, variable="e"):
+    return sorted(str(row[variable].type()) for row in rows)
+
+
+class TestMatchIncident:
+    def test_incoming_and_outgoing_edges_bind(self):
+        rows = _graph().query().match("(n:A)").match(edge="e", incident="n").return_("e")
+
+        assert _types(rows) == ["(A -> A)", "(A -> B)", "(C -> A)"]
+
+    def test_incident_requires_a_bound_node_to_constrain(self):
+        rows = _graph().query().match(edge="e", incident="n").return_("n", "e")
+
+        # every edge once from each endpoint, a self-loop only once
+        assert len(rows) == 7
+
+    def test_undirected_pattern_binds_far_endpoint(self):
+        rows = _graph().query().match("(n:B)-[e]-(m)").return_("e", "m")
+
+        assert sorted((str(r["e"].type()), str(r["m"].type())) for r in rows) == [
+            ("(A -> B)", "A"),
+            ("(B -> C)", "C"),
+        ]
+
+    def test_pattern_and_incident_cannot_be_mixed(self):
+        with pytest.raises(ValueError):
+            _graph().query().match("(n)", edge="e", incident="n")
+
+        with pytest.raises(ValueError):
+            _graph().query().match(edge="e")
+
+    def test_from_node_binds_only_outgoing_edges(self):
+        rows = _graph().query().match("(n:A)").match(edge="e", from_node="n").return_("e")
+
+        assert _types(rows) == ["(A -> A)", "(A -> B)"]
+
+    def test_to_node_binds_only_incoming_edges(self):
+        rows = _graph().query().match("(n:A)").match(edge="e", to_node="n").return_("e")
+
+        assert _types(rows) == ["(A -> A)", "(C -> A)"]
+
+    def test_only_one_endpoint_role_can_be_given(self):
+        with pytest.raises(ValueError):
+            _graph().query().match(edge="e", from_node="n", to_node="m")
+
+        with pytest.raises(ValueError):
+            _graph().query().match(edge="e", incident="n", from_node="n")
@@ -0,0 +1,116 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph()
+    (
+        graph.query()
+        .create("(:A { archived: true })")
+        .create("(:B { archived: false, meta: { deleted: true } })")
+        .create("(:C { name: 'c' })")
+        .create("(:D)")
+        .execute()
+    )
+    return graph
+
+
+def _types(result, variable="n"):
+    return sorted(str(row[variable].type()) for row in result)
+
+
+class TestMatchMissingProperties:
+    def test_missing_property_selects_nodes_lacking_it(self):
+        graph = _graph()
+
+        result = graph.query().match("(n | missing_properties=archived)").return_("n")
+        assert _types(result) == ["C", "D"]
+
+    def test_missing_properties_list(self):
+        graph = _graph()
+
+        result = graph.query().match("(n | missing_properties=[archived, name])").return_("n")
+        assert _types(result) == ["D"]
+
+    def test_missing_nested_property(self):
+        graph = _graph()
+
+        result = graph.query().match("(n | missing_properties=[meta.deleted])").return_("n")
+        assert _types(result) == ["A", "C", "D"]
+
+    def test_missing_properties_combine_with_type_and_properties(self):
+        graph = _graph()
+
+        result = graph.query().match("(n:C { name: 'c' } | missing_properties=archived)").return_("n")
+        assert _types(result) == ["C"]
+
+        result = graph.query().match("(n:A | missing_properties=archived)").return_("n")
+        assert result == []
+
+    def test_missing_properties_on_bound_variable(self):
+        graph = _graph()
+
+        result = (
+            graph.query()
+            .match("(n)")
+            .match("(n | missing_properties=archived)")
+            .return_("n")
+        )
+        assert _types(result) == ["C", "D"]
+
+    def test_missing_properties_with_exists(self):
+        graph = _graph()
+
+        assert graph.query().match("(n | missing_properties=archived)").exists()
+        assert not graph.query().match("(n:A | missing_properties=archived)").exists()
+
+    def test_invalid_node_modifiers_raise(self):
+        graph = _graph()
+
+        with pytest.raises(ValueError):
+            graph.query().match("(n | hidden=archived)")
+
+        with pytest.raises(ValueError):
+            graph.query().match("(n | missing_properties=[])")
+
+        with pytest.raises(ValueError):
+            graph.query().create("(:E | missing_properties=archived)").execute()
+
+    def test_missing_properties_keyword(self):
+        graph = _graph()
+
+        result = graph.query().match(node="n", missing_properties=["archived"]).return_("n")
+        assert _types(result) == ["C", "D"]
+
+        result = graph.query().match("(n:C)", missing_properties=["archived", "name"]).return_("n")
+        assert result == []
+
+    def test_missing_properties_keyword_adds_to_modifier(self):
+        graph = _graph()
+
+        result = (
+            graph.query()
+            .match("(n | missing_properties=archived)", missing_properties=["name"])
+            .return_("n")
+        )
+        assert _types(result) == ["D"]
+
+    def test_missing_properties_keyword_needs_a_single_node(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+
+        with pytest.raises(ValueError):
+            graph.query().match("(a)-[e]->(b)", missing_properties=["archived"])
+
+        with pytest.raises(ValueError):
+            graph.query().match(node="n", missing_properties=[""])
+
+    def test_cached_queries_tell_the_keyword_apart(self):
+        graph = _graph()
+
+        result = graph.query().cached(True).match("(n)").return_("n")
+        assert _types(result) == ["A", "B", "C", "D"]
+
+        result = (
+            graph.query().cached(True).match("(n)", missing_properties=["archived"]).return_("n")
+        )
+        assert _types(result) == ["C", "D"]
@@ -0,0 +1,82 @@
+import pytest
+import implica
+
+
+def _graph():
+    graph = implica.Graph()
+    graph.query().create("(:A {name: 'a'})").execute()
+    graph.query().create("(:B {name: 'a', extra: 1})").execute()
+    graph.query().create("(:C)").execute()
+    return graph
+
+
+def _types(graph, pattern, mode):
+    rows = graph.query().match(pattern, property_match=mode).return_("n")
+    return sorted(str(row["n"].type()) for row in rows)
+
+
+class TestPropertyMatchModes:
+    """Tests for the property_match parameter of match."""
+
+    def test_superset_is_the_default(self):
+        """Without property_match, nodes with extra properties still match."""
+        graph = _graph()
+
+        rows = graph.query().match("(n {name: 'a'})").return_("n")
+
+        assert sorted(str(row["n"].type()) for row in rows) == ["A", "B"]
+
+    def test_superset_accepts_extra_properties(self):
+        graph = _graph()
+
+        assert _types(graph, "(n {name: 'a'})", "superset") == ["A", "B"]
+
+    def test_exact_rejects_extra_properties(self):
+        """Only the node whose properties are exactly the pattern's matches."""
+        graph = _graph()
+
+        assert _types(graph, "(n {name: 'a'})", "exact") == ["A"]
+
+    def test_exact_without_properties_matches_bare_nodes(self):
+        graph = _graph()
+
+        assert _types(graph, "(n)", "exact") == ["C"]
+
+    def test_subset_accepts_nodes_with_fewer_properties(self):
+        """Every property of the node must appear in the pattern with the same value."""
+        graph = _graph()
+
+        assert _types(graph, "(n {name: 'a', extra: 1})", "subset") == ["A", "B", "C"]
+
+    def test_subset_rejects_extra_properties(self):
+        graph = _graph()
+
+        assert _types(graph, "(n {name: 'a'})", "subset") == ["A", "C"]
+
+    def test_subset_compares_values(self):
+        graph = _graph()
+
+        assert _types(graph, "(n {name: 'b', extra: 1})", "subset") == ["C"]
+
+    def test_mode_applies_to_every_node_in_a_path(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+        graph.query().create("(:A {name: 'a'})-[::@f()]->(:B {name: 'b', extra: 1})").execute()
+
+        exact = graph.query().match("(a {name: 'a'})-[e]->(b {name: 'b'})", property_match="exact")
+        superset = graph.query().match("(a {name: 'a'})-[e]->(b {name: 'b'})")
+
+        assert len(exact.return_("e")) == 0
+        assert len(superset.return_("e")) == 1
+
+    def test_describe_mentions_non_default_modes(self):
+        graph = _graph()
+
+        described = graph.query().match("(n {name: 'a'})", property_match="exact").describe_operations()
+
+        assert "PROPERTIES EXACT" in str(described)
+
+    def test_invalid_mode_is_rejected(self):
+        graph = _graph()
+
+        with pytest.raises(ValueError):
+            graph.query().match("(n)", property_match="equal")
@@ -0,0 +1,116 @@
+import pytest
+import implica
+
+
+def _graph():
+    graph = implica.Graph()
+    graph.query().create("(:A {scores: [95, 98, 91]})").execute()
+    graph.query().create("(:B {scores: [50, 99]})").execute()
+    graph.query().create("(:C {scores: [10, 20.5]})").execute()
+    graph.query().create("(:D {scores: []})").execute()
+    graph.query().create("(:E)").execute()
+    return graph
+
+
+def _types(rows):
+    return sorted(str(row["n"].type()) for row in rows)
+
+
+class TestMatchQuantified:
+    """Tests for the any_property and all_property parameters of match."""
+
+    def test_any_element_above(self):
+        graph = _graph()
+
+        rows = graph.query().match(node="n", any_property={"scores": {">": 90}}).return_("n")
+
+        assert _types(rows) == ["A", "B"]
+
+    def test_all_elements_above(self):
+        graph = _graph()
+
+        rows = graph.query().match(node="n", all_property={"scores": {">": 90}}).return_("n")
+
+        # An empty list satisfies ALL; a node without the property never matches
+        assert _types(rows) == ["A", "D"]
+
+    def test_comparisons_apply_to_the_same_element(self):
+        """Under ANY, one element must satisfy every comparison of the property."""
+        graph = _graph()
+
+        rows = (
+            graph.query()
+            .match(node="n", any_property={"scores": {">": 20, "<": 60}})
+            .return_("n")
+        )
+
+        assert _types(rows) == ["B", "C"]
+
+    def test_ints_and_floats_compare_by_value(self):
+        graph = _graph()
+
+        rows = graph.query().match(node="n", any_property={"scores": {"==": 20.5}}).return_("n")
+
+        assert _types(rows) == ["C"]
+
+    def test_any_and_all_combine(self):
+        graph = _graph()
+
+        rows = (
+            graph.query()
+            .match(
+                node="n",
+                any_property={"scores": {">=": 99}},
+                all_property={"scores": {"!=": 50}},
+            )
+            .return_("n")
+        )
+
+        assert _types(rows) == []
+
+    def test_quantifiers_on_a_pattern(self):
+        graph = _graph()
+
+        rows = (
+            graph.query()
+            .match("(n:A)", all_property={"scores": {">": 90}})
+            .return_("n")
+        )
+
+        assert _types(rows) == ["A"]
+
+    def test_non_list_property_raises_type_error(self):
+        graph = implica.Graph()
+        graph.query().create("(:A {scores: 95})").execute()
+
+        with pytest.raises(TypeError, match="scores"):
+            graph.query().match(node="n", any_property={"scores": {">": 90}}).return_("n")
+
+    def test_unknown_operator_raises_value_error(self):
+        graph = _graph()
+
+        with pytest.raises(ValueError, match="unknown comparison"):
+            graph.query().match(node="n", any_property={"scores": {"~": 90}})
+
+    def test_empty_comparisons_raise_value_error(self):
+        graph = _graph()
+
+        with pytest.raises(ValueError, match="at least one comparison"):
+            graph.query().match(node="n", all_property={"scores": {}})
+
+    def test_multi_node_pattern_raises_value_error(self):
+        graph = _graph()
+
+        with pytest.raises(ValueError, match="single node"):
+            graph.query().match("(n)-[e]->(m)", any_property={"scores": {">": 90}})
+
+    def test_node_and_pattern_are_exclusive(self):
+        graph = _graph()
+
+        with pytest.raises(ValueError):
+            graph.query().match("(n)", node="m")
+
+    def test_describe_shows_the_predicates(self):
+        query = implica.Graph().query().match(node="n", any_property={"scores": {">": 90}})
+
+        assert "ANY(scores > 90)" in str(query)
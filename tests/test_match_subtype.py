@@ -0,0 +1,118 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph()
+    (
+        graph.query()
+        .create("(:Animal { name: 'generic' })")
+        .create("(:Dog { name: 'rex' })")
+        .create("(:Puppy { name: 'bit' })")
+        .create("(:Cat { name: 'tom' })")
+        .create("(:Rock { name: 'stone' })")
+        .execute()
+    )
+    return graph
+
+
+def _names(rows, variable="n"):
+    return sorted(row[variable].properties()["name"] for row in rows)
+
+
+class TestMatchSubtype:
+    def test_without_declarations_matches_exactly(self):
+        rows = _graph().query().match("(n | subtype_of=Animal)").return_("n")
+
+        assert _names(rows) == ["generic"]
+
+    def test_declared_subtype_matches(self):
+        graph = _graph()
+        graph.declare_subtype("Dog", "Animal")
+
+        rows = graph.query().match("(n | subtype_of=Animal)").return_("n")
+
+        assert _names(rows) == ["generic", "rex"]
+
+    def test_subtyping_is_transitive(self):
+        graph = _graph()
+        graph.declare_subtype("Dog", "Animal")
+        graph.declare_subtype("Cat", "Animal")
+        graph.declare_subtype("Puppy", "Dog")
+
+        assert _names(graph.query().match("(n | subtype_of=Animal)").return_("n")) == [
+            "bit",
+            "generic",
+            "rex",
+            "tom",
+        ]
+        assert _names(graph.query().match("(n | subtype_of=Dog)").return_("n")) == ["bit", "rex"]
+
+    def test_cyclic_declarations_terminate(self):
+        graph = _graph()
+        graph.declare_subtype("Dog", "Cat")
+        graph.declare_subtype("Cat", "Dog")
+
+        assert _names(graph.query().match("(n | subtype_of=Cat)").return_("n")) == ["rex", "tom"]
+
+    def test_combines_with_type_and_properties(self):
+        graph = _graph()
+        graph.declare_subtype("Dog", "Animal")
+        graph.declare_subtype("Puppy", "Dog")
+
+        rows = graph.query().match("(n:Puppy { name: 'bit' } | subtype_of=Animal)").return_("n")
+        assert _names(rows) == ["bit"]
+
+        rows = graph.query().match("(n:Rock | subtype_of=Animal)").return_("n")
+        assert rows == []
+
+    def test_subtype_of_a_sum_type(self):
+        graph = implica.Graph(
+            sums=[implica.Sum("Pet", [implica.Constant("dog", "Dog"), implica.Constant("cat", "Cat")])]
+        )
+        graph.query().create("(:Dog:@dog())").create("(:Cat:@cat())").create("(:Puppy { name: 'bit' })").execute()
+        graph.declare_subtype("Puppy", "Dog")
+
+        rows = graph.query().match("(n | subtype_of=$Pet$)").return_("n")
+
+        assert len(rows) == 3
+
+    def test_declaration_invalidates_cached_matches(self):
+        graph = _graph()
+        query = graph.query().match("(n | subtype_of=Animal)").cached()
+        assert _names(query.return_("n")) == ["generic"]
+
+        graph.declare_subtype("Dog", "Animal")
+
+        assert _names(graph.query().match("(n | subtype_of=Animal)").cached().return_("n")) == [
+            "generic",
+            "rex",
+        ]
+
+    def test_subtype_of_cannot_be_created_or_capture(self):
+        graph = _graph()
+
+        with pytest.raises(ValueError):
+            graph.query().create("(n:Wolf | subtype_of=Animal)").execute()
+        with pytest.raises(ValueError):
+            graph.query().match("(n | subtype_of=(X:Animal))")
+
+    def test_declare_requires_concrete_types(self):
+        with pytest.raises(ValueError):
+            _graph().declare_subtype("*", "Animal")
+
+    def test_declaring_adds_no_types(self):
+        graph = _graph()
+        types = graph.stats()["types"]
+
+        graph.declare_subtype("Dog", "Pet -> Animal")
+
+        assert graph.stats()["types"] == types
+
+    def test_undeclared_structure_of_supertype_is_matched(self):
+        graph = _graph()
+        graph.declare_subtype("Dog", "Pet -> Animal")
+
+        rows = graph.query().match("(n | subtype_of=* -> Animal)").return_("n")
+
+        assert _names(rows) == ["rex"]
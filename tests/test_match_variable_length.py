@@ -0,0 +1,165 @@
+import implica
+import pytest
+
+
+def _cycle():
+    graph = implica.Graph(
+        constants=[
+            implica.Constant("f", "A -> B"),
+            implica.Constant("g", "B -> C"),
+            implica.Constant("h", "C -> A"),
+        ]
+    )
+    (
+        graph.query()
+        .create("(:A)-[::@f()]->(:B)")
+        .create("(:B)-[::@g()]->(:C)")
+        .create("(:C)-[::@h()]->(:A)")
+        .execute()
+    )
+    return graph
+
+
+def _diamond():
+    graph = implica.Graph(
+        constants=[
+            implica.Constant("f", "A -> B"),
+            implica.Constant("g", "A -> C"),
+            implica.Constant("h", "B -> D"),
+            implica.Constant("k", "C -> D"),
+        ]
+    )
+    (
+        graph.query()
+        .create("(:A)-[::@f()]->(:B)")
+        .create("(:A)-[::@g()]->(:C)")
+        .create("(:B)-[::@h()]->(:D)")
+        .create("(:C)-[::@k()]->(:D)")
+        .execute()
+    )
+    return graph
+
+
+def _ends(result, variable="b"):
+    return sorted(str(row[variable].type()) for row in result)
+
+
+class TestVariableLengthPaths:
+    def test_exact_hops(self):
+        graph = _diamond()
+
+        result = graph.query().match("(a:A)-[| hops=2]->(b)").return_("b")
+        assert _ends(result) == ["D", "D"]
+
+    def test_hop_range(self):
+        graph = _diamond()
+
+        result = graph.query().match("(a:A)-[| hops=1..2]->(b)").return_("b")
+        assert _ends(result) == ["B", "C", "D", "D"]
+
+    def test_backward_hops(self):
+        graph = _diamond()
+
+        result = graph.query().match("(a:D)<-[| hops=..2]-(b)").return_("b")
+        assert _ends(result) == ["A", "A", "B", "C"]
+
+    def test_hops_respect_edge_filters(self):
+        graph = _diamond()
+
+        result = graph.query().match("(a:A)-[::@f() | hops=1..2]->(b)").return_("b")
+        assert _ends(result) == ["B"]
+
+    def test_end_node_pattern_filters_paths(self):
+        graph = _diamond()
+
+        result = graph.query().match("(a:A)-[| hops=1..3]->(b:D)").return_("a", "b")
+        assert _ends(result) == ["D", "D"]
+
+
+class TestVariableLengthCycles:
+    def test_default_forbids_revisiting_nodes(self):
+        graph = _cycle()
+
+        result = graph.query().match("(a:A)-[| hops=1..5]->(b)").return_("b")
+        assert _ends(result) == ["B", "C"]
+
+    def test_unbounded_hops_terminate_on_cycles(self):
+        graph = _cycle()
+
+        result = graph.query().match("(a:A)-[| hops=1..]->(b)").return_("b")
+        assert _ends(result) == ["B", "C"]
+
+        result = graph.query().match("(a:A)-[| hops=1.., cycles=relationships]->(b)").return_("b")
+        assert _ends(result) == ["A", "B", "C"]
+
+    def test_relationships_mode_allows_returning_to_a_node(self):
+        graph = _cycle()
+
+        result = graph.query().match("(a:A)-[| hops=1..5, cycles=relationships]->(b)").return_("b")
+        assert _ends(result) == ["A", "B", "C"]
+
+    def test_any_mode_walks_up_to_the_hop_cap(self):
+        graph = _cycle()
+
+        result = graph.query().match("(a:A)-[| hops=1..5, cycles=any]->(b)").return_("b")
+        assert _ends(result) == ["A", "B", "B", "C", "C"]
+
+    def test_any_mode_requires_an_upper_bound(self):
+        with pytest.raises(ValueError):
+            implica.Graph().query().match("(a)-[| hops=1.., cycles=any]->(b)")
+
+    def test_zero_hops_include_the_start_node(self):
+        graph = _diamond()
+
+        result = graph.query().match("(a)-[| hops=0..1]->(b)").return_("a", "b")
+        pairs = sorted((str(row["a"].type()), str(row["b"].type())) for row in result)
+
+        assert pairs == [
+            ("A", "A"),
+            ("A", "B"),
+            ("A", "C"),
+            ("B", "B"),
+            ("B", "D"),
+            ("C", "C"),
+            ("C", "D"),
+            ("D", "D"),
+        ]
+
+    def test_zero_hops_respect_the_end_pattern(self):
+        graph = _diamond()
+
+        result = graph.query().match("(a:A)-[| hops=0..2]->(b:A)").return_("b")
+
+        assert _ends(result) == ["A"]
+
+    def test_zero_hops_with_bound_end_keep_only_self_pairs(self):
+        graph = _diamond()
+
+        result = graph.query().match("(a:B)").match("(a)-[| hops=0..]->(a)").return_("a")
+
+        assert _ends(result, "a") == ["B"]
+
+
+class TestVariableLengthErrors:
+    def test_invalid_hops_raise(self):
+        query = implica.Graph().query()
+
+        for hops in ["0", "0..0", "3..2", "x", "1..y"]:
+            with pytest.raises(ValueError):
+                query.match(f"(a)-[| hops={hops}]->(b)")
+
+    def test_variable_length_edge_cannot_bind_variable(self):
+        with pytest.raises(ValueError):
+            implica.Graph().query().match("(a)-[e | hops=1..2]->(b)")
+
+    def test_cycles_requires_hops(self):
+        with pytest.raises(ValueError):
+            implica.Graph().query().match("(a)-[| cycles=any]->(b)")
+
+    def test_unknown_cycle_mode_raises(self):
+        with pytest.raises(ValueError):
+            implica.Graph().query().match("(a)-[| hops=2, cycles=sometimes]->(b)")
+
+    def test_create_rejects_hops(self):
+        with pytest.raises(ValueError):
+            implica.Graph().query().create("(:A)-[| hops=2]->(:B)").execute()
@@ -0,0 +1,72 @@
+import implica
+import pytest
+
+
+def _graph():
+    # A -> B -> C -> D -> E
+    graph = implica.Graph(
+        constants=[
+            implica.Constant(f"f{i}", f"{start} -> {end}")
+            for i, (start, end) in enumerate(zip("ABCD", "BCDE"))
+        ]
+    )
+    graph.query().create("(:A)-[::@f0()]->(:B)-[::@f1()]->(:C)-[::@f2()]->(:D)-[::@f3()]->(:E)").execute()
+    return graph
+
+
+def _node(graph, name):
+    return graph.query().match(f"(n:{name})").one("n")["n"]
+
+
+def _types(rows, variable="n"):
+    return sorted(str(row[variable].type()) for row in rows)
+
+
+class TestMatchWithin:
+    def test_two_hop_forward_neighbourhood(self):
+        graph = _graph()
+
+        rows = graph.query().match_within([_node(graph, "B")], k=2).return_("n")
+
+        assert _types(rows) == ["B", "C", "D"]
+
+    def test_backward_and_any_direction(self):
+        graph = _graph()
+        seed = _node(graph, "C")
+
+        backward = graph.query().match_within([seed], k=2, direction="backward").return_("n")
+        both = graph.query().match_within([seed], k=1, direction="any").return_("n")
+
+        assert _types(backward) == ["A", "B", "C"]
+        assert _types(both) == ["B", "C", "D"]
+
+    def test_several_seeds_are_merged(self):
+        graph = _graph()
+        seeds = [_node(graph, "A"), _node(graph, "D").uid()]
+
+        rows = graph.query().match_within(seeds, k=1).return_("n")
+
+        assert _types(rows) == ["A", "B", "D", "E"]
+
+    def test_zero_hops_returns_the_seeds(self):
+        graph = _graph()
+
+        rows = graph.query().match_within([_node(graph, "C")], k=0).return_("n")
+
+        assert _types(rows) == ["C"]
+
+    def test_bound_variable_is_filtered(self):
+        graph = _graph()
+        seed = _node(graph, "A")
+
+        inside = graph.query().match("(m:B)").match_within([seed], k=1, variable="m").return_("m")
+        outside = graph.query().match("(m:C)").match_within([seed], k=1, variable="m").return_("m")
+
+        assert _types(inside, "m") == ["B"]
+        assert outside == []
+
+    def test_invalid_direction_raises(self):
+        graph = _graph()
+
+        with pytest.raises(ValueError):
+            graph.query().match_within([_node(graph, "A")], k=1, direction="sideways")
@@ -0,0 +1,39 @@
+import implica
+import pytest
+
+
+def _graph(size=20):
+    graph = implica.Graph()
+    for i in range(size):
+        graph.query().create(f"(:T{i} {{ i: {i} }})").execute()
+    return graph
+
+
+class TestMaxIntermediateRows:
+    def test_exploding_product_is_aborted(self):
+        query = _graph().query().max_intermediate_rows(1000).match("(a)").match("(b)").match("(c)").where_("a.i == 0")
+
+        with pytest.raises(ValueError, match="intermediate result set exceeded 1000 rows"):
+            query.return_("a", "b", "c")
+
+    def test_queries_under_the_bound_run(self):
+        rows = _graph().query().max_intermediate_rows(400).match("(a)").match("(b)").where_("a.i == b.i").return_("a")
+
+        assert len(rows) == 20
+
+    def test_bound_is_checked_after_each_match(self):
+        query = _graph().query().max_intermediate_rows(10).match("(a)")
+
+        with pytest.raises(ValueError):
+            query.return_("a")
+
+    def test_without_a_bound_nothing_is_aborted(self):
+        rows = _graph().query().match("(a)").match("(b)").match("(c)").return_("a")
+
+        assert len(rows) == 8000
+
+    def test_bound_survives_run_on(self):
+        query = implica.Graph().query().max_intermediate_rows(5).match("(a)").match("(b)")
+
+        with pytest.raises(ValueError):
+            query.run_on(_graph()).return_("a")
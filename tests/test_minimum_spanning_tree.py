@@ -0,0 +1,50 @@
+import implica
+import pytest
+
+EDGES = [("A", "B", 1), ("B", "C", 2), ("A", "C", 3), ("C", "D", 4), ("B", "D", 5)]
+
+
+def _graph(edges=EDGES):
+    graph = implica.Graph(
+        constants=[implica.Constant(f"{start.lower()}{end.lower()}", f"{start} -> {end}") for start, end, _ in edges]
+    )
+    for start, end, cost in edges:
+        graph.query().create(f"(:{start})-[::@{start.lower()}{end.lower()}() {{ cost: {cost} }}]->(:{end})").execute()
+    return graph
+
+
+def _types(edges):
+    return sorted(str(e.type()) for e in edges)
+
+
+class TestMinimumSpanningTree:
+    def test_known_tree(self):
+        edges, total = _graph().minimum_spanning_tree(weight="cost")
+
+        assert _types(edges) == ["(A -> B)", "(B -> C)", "(C -> D)"]
+        assert total == 7
+
+    def test_disconnected_graph_gives_a_forest(self):
+        graph = _graph(EDGES + [("E", "F", 2.5)])
+
+        edges, total = graph.minimum_spanning_tree(weight="cost")
+
+        assert _types(edges) == ["(A -> B)", "(B -> C)", "(C -> D)", "(E -> F)"]
+        assert total == 9.5
+
+    def test_missing_weight_raises_without_default(self):
+        graph = _graph()
+        graph.query().match("(:A)-[e]->(:B)").set("e", {}).execute()
+
+        with pytest.raises(TypeError):
+            graph.minimum_spanning_tree(weight="cost")
+
+    def test_default_weight_replaces_missing_and_non_numeric(self):
+        graph = _graph()
+        graph.query().match("(:A)-[e]->(:B)").set("e", {}).execute()
+        graph.query().match("(:C)-[e]->(:D)").set("e", {"cost": "high"}).execute()
+
+        edges, total = graph.minimum_spanning_tree(weight="cost", default=10)
+
+        assert _types(edges) == ["(A -> C)", "(B -> C)", "(B -> D)"]
+        assert total == 10
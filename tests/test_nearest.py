@@ -0,0 +1,63 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph()
+    (
+        graph.query()
+        .create("(:A { name: 'a', score: 0.1 })")
+        .create("(:B { name: 'b', score: 0.75 })")
+        .create("(:C { name: 'c', score: 1 })")
+        .create("(:D { name: 'd', score: 0.9 })")
+        .create("(:E { name: 'e', score: 0.5 })")
+        .create("(:F { name: 'f' })")
+        .execute()
+    )
+    return graph
+
+
+def _names(rows, variable="a"):
+    return [row[variable].properties()["name"] for row in rows]
+
+
+class TestNearest:
+    def test_returns_the_k_closest_rows_nearest_first(self):
+        rows = _graph().query().match("(a)").nearest("a.score", target=0.8, k=3)
+
+        assert _names(rows) == ["b", "d", "c"]
+
+    def test_rows_bind_every_variable(self):
+        rows = _graph().query().match("(a)").nearest("a.score", target=0.1, k=1)
+
+        assert list(rows[0].keys()) == ["a"]
+        assert _names(rows) == ["a"]
+
+    def test_mixes_ints_and_floats(self):
+        rows = _graph().query().match("(a)").nearest("a.score", target=2, k=2)
+
+        assert _names(rows) == ["c", "d"]
+
+    def test_rows_missing_the_property_come_last(self):
+        rows = _graph().query().match("(a)").nearest("a.score", target=0.8, k=10)
+
+        assert len(rows) == 6
+        assert _names(rows)[-1] == "f"
+
+    def test_zero_k_returns_no_rows(self):
+        assert _graph().query().match("(a)").nearest("a.score", target=0.8, k=0) == []
+
+    def test_expression_is_evaluated_per_row(self):
+        rows = (
+            _graph()
+            .query()
+            .match("(a)")
+            .where_("a.name != 'f'")
+            .nearest("a.score * 10", target=5, k=1)
+        )
+
+        assert _names(rows) == ["e"]
+
+    def test_non_numeric_value_raises(self):
+        with pytest.raises(TypeError):
+            _graph().query().match("(a)").nearest("a.name", target=0.8, k=2)
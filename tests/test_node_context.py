@@ -0,0 +1,45 @@
+import implica
+
+
+def _graph():
+    graph = implica.Graph(constants=[implica.Constant("f", "A -> B"), implica.Constant("a", "A")])
+    graph.query().create("(:A)-[::@f()]->(:B)").execute()
+    return graph
+
+
+def _node(graph, type_name):
+    return graph.query().match(f"(n:{type_name})").one("n")["n"]
+
+
+class TestNodeContext:
+    """Tests for Node.context."""
+
+    def test_context_lists_every_constant_of_the_term(self):
+        """B holds (f a), which depends on both constants."""
+        context = _node(_graph(), "B").context()
+
+        assert sorted(context["constants"]) == ["a", "f"]
+        assert [str(t) for t in context["constants"]["f"]] == ["(A -> B)"]
+        assert [str(t) for t in context["constants"]["a"]] == ["A"]
+
+    def test_context_lists_the_types_of_all_subterms(self):
+        context = _node(_graph(), "B").context()
+
+        assert sorted(str(t) for t in context["types"]) == ["(A -> B)", "A", "B"]
+
+    def test_terms_have_no_free_variables(self):
+        context = _node(_graph(), "B").context()
+
+        assert context["variables"] == {}
+
+    def test_constant_term_depends_on_itself(self):
+        context = _node(_graph(), "A").context()
+
+        assert list(context["constants"]) == ["a"]
+        assert [str(t) for t in context["types"]] == ["A"]
+
+    def test_node_without_term_has_no_context(self):
+        graph = implica.Graph()
+        graph.query().create("(:C)").execute()
+
+        assert _node(graph, "C").context() is None
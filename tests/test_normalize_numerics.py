@@ -0,0 +1,72 @@
+import implica
+
+
+def _people(normalize_numerics):
+    graph = implica.Graph(normalize_numerics=normalize_numerics)
+    (
+        graph.query()
+        .create("(:A { age: 30 })")
+        .create("(:B { age: 25.5 })")
+        .create("(:C { age: 40, scores: [1, 2.5], meta: { rank: 3 } })")
+        .execute()
+    )
+    return graph
+
+
+def _ages(graph):
+    return {str(n.type()): n.properties()["age"] for n in graph.nodes()}
+
+
+class TestNormalizeNumerics:
+    def test_integers_are_kept_by_default(self):
+        graph = _people(False)
+
+        assert isinstance(_ages(graph)["A"], int)
+
+    def test_integers_are_widened_when_enabled(self):
+        graph = _people(True)
+
+        ages = _ages(graph)
+        assert all(isinstance(age, float) for age in ages.values())
+        assert sorted(ages, key=ages.get) == ["B", "A", "C"]
+
+        props = graph.query().match("(n:C)").one("n")["n"].properties()
+        assert props["scores"] == [1.0, 2.5] and isinstance(props["scores"][0], float)
+        assert isinstance(props["meta"]["rank"], float)
+
+    def test_set_properties_are_widened(self):
+        graph = _people(True)
+
+        graph.query().match("(n:A)").set("n", {"age": 31}).execute()
+
+        assert _ages(graph)["A"] == 31.0 and isinstance(_ages(graph)["A"], float)
+
+    def test_integer_patterns_match_widened_values(self):
+        graph = _people(True)
+
+        result = graph.query().match("(n { age: 30 })").return_("n")
+        assert [str(row["n"].type()) for row in result] == ["A"]
+
+    def test_where_orders_mixed_numbers_consistently(self):
+        for normalize in [False, True]:
+            graph = _people(normalize)
+
+            result = graph.query().match("(n)").where_("n.age > 26").return_("n")
+            assert sorted(str(row["n"].type()) for row in result) == ["A", "C"]
+
+            result = graph.query().match("(n)").where_("n.age < 30.5").return_("n")
+            assert sorted(str(row["n"].type()) for row in result) == ["A", "B"]
+
+    def test_integers_beyond_2_to_the_53_are_rounded(self):
+        graph = implica.Graph(normalize_numerics=True)
+        graph.query().create("(:A { id: 9007199254740993 })").execute()
+
+        assert graph.nodes()[0].properties()["id"] == 9007199254740992.0
+
+
+class TestMixedNumericMatching:
+    def test_float_pattern_matches_integer_property(self):
+        graph = _people(False)
+
+        result = graph.query().match("(n { age: 30.0 })").return_("n")
+        assert [str(row["n"].type()) for row in result] == ["A"]
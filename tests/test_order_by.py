@@ -0,0 +1,115 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph()
+    (
+        graph.query()
+        .create("(:A { name: 'a', price: 2, quantity: 10 })")
+        .create("(:B { name: 'b', price: 5, quantity: 1 })")
+        .create("(:C { name: 'c', price: 3.5, quantity: 2 })")
+        .create("(:D { name: 'd', price: 1, quantity: 3 })")
+        .execute()
+    )
+    return graph
+
+
+def _names(rows, variable="n"):
+    return [row[variable].properties()["name"] for row in rows]
+
+
+class TestOrderBy:
+    def test_order_by_property(self):
+        rows = _graph().query().match("(n)").order_by("n.price").return_("n")
+
+        assert _names(rows) == ["d", "a", "c", "b"]
+
+    def test_order_by_computed_expression(self):
+        rows = _graph().query().match("(n)").order_by("n.price * n.quantity").return_("n")
+
+        assert _names(rows) == ["d", "b", "c", "a"]
+
+    def test_order_by_descending(self):
+        rows = (
+            _graph()
+            .query()
+            .match("(n)")
+            .order_by("n.price * n.quantity", descending=True)
+            .return_("n")
+        )
+
+        assert _names(rows) == ["a", "c", "b", "d"]
+
+    def test_order_by_string_expression(self):
+        rows = _graph().query().match("(n)").order_by("n.name + 'x'", descending=True).return_("n")
+
+        assert _names(rows) == ["d", "c", "b", "a"]
+
+    def test_order_by_several_expressions(self):
+        graph = _graph()
+        graph.query().create("(:E { name: 'e', price: 2, quantity: 0 })").execute()
+
+        rows = graph.query().match("(n)").order_by("n.price", "n.quantity").return_("n")
+
+        assert _names(rows) == ["d", "e", "a", "c", "b"]
+
+    def test_missing_values_and_nan_sort_last(self):
+        graph = _graph()
+        graph.query().create("(:E { name: 'e' })").create("(:F)").execute()
+        uid = graph.query().match("(n:F)").one("n")["n"].uid()
+        graph.set_node_properties({uid: {"name": "f", "price": float("nan")}})
+
+        ascending = graph.query().match("(n)").order_by("n.price").return_("n")
+        descending = graph.query().match("(n)").order_by("n.price", descending=True).return_("n")
+
+        assert _names(ascending)[:4] == ["d", "a", "c", "b"]
+        assert set(_names(ascending)[4:]) == {"e", "f"}
+        assert _names(descending)[:4] == ["b", "c", "a", "d"]
+        assert set(_names(descending)[4:]) == {"e", "f"}
+
+    def test_order_survives_where_and_limit(self):
+        rows = (
+            _graph()
+            .query()
+            .match("(n)")
+            .order_by("n.price")
+            .where_("n.quantity > 1")
+            .limit(2)
+            .return_("n")
+        )
+
+        assert _names(rows) == ["d", "a"]
+
+    def test_ties_are_broken_by_binding_uids(self):
+        """Rows that tie on every key come in uid order, so LIMIT keeps the same ones."""
+        types = ["T%d" % i for i in range(12)]
+        selections = []
+
+        for order in (types, types[::-1], types[5:] + types[:5]):
+            graph = _graph()
+            for name in order:
+                graph.query().create("(:%s { name: '%s', price: 0 })" % (name, name)).execute()
+
+            for _ in range(3):
+                rows = graph.query().match("(n)").order_by("n.price").limit(5).return_("n")
+                selections.append([row["n"].uid() for row in rows])
+
+        tied = sorted(n.uid() for n in graph.nodes() if n.properties().get("price") == 0)
+        assert all(selection == tied[:5] for selection in selections)
+
+    def test_first_follows_order(self):
+        row = _graph().query().match("(n)").order_by("n.price", descending=True).first("n")
+
+        assert row["n"].properties()["name"] == "b"
+
+    def test_non_comparable_values_raise(self):
+        graph = _graph()
+        graph.query().create("(:E { name: 'e', price: 'free' })").execute()
+
+        with pytest.raises(TypeError):
+            graph.query().match("(n)").order_by("n.price").return_("n")
+
+    def test_order_by_requires_an_expression(self):
+        with pytest.raises(ValueError):
+            _graph().query().match("(n)").order_by()
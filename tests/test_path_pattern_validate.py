@@ -0,0 +1,43 @@
+import pytest
+import implica
+
+
+class TestPathPatternValidate:
+    """Tests for PathPattern.parse and PathPattern.validate."""
+
+    def test_well_formed_pattern_validates(self):
+        pattern = implica.PathPattern.parse("(a:A)-[f:(A -> B)]->(b:B)<-[g]-(c)")
+
+        assert pattern.validate() is None
+        assert str(pattern) == "(a:A)-[f:(A -> B)]->(b:B)<-[g]-(c)"
+        assert repr(pattern) == "PathPattern(3 nodes, 2 edges)"
+
+    def test_single_node_pattern_validates(self):
+        implica.PathPattern.parse("(n)").validate()
+
+    def test_adjacent_nodes_without_an_edge_fail_validation(self):
+        pattern = implica.PathPattern.parse("(a)(b)")
+
+        with pytest.raises(ValueError, match="number of nodes"):
+            pattern.validate()
+
+    def test_variable_naming_a_node_and_an_edge_fails_validation(self):
+        pattern = implica.PathPattern.parse("(a)-[a]->(b)")
+
+        with pytest.raises(ValueError, match="names both a node and an edge"):
+            pattern.validate()
+
+    def test_pattern_ending_in_an_edge_fails_to_parse(self):
+        with pytest.raises(ValueError):
+            implica.PathPattern.parse("(a)-[e]->")
+
+    def test_empty_pattern_fails_to_parse(self):
+        with pytest.raises(ValueError):
+            implica.PathPattern.parse("   ")
+
+    def test_match_rejects_a_pattern_that_fails_validation(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+        graph.query().create("(:A)-[::@f()]->(:B)").execute()
+
+        with pytest.raises(ValueError, match="names both a node and an edge"):
+            graph.query().match("(a)-[a]->(b)").return_("a")
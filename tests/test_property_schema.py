@@ -0,0 +1,113 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph()
+    graph.declare_schema("Person", {"name": "str", "age": "int?"})
+    return graph
+
+
+def _people(graph):
+    return graph.query().match("(p:Person)").return_("p")
+
+
+class TestPropertySchema:
+    def test_valid_node_is_created(self):
+        graph = _graph()
+        graph.query().create("(:Person { name: 'Ann', age: 30 })").execute()
+
+        assert _people(graph)[0]["p"].properties() == {"name": "Ann", "age": 30}
+
+    def test_optional_property_may_be_absent(self):
+        graph = _graph()
+        graph.query().create("(:Person { name: 'Ann', nickname: 'A' })").execute()
+
+        assert len(_people(graph)) == 1
+
+    def test_missing_required_property_raises(self):
+        graph = _graph()
+
+        with pytest.raises(ValueError, match="missing required property 'name'"):
+            graph.query().create("(:Person { age: 30 })").execute()
+
+        assert len(_people(graph)) == 0
+
+    def test_wrong_typed_property_raises(self):
+        graph = _graph()
+
+        with pytest.raises(TypeError, match="'age'"):
+            graph.query().create("(:Person { name: 'Ann', age: 'thirty' })").execute()
+
+        assert len(_people(graph)) == 0
+
+    def test_float_accepts_ints(self):
+        graph = implica.Graph()
+        graph.declare_schema("Item", {"price": "float"})
+        graph.query().create("(:Item { price: 3 })").create("(:Other { price: 'x' })").execute()
+
+        assert len(graph.query().match("(i:Item)").return_("i")) == 1
+
+    def test_set_is_checked(self):
+        graph = _graph()
+        graph.query().create("(:Person { name: 'Ann' })").execute()
+
+        with pytest.raises(TypeError):
+            graph.query().match("(p:Person)").set("p", {"age": 30.5}, False).execute()
+        with pytest.raises(ValueError):
+            graph.query().match("(p:Person)").set("p", {"age": 31}).execute()
+
+        graph.query().match("(p:Person)").set("p", {"age": 31}, False).execute()
+        assert _people(graph)[0]["p"].properties() == {"name": "Ann", "age": 31}
+
+    def test_bulk_set_is_checked(self):
+        graph = _graph()
+        graph.query().create("(:Person { name: 'Ann' })").execute()
+        uid = _people(graph)[0]["p"].uid()
+
+        with pytest.raises(TypeError):
+            graph.set_node_properties({uid: {"name": 1}})
+
+        assert _people(graph)[0]["p"].properties() == {"name": "Ann"}
+
+    def test_existing_node_must_conform(self):
+        graph = implica.Graph()
+        graph.query().create("(:Person { age: 30 })").execute()
+
+        with pytest.raises(ValueError, match="name"):
+            graph.declare_schema("Person", {"name": "str"})
+
+    def test_unknown_type_name_raises(self):
+        with pytest.raises(ValueError, match="declared as 'integer'"):
+            implica.Graph().declare_schema("Person", {"age": "integer"})
+
+    def test_empty_schema_removes_it(self):
+        graph = _graph()
+        graph.declare_schema("Person", {})
+
+        graph.query().create("(:Person { age: 'old' })").execute()
+        assert len(_people(graph)) == 1
+
+    def test_other_types_are_unchecked(self):
+        graph = _graph()
+        graph.query().create("(:Company { name: 1 })").execute()
+
+        assert len(graph.query().match("(c:Company)").return_("c")) == 1
+
+    def test_normalized_ints_still_satisfy_int(self):
+        graph = implica.Graph(normalize_numerics=True)
+        graph.declare_schema("Person", {"name": "str", "age": "int"})
+        graph.query().create("(:Person { name: 'Ann', age: 30 })").execute()
+
+        graph.query().match("(p:Person)").set("p", {"name": "Bo"}, False).execute()
+        graph.query().match("(p:Person)").set("p", {"age": 31}, False).execute()
+        assert _people(graph)[0]["p"].properties() == {"name": "Bo", "age": 31.0}
+
+        with pytest.raises(TypeError, match="'age'"):
+            graph.query().match("(p:Person)").set("p", {"age": 31.5}, False).execute()
+
+    def test_declaring_adds_no_types(self):
+        graph = _graph()
+
+        assert graph.stats()["types"] == 0
+        assert graph.query().match("(p:Person)").return_("p") == []
@@ -0,0 +1,101 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+    graph.query().create("(:A)-[::@f()]->(:B)").create("(:C)").execute()
+    return graph
+
+
+def _node(graph, type_name):
+    return next(n for n in graph.nodes() if str(n.type()) == type_name)
+
+
+class TestAssertCount:
+    def test_assert_count_passes_on_correct_count(self):
+        graph = _graph()
+        graph.query().match("(n)").assert_count(3)
+        graph.query().match("(n:D)").assert_count(0)
+
+    def test_assert_count_fails_with_expected_and_actual(self):
+        graph = _graph()
+        with pytest.raises(AssertionError, match="expected 2 rows, got 3"):
+            graph.query().match("(n)").assert_count(2)
+
+
+class TestAssertReturns:
+    def test_assert_returns_passes_regardless_of_order(self):
+        graph = _graph()
+        a, b, c = (_node(graph, t) for t in "ABC")
+
+        graph.query().match("(n)").assert_returns([{"n": c}, {"n": a}, {"n": b}])
+
+    def test_assert_returns_compares_several_bindings(self):
+        graph = _graph()
+        a, b = _node(graph, "A"), _node(graph, "B")
+        e = graph.edges()[0]
+
+        graph.query().match("(x)-[e]->(y)").assert_returns([{"x": a, "e": e, "y": b}])
+
+    def test_assert_returns_passes_on_empty_result(self):
+        graph = _graph()
+        graph.query().match("(n:D)").assert_returns([])
+
+    def test_assert_returns_fails_on_missing_row(self):
+        graph = _graph()
+        a = _node(graph, "A")
+
+        with pytest.raises(AssertionError, match="expected rows"):
+            graph.query().match("(n)").assert_returns([{"n": a}])
+
+    def test_assert_returns_fails_on_wrong_row(self):
+        graph = _graph()
+        a, c = _node(graph, "A"), _node(graph, "C")
+
+        with pytest.raises(AssertionError) as exc_info:
+            graph.query().match("(n:A)").assert_returns([{"n": c}])
+
+        message = str(exc_info.value)
+        assert "Node(C: {})" in message
+        assert "Node(A: {})" in message
+
+    def test_assert_returns_counts_duplicate_rows(self):
+        graph = _graph()
+        a = _node(graph, "A")
+
+        with pytest.raises(AssertionError):
+            graph.query().match("(n:A)").assert_returns([{"n": a}, {"n": a}])
+
+
+class TestCompareTo:
+    def test_matching_rows_are_all_in_both(self):
+        graph = _graph()
+        a, b, c = (_node(graph, t) for t in "ABC")
+
+        report = graph.query().match("(n)").compare_to([{"n": c}, {"n": a}, {"n": b}])
+
+        assert report["only_in_result"] == []
+        assert report["only_in_expected"] == []
+        assert len(report["in_both"]) == 3
+
+    def test_report_lists_the_differing_rows(self):
+        graph = _graph()
+        a, b, c = (_node(graph, t) for t in "ABC")
+
+        report = graph.query().match("(n)").compare_to([{"n": a}, {"n": b}, {"n": a}])
+
+        assert report["in_both"] == [{"n": a}, {"n": b}]
+        assert report["only_in_result"] == [{"n": c}]
+        assert report["only_in_expected"] == [{"n": a}]
+
+    def test_several_bindings_are_compared_together(self):
+        graph = _graph()
+        a, b, c = (_node(graph, t) for t in "ABC")
+        e = graph.edges()[0]
+
+        report = graph.query().match("(x)-[e]->(y)").compare_to([{"x": a, "e": e, "y": c}])
+
+        assert report["only_in_result"] == [{"x": a, "e": e, "y": b}]
+        assert report["only_in_expected"] == [{"x": a, "e": e, "y": c}]
+        assert report["in_both"] == []
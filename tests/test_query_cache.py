@@ -0,0 +1,84 @@
+import pytest
+import implica
+
+
+class TestGraphVersion:
+    """Tests for the graph version counter."""
+
+    def test_version_starts_at_zero(self):
+        graph = implica.Graph()
+
+        assert graph.version() == 0
+
+    def test_version_increases_on_mutation(self):
+        graph = implica.Graph()
+
+        graph.query().create("(:A)").execute()
+        after_create = graph.version()
+        graph.query().match("(N:A)").set("N", {"x": 1}).execute()
+        after_set = graph.version()
+        graph.query().match("(N:A)").remove("N").execute()
+        after_remove = graph.version()
+
+        assert 0 < after_create < after_set < after_remove
+
+    def test_version_unchanged_by_reads(self):
+        graph = implica.Graph()
+        graph.query().create("(:A)").execute()
+        version = graph.version()
+
+        graph.query().match("(N)").return_("N")
+
+        assert graph.version() == version
+
+
+class TestQueryCached:
+    """Tests for caching match results keyed on the graph version."""
+
+    def test_cached_query_hits_when_graph_unchanged(self):
+        graph = implica.Graph()
+        graph.query().create("(:A)").create("(:B)").execute()
+        query = graph.query().match("(N)").cached()
+
+        first = query.return_("N")
+        second = query.return_("N")
+
+        assert len(first) == len(second) == 2
+        assert graph.cache_info() == {"hits": 1, "misses": 1, "size": 1}
+
+    def test_cached_query_misses_after_mutation(self):
+        graph = implica.Graph()
+        graph.query().create("(:A)").execute()
+        query = graph.query().match("(N)").cached()
+
+        assert len(query.return_("N")) == 1
+        graph.query().create("(:B)").execute()
+        assert len(query.return_("N")) == 2
+
+        assert graph.cache_info()["hits"] == 0
+        assert graph.cache_info()["misses"] == 2
+
+    def test_cache_is_shared_by_equal_queries(self):
+        graph = implica.Graph()
+        graph.query().create("(:A)").execute()
+
+        graph.query().match("(N:A)").cached().return_("N")
+        graph.query().match("(N:A)").cached().return_("N")
+
+        assert graph.cache_info()["hits"] == 1
+
+    def test_mutating_queries_are_not_cached(self):
+        graph = implica.Graph()
+        query = graph.query().create("(:A)").cached()
+
+        query.execute()
+
+        assert graph.cache_info() == {"hits": 0, "misses": 0, "size": 0}
+
+    def test_uncached_queries_do_not_touch_the_cache(self):
+        graph = implica.Graph()
+        graph.query().create("(:A)").execute()
+
+        graph.query().match("(N)").return_("N")
+
+        assert graph.cache_info() == {"hits": 0, "misses": 0, "size": 0}
@@ -0,0 +1,41 @@
+import implica
+
+
+def _build(order):
+    graph = implica.Graph(
+        constants=[implica.Constant("f", "A -> B"), implica.Constant("g", "A -> C")]
+    )
+    for pattern in order:
+        graph.query().create(pattern).execute()
+    return graph
+
+
+class TestQueryCanonical:
+    def test_canonical_is_independent_of_build_order(self):
+        patterns = [
+            "(:D { x: 1, y: 2 })",
+            "(:A)-[::@f() { w: 1 }]->(:B)",
+            "(:A)-[::@g()]->(:C { z: 'c' })",
+        ]
+        first = _build(patterns)
+        second = _build(list(reversed(patterns)))
+
+        for query in ["(a)-[e]->(b)", "(n)"]:
+            assert (
+                first.query().match(query).canonical() == second.query().match(query).canonical()
+            )
+
+    def test_canonical_lists_sorted_rows_and_bindings(self):
+        graph = _build(["(:A)-[::@f()]->(:B)", "(:A)-[::@g()]->(:C)"])
+
+        assert graph.query().match("(a)-[e]->(b)").canonical() == "\n".join(
+            [
+                "{a: Node(A: {}), b: Node(B: {}), e: Edge((A -> B):f {})}",
+                "{a: Node(A: {}), b: Node(C: {}), e: Edge((A -> C):g {})}",
+            ]
+        )
+
+    def test_canonical_of_empty_result_is_empty(self):
+        graph = _build([])
+
+        assert graph.query().match("(n)").canonical() == ""
@@ -0,0 +1,46 @@
+import implica
+
+
+class TestQueryDescribe:
+    def test_empty_query(self):
+        query = implica.Graph().query()
+
+        assert query.pending_operations() == 0
+        assert query.describe_operations() == []
+
+    def test_describes_each_operation_in_order(self):
+        query = (
+            implica.Graph()
+            .query()
+            .match("(n:A { x: 1 })-[e]->(m)")
+            .where_("n.x > 0")
+            .set("n", {"y": 2})
+            .order_by("n.x", descending=True)
+            .limit(3)
+        )
+
+        assert query.pending_operations() == 5
+        assert query.describe_operations() == [
+            "MATCH (n:A { x: 1 })-[e]->(m)",
+            "WHERE n.x > 0",
+            "SET n = {y: 2}",
+            "ORDER BY n.x DESC",
+            "LIMIT 3",
+        ]
+
+    def test_conditionally_appended_operations(self):
+        def build(archived):
+            query = implica.Graph().query().match("(n)")
+            if archived:
+                query = query.where_("n.archived")
+            return query.with_([("n", "node")]).remove("node")
+
+        assert build(False).describe_operations() == ["MATCH (n)", "WITH n AS node", "REMOVE node"]
+        assert build(True).pending_operations() == 4
+
+    def test_describing_does_not_execute(self):
+        graph = implica.Graph()
+        query = graph.query().create("(:A)")
+
+        assert query.describe_operations() == ["CREATE (:A)"]
+        assert graph.nodes() == []
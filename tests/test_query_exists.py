@@ -0,0 +1,68 @@
+import implica
+
+
+def _graph():
+    graph = implica.Graph(constants=[implica.Constant("f", "A -> B"), implica.Constant("a", "A")])
+    graph.query().create("(:A:@a() { x: 1 })-[::@f()]->(:B { x: 2 })").create("(:C)").execute()
+    return graph
+
+
+class TestQueryExists:
+    def test_exists_on_node_type(self):
+        graph = _graph()
+
+        assert graph.query().match("(n:A)").exists() is True
+        assert graph.query().match("(n:D)").exists() is False
+
+    def test_exists_on_wildcard_node(self):
+        assert _graph().query().match("(n)").exists() is True
+        assert implica.Graph().query().match("(n)").exists() is False
+
+    def test_exists_on_node_properties(self):
+        graph = _graph()
+
+        assert graph.query().match("(n { x: 2 })").exists() is True
+        assert graph.query().match("(n { x: 3 })").exists() is False
+
+    def test_exists_on_node_term(self):
+        graph = _graph()
+
+        assert graph.query().match("(n::@a())").exists() is True
+        assert graph.query().match("(n:C:@a())").exists() is False
+
+    def test_exists_on_path_pattern(self):
+        graph = _graph()
+
+        assert graph.query().match("(:A)-[e]->(:B)").exists() is True
+        assert graph.query().match("(:B)-[e]->(:A)").exists() is False
+
+    def test_exists_after_where(self):
+        graph = _graph()
+
+        assert graph.query().match("(n)").where_("n.x > 1").exists() is True
+        assert graph.query().match("(n)").where_("n.x > 5").exists() is False
+
+    def test_exists_on_large_graph_agrees_with_return(self):
+        graph = implica.Graph()
+        for i in range(200):
+            graph.query().create(f"(:T{i} {{ i: {i} }})").execute()
+
+        assert graph.query().match("(n { i: 150 })").exists() is True
+        assert graph.query().match("(n { i: 500 })").exists() is False
+        assert len(graph.query().match("(n { i: 150 })").return_("n")) == 1
+
+    def test_exists_stops_scanning_at_a_match(self):
+        graph = implica.Graph()
+        for i in range(1000):
+            graph.query().create(f"(:T{i})").execute()
+
+        before = graph.scan_info()["nodes_scanned"]
+        graph.query().match("(n)").return_("n")
+        full_scan = graph.scan_info()["nodes_scanned"] - before
+
+        before = graph.scan_info()["nodes_scanned"]
+        assert graph.query().match("(n)").exists() is True
+        exists_scan = graph.scan_info()["nodes_scanned"] - before
+
+        assert full_scan == 1000
+        assert 1 <= exists_scan < full_scan // 2
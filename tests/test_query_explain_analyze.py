@@ -0,0 +1,65 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph(constants=[implica.Constant("f", "A -> B"), implica.Constant("g", "C -> B")])
+    (
+        graph.query()
+        .create("(:A { x: 1 })-[::@f()]->(:B { x: 2 })")
+        .create("(:C { x: 3 })-[::@g()]->(:B)")
+        .execute()
+    )
+    return graph
+
+
+class TestExplainAnalyze:
+    def test_returns_plan_counts_timings_and_rows(self):
+        query = _graph().query().match("(n)").where_("n.x > 1")
+
+        analysis = query.explain_analyze(["n"])
+
+        assert analysis["plan"] == ["MATCH (n)", "WHERE n.x > 1"]
+        assert [op["operation"] for op in analysis["operations"]] == analysis["plan"]
+        assert [op["rows"] for op in analysis["operations"]] == [3, 2]
+        assert all(op["seconds"] >= 0 for op in analysis["operations"])
+        assert analysis["total_seconds"] >= sum(op["seconds"] for op in analysis["operations"])
+        assert sorted(str(row["n"].type()) for row in analysis["rows"]) == ["B", "C"]
+
+    def test_rows_match_return(self):
+        graph = _graph()
+        query = graph.query().match("(a)-[e]->(b:B)").order_by("a.x")
+
+        analysis = query.explain_analyze("a", "b.x")
+
+        assert analysis["rows"] == graph.query().match("(a)-[e]->(b:B)").order_by("a.x").return_("a", "b.x")
+        assert [op["rows"] for op in analysis["operations"]] == [2, 2]
+
+    def test_plan_follows_optimization(self):
+        query = _graph().query().optimize().match("(n)").match("(m:A)")
+
+        analysis = query.explain_analyze("n", "m")
+
+        assert analysis["plan"] == ["MATCH (m:A)", "MATCH (n)"]
+        assert [op["rows"] for op in analysis["operations"]] == [1, 3]
+        assert len(analysis["rows"]) == 3
+
+    def test_pushed_down_limit_is_one_operation(self):
+        analysis = _graph().query().match("(n)").limit(2).explain_analyze("n")
+
+        assert [op["operation"] for op in analysis["operations"]] == ["MATCH (n) LIMIT 2"]
+        assert analysis["operations"][0]["rows"] == 2
+        assert len(analysis["rows"]) == 2
+
+    def test_cached_queries_still_execute(self):
+        graph = _graph()
+        query = graph.query().cached().match("(n:A)")
+        query.return_("n")
+
+        analysis = query.explain_analyze("n")
+
+        assert [op["rows"] for op in analysis["operations"]] == [1]
+
+    def test_invalid_row_format_raises(self):
+        with pytest.raises(ValueError):
+            _graph().query().match("(n)").explain_analyze("n", as_="set")
@@ -0,0 +1,66 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph()
+    (
+        graph.query()
+        .create("(:A { name: 'a', price: 2 })")
+        .create("(:B { name: 'b', price: 5 })")
+        .create("(:C { name: 'c', price: 3 })")
+        .create("(:D { name: 'd', price: 1 })")
+        .execute()
+    )
+    return graph
+
+
+def _names(rows):
+    return [row["n"].properties()["name"] for row in rows]
+
+
+class TestQueryIndexing:
+    def test_len_counts_rows(self):
+        assert len(_graph().query().match("(n)")) == 4
+        assert len(_graph().query().match("(n:E)")) == 0
+
+    def test_index_returns_row_of_every_binding(self):
+        query = _graph().query().match("(n)").order_by("n.price")
+
+        assert _names([query[0], query[1]]) == ["d", "a"]
+        assert set(query[0]) == {"n"}
+        assert query[-1]["n"].properties()["name"] == "b"
+
+    def test_slices_return_lists_of_rows(self):
+        query = _graph().query().match("(n)").order_by("n.price")
+
+        assert _names(query[1:3]) == ["a", "c"]
+        assert _names(query[::-1]) == ["b", "c", "a", "d"]
+        assert query[10:] == []
+
+    def test_out_of_range_index_raises_index_error(self):
+        query = _graph().query().match("(n)")
+
+        with pytest.raises(IndexError):
+            query[4]
+        with pytest.raises(IndexError):
+            query[-5]
+
+    def test_query_is_executed_once(self):
+        graph = _graph()
+        query = graph.query().match("(n:A)").set("n", {"seen": True})
+        events = []
+        graph.on_change(events.append)
+
+        assert len(query) == 1
+        assert query[0]["n"].properties()["seen"] is True
+        assert len(events) == 1
+
+    def test_adding_operations_runs_the_query_again(self):
+        query = _graph().query().match("(n)")
+        assert len(query) == 4
+
+        query.where_("n.price > 2")
+
+        assert len(query) == 2
+        assert sorted(_names(query[:])) == ["b", "c"]
@@ -0,0 +1,125 @@
+import implica
+
+
+def _graph(size=200):
+    graph = implica.Graph()
+    for i in range(size):
+        graph.query().create(f"(:T{i} {{ i: {i}, even: {'true' if i % 2 == 0 else 'false'} }})").execute()
+    return graph
+
+
+def _values(rows):
+    return sorted(row["n"].properties()["i"] for row in rows)
+
+
+class TestQueryLimit:
+    def test_limit_caps_rows(self):
+        graph = _graph()
+
+        assert len(graph.query().match("(n)").limit(5).return_("n")) == 5
+        assert len(graph.query().match("(n)").limit(0).return_("n")) == 0
+
+    def test_limit_above_row_count_returns_everything(self):
+        graph = _graph()
+
+        limited = graph.query().match("(n { even: true })").limit(500).return_("n")
+        full = graph.query().match("(n { even: true })").return_("n")
+
+        assert _values(limited) == _values(full)
+        assert len(limited) == 100
+
+    def test_limited_rows_are_matches(self):
+        graph = _graph()
+
+        full = set(_values(graph.query().match("(n { even: true })").return_("n")))
+        limited = _values(graph.query().match("(n { even: true })").limit(3).return_("n"))
+
+        assert len(limited) == 3
+        assert set(limited) <= full
+
+    def test_limit_is_pushed_into_node_scan(self):
+        graph = _graph()
+
+        before = graph.scan_info()["nodes_scanned"]
+        graph.query().match("(n)").return_("n")
+        full_scan = graph.scan_info()["nodes_scanned"] - before
+
+        before = graph.scan_info()["nodes_scanned"]
+        graph.query().match("(n)").limit(5).return_("n")
+        limited_scan = graph.scan_info()["nodes_scanned"] - before
+
+        assert full_scan == 200
+        assert limited_scan == 5
+
+    def test_limit_after_other_operations_is_not_pushed_down(self):
+        graph = _graph()
+
+        before = graph.scan_info()["nodes_scanned"]
+        rows = graph.query().match("(n)").where_("n.i >= 190").limit(5).return_("n")
+
+        assert graph.scan_info()["nodes_scanned"] - before == 200
+        assert len(rows) == 5
+        assert all(value >= 190 for value in _values(rows))
+
+    def test_limit_on_path_pattern(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "A -> B"), implica.Constant("g", "C -> B")])
+        graph.query().create("(:A)-[::@f()]->(:B)").create("(:C)-[::@g()]->(:B)").execute()
+
+        assert len(graph.query().match("(a)-[e]->(b:B)").limit(1).return_("e")) == 1
+        assert len(graph.query().match("(a)-[e]->(b:B)").limit(5).return_("e")) == 2
+
+
+
+def _path_graph(size=100):
+    graph = implica.Graph(constants=[implica.Constant(f"f{i}", f"A{i} -> B{i}") for i in range(size)])
+    for i in range(size):
+        graph.query().create(f"(:A{i})-[::@f{i}()]->(:B{i})").execute()
+    return graph
+
+
+def _edges(rows):
+    return sorted(str(row["e"].type()) for row in rows)
+
+
+class TestQueryLimitOnPaths:
+    def test_limited_paths_are_matches(self):
+        graph = _path_graph()
+
+        full = set(_edges(graph.query().match("(a)-[e]->(b)").return_("e")))
+        limited = _edges(graph.query().match("(a)-[e]->(b)").limit(5).return_("e"))
+
+        assert len(limited) == 5
+        assert set(limited) <= full
+
+    def test_limit_above_path_count_returns_everything(self):
+        graph = _path_graph()
+
+        limited = graph.query().match("(a)-[e]->(b)").limit(500).return_("e")
+        full = graph.query().match("(a)-[e]->(b)").return_("e")
+
+        assert _edges(limited) == _edges(full)
+        assert len(limited) == 100
+
+    def test_limit_is_pushed_into_single_edge_path(self):
+        graph = _path_graph()
+
+        before = graph.scan_info()["nodes_scanned"]
+        graph.query().match("(a)-[e]->(b)").return_("e")
+        full_scan = graph.scan_info()["nodes_scanned"] - before
+
+        before = graph.scan_info()["nodes_scanned"]
+        graph.query().match("(a)-[e]->(b)").limit(5).return_("e")
+        limited_scan = graph.scan_info()["nodes_scanned"] - before
+
+        # At worst every B node, none of which starts an edge, is tried before five A nodes.
+        assert full_scan >= 200
+        assert limited_scan <= 105
+
+    def test_limit_on_variable_length_path_is_not_pushed_down(self):
+        graph = _path_graph()
+
+        before = graph.scan_info()["nodes_scanned"]
+        rows = graph.query().match("(a)-[| hops=1..2]->(b)").limit(5).return_("a", "b")
+
+        assert graph.scan_info()["nodes_scanned"] - before >= 200
+        assert len(rows) == 5
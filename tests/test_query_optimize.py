@@ -0,0 +1,79 @@
+import pytest
+import implica
+
+
+def _node_rows(result, *variables):
+    return sorted(tuple(str(row[v]) for v in variables) for row in result)
+
+
+class TestQueryOptimize:
+    """Tests for selectivity-based reordering of MATCH operations."""
+
+    def test_optimize_returns_same_results_as_unoptimized(self):
+        """Reordering independent matches does not change the result set."""
+        graph = implica.Graph()
+        graph.query().create("(:A)").create("(:B)").create("(:C)").create("(:D)").execute()
+
+        plain = graph.query().match("(N)").match("(M:A)").return_("N", "M")
+        optimized = graph.query().match("(N)").match("(M:A)").optimize().return_("N", "M")
+
+        assert len(plain) == 4
+        assert _node_rows(plain, "N", "M") == _node_rows(optimized, "N", "M")
+
+    def test_optimize_keeps_dependent_matches_in_order(self):
+        """A match using a captured type variable is not moved before the capture."""
+        graph = implica.Graph()
+        graph.query().create("(:A)").create("(:A -> B)").create("(:T)").execute()
+
+        query = graph.query().match("(N:(T:*) -> B)").match("(M:T)")
+        plain = query.return_("N", "M")
+        optimized = query.optimize().return_("N", "M")
+
+        assert len(plain) == 1
+        assert str(plain[0]["M"]) == "Node(A: {})"
+        assert _node_rows(plain, "N", "M") == _node_rows(optimized, "N", "M")
+
+    def test_optimize_does_not_cross_other_operations(self):
+        """Matches are only reordered within runs of consecutive MATCH operations."""
+        graph = implica.Graph()
+        graph.query().create("(:A)").create("(:B)").execute()
+
+        result = (
+            graph.query()
+            .match("(N)")
+            .create("(:C)")
+            .match("(M:C)")
+            .optimize()
+            .return_("N", "M")
+        )
+
+        assert _node_rows(result, "N", "M") == [
+            ("Node(A: {})", "Node(C: {})"),
+            ("Node(B: {})", "Node(C: {})"),
+        ]
+
+    def test_optimize_can_be_disabled(self):
+        """optimize(False) restores the written order."""
+        graph = implica.Graph()
+        graph.query().create("(:A)").create("(:B)").execute()
+
+        result = graph.query().match("(N)").match("(M:B)").optimize(False).return_("N", "M")
+
+        assert len(result) == 2
+
+    def test_optimize_leaves_fewer_intermediate_rows(self):
+        """Running the selective match first leaves fewer rows between the two matches."""
+        graph = implica.Graph()
+        for i in range(50):
+            graph.query().create(f"(:T{i})").execute()
+        graph.query().create("(:A)").execute()
+
+        plain = graph.query().match("(N)").match("(M:A)").explain_analyze("N", "M")
+        optimized = graph.query().match("(N)").match("(M:A)").optimize().explain_analyze("N", "M")
+
+        plain_rows = [op["rows"] for op in plain["operations"]]
+        optimized_rows = [op["rows"] for op in optimized["operations"]]
+
+        assert plain_rows == [51, 51]
+        assert optimized_rows == [1, 51]
+        assert _node_rows(plain["rows"], "N", "M") == _node_rows(optimized["rows"], "N", "M")
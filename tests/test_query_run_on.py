@@ -0,0 +1,40 @@
+import pytest
+import implica
+
+
+class TestQueryRunOn:
+    """Tests for reusing a query as a template against other graphs."""
+
+    def test_run_on_uses_target_graph(self):
+        template = implica.Graph().query().match("(N:A)")
+
+        first = implica.Graph()
+        first.query().create("(:A { name: 'first' })").execute()
+        second = implica.Graph()
+        second.query().create("(:A { name: 'second' })").create("(:B)").execute()
+
+        first_result = template.run_on(first).return_("N")
+        second_result = template.run_on(second).return_("N")
+
+        assert [r["N"].properties() for r in first_result] == [{"name": "first"}]
+        assert [r["N"].properties() for r in second_result] == [{"name": "second"}]
+
+    def test_run_on_with_mutations_only_affects_target_graph(self):
+        template = implica.Graph().query().create("(:A)")
+
+        source = implica.Graph()
+        target = implica.Graph()
+        template.run_on(target).execute()
+
+        assert len(source.nodes()) == 0
+        assert len(target.nodes()) == 1
+
+    def test_run_on_returns_independent_query(self):
+        graph = implica.Graph()
+        graph.query().create("(:A)").create("(:B)").execute()
+        template = implica.Graph().query().match("(N)")
+
+        bound = template.run_on(graph).match("(N:A)")
+
+        assert len(bound.return_("N")) == 1
+        assert len(template.run_on(graph).return_("N")) == 2
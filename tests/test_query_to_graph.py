@@ -0,0 +1,63 @@
+import implica
+
+
+def _graph():
+    """A -> B -> C, with A -> C as a shortcut."""
+    graph = implica.Graph(
+        constants=[
+            implica.Constant("f", "A -> B"),
+            implica.Constant("g", "B -> C"),
+            implica.Constant("h", "A -> C"),
+        ]
+    )
+    graph.query().create("(:A {name: 'a'})-[::@f()]->(:B)-[::@g()]->(:C)").execute()
+    graph.query().match("(a:A)").match("(c:C)").create("(a)-[::@h()]->(c)").execute()
+    return graph
+
+
+class TestQueryToGraph:
+    """Tests for Query.to_graph."""
+
+    def test_path_results_become_a_graph(self):
+        """Only the matched edges are copied, not every edge among the matched nodes."""
+        graph = _graph()
+
+        copy = graph.query().match("(a:A)-[e1]->(b:B)-[e2]->(c:C)").to_graph()
+
+        assert len(copy.nodes()) == 3
+        assert len(copy.edges()) == 2
+        assert len(graph.edges()) == 3
+
+    def test_edges_bring_their_endpoints(self):
+        graph = _graph()
+
+        copy = graph.query().match("(a:A)-[e]->(c:C)").match("(x:B)").to_graph()
+
+        assert sorted(str(n.type()) for n in copy.nodes()) == ["A", "B", "C"]
+        assert len(copy.edges()) == 1
+
+    def test_elements_bound_in_several_rows_are_copied_once(self):
+        graph = _graph()
+
+        copy = graph.query().match("(a:A)-[e]->(x)").to_graph()
+
+        assert len(copy.nodes()) == 3
+        assert len(copy.edges()) == 2
+
+    def test_copy_is_independent(self):
+        """Properties are copied, so changing the copy leaves the original alone."""
+        graph = _graph()
+        copy = graph.query().match("(a:A)").to_graph()
+
+        copy.query().match("(a:A)").set("a", {"name": "changed"}).execute()
+
+        original = graph.query().match("(a:A)").one("a")["a"]
+        assert original.properties()["name"] == "a"
+
+    def test_empty_results_give_an_empty_graph(self):
+        graph = _graph()
+
+        copy = graph.query().match("(n:D)").to_graph()
+
+        assert len(copy.nodes()) == 0
+        assert len(copy.edges()) == 0
@@ -0,0 +1,116 @@
+import os
+import tempfile
+
+import pytest
+import implica
+
+
+def _graph():
+    graph = implica.Graph()
+    (
+        graph.query()
+        .create("(:A { name: 'a', score: 1, ratio: 0.5, active: true })")
+        .create("(:B { name: 'b', score: 2.5, active: false })")
+        .create("(:C { name: 'c' })")
+        .execute()
+    )
+    return graph
+
+
+class TestQueryToParquet:
+    """Tests for Query.to_parquet."""
+
+    def test_written_file_reads_back_with_inferred_types(self):
+        pq = pytest.importorskip("pyarrow.parquet")
+        graph = _graph()
+
+        with tempfile.TemporaryDirectory() as directory:
+            path = os.path.join(directory, "rows.parquet")
+            written = (
+                graph.query()
+                .match("(n)")
+                .order_by("n.name")
+                .to_parquet(path, "n.name", "n.score", "n.active")
+            )
+            table = pq.read_table(path)
+
+        assert written == 3
+        assert table.schema.names == ["n.name", "n.score", "n.active"]
+        assert [str(t) for t in table.schema.types] == ["string", "double", "bool"]
+        assert table.to_pydict() == {
+            "n.name": ["a", "b", "c"],
+            "n.score": [1.0, 2.5, None],
+            "n.active": [True, False, None],
+        }
+
+    def test_integer_column_and_defaults(self):
+        pq = pytest.importorskip("pyarrow.parquet")
+        graph = implica.Graph()
+        graph.query().create("(:A { n: 1 })").create("(:B { n: 2 })").create("(:C)").execute()
+
+        with tempfile.TemporaryDirectory() as directory:
+            path = os.path.join(directory, "rows.parquet")
+            graph.query().match("(x)").order_by("x.n").to_parquet(path, ("x.n", 0))
+            table = pq.read_table(path)
+
+        assert str(table.schema.types[0]) == "int64"
+        assert table.to_pydict() == {"x.n": [1, 2, 0]}
+
+    def test_node_variables_are_written_as_uids(self):
+        pq = pytest.importorskip("pyarrow.parquet")
+        graph = _graph()
+        uids = sorted(node.uid() for node in graph.nodes())
+
+        with tempfile.TemporaryDirectory() as directory:
+            path = os.path.join(directory, "rows.parquet")
+            graph.query().match("(n)").to_parquet(path, "n")
+            table = pq.read_table(path)
+
+        assert sorted(table.to_pydict()["n"]) == uids
+
+    def test_returns_the_number_of_rows_written(self):
+        with tempfile.TemporaryDirectory() as directory:
+            path = os.path.join(directory, "rows.parquet")
+
+            written = _graph().query().match("(n)").to_parquet(path, "n.name")
+
+            assert written == 3
+            assert os.path.getsize(path) > 0
+
+    def test_unsupported_property_type_raises_with_column_name(self):
+        graph = _graph()
+        graph.query().create("(:D { name: 'd', tags: ['x'] })").execute()
+
+        with tempfile.TemporaryDirectory() as directory:
+            path = os.path.join(directory, "rows.parquet")
+
+            with pytest.raises(TypeError, match="n.tags"):
+                graph.query().match("(n)").to_parquet(path, "n.tags")
+
+    def test_mixed_column_types_raise(self):
+        graph = implica.Graph()
+        graph.query().create("(:A { v: 1 })").create("(:B { v: 'one' })").execute()
+
+        with tempfile.TemporaryDirectory() as directory:
+            path = os.path.join(directory, "rows.parquet")
+
+            with pytest.raises(TypeError, match="x.v"):
+                graph.query().match("(x)").to_parquet(path, "x.v")
+
+    def test_edge_variables_raise(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+        graph.query().create("(:A)-[::@f()]->(:B)").execute()
+
+        with tempfile.TemporaryDirectory() as directory:
+            path = os.path.join(directory, "rows.parquet")
+
+            with pytest.raises(TypeError, match="column 'e'"):
+                graph.query().match("()-[e]->()").to_parquet(path, "e")
+
+    def test_no_columns_raises(self):
+        with pytest.raises(ValueError):
+            _graph().query().match("(n)").to_parquet("unused.parquet")
+
+    def test_unwritable_path_raises_os_error(self):
+        with pytest.raises(OSError):
+            _graph().query().match("(n)").to_parquet("/nonexistent/dir/rows.parquet", "n.name")
@@ -0,0 +1,59 @@
+import implica
+import pytest
+
+
+def _graph(size=10):
+    graph = implica.Graph()
+    for i in range(size):
+        graph.query().create(f"(:T{i} {{ i: {i} }})").execute()
+    return graph
+
+
+def _ordered(graph):
+    return graph.query().match("(n)").order_by("n.i").iter_("n")
+
+
+class TestResultIterator:
+    def test_iterates_every_row_in_order(self):
+        values = [row["n"].properties()["i"] for row in _ordered(_graph())]
+
+        assert values == list(range(10))
+
+    def test_seek_skips_ahead(self):
+        rows = _ordered(_graph())
+
+        rows.seek(7)
+
+        assert next(rows)["n"].properties()["i"] == 7
+        assert rows.position() == 8
+
+    def test_seek_backwards_revisits_rows(self):
+        rows = _ordered(_graph())
+        list(rows)
+
+        rows.seek(2)
+
+        assert [row["n"].properties()["i"] for row in rows] == list(range(2, 10))
+
+    def test_seek_to_end_exhausts(self):
+        rows = _ordered(_graph())
+
+        rows.seek(len(rows))
+
+        with pytest.raises(StopIteration):
+            next(rows)
+
+    def test_seek_past_end_raises(self):
+        rows = _ordered(_graph())
+
+        with pytest.raises(KeyError):
+            rows.seek(11)
+
+    def test_rows_are_taken_when_iterator_is_created(self):
+        graph = _graph(3)
+        rows = _ordered(graph)
+
+        graph.query().create("(:Extra { i: 99 })").execute()
+
+        assert len(rows) == 3
+        assert len(list(rows)) == 3
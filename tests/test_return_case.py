@@ -0,0 +1,65 @@
+import implica
+
+
+def _graph():
+    graph = implica.Graph()
+    graph.query().create("(:A {status: 'active', age: 40})").execute()
+    graph.query().create("(:B {status: 'inactive', age: 12})").execute()
+    graph.query().create("(:C {status: 'banned', age: 30})").execute()
+    graph.query().create("(:D)").execute()
+    return graph
+
+
+def _codes(rows, key="code"):
+    return {str(row["a"].type()): row[key] for row in rows}
+
+
+class TestReturnCase:
+    """Tests for Query.return_case."""
+
+    def test_maps_a_categorical_property_to_codes(self):
+        graph = _graph()
+
+        rows = graph.query().match("(a)").return_case(
+            "a.status", {"active": 1, "inactive": 0}, "a", default=-1, as_="code"
+        )
+
+        assert _codes(rows) == {"A": 1, "B": 0, "C": -1, "D": -1}
+
+    def test_unmatched_values_are_none_without_default(self):
+        graph = _graph()
+
+        rows = graph.query().match("(a)").return_case("a.status", {"active": 1}, "a", as_="code")
+
+        assert _codes(rows) == {"A": 1, "B": None, "C": None, "D": None}
+
+    def test_cases_over_a_computed_expression(self):
+        graph = _graph()
+
+        rows = graph.query().match("(a)").where_("a.age > 0").return_case(
+            "a.age >= 18", {True: "adult", False: "minor"}, "a"
+        )
+
+        assert _codes(rows, "case") == {"A": "adult", "B": "minor", "C": "adult"}
+
+    def test_case_column_can_stand_alone(self):
+        graph = _graph()
+
+        rows = graph.query().match("(a:A)").return_case("a.status", {"active": 1}, as_="code")
+
+        assert rows == [{"code": 1}]
+
+    def test_case_alongside_property_items(self):
+        graph = _graph()
+
+        rows = graph.query().match("(a:B)").return_case("a.status", {"inactive": 0}, "a.age", as_="code")
+
+        assert rows == [{"a.age": 12, "code": 0}]
+
+    def test_integer_cases_match_float_values(self):
+        graph = _graph()
+        graph.query().create("(:E {level: 2.0})").execute()
+
+        rows = graph.query().match("(a:E)").return_case("a.level", {2: "two"}, as_="name")
+
+        assert rows == [{"name": "two"}]
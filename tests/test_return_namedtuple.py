@@ -0,0 +1,58 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+    graph.query().create("(:A { x: 1 })-[::@f()]->(:B)").execute()
+    return graph
+
+
+class TestReturnNamedtuple:
+    def test_rows_expose_fields_as_attributes(self):
+        graph = _graph()
+
+        rows = graph.query().match("(a)-[e]->(b)").return_("a", "e", "b", as_="namedtuple")
+
+        assert len(rows) == 1
+        row = rows[0]
+        assert row._fields == ("a", "e", "b")
+        assert str(row.a.type()) == "A"
+        assert row.a.properties() == {"x": 1}
+        assert row.e.uid() == (row.a.uid(), row.b.uid())
+
+    def test_rows_unpack_in_variable_order(self):
+        graph = _graph()
+
+        (b, a), = graph.query().match("(a)-[]->(b)").return_("b", "a", as_="namedtuple")
+
+        assert str(a.type()) == "A"
+        assert str(b.type()) == "B"
+
+    def test_rows_share_a_single_class(self):
+        graph = implica.Graph()
+        graph.query().create("(:A)").create("(:B)").execute()
+
+        rows = graph.query().match("(n)").return_("n", as_="namedtuple")
+
+        assert len(rows) == 2
+        assert type(rows[0]) is type(rows[1])
+
+    def test_dict_rows_stay_the_default(self):
+        graph = _graph()
+
+        rows = graph.query().match("(a:A)").return_("a")
+
+        assert isinstance(rows[0], dict)
+
+    def test_names_that_are_not_field_names_raise(self):
+        graph = _graph()
+
+        with pytest.raises(ValueError):
+            graph.query().match("(_a:A)").return_("_a", as_="namedtuple")
+
+    def test_unknown_row_format_raises(self):
+        graph = _graph()
+
+        with pytest.raises(ValueError):
+            graph.query().match("(a:A)").return_("a", as_="list")
@@ -0,0 +1,63 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+    (
+        graph.query()
+        .create("(:A { name: 'a', score: 3 })-[::@f() { weight: 2 }]->(:B { name: 'b' })")
+        .create("(:C { name: 'c', score: 0.5, meta: { level: 1 } })")
+        .execute()
+    )
+    return graph
+
+
+def _by_name(rows):
+    return {row["n.name"]: row for row in rows}
+
+
+class TestReturnProperties:
+    def test_default_fills_missing_property(self):
+        rows = _by_name(_graph().query().match("(n)").return_("n.name", ("n.score", 0)))
+
+        assert rows["a"]["n.score"] == 3
+        assert rows["b"]["n.score"] == 0
+        assert rows["c"]["n.score"] == 0.5
+
+    def test_list_of_items(self):
+        rows = _by_name(_graph().query().match("(n)").return_(["n.name", ("n.score", -1)]))
+
+        assert rows["b"]["n.score"] == -1
+
+    def test_missing_property_without_default_is_none(self):
+        rows = _by_name(_graph().query().match("(n)").return_("n.name", "n.score"))
+
+        assert rows["b"]["n.score"] is None
+        assert rows["a"]["n.score"] == 3
+
+    def test_nested_property_path(self):
+        rows = _by_name(_graph().query().match("(n)").return_("n.name", ("n.meta.level", 0)))
+
+        assert rows["c"]["n.meta.level"] == 1
+        assert rows["a"]["n.meta.level"] == 0
+
+    def test_properties_and_elements_together(self):
+        rows = _graph().query().match("(n:A)-[e]->(m)").return_("n", ("e.weight", 1), ("m.score", 7))
+
+        assert len(rows) == 1
+        assert rows[0]["n"].properties()["name"] == "a"
+        assert rows[0]["e.weight"] == 2
+        assert rows[0]["m.score"] == 7
+
+    def test_default_requires_a_property_path(self):
+        with pytest.raises(ValueError):
+            _graph().query().match("(n)").return_(("n", 0))
+
+    def test_types_have_no_properties(self):
+        with pytest.raises(ValueError):
+            _graph().query().match("(n:(X:A))").return_("X.name")
+
+    def test_unbound_variable_raises(self):
+        with pytest.raises(KeyError):
+            _graph().query().match("(n)").return_(("m.score", 0))
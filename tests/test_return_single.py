@@ -0,0 +1,81 @@
+import pytest
+import implica
+
+
+class TestQueryFirst:
+    """Tests for the first() terminal."""
+
+    def test_first_with_no_rows_returns_none(self):
+        graph = implica.Graph()
+
+        assert graph.query().match("(N:A)").first("N") is None
+
+    def test_first_with_one_row_returns_it(self):
+        graph = implica.Graph()
+        graph.query().create("(:A)").create("(:B)").execute()
+
+        row = graph.query().match("(N:A)").first("N")
+
+        assert row is not None
+        assert str(row["N"]) == "Node(A: {})"
+
+    def test_first_with_many_rows_returns_one_of_them(self):
+        graph = implica.Graph()
+        graph.query().create("(:A)").create("(:B)").create("(:C)").execute()
+
+        row = graph.query().match("(N)").first("N")
+
+        assert row is not None
+        assert str(row["N"]) in {"Node(A: {})", "Node(B: {})", "Node(C: {})"}
+
+    def test_first_stops_scanning_at_the_first_match(self):
+        graph = implica.Graph()
+        for i in range(100):
+            graph.query().create(f"(:T{i})").execute()
+
+        before = graph.scan_info()["nodes_scanned"]
+        row = graph.query().match("(N)").first("N")
+
+        assert row is not None
+        assert graph.scan_info()["nodes_scanned"] - before == 1
+
+    def test_first_after_order_by_still_sees_every_row(self):
+        graph = implica.Graph()
+        for i in range(10):
+            graph.query().create(f"(:T{i} {{ i: {i} }})").execute()
+
+        row = graph.query().match("(N)").order_by("N.i", descending=True).first("N")
+
+        assert row["N"].properties() == {"i": 9}
+
+    def test_first_with_unknown_variable_raises(self):
+        graph = implica.Graph()
+        graph.query().create("(:A)").execute()
+
+        with pytest.raises(KeyError):
+            graph.query().match("(N)").first("M")
+
+
+class TestQueryOne:
+    """Tests for the one() terminal."""
+
+    def test_one_with_no_rows_raises(self):
+        graph = implica.Graph()
+
+        with pytest.raises(ValueError):
+            graph.query().match("(N:A)").one("N")
+
+    def test_one_with_one_row_returns_it(self):
+        graph = implica.Graph()
+        graph.query().create("(:A)").create("(:B)").execute()
+
+        row = graph.query().match("(N:B)").one("N")
+
+        assert str(row["N"]) == "Node(B: {})"
+
+    def test_one_with_many_rows_raises(self):
+        graph = implica.Graph()
+        graph.query().create("(:A)").create("(:B)").execute()
+
+        with pytest.raises(ValueError):
+            graph.query().match("(N)").one("N")
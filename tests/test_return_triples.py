@@ -0,0 +1,53 @@
+import pytest
+import implica
+
+
+class TestReturnTriples:
+    """Tests for returning edges together with their endpoints."""
+
+    def test_return_triples_for_all_edges_of_a_type(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "(A:*)->(B:*)")])
+        graph.query().create("(:A)").create("(:B)").create("(:C)").execute()
+        (
+            graph.query()
+            .create("()-[::@f(A, B)]->()")
+            .create("()-[::@f(A, C)]->()")
+            .create("()-[::@f(B, C)]->()")
+            .execute()
+        )
+
+        result = graph.query().match("()-[E:A->*]->()").return_triples("E")
+
+        assert len(result) == 2
+        assert all(set(row.keys()) == {"start", "edge", "end"} for row in result)
+        assert all(isinstance(row["start"], implica.Node) for row in result)
+        assert all(isinstance(row["edge"], implica.Edge) for row in result)
+        assert all(isinstance(row["end"], implica.Node) for row in result)
+        assert {(str(r["start"]), str(r["edge"]), str(r["end"])) for r in result} == {
+            ("Node(A: {})", "Edge((A -> B):f {})", "Node(B: {})"),
+            ("Node(A: {})", "Edge((A -> C):f {})", "Node(C: {})"),
+        }
+
+    def test_return_triples_with_backward_edge_keeps_edge_orientation(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+        graph.query().create("(:A)-[::@f()]->(:B)").execute()
+
+        result = graph.query().match("(:B)<-[E]-()").return_triples("E")
+
+        assert len(result) == 1
+        assert str(result[0]["start"]) == "Node(A: {})"
+        assert str(result[0]["end"]) == "Node(B: {})"
+
+    def test_return_triples_on_node_variable_raises(self):
+        graph = implica.Graph()
+        graph.query().create("(:A)").execute()
+
+        with pytest.raises(ValueError):
+            graph.query().match("(N)").return_triples("N")
+
+    def test_return_triples_on_unknown_variable_raises(self):
+        graph = implica.Graph()
+        graph.query().create("(:A)").execute()
+
+        with pytest.raises(KeyError):
+            graph.query().match("(N)").return_triples("E")
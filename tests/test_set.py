@@ -125,3 +125,12 @@ class TestSetQueryFailure:
 
         with pytest.raises(ValueError):
             graph.query().match("(::f)").set("f", {"foo": "var"}).execute()
+
+    def test_set_query_error_names_the_non_element_binding(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "A")])
+        graph.query().create("(::@f())").execute()
+
+        with pytest.raises(ValueError, match="'X'.*bound to a type"):
+            graph.query().match("(:(X:*))").set("X", {"foo": "var"}).execute()
+        with pytest.raises(ValueError, match="'f'.*bound to a term"):
+            graph.query().match("(::f)").set("f", {"foo": "var"}).execute()
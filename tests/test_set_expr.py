@@ -0,0 +1,73 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+    (
+        graph.query()
+        .create("(:A { name: 'a', score: 10 })-[::@f() { w: 2 }]->(:B { name: 'b', score: 2.5 })")
+        .create("(:C { name: 'c', score: 4, label: 'x' })")
+        .execute()
+    )
+    return graph
+
+
+def _scores(graph):
+    rows = graph.query().match("(n)").return_("n")
+    return {row["n"].properties()["name"]: row["n"].properties()["score"] for row in rows}
+
+
+class TestSetExpr:
+    def test_scales_a_numeric_property_on_every_row(self):
+        graph = _graph()
+
+        graph.query().match("(n)").set_expr("n.score", "n.score * 2").execute()
+
+        assert _scores(graph) == {"a": 20, "b": 5.0, "c": 8}
+
+    def test_mixed_int_and_float_arithmetic(self):
+        graph = _graph()
+
+        graph.query().match("(n:A)").set_expr("n.score", "n.score * 1.5").execute()
+
+        assert _scores(graph)["a"] == pytest.approx(15.0)
+
+    def test_expression_can_read_other_bindings(self):
+        graph = _graph()
+
+        graph.query().match("(a)-[e]->(b)").set_expr("e.total", "a.score + b.score * e.w").execute()
+
+        assert graph.edges()[0].properties() == {"w": 2, "total": 15.0}
+
+    def test_new_property_is_added_and_others_kept(self):
+        graph = _graph()
+
+        graph.query().match("(n:C)").set_expr("n.tag", "n.label + '!'").execute()
+
+        assert graph.query().match("(n:C)").one("n")["n"].properties() == {
+            "name": "c",
+            "score": 4,
+            "label": "x",
+            "tag": "x!",
+        }
+
+    def test_rows_sharing_an_element_see_the_original_value(self):
+        graph = _graph()
+
+        graph.query().match("(n:A)").match("(m)").set_expr("n.score", "n.score + 1").execute()
+
+        assert _scores(graph)["a"] == 11
+
+    def test_type_errors_raise(self):
+        graph = _graph()
+
+        with pytest.raises(RuntimeError):
+            graph.query().match("(n:C)").set_expr("n.label", "n.label * 1.1").execute()
+
+        assert graph.query().match("(n:C)").one("n")["n"].properties()["label"] == "x"
+
+    def test_property_must_be_variable_dot_key(self):
+        for property in ["score", "n.", "n.a.b"]:
+            with pytest.raises(ValueError):
+                _graph().query().match("(n)").set_expr(property, "1")
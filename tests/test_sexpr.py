@@ -0,0 +1,90 @@
+import pytest
+import implica
+
+
+def _graph():
+    return implica.Graph(
+        constants=[
+            implica.Constant("a", "A"),
+            implica.Constant("f", "A -> B"),
+            implica.Constant("g", "B -> C"),
+        ]
+    )
+
+
+class TestTypeSexpr:
+    """Tests for Type.to_sexpr and Type.from_sexpr."""
+
+    def test_multi_arrow_type_round_trips(self):
+        graph = _graph()
+
+        parsed = implica.Type.from_sexpr("(arrow A (arrow B C))", graph)
+
+        assert parsed.to_sexpr() == "(arrow A (arrow B C))"
+
+    def test_arrow_argument_nests_on_the_left(self):
+        graph = _graph()
+
+        parsed = implica.Type.from_sexpr("(arrow (arrow A B) C)", graph)
+
+        assert parsed.to_sexpr() == "(arrow (arrow A B) C)"
+
+    def test_types_of_the_graph_export(self):
+        graph = _graph()
+        graph.query().create("(:A)-[::@f()]->(:B)").execute()
+
+        edge = graph.edges()[0]
+
+        assert edge.type().to_sexpr() == "(arrow A B)"
+        assert implica.Type.from_sexpr("(arrow A B)", graph) == edge.type()
+
+    def test_frozen_graph_is_left_untouched(self):
+        graph = _graph()
+        version = graph.version()
+        graph.freeze()
+
+        with pytest.raises(ValueError, match="graph is frozen"):
+            implica.Type.from_sexpr("(arrow A B)", graph)
+
+        assert graph.version() == version
+        assert graph.stats()["types"] == 0
+
+    @pytest.mark.parametrize("text", ["", "(arrow A)", "(arrow A B", "(pair A B)", "A B", ")"])
+    def test_malformed_types_are_rejected(self, text):
+        with pytest.raises(TypeError):
+            implica.Type.from_sexpr(text, _graph())
+
+
+class TestTermSexpr:
+    """Tests for Term.to_sexpr and Term.from_sexpr."""
+
+    def test_nested_application_round_trips(self):
+        graph = _graph()
+
+        term = implica.Term.from_sexpr("(app g (app f a))", graph)
+
+        assert str(term) == "(g (f a))"
+        assert term.to_sexpr() == "(app g (app f a))"
+        assert term.type().to_sexpr() == "C"
+
+    def test_terms_of_the_graph_export(self):
+        graph = _graph()
+        graph.query().create("(:A)-[::@f()]->(:B)").execute()
+
+        node = graph.query().match("(n:B)").one("n")["n"]
+
+        assert node.term().to_sexpr() == "(app f a)"
+        assert implica.Term.from_sexpr("(app f a)", graph) == node.term()
+
+    def test_ill_typed_application_is_rejected(self):
+        with pytest.raises(TypeError):
+            implica.Term.from_sexpr("(app g a)", _graph())
+
+    def test_unknown_constant_is_rejected(self):
+        with pytest.raises(KeyError):
+            implica.Term.from_sexpr("(app f b)", _graph())
+
+    @pytest.mark.parametrize("text", ["", "(app f)", "(apply f a)", "(app f a"])
+    def test_malformed_terms_are_rejected(self, text):
+        with pytest.raises(ValueError):
+            implica.Term.from_sexpr(text, _graph())
@@ -0,0 +1,47 @@
+import implica
+
+
+def _graph(name="a", weight=2):
+    graph = implica.Graph(constants=[implica.Constant("f", "A -> B"), implica.Constant("x", "A")])
+    graph.query().create(f"(:A:@x() {{ name: '{name}' }})-[::@f() {{ w: {weight} }}]->(:B)").execute()
+    return graph
+
+
+def _node(graph, type_name="A"):
+    return graph.query().match(f"(n:{type_name})").one("n")["n"]
+
+
+class TestStructuralEquality:
+    def test_nodes_of_independent_graphs_are_equal(self):
+        assert _node(_graph()).structurally_equals(_node(_graph()))
+
+    def test_nodes_differing_in_a_property_are_not_equal(self):
+        assert not _node(_graph()).structurally_equals(_node(_graph(name="b")))
+
+    def test_nodes_differing_in_type_are_not_equal(self):
+        graph = _graph()
+
+        assert not _node(graph).structurally_equals(_node(graph, "B"))
+
+    def test_node_with_and_without_term_are_not_equal(self):
+        other = implica.Graph()
+        other.query().create("(:A { name: 'a' })").execute()
+
+        assert not _node(_graph()).structurally_equals(_node(other))
+
+    def test_integer_and_float_properties_compare_by_value(self):
+        first = implica.Graph()
+        first.query().create("(:A { v: 1 })").execute()
+        second = implica.Graph()
+        second.query().create("(:A { v: 1.0 })").execute()
+
+        assert _node(first).structurally_equals(_node(second))
+
+    def test_edges_of_independent_graphs_are_equal(self):
+        assert _graph().edges()[0].structurally_equals(_graph().edges()[0])
+
+    def test_edges_differing_in_a_property_are_not_equal(self):
+        assert not _graph().edges()[0].structurally_equals(_graph(weight=3).edges()[0])
+
+    def test_edges_with_differing_endpoints_are_not_equal(self):
+        assert not _graph().edges()[0].structurally_equals(_graph(name="b").edges()[0])
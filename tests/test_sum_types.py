@@ -0,0 +1,63 @@
+import implica
+import pytest
+
+
+def _graph():
+    color = implica.Sum(
+        "Color",
+        [
+            implica.Constant("red", "Red"),
+            implica.Constant("green", "Green"),
+            implica.Constant("blue", "Blue"),
+        ],
+    )
+    graph = implica.Graph(constants=[implica.Constant("circle", "Shape")], sums=[color])
+    (
+        graph.query()
+        .create("(:Red:@red())")
+        .create("(:Green:@green())")
+        .create("(:Blue:@blue())")
+        .create("(:Shape:@circle())")
+        .create("(:Yellow)")
+        .execute()
+    )
+    return graph
+
+
+class TestSumTypes:
+    def test_match_every_case_of_a_sum(self):
+        rows = _graph().query().match("(n:$Color$)").return_("n")
+
+        assert sorted(str(row["n"].term()) for row in rows) == ["blue", "green", "red"]
+
+    def test_match_each_case_alone(self):
+        graph = _graph()
+
+        for case in ["red", "green", "blue"]:
+            rows = graph.query().match(f"(n:$Color$:@{case}())").return_("n")
+            assert [str(row["n"].term()) for row in rows] == [case]
+
+    def test_non_inhabitants_do_not_match(self):
+        rows = _graph().query().match("(n:$Color$)").return_("n")
+
+        assert "Shape" not in {str(row["n"].type()) for row in rows}
+        assert "Yellow" not in {str(row["n"].type()) for row in rows}
+
+    def test_cases_are_registered_as_constants(self):
+        graph = _graph()
+        graph.query().match("(n:Red)").remove("n").execute()
+        graph.query().create("(:Red:@red())").execute()
+
+        assert len(graph.query().match("(n:$Color$)").return_("n")) == 3
+
+    def test_unknown_sum_raises(self):
+        with pytest.raises(ValueError):
+            _graph().query().match("(n:$Size$)").return_("n")
+
+    def test_sum_cannot_be_created(self):
+        with pytest.raises(ValueError):
+            _graph().query().create("(:$Color$)").execute()
+
+    def test_sum_needs_cases(self):
+        with pytest.raises(TypeError):
+            implica.Sum("Empty", [])
@@ -0,0 +1,81 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph(
+        constants=[
+            implica.Constant("h", "A -> B -> C"),
+            implica.Constant("a", "A"),
+            implica.Constant("b", "B"),
+        ]
+    )
+    graph.query().create("(:A)").create("(:B:@b())").create("(:A -> B -> C:@h())").execute()
+    return graph
+
+
+def _term(graph, pattern):
+    return graph.query().match(f"(n:{pattern})").one("n")["n"].term()
+
+
+def _graph_with_a():
+    graph = _graph()
+    graph.query().create("(:C)").execute()
+    graph.build().node("a", type="A", term="@a()").commit()
+    return graph
+
+
+class TestTermPartialApply:
+    def test_applying_one_argument_to_a_curried_function(self):
+        graph = _graph_with_a()
+        function = _term(graph, "A -> B -> C")
+        argument = _term(graph, "A")
+
+        applied = function.partial_apply(argument)
+
+        assert str(applied.type()) == "(B -> C)"
+        assert str(applied) == "(h a)"
+
+    def test_fully_applying_a_curried_function(self):
+        graph = _graph_with_a()
+        function = _term(graph, "A -> B -> C")
+
+        applied = function.partial_apply(_term(graph, "A")).partial_apply(_term(graph, "B"))
+
+        assert str(applied.type()) == "C"
+        assert str(applied) == "((h a) b)"
+
+    def test_result_is_the_term_labelling_edges_of_its_type(self):
+        graph = _graph_with_a()
+        applied = _term(graph, "A -> B -> C").partial_apply(_term(graph, "A"))
+
+        edge = graph.query().match("()-[e:B -> C]->()").one("e")["e"]
+
+        assert edge.term() == applied
+
+    def test_argument_of_the_wrong_type_raises(self):
+        graph = _graph_with_a()
+        function = _term(graph, "A -> B -> C")
+
+        with pytest.raises(TypeError):
+            function.partial_apply(_term(graph, "B"))
+
+    def test_conflicting_term_for_the_result_type_raises(self):
+        graph = implica.Graph(
+            constants=[
+                implica.Constant("h", "A -> B -> C"),
+                implica.Constant("g", "B -> C"),
+                implica.Constant("a", "A"),
+            ]
+        )
+        graph.query().create("(:A)").create("(:B)-[::@g()]->(:C)").create("(:A -> B -> C:@h())").execute()
+        graph.build().node("a", type="A", term="@a()").commit()
+
+        with pytest.raises(ValueError, match="already holds"):
+            _term(graph, "A -> B -> C").partial_apply(_term(graph, "A"))
+
+    def test_argument_from_another_graph_raises(self):
+        graph = _graph_with_a()
+
+        with pytest.raises(ValueError):
+            _term(graph, "A -> B -> C").partial_apply(_term(_graph_with_a(), "A"))
@@ -0,0 +1,98 @@
+import time
+
+import pytest
+import implica
+
+
+class TestNodeTimestamps:
+    def test_node_timestamps_are_set_on_creation(self):
+        before = time.time()
+        graph = implica.Graph()
+        graph.query().create("(:A)").execute()
+        after = time.time()
+
+        node = graph.nodes()[0]
+
+        assert before <= node.created_at() <= after
+        assert node.modified_at() == node.created_at()
+
+    def test_node_modified_at_bumps_on_set(self):
+        graph = implica.Graph()
+        graph.query().create("(:A)").execute()
+        node = graph.nodes()[0]
+        created_at = node.created_at()
+
+        time.sleep(0.01)
+        graph.query().match("(N)").set("N", {"x": 1}, False).execute()
+
+        assert node.created_at() == created_at
+        assert node.modified_at() > created_at
+
+    def test_node_timestamps_of_removed_node_raise(self):
+        graph = implica.Graph()
+        graph.query().create("(:A)").execute()
+        node = graph.nodes()[0]
+
+        graph.query().match("(N)").remove("N").execute()
+
+        with pytest.raises(KeyError):
+            node.created_at()
+
+
+class TestEdgeTimestamps:
+    def test_edge_timestamps_are_set_on_creation(self):
+        before = time.time()
+        graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+        graph.query().create("(:A)-[::@f()]->(:B)").execute()
+        after = time.time()
+
+        edge = graph.edges()[0]
+
+        assert before <= edge.created_at() <= after
+        assert edge.modified_at() == edge.created_at()
+
+    def test_edge_modified_at_bumps_on_set(self):
+        graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+        graph.query().create("(:A)-[::@f()]->(:B)").execute()
+        edge = graph.edges()[0]
+        created_at = edge.created_at()
+
+        time.sleep(0.01)
+        graph.query().match("()-[E]->()").set("E", {"w": 2}).execute()
+
+        assert edge.created_at() == created_at
+        assert edge.modified_at() > created_at
+
+
+class TestTimestampsInWhere:
+    def test_where_filters_nodes_by_creation_time(self):
+        graph = implica.Graph()
+        graph.query().create("(:Old)").execute()
+        time.sleep(0.01)
+        cutoff = time.time()
+        time.sleep(0.01)
+        graph.query().create("(:New)").execute()
+
+        rows = graph.query().match("(n)").where_(f"n.created_at > {cutoff}").return_("n")
+
+        assert [str(row["n"].type()) for row in rows] == ["New"]
+
+    def test_where_filters_edges_by_modification_time(self):
+        graph = implica.Graph(
+            constants=[implica.Constant("f", "A -> B"), implica.Constant("g", "B -> C")]
+        )
+        graph.query().create("(:A)-[::@f()]->(:B)-[::@g()]->(:C)").execute()
+        time.sleep(0.01)
+        cutoff = time.time()
+        time.sleep(0.01)
+        graph.query().match("(:B)-[e]->()").set("e", {"w": 1}).execute()
+
+        rows = graph.query().match("()-[e]->()").where_(f"e.modified_at > {cutoff}").return_("e")
+
+        assert [str(row["e"].type()) for row in rows] == ["(B -> C)"]
+
+    def test_properties_shadow_timestamps(self):
+        graph = implica.Graph()
+        graph.query().create("(:A { created_at: 0 })").execute()
+
+        assert graph.query().match("(n)").where_("n.created_at == 0").return_("n") != []
@@ -0,0 +1,53 @@
+import implica
+
+
+def _graph():
+    return implica.Graph(
+        constants=[
+            implica.Constant("a", "A"),
+            implica.Constant("f", "A -> B"),
+            implica.Constant("g", "B -> C"),
+            implica.Constant("k", "(X:*) -> D"),
+        ]
+    )
+
+
+class TestTypeCheck:
+    """Tests for Graph.type_check."""
+
+    def test_empty_graph_is_consistent(self):
+        assert _graph().type_check() == []
+
+    def test_nodes_without_terms_are_consistent(self):
+        graph = _graph()
+        graph.query().create("(:A)").create("(:B)").execute()
+
+        assert graph.type_check() == []
+
+    def test_edges_and_applied_terms_are_consistent(self):
+        graph = _graph()
+        (
+            graph.query()
+            .create("(:A:@a())")
+            .create("(:A)-[::@f()]->(:B)-[::@g()]->(:C)")
+            .execute()
+        )
+
+        assert len(graph.edges()) == 2
+        assert any(str(node.term()) == "(g (f a))" for node in graph.nodes() if node.term())
+        assert graph.type_check() == []
+
+    def test_edges_of_parametric_constants_are_consistent(self):
+        graph = _graph()
+        graph.query().create("(:A)-[::@k(A)]->(:D)").create("(:B)-[::@k(B)]->(:D)").execute()
+
+        assert len(graph.edges()) == 2
+        assert graph.type_check() == []
+
+    def test_subgraph_copies_stay_consistent(self):
+        graph = _graph()
+        graph.query().create("(:A:@a())").create("(:A)-[::@f()]->(:B)").execute()
+
+        copy = graph.query().match("(x)-[e]->(y)").to_graph()
+
+        assert copy.type_check() == []
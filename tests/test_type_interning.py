@@ -0,0 +1,68 @@
+import implica
+
+
+def _graph(size=50):
+    graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+    graph.query().create("(:A)-[::@f()]->(:B)").execute()
+    for i in range(size):
+        graph.query().create(f"(:T{i} -> T{(i + 1) % size})").execute()
+        graph.query().create(f"(:(T{i} -> T{(i + 1) % size}) -> T{i})").execute()
+    return graph
+
+
+def _uids(rows, variable):
+    return sorted(row[variable].uid() for row in rows)
+
+
+class TestTypeInterning:
+    def test_concrete_types_match_like_structural_patterns(self):
+        graph = _graph()
+
+        for i in range(0, 50, 7):
+            j = (i + 1) % 50
+            interned = graph.query().match(f"(n:T{i} -> T{j})").return_("n")
+            structural = graph.query().match(f"(n:(X:T{i}) -> (Y:T{j}))").return_("n")
+
+            assert len(interned) == 1
+            assert _uids(interned, "n") == _uids(structural, "n")
+
+    def test_nested_concrete_types_match_like_structural_patterns(self):
+        graph = _graph()
+
+        interned = graph.query().match("(n:(T3 -> T4) -> T3)").return_("n")
+        structural = graph.query().match("(n:((X:T3) -> T4) -> (Y:*))").return_("n")
+
+        assert len(interned) == 1
+        assert _uids(interned, "n") == _uids(structural, "n")
+
+    def test_edge_types_match_like_structural_patterns(self):
+        graph = _graph()
+
+        interned = graph.query().match("()-[e:A -> B]->()").return_("e")
+        structural = graph.query().match("()-[e:(X:A) -> (Y:B)]->()").return_("e")
+
+        assert len(interned) == 1
+        assert _uids(interned, "e") == _uids(structural, "e")
+
+    def test_missing_concrete_type_matches_nothing(self):
+        graph = _graph()
+
+        assert graph.query().match("(n:T1 -> T3)").return_("n") == []
+        assert graph.query().match("(n:Missing)").return_("n") == []
+
+    def test_type_name_bound_earlier_refers_to_bound_type(self):
+        graph = _graph()
+
+        rows = graph.query().match("(m:(X:T5) -> T6)").match("(n:X -> T6)").return_("n")
+        assert _uids(rows, "n") == _uids(graph.query().match("(n:T5 -> T6)").return_("n"), "n")
+
+        rows = graph.query().match("(m:(T5:T7) -> T8)").match("(n:T5 -> T8)").return_("n")
+        assert _uids(rows, "n") == _uids(graph.query().match("(n:T7 -> T8)").return_("n"), "n")
+
+    def test_repeated_matching_with_same_schema(self):
+        graph = _graph()
+        query_results = [
+            _uids(graph.query().match("(n:T10 -> T11)").return_("n"), "n") for _ in range(20)
+        ]
+
+        assert all(result == query_results[0] for result in query_results)
@@ -0,0 +1,38 @@
+import implica
+
+
+def _build():
+    graph = implica.Graph(
+        constants=[implica.Constant("f", "A -> B"), implica.Constant("a", "A")]
+    )
+    (
+        graph.query()
+        .create("(:A:@a() { x: 1 })-[::@f() { w: 2 }]->(:B)")
+        .create("(:(A -> B) -> C)")
+        .execute()
+    )
+    return graph
+
+
+class TestContentAddressedUids:
+    def test_same_graph_has_same_uids_across_builds(self):
+        first, second = _build(), _build()
+
+        assert sorted(n.uid() for n in first.nodes()) == sorted(n.uid() for n in second.nodes())
+        assert sorted(e.uid() for e in first.edges()) == sorted(e.uid() for e in second.edges())
+
+    def test_node_uid_depends_only_on_type(self):
+        first = implica.Graph()
+        first.query().create("(:A { x: 1 })").execute()
+        second = implica.Graph()
+        second.query().create("(:B)").create("(:A { x: 2 })").execute()
+
+        uid = first.nodes()[0].uid()
+        assert uid in [n.uid() for n in second.nodes()]
+        assert first.nodes()[0].type().uid() == uid
+
+    def test_edge_uid_is_pair_of_endpoint_uids(self):
+        graph = _build()
+        row = graph.query().match("(a)-[e]->(b)").one("a", "e", "b")
+
+        assert row["e"].uid() == (row["a"].uid(), row["b"].uid())
@@ -0,0 +1,118 @@
+import implica
+import pytest
+
+
+def _graph(directed=False):
+    """A -> B -> C."""
+    graph = implica.Graph(
+        constants=[implica.Constant("f", "A -> B"), implica.Constant("g", "B -> C")],
+        directed=directed,
+    )
+    graph.query().create("(:A)-[::@f()]->(:B)-[::@g()]->(:C)").execute()
+    return graph
+
+
+def _node(graph, type_name):
+    return graph.query().match(f"(n:{type_name})").one("n")["n"]
+
+
+class TestUndirectedGraph:
+    """Tests for graphs created with directed=False."""
+
+    def test_graphs_are_directed_by_default(self):
+        assert implica.Graph().is_directed()
+        assert not _graph().is_directed()
+
+    def test_forward_patterns_match_edges_both_ways(self):
+        graph = _graph()
+
+        forward = graph.query().match("(x:B)-[e]->(y)").return_("y")
+        backward = graph.query().match("(x:B)<-[e]-(y)").return_("y")
+
+        assert sorted(str(row["y"].type()) for row in forward) == ["A", "C"]
+        assert sorted(str(row["y"].type()) for row in backward) == ["A", "C"]
+
+    def test_directed_graph_keeps_directions(self):
+        graph = _graph(directed=True)
+
+        rows = graph.query().match("(x:B)-[e]->(y)").return_("y")
+
+        assert [str(row["y"].type()) for row in rows] == ["C"]
+
+    def test_neighbours_are_symmetric(self):
+        """out_edges and in_edges both list every edge touching the node."""
+        graph = _graph()
+        b = _node(graph, "B")
+
+        out_edges = sorted(e.uid() for e in graph.out_edges(b))
+        in_edges = sorted(e.uid() for e in graph.in_edges(b))
+
+        assert out_edges == in_edges
+        assert len(out_edges) == 2
+
+    def test_match_within_ignores_directions(self):
+        graph = _graph()
+        c = _node(graph, "C")
+
+        rows = graph.query().match_within([c], k=2).return_("n")
+
+        assert sorted(str(row["n"].type()) for row in rows) == ["A", "B", "C"]
+
+    def test_variable_length_edges_walk_both_ways(self):
+        graph = _graph()
+
+        rows = graph.query().match("(x:C)-[| hops=1..2]->(y)").return_("y")
+
+        assert sorted(str(row["y"].type()) for row in rows) == ["A", "B"]
+
+    def test_variable_length_edges_need_no_direction(self):
+        graph = _graph()
+
+        rows = graph.query().match("(x:C)-[| hops=1..2]-(y)").return_("y")
+
+        assert sorted(str(row["y"].type()) for row in rows) == ["A", "B"]
+
+    def test_directed_graph_needs_a_direction_for_variable_length_edges(self):
+        graph = _graph(directed=True)
+
+        with pytest.raises(ValueError, match="needs a direction"):
+            graph.query().match("(x:C)-[| hops=1..2]-(y)").return_("y")
+
+    def test_edge_types_keep_their_direction(self):
+        """Edges are still stored from start to end, typed by their constants."""
+        graph = _graph()
+
+        rows = graph.query().match("(x:C)-[e]->(y:B)").return_("e")
+
+        assert str(rows[0]["e"].type()) == "(B -> C)"
+
+    def test_copies_stay_undirected(self):
+        graph = _graph()
+
+        copy = graph.query().match("(x:A)-[e]->(y)").to_graph()
+
+        assert not copy.is_directed()
+
+    def test_only_isolated_nodes_are_sources_or_sinks(self):
+        graph = _graph()
+        graph.query().create("(:D)").execute()
+
+        assert [str(n.type()) for n in graph.sources()] == ["D"]
+        assert [str(n.type()) for n in graph.sinks()] == ["D"]
+        assert [str(n.type()) for n in graph.isolated()] == ["D"]
+
+    def test_adjacency_matrix_is_symmetric(self):
+        graph = _graph()
+
+        matrix, nodes = graph.adjacency_matrix()
+
+        size = len(nodes)
+        assert all(matrix[i][j] == matrix[j][i] for i in range(size) for j in range(size))
+        assert sum(map(sum, matrix)) == 4
+
+    def test_schema_records_the_mode(self):
+        schema = _graph().export_schema()
+
+        assert schema["directed"] is False
+        assert not implica.Graph.load_schema(schema).is_directed()
+        assert implica.Graph.load_schema({}).is_directed()
@@ -0,0 +1,55 @@
+import implica
+import pytest
+
+
+class Money:
+    def __init__(self, amount):
+        self.amount = amount
+
+
+def _graph():
+    graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+    graph.query().create("(:A)-[::@f()]->(:B)").execute()
+    graph.query().match("()-[e]->()").set("e", {"w": Money(3)}).execute()
+    return graph
+
+
+class TestValueSerializer:
+    def test_custom_weight_is_exported_through_serializer(self):
+        graph = _graph()
+        graph.set_value_serializer(lambda value: value.amount * 2.5)
+
+        matrix, _ = graph.adjacency_matrix(weight="w")
+
+        assert sorted(v for row in matrix for v in row) == [0.0, 0.0, 0.0, 7.5]
+
+    def test_custom_weight_without_serializer_raises(self):
+        with pytest.raises(TypeError):
+            _graph().adjacency_matrix(weight="w")
+
+    def test_serializer_can_be_removed(self):
+        graph = _graph()
+        graph.set_value_serializer(lambda value: value.amount)
+        graph.set_value_serializer(None)
+
+        with pytest.raises(TypeError):
+            graph.adjacency_matrix(weight="w")
+
+    def test_serializer_errors_propagate(self):
+        graph = _graph()
+
+        def serializer(value):
+            raise RuntimeError("cannot serialize")
+
+        graph.set_value_serializer(serializer)
+
+        with pytest.raises(RuntimeError, match="cannot serialize"):
+            graph.adjacency_matrix(weight="w")
+
+    def test_stored_value_is_left_untouched(self):
+        graph = _graph()
+        graph.set_value_serializer(lambda value: value.amount)
+        graph.adjacency_matrix(weight="w")
+
+        edge = graph.edges()[0]
+        assert isinstance(edge.properties()["w"], Money)
@@ -0,0 +1,58 @@
+import implica
+import pytest
+
+
+class TestPatternVariableValidation:
+    @pytest.mark.parametrize("pattern", ["(__n:A)", "(n:A)-[__e]->(m:B)", "(1n:A)"])
+    def test_match_rejects_invalid_pattern_variables(self, pattern):
+        graph = implica.Graph()
+
+        with pytest.raises(ValueError):
+            graph.query().match(pattern)
+
+    def test_create_rejects_reserved_node_variable(self):
+        graph = implica.Graph()
+
+        with pytest.raises(ValueError):
+            graph.query().create("(__n:A)")
+
+        assert graph.nodes() == []
+
+    @pytest.mark.parametrize("pattern", ["(n:A)", "(_n:A)", "(node_1:A)", "(n:A)-[e]->(m:B)"])
+    def test_match_accepts_valid_pattern_variables(self, pattern):
+        graph = implica.Graph()
+
+        graph.query().match(pattern).execute()
+
+
+class TestQueryVariableValidation:
+    @pytest.mark.parametrize("name", ["__n", "1n", "a b", "a-b", "None", ""])
+    def test_remove_rejects_invalid_variable_at_build_time(self, name):
+        graph = implica.Graph()
+        query = graph.query().match("(n:A)")
+
+        with pytest.raises(ValueError, match="Invalid Query"):
+            query.remove(name)
+
+    def test_set_rejects_invalid_variable_at_build_time(self):
+        graph = implica.Graph()
+
+        with pytest.raises(ValueError, match="Invalid Query"):
+            graph.query().match("(n:A)").set("__n", {"x": 1})
+
+    def test_return_rejects_invalid_variable(self):
+        graph = implica.Graph()
+        graph.query().create("(:A)").execute()
+
+        with pytest.raises(ValueError, match="Invalid Query"):
+            graph.query().match("(n:A)").return_("n", "__n")
+
+    def test_valid_variables_are_accepted(self):
+        graph = implica.Graph()
+        graph.query().create("(:A)").execute()
+
+        result = graph.query().match("(_n:A)").set("_n", {"x": 1}).return_("_n")
+        assert result[0]["_n"].properties() == {"x": 1}
+
+        graph.query().match("(_n:A)").remove("_n").execute()
+        assert graph.nodes() == []
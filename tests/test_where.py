@@ -0,0 +1,133 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph()
+    (
+        graph.query()
+        .create("(:A { name: 'alice', age: 30, tags: ['a', 'b', 'c', 'd'] })")
+        .create("(:B { name: 'bob', age: 20, tags: ['a'] })")
+        .create("(:C { name: 'carol', age: 40 })")
+        .execute()
+    )
+    return graph
+
+
+def _types(result, variable="n"):
+    return sorted(str(row[variable].type()) for row in result)
+
+
+class TestWhere:
+    def test_where_compares_properties(self):
+        graph = _graph()
+
+        result = graph.query().match("(n)").where_("n.age > 25").return_("n")
+        assert _types(result) == ["A", "C"]
+
+    def test_where_supports_boolean_keywords(self):
+        graph = _graph()
+
+        result = graph.query().match("(n)").where_("n.age > 25 AND n.name = 'carol'").return_("n")
+        assert _types(result) == ["C"]
+
+        result = graph.query().match("(n)").where_("n.age < 25 OR n.name = 'carol'").return_("n")
+        assert _types(result) == ["B", "C"]
+
+    def test_where_supports_string_predicates(self):
+        graph = _graph()
+
+        result = graph.query().match("(n)").where_("n.name STARTS WITH 'ca'").return_("n")
+        assert _types(result) == ["C"]
+
+    def test_where_string_literal_may_hold_double_quotes(self):
+        graph = _graph()
+        graph.query().match("(n:B)").set("n", {"quote": 'say "hi"'}, False).execute()
+
+        result = graph.query().match("(n)").where_("n.quote == 'say \"hi\"'").return_("n")
+        assert _types(result) == ["B"]
+
+    def test_where_exposes_lists_as_arrays(self):
+        graph = _graph()
+
+        result = graph.query().match("(n)").where_("size(n.tags) > 3").return_("n")
+        assert _types(result) == ["A"]
+
+    def test_where_size_of_missing_property_is_zero(self):
+        graph = _graph()
+
+        result = graph.query().match("(n)").where_("size(n.tags) == 0").return_("n")
+        assert _types(result) == ["C"]
+
+    def test_where_over_edge_and_endpoints(self):
+        graph = implica.Graph(
+            constants=[implica.Constant("f", "A -> B"), implica.Constant("g", "A -> C")]
+        )
+        (
+            graph.query()
+            .create("(:A { x: 1 })-[::@f() { w: 5 }]->(:B { x: 2 })")
+            .create("(:A)-[::@g() { w: 1 }]->(:C { x: 3 })")
+            .execute()
+        )
+
+        result = graph.query().match("(a)-[e]->(b)").where_("e.w > 2").return_("b")
+        assert _types(result, "b") == ["B"]
+
+    def test_where_invalid_expression_raises(self):
+        graph = _graph()
+
+        with pytest.raises(RuntimeError):
+            graph.query().match("(n)").where_("n.age >").return_("n")
+
+
+def _float_graph():
+    graph = implica.Graph()
+    graph.query().create("(:A)").create("(:B)").create("(:C)").execute()
+
+    uids = {str(node.type()): node.uid() for node in graph.nodes()}
+    graph.set_node_properties(
+        {
+            uids["A"]: {"x": 0.1 + 0.2},
+            uids["B"]: {"x": float("nan")},
+            uids["C"]: {"x": 0.5},
+        }
+    )
+    return graph
+
+
+class TestWhereFloats:
+    def test_where_nan_is_never_equal(self):
+        graph = _float_graph()
+
+        result = graph.query().match("(n)").where_("n.x == n.x").return_("n")
+        assert _types(result) == ["A", "C"]
+
+    def test_where_approx_eq_tolerates_rounding(self):
+        graph = _float_graph()
+
+        result = graph.query().match("(n)").where_("n.x == 0.3").return_("n")
+        assert _types(result) == []
+
+        result = graph.query().match("(n)").where_("approx_eq(n.x, 0.3, 0.000001)").return_("n")
+        assert _types(result) == ["A"]
+
+    def test_where_approx_eq_accepts_integers_and_rejects_nan(self):
+        graph = _float_graph()
+
+        result = graph.query().match("(n)").where_("approx_eq(n.x, 1, 0.5)").return_("n")
+        assert _types(result) == ["C"]
+
+        result = graph.query().match("(n)").where_("approx_eq(n.x, n.x, 1)").return_("n")
+        assert _types(result) == ["A", "C"]
+
+    def test_where_nan_fails_every_comparison(self):
+        graph = _float_graph()
+
+        result = graph.query().match("(n)").where_("n.x < 1.0").return_("n")
+        assert _types(result) == ["A", "C"]
+
+        result = graph.query().match("(n)").where_("n.x - 0.3 >= 0 OR n.x - 0.3 < 0").return_("n")
+        assert _types(result) == ["A", "C"]
+
+        result = graph.query().match("(n)").where_("n.x != 0.5").return_("n")
+        assert _types(result) == ["A", "B"]
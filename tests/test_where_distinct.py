@@ -0,0 +1,50 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph()
+    graph.query().create("(:A)").create("(:B)").create("(:C)").execute()
+    return graph
+
+
+class TestWhereDistinct:
+    def test_where_distinct_drops_self_pairs(self):
+        graph = _graph()
+
+        assert len(graph.query().match("(a)").match("(b)").return_("a", "b")) == 9
+
+        rows = graph.query().match("(a)").match("(b)").where_distinct(["a", "b"]).return_("a", "b")
+        assert len(rows) == 6
+        assert all(row["a"] != row["b"] for row in rows)
+
+    def test_where_distinct_on_three_variables(self):
+        rows = (
+            _graph()
+            .query()
+            .match("(a)")
+            .match("(b)")
+            .match("(c)")
+            .where_distinct(["a", "b", "c"])
+            .return_("a", "b", "c")
+        )
+
+        assert len(rows) == 6
+        assert all(len({str(row[v].type()) for v in "abc"}) == 3 for row in rows)
+
+    def test_where_distinct_on_edges(self):
+        graph = implica.Graph(
+            constants=[implica.Constant("f", "A -> B"), implica.Constant("g", "A -> C")]
+        )
+        graph.query().create("(:A)-[::@f()]->(:B)").create("(:A)-[::@g()]->(:C)").execute()
+
+        rows = graph.query().match("()-[e]->()").match("()-[d]->()").where_distinct(["e", "d"]).return_("e", "d")
+        assert len(rows) == 2
+
+    def test_where_distinct_unknown_variable_raises(self):
+        with pytest.raises(KeyError):
+            _graph().query().match("(a)").where_distinct(["a", "z"]).return_("a")
+
+    def test_where_distinct_needs_two_variables(self):
+        with pytest.raises(ValueError):
+            _graph().query().match("(a)").where_distinct(["a"])
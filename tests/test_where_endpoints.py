@@ -0,0 +1,61 @@
+import pytest
+import implica
+
+
+def _graph():
+    """Alice and Bob both work at Acme; Alice also knows Bob."""
+    graph = implica.Graph(
+        constants=[
+            implica.Constant("works", "Person -> Company"),
+            implica.Constant("knows", "Person -> Friend"),
+        ]
+    )
+    graph.query().create("(:Person {name: 'Alice', age: 35})-[::@works()]->(:Company {name: 'Acme'})").execute()
+    graph.query().match("(p:Person)").create("(p)-[::@knows()]->(:Friend {name: 'Bob', age: 25})").execute()
+    return graph
+
+
+class TestWhereEdgeEndpoints:
+    """Tests for startNode(e) and endNode(e) in WHERE expressions."""
+
+    def test_filter_edges_by_a_property_of_their_start_node(self):
+        graph = _graph()
+
+        rows = graph.query().match("()-[e]->()").where_("startNode(e).age > 30").return_("e")
+
+        assert len(rows) == 2
+
+    def test_filter_edges_by_a_property_of_their_end_node(self):
+        graph = _graph()
+
+        rows = graph.query().match("()-[e]->()").where_("endNode(e).age < 30").return_("e")
+
+        assert [str(row["e"].type()) for row in rows] == ["(Person -> Friend)"]
+
+    def test_endpoints_do_not_need_to_be_bound(self):
+        """Endpoint properties are read even when the pattern leaves the nodes anonymous."""
+        graph = _graph()
+
+        rows = graph.query().match("()-[e]->()").where_("endNode(e).name = 'Acme'").return_("e")
+
+        assert [str(row["e"].type()) for row in rows] == ["(Person -> Company)"]
+
+    def test_accessors_follow_the_edge_not_the_pattern_direction(self):
+        graph = _graph()
+
+        rows = graph.query().match("(c:Company)<-[e]-()").where_("startNode(e).name = 'Alice'").return_("e")
+
+        assert len(rows) == 1
+
+    def test_accessors_work_in_order_by(self):
+        graph = _graph()
+
+        rows = graph.query().match("()-[e]->()").order_by("endNode(e).name").return_("e")
+
+        assert [str(row["e"].type()) for row in rows] == ["(Person -> Company)", "(Person -> Friend)"]
+
+    def test_accessor_on_a_node_variable_raises(self):
+        graph = _graph()
+
+        with pytest.raises(RuntimeError, match="take an edge variable"):
+            graph.query().match("(p:Person)").where_("startNode(p).age > 30").return_("p")
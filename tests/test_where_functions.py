@@ -0,0 +1,98 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph()
+    (
+        graph.query()
+        .create("(:A { name: 'a', score: 0.9, debt: 10 })")
+        .create("(:B { name: 'b', score: 0.2, debt: 50 })")
+        .create("(:C { name: 'c', score: 0.7, debt: 5 })")
+        .create("(:D { name: 'd' })")
+        .execute()
+    )
+    return graph
+
+
+def _names(rows, variable="n"):
+    return sorted(row[variable].properties()["name"] for row in rows)
+
+
+def risk(score):
+    return 0.0 if score is None else score * 2 - 0.5
+
+
+class TestWhereFunctions:
+    def test_filter_with_registered_function(self):
+        rows = (
+            _graph()
+            .query()
+            .register_function("risk", risk)
+            .match("(n)")
+            .where_("risk(n.score) > 0.5")
+            .return_("n")
+        )
+
+        assert _names(rows) == ["a", "c"]
+
+    def test_function_with_several_arguments(self):
+        rows = (
+            _graph()
+            .query()
+            .register_function("ratio", lambda debt, score: debt / score)
+            .match("(n)")
+            .where_("n.score != () AND ratio(n.debt, n.score) < 20")
+            .return_("n")
+        )
+
+        assert _names(rows) == ["a", "c"]
+
+    def test_function_returning_strings_and_bools(self):
+        rows = (
+            _graph()
+            .query()
+            .register_function("shout", lambda s: s.upper())
+            .register_function("is_vowel", lambda s: s in "aeiou")
+            .match("(n)")
+            .where_("shout(n.name) = 'A' OR is_vowel(n.name)")
+            .return_("n")
+        )
+
+        assert _names(rows) == ["a"]
+
+    def test_function_in_order_by(self):
+        rows = (
+            _graph()
+            .query()
+            .register_function("risk", risk)
+            .match("(n)")
+            .order_by("risk(n.score)", descending=True)
+            .return_("n")
+        )
+
+        assert [row["n"].properties()["name"] for row in rows] == ["a", "c", "d", "b"]
+
+    def test_exception_propagates(self):
+        def broken(_):
+            raise ZeroDivisionError("boom")
+
+        query = _graph().query().register_function("broken", broken).match("(n)").where_("broken(n.score)")
+
+        with pytest.raises(ZeroDivisionError, match="boom"):
+            query.return_("n")
+
+    def test_unregistered_function_raises(self):
+        with pytest.raises(RuntimeError):
+            _graph().query().match("(n)").where_("risk(n.score) > 0.5").return_("n")
+
+    def test_register_requires_callable_and_valid_name(self):
+        with pytest.raises(ValueError):
+            _graph().query().register_function("risk", 42)
+        with pytest.raises(ValueError):
+            _graph().query().register_function("not a name", risk)
+
+    def test_functions_carry_over_with_run_on(self):
+        query = implica.Graph().query().register_function("risk", risk).match("(n)").where_("risk(n.score) > 0.5")
+
+        assert _names(query.run_on(_graph()).return_("n")) == ["a", "c"]
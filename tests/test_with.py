@@ -0,0 +1,55 @@
+import implica
+import pytest
+
+
+def _graph():
+    graph = implica.Graph(constants=[implica.Constant("f", "A -> B")])
+    graph.query().create("(:A { name: 'a' })-[::@f()]->(:B { name: 'b' })").execute()
+    return graph
+
+
+class TestWith:
+    def test_with_keeps_only_projected_variables(self):
+        graph = _graph()
+
+        query = graph.query().match("(x)-[e]->(y)").with_(["x"])
+        assert len(query.return_("x")) == 1
+
+        with pytest.raises(KeyError):
+            graph.query().match("(x)-[e]->(y)").with_(["x"]).return_("y")
+
+    def test_with_renames_binding(self):
+        graph = _graph()
+
+        result = graph.query().match("(x:A)").with_([("x", "person")]).return_("person")
+        assert len(result) == 1
+        assert str(result[0]["person"].type()) == "A"
+
+        with pytest.raises(KeyError):
+            graph.query().match("(x:A)").with_([("x", "person")]).return_("x")
+
+    def test_with_mixes_plain_and_renamed_items(self):
+        graph = _graph()
+
+        result = graph.query().match("(x)-[e]->(y)").with_(["e", ("y", "target")]).return_("e", "target")
+        assert len(result) == 1
+        assert str(result[0]["target"].type()) == "B"
+
+    def test_renamed_binding_can_be_matched_again(self):
+        graph = _graph()
+
+        result = (
+            graph.query()
+            .match("(x:A)")
+            .with_([("x", "start")])
+            .match("(start)-[e]->(end)")
+            .return_("end")
+        )
+        assert len(result) == 1
+        assert str(result[0]["end"].type()) == "B"
+
+    def test_with_rejects_duplicate_aliases(self):
+        graph = _graph()
+
+        with pytest.raises(ValueError):
+            graph.query().match("(x)-[e]->(y)").with_([("x", "n"), ("y", "n")])